@@ -1,16 +1,28 @@
 use {
     crate::models::keypair::KeyPairError,
-    smbcloud_wallet_core_http::price_data::get_asset_price::get_asset_price,
+    smbcloud_wallet_core_http::{
+        fx_data::fx_service::FxService, price_data::price_service::PriceService,
+    },
+    smbcloud_wallet_core_model::models::currency::FiatCurrency,
 };
 
-#[uniffi::export(async_runtime = "tokio")]
+/// USD price of `asset`, or its price in `currency` when one is given.
+#[uniffi::export(async_runtime = "tokio", default(currency = None))]
 pub async fn asset_price(
     asset: String,
     api_key: &str,
     user_agent: &str,
+    currency: Option<FiatCurrency>,
 ) -> Result<f64, KeyPairError> {
-    match get_asset_price(&asset, api_key, user_agent).await {
-        Ok(response) => Ok(response.data.value),
-        Err(error) => Err(KeyPairError::AssetPrice(error.to_string())),
-    }
+    let usd_price = match PriceService::shared(api_key, user_agent)
+        .get_price(&asset)
+        .await
+    {
+        Ok(price) => price.usd_price,
+        Err(error) => return Err(KeyPairError::AssetPrice(error.to_string())),
+    };
+    FxService::shared(user_agent)
+        .convert_usd(usd_price, currency.unwrap_or_default())
+        .await
+        .map_err(|error| KeyPairError::AssetPrice(error.to_string()))
 }
@@ -20,9 +20,9 @@ pub fn derive_new_account(
                 id: Uuid::new_v4().to_string(),
                 username: None,
                 name: format!("Account {}", account),
-                account: account,
-                pubkey: pubkey,
-                privkey: privkey,
+                account,
+                pubkey,
+                privkey,
                 seed_id: seed_id.clone(),
             };
 
@@ -3,6 +3,8 @@ mod create_wallet;
 mod derive_keypair_default;
 mod derive_new_account;
 mod import_wallet;
+mod primary_domain;
+mod resolve_domain;
 mod sol_balance;
 mod wallet_balance;
 mod wallet_balance_aggregate;
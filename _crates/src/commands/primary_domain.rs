@@ -0,0 +1,17 @@
+use {
+    crate::models::keypair::KeyPairError,
+    smbcloud_wallet_core_model::models::environment::Environment,
+    smbcloud_wallet_core_rpc::blocking::primary_domain as core_primary_domain,
+    smbcloud_wallet_kit::rpc_endpoints::RpcEndpoints,
+};
+
+#[uniffi::export]
+pub fn primary_domain(
+    network: Environment,
+    pubkey: String,
+) -> Result<Option<String>, KeyPairError> {
+    match core_primary_domain(RpcEndpoints::shared(network).active_url(), pubkey) {
+        Ok(domain) => Ok(domain),
+        Err(e) => Err(KeyPairError::InvalidAddress(e.to_string())),
+    }
+}
@@ -0,0 +1,14 @@
+use {
+    crate::models::keypair::KeyPairError,
+    smbcloud_wallet_core_model::models::environment::Environment,
+    smbcloud_wallet_core_rpc::blocking::resolve_domain as core_resolve_domain,
+    smbcloud_wallet_kit::rpc_endpoints::RpcEndpoints,
+};
+
+#[uniffi::export]
+pub fn resolve_domain(network: Environment, domain: String) -> Result<String, KeyPairError> {
+    match core_resolve_domain(RpcEndpoints::shared(network).active_url(), domain) {
+        Ok(address) => Ok(address),
+        Err(e) => Err(KeyPairError::InvalidAddress(e.to_string())),
+    }
+}
@@ -1,12 +1,13 @@
 use {
     crate::models::keypair::KeyPairError,
     smbcloud_wallet_core_model::models::environment::Environment,
-    smbcloud_wallet_core_rpc::balance::sol_balance::sol_balance as core_sol_balance,
+    smbcloud_wallet_core_rpc::blocking::sol_balance as core_sol_balance,
+    smbcloud_wallet_kit::rpc_endpoints::RpcEndpoints,
 };
 
 #[uniffi::export]
 pub fn sol_balance(network: Environment, pubkey: String) -> Result<f64, KeyPairError> {
-    match core_sol_balance(network.rpc_url(), pubkey) {
+    match core_sol_balance(RpcEndpoints::shared(network).active_url(), pubkey) {
         Ok(balance) => Ok(balance.1),
         Err(e) => Err(KeyPairError::InvalidAddress(e.to_string())),
     }
@@ -1,21 +1,32 @@
 use {
     crate::models::keypair::KeyPairError,
+    log::debug,
     smbcloud_wallet_core::balance::wallet_balance::wallet_balance as core_wallet_balance,
-    smbcloud_wallet_core_model::models::environment::Environment,
+    smbcloud_wallet_core_model::models::{
+        currency::FiatCurrency, environment::Environment, portfolio::PortfolioValuation,
+    },
+    smbcloud_wallet_kit::rpc_endpoints::RpcEndpoints,
 };
 
-#[uniffi::export(async_runtime = "tokio")]
+/// Holdings of `pubkey`, valued in USD or in `currency` when one is given.
+#[uniffi::export(async_runtime = "tokio", default(currency = None))]
 pub async fn wallet_balance(
     network: Environment,
     pubkey: String,
     api_key: &str,
     user_agent: &str,
-) -> Result<String, KeyPairError> {
-    println!(
-        "🦀🦀  Will load wallet balance for {} in {}",
-        pubkey, network
-    );
-    match core_wallet_balance(network.rpc_url(), api_key, user_agent, pubkey).await {
+    currency: Option<FiatCurrency>,
+) -> Result<PortfolioValuation, KeyPairError> {
+    debug!("Will load wallet balance for {} in {}", pubkey, network);
+    match core_wallet_balance(
+        RpcEndpoints::shared(network).active_url(),
+        api_key,
+        user_agent,
+        pubkey,
+        currency.unwrap_or_default(),
+    )
+    .await
+    {
         Ok(balance) => Ok(balance),
         Err(e) => Err(KeyPairError::InvalidAddress(e.to_string())),
     }
@@ -2,6 +2,7 @@ use {
     crate::models::keypair::KeyPairError,
     smbcloud_wallet_core::balance::wallet_balance_aggregate::wallet_balance_aggregate as core_wallet_balance_aggregate,
     smbcloud_wallet_core_model::models::{balance::Balance, environment::Environment},
+    smbcloud_wallet_kit::rpc_endpoints::RpcEndpoints,
 };
 
 #[uniffi::export(async_runtime = "tokio")]
@@ -13,7 +14,7 @@ pub async fn wallet_balance_aggregate(
         "🦀🦀  Will load wallet balance for {} in {}",
         pubkey, network
     );
-    match core_wallet_balance_aggregate(network.rpc_url(), pubkey).await {
+    match core_wallet_balance_aggregate(RpcEndpoints::shared(network).active_url(), pubkey).await {
         Ok(balances) => Ok(balances),
         Err(e) => Err(KeyPairError::InvalidAddress(e.to_string())),
     }
@@ -2,5 +2,6 @@ use uniffi::setup_scaffolding;
 
 pub(crate) mod commands;
 pub mod models;
+pub mod wallet_kit;
 
 setup_scaffolding!();
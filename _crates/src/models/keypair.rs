@@ -11,6 +11,10 @@ pub enum KeyPairError {
     MnemonicError(String),
     InvalidAddress(String),
     AssetPrice(String),
+    Staking(String),
+    Transaction(String),
+    Client(String),
+    Network(String),
 }
 
 impl Display for KeyPairError {
@@ -19,6 +23,10 @@ impl Display for KeyPairError {
             Self::InvalidAddress(addr) => write!(f, "Invalid address: {}", addr),
             Self::MnemonicError(err) => write!(f, "Mnemonic error: {}", err),
             Self::AssetPrice(err) => write!(f, "Asset price error: {}", err),
+            Self::Staking(err) => write!(f, "Staking error: {}", err),
+            Self::Transaction(err) => write!(f, "Transaction error: {}", err),
+            Self::Client(err) => write!(f, "Client error: {}", err),
+            Self::Network(err) => write!(f, "Network error: {}", err),
         }
     }
 }
@@ -0,0 +1,214 @@
+use {
+    crate::models::keypair::KeyPairError,
+    smbcloud_wallet_core_model::models::{
+        currency::FiatCurrency,
+        portfolio::PortfolioValuation,
+        stake::{StakeAccount, StakeOperation, StakeReward, Validator},
+        wallet_kit::WalletKitConfig,
+    },
+    smbcloud_wallet_kit::{
+        kit::{WalletKit as Kit, WalletKitError},
+        staking::{self, keypair_from_base58, StakingError},
+    },
+    std::sync::Arc,
+};
+
+/// One long-lived client per network, sharing its RPC and HTTP connections
+/// and price caches between calls.
+#[derive(uniffi::Object)]
+pub struct WalletKit {
+    kit: Kit,
+}
+
+#[uniffi::export(async_runtime = "tokio")]
+impl WalletKit {
+    #[uniffi::constructor]
+    pub fn new(config: WalletKitConfig) -> Result<Arc<Self>, KeyPairError> {
+        let kit = Kit::new(config).map_err(|e| KeyPairError::Client(e.to_string()))?;
+        Ok(Arc::new(WalletKit { kit }))
+    }
+
+    pub fn config(&self) -> WalletKitConfig {
+        self.kit.config().clone()
+    }
+
+    pub async fn sol_balance(&self, pubkey: String) -> Result<f64, KeyPairError> {
+        self.kit.sol_balance(&pubkey).await.map_err(read_error)
+    }
+
+    #[uniffi::method(default(currency = None))]
+    pub async fn wallet_balance(
+        &self,
+        pubkey: String,
+        currency: Option<FiatCurrency>,
+    ) -> Result<PortfolioValuation, KeyPairError> {
+        self.kit
+            .wallet_balance(&pubkey, currency.unwrap_or_default())
+            .await
+            .map_err(read_error)
+    }
+
+    #[uniffi::method(default(currency = None))]
+    pub async fn asset_price(
+        &self,
+        mint: String,
+        currency: Option<FiatCurrency>,
+    ) -> Result<f64, KeyPairError> {
+        self.kit
+            .asset_price(&mint, currency.unwrap_or_default())
+            .await
+            .map_err(|e| KeyPairError::AssetPrice(e.to_string()))
+    }
+
+    pub async fn transfer_sol(
+        &self,
+        privkey: String,
+        to: String,
+        amount: f64,
+    ) -> Result<String, KeyPairError> {
+        let keypair =
+            keypair_from_base58(&privkey).map_err(|e| KeyPairError::Transaction(e.to_string()))?;
+        self.kit
+            .transfer_sol(&keypair, &to, amount)
+            .await
+            .map_err(|e| KeyPairError::Transaction(e.to_string()))
+    }
+
+    pub async fn transfer_token(
+        &self,
+        privkey: String,
+        to: String,
+        mint: String,
+        amount: f64,
+    ) -> Result<String, KeyPairError> {
+        let keypair =
+            keypair_from_base58(&privkey).map_err(|e| KeyPairError::Transaction(e.to_string()))?;
+        self.kit
+            .transfer_token(&keypair, &to, &mint, amount)
+            .await
+            .map_err(|e| KeyPairError::Transaction(e.to_string()))
+    }
+
+    /// Swap `amount` of `from_token` into `to_token` through Jupiter.
+    pub async fn swap(
+        &self,
+        privkey: String,
+        from_token: String,
+        to_token: String,
+        amount: f64,
+        slippage_bps: u64,
+    ) -> Result<String, KeyPairError> {
+        let keypair =
+            keypair_from_base58(&privkey).map_err(|e| KeyPairError::Transaction(e.to_string()))?;
+        self.kit
+            .swap(&keypair, &from_token, &to_token, amount, slippage_bps)
+            .await
+            .map(|signature| signature.to_string())
+            .map_err(|e| KeyPairError::Transaction(e.to_string()))
+    }
+
+    pub async fn validators(&self) -> Result<Vec<Validator>, KeyPairError> {
+        staking::get_validators(&self.kit.rpc_client())
+            .await
+            .map_err(staking_error)
+    }
+
+    pub async fn stake_accounts(&self, pubkey: String) -> Result<Vec<StakeAccount>, KeyPairError> {
+        staking::get_stake_accounts(&self.kit.rpc_client(), pubkey)
+            .await
+            .map_err(staking_error)
+    }
+
+    pub async fn create_stake_account(
+        &self,
+        privkey: String,
+        amount: f64,
+        vote_account: String,
+    ) -> Result<StakeOperation, KeyPairError> {
+        let keypair = keypair_from_base58(&privkey).map_err(staking_error)?;
+        staking::create_stake_account(&self.kit.rpc_client(), keypair, amount, vote_account)
+            .await
+            .map_err(staking_error)
+    }
+
+    pub async fn delegate_stake(
+        &self,
+        privkey: String,
+        stake_account: String,
+        vote_account: String,
+    ) -> Result<String, KeyPairError> {
+        let keypair = keypair_from_base58(&privkey).map_err(staking_error)?;
+        staking::delegate_stake(&self.kit.rpc_client(), keypair, stake_account, vote_account)
+            .await
+            .map_err(staking_error)
+    }
+
+    pub async fn deactivate_stake(
+        &self,
+        privkey: String,
+        stake_account: String,
+    ) -> Result<String, KeyPairError> {
+        let keypair = keypair_from_base58(&privkey).map_err(staking_error)?;
+        staking::deactivate_stake(&self.kit.rpc_client(), keypair, stake_account)
+            .await
+            .map_err(staking_error)
+    }
+
+    pub async fn withdraw_stake(
+        &self,
+        privkey: String,
+        stake_account: String,
+        amount: Option<f64>,
+    ) -> Result<String, KeyPairError> {
+        let keypair = keypair_from_base58(&privkey).map_err(staking_error)?;
+        staking::withdraw_stake(&self.kit.rpc_client(), keypair, stake_account, amount)
+            .await
+            .map_err(staking_error)
+    }
+
+    pub async fn split_stake(
+        &self,
+        privkey: String,
+        stake_account: String,
+        amount: f64,
+    ) -> Result<StakeOperation, KeyPairError> {
+        let keypair = keypair_from_base58(&privkey).map_err(staking_error)?;
+        staking::split_stake(&self.kit.rpc_client(), keypair, stake_account, amount)
+            .await
+            .map_err(staking_error)
+    }
+
+    pub async fn merge_stake(
+        &self,
+        privkey: String,
+        destination: String,
+        source: String,
+    ) -> Result<String, KeyPairError> {
+        let keypair = keypair_from_base58(&privkey).map_err(staking_error)?;
+        staking::merge_stake(&self.kit.rpc_client(), keypair, destination, source)
+            .await
+            .map_err(staking_error)
+    }
+
+    pub async fn stake_rewards(
+        &self,
+        stake_accounts: Vec<String>,
+        epoch: Option<u64>,
+    ) -> Result<Vec<StakeReward>, KeyPairError> {
+        staking::get_stake_rewards(&self.kit.rpc_client(), stake_accounts, epoch)
+            .await
+            .map_err(staking_error)
+    }
+}
+
+/// A failed read, as a network error unless the address itself was invalid.
+fn read_error(error: WalletKitError) -> KeyPairError {
+    match error {
+        WalletKitError::InvalidAddress(address) => KeyPairError::InvalidAddress(address),
+        error => KeyPairError::Network(error.to_string()),
+    }
+}
+
+fn staking_error(error: StakingError) -> KeyPairError {
+    KeyPairError::Staking(error.to_string())
+}
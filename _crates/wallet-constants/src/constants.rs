@@ -19,6 +19,36 @@ pub const THE_STABLE_FOUNDATION_TREASURY_WALLET_FEE: &str =
 pub const PLATFORM_FEE_BPS: u16 = 10;
 #[tsync]
 pub const SPL_TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+#[tsync]
+pub const SPL_TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+pub const SYSTEM_PROGRAM_ID: &str = "11111111111111111111111111111111";
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+pub const COMPUTE_BUDGET_PROGRAM_ID: &str = "ComputeBudget111111111111111111111111111111";
+#[tsync]
+pub const SPL_MEMO_PROGRAM_ID: &str = "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr";
+
+/// Metaplex
+pub const METAPLEX_TOKEN_METADATA_PROGRAM_ID: &str = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s";
+pub const METAPLEX_TOKEN_AUTH_RULES_PROGRAM_ID: &str = "auth9SigNpDKz4sJJ1DfCTuZrZNSAgh9sFD3rboVmgg";
+pub const METAPLEX_BUBBLEGUM_PROGRAM_ID: &str = "BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY";
+pub const SPL_ACCOUNT_COMPRESSION_PROGRAM_ID: &str = "cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK";
+pub const SPL_NOOP_PROGRAM_ID: &str = "noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV";
+
+/// Solana Pay
+pub const SOLANA_PAY_SCHEME: &str = "solana";
+pub const NOTWALLET_DEEP_LINK_SCHEME: &str = "notwallet";
+
+/// SPL stake pools
+pub const SPL_STAKE_POOL_PROGRAM_ID: &str = "SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy";
+pub const JITO_STAKE_POOL: &str = "Jito4APyf642JPZPx3hGc6WWJ8zPKtRbRs4P815Awbb";
+
+/// Solana Name Service
+pub const SNS_NAME_PROGRAM_ID: &str = "namesLPneVptA9Z5rqUDD9tMTWEJwofgaYwp8cawRkX";
+pub const SNS_SOL_TLD_AUTHORITY: &str = "58PwtjSDuFHuUkYjH9BYnnQKHfwo9reZhC2zMJv9JPkx";
+pub const SNS_REVERSE_LOOKUP_CLASS: &str = "33m47vH6Eav6jr5Ry86XjhRft2jRBLDnDgPSHoquXi2Z";
+pub const SNS_RECORDS_CENTRAL_STATE: &str = "2pMnqHvei2N5oDcVGCRdZx48gqti199wp5CsyTTafsbo";
+pub const SNS_NAME_OFFERS_PROGRAM_ID: &str = "85iDfUvr3HJyLM2zcq5BXSiDvUWfw6cSE1FfNBo8Ap29";
+pub const SNS_NAME_TOKENIZER_PROGRAM_ID: &str = "nftD3vbNkNqfj2Sd3HZwbpw4BxxKWr4AjGb9X38JeZk";
 
 /// Jupiter Aggregator
 pub const JUPITER_BASE_URL: &str = "https://lite-api.jup.ag/";
@@ -29,7 +59,15 @@ pub const JUPITER_PRICE_PATH: &str = "price/v3";
 /// Birdeye
 pub const BIRDEYE_BASE_URL: &str = "https://public-api.birdeye.so/";
 pub const BIRDEYE_PRICE_PATH: &str = "defi/price";
+pub const BIRDEYE_MULTI_PRICE_PATH: &str = "defi/multi_price";
+pub const BIRDEYE_PRICE_HISTORY_PATH: &str = "defi/history_price";
+
+/// ExchangeRate-API open access, daily fiat exchange rates
+pub const FX_RATES_BASE_URL: &str = "https://open.er-api.com/";
+pub const FX_RATES_LATEST_USD_PATH: &str = "v6/latest/USD";
+
+/// Frankfurter, past fiat exchange rates published by the European Central Bank
+pub const FX_HISTORY_BASE_URL: &str = "https://api.frankfurter.dev/v1/";
 
 /// XLP API Service
-pub const XLP_WALLET_BALANCE_PATH: &str = "/v1/wallets/wallet_balance";
 pub const XLP_WALLET_TOKEN_LIST_PATH: &str = "/v1/wallets/token_list";
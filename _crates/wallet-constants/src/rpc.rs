@@ -1,3 +1,5 @@
+use tsync::tsync;
+
 pub fn mainnet_rpc_url() -> String {
     dotenv!("SOLANA_MAINNET_RPC_BASE_URL").to_string()
 }
@@ -13,3 +15,41 @@ pub fn devnet_rpc_url() -> String {
 pub fn local_rpc_url() -> String {
     "http://localhost:8899".to_string()
 }
+
+/// Public endpoints of the Solana Foundation, the fallback when the configured
+/// provider is down. They are rate limited, so never the first choice.
+pub const MAINNET_PUBLIC_RPC_URL: &str = "https://api.mainnet-beta.solana.com";
+pub const TESTNET_PUBLIC_RPC_URL: &str = "https://api.testnet.solana.com";
+pub const DEVNET_PUBLIC_RPC_URL: &str = "https://api.devnet.solana.com";
+
+/// Mainnet endpoints by preference.
+pub fn mainnet_rpc_urls() -> Vec<String> {
+    ranked(mainnet_rpc_url(), MAINNET_PUBLIC_RPC_URL)
+}
+
+/// Testnet endpoints by preference.
+pub fn testnet_rpc_urls() -> Vec<String> {
+    ranked(testnet_rpc_url(), TESTNET_PUBLIC_RPC_URL)
+}
+
+/// Devnet endpoints by preference.
+pub fn devnet_rpc_urls() -> Vec<String> {
+    ranked(devnet_rpc_url(), DEVNET_PUBLIC_RPC_URL)
+}
+
+fn ranked(configured: String, public: &str) -> Vec<String> {
+    if configured.trim_end_matches('/') == public {
+        vec![configured]
+    } else {
+        vec![configured, public.to_string()]
+    }
+}
+
+/// Genesis hashes of the public clusters, returned by `getGenesisHash`. They
+/// tell which cluster an endpoint serves whatever its URL.
+#[tsync]
+pub const MAINNET_GENESIS_HASH: &str = "5eykt4UsFv8P8NJdTREpY1vzqKqZKvdpKuc147dw2N9d";
+#[tsync]
+pub const TESTNET_GENESIS_HASH: &str = "4uhcVJyU9pJkvQyS88uRDiswHXSCkY3zQawwpjk2NsNY";
+#[tsync]
+pub const DEVNET_GENESIS_HASH: &str = "EtWTRABZaYq6iMfeYKouRu166VU2xqa1wcaWoxPkrZBG";
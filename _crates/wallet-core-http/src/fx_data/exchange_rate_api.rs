@@ -0,0 +1,57 @@
+use {
+    crate::fx_data::fx_rate_provider::FxRateProvider,
+    async_trait::async_trait,
+    log::debug,
+    reqwest::Client,
+    smbcloud_wallet_constants::constants::{FX_RATES_BASE_URL, FX_RATES_LATEST_USD_PATH},
+    smbcloud_wallet_core_model::models::{
+        currency::FiatCurrency, fx_rates_response::FxRatesResponse,
+    },
+    smbcloud_wallet_core_network::{
+        model::{ErrorCode, ErrorResponse},
+        request,
+    },
+    std::collections::HashMap,
+};
+
+/// Daily rates from the ExchangeRate-API open access endpoint, no API key needed.
+pub struct ExchangeRateApiProvider {
+    client: Client,
+    user_agent: String,
+}
+
+impl ExchangeRateApiProvider {
+    pub fn new(user_agent: &str) -> Self {
+        Self::with_client(Client::new(), user_agent)
+    }
+
+    /// Same as [`ExchangeRateApiProvider::new`], sending requests through `client`.
+    pub fn with_client(client: Client, user_agent: &str) -> Self {
+        ExchangeRateApiProvider {
+            client,
+            user_agent: user_agent.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl FxRateProvider for ExchangeRateApiProvider {
+    async fn usd_rates(&self) -> Result<HashMap<FiatCurrency, f64>, ErrorResponse> {
+        let url = format!("{}{}", FX_RATES_BASE_URL, FX_RATES_LATEST_USD_PATH);
+        debug!("Get exchange rates from {}", url);
+        let builder = self.client.get(url).header("User-Agent", &self.user_agent);
+
+        let response: FxRatesResponse = request(builder).await?;
+        if response.result != "success" || response.base_code != FiatCurrency::USD.code() {
+            return Err(ErrorResponse::Error {
+                code: ErrorCode::NetworkError,
+                message: "Exchange rates are not available".to_string(),
+            });
+        }
+        Ok(response
+            .rates
+            .into_iter()
+            .filter_map(|(code, rate)| Some((FiatCurrency::from_code(&code)?, rate)))
+            .collect())
+    }
+}
@@ -0,0 +1,63 @@
+use {
+    crate::fx_data::{fx_history::FxHistoryProvider, fx_rate_provider::FxRateProvider},
+    async_trait::async_trait,
+    smbcloud_wallet_core_model::models::{currency::FiatCurrency, price::FxRatePoint},
+    smbcloud_wallet_core_network::model::{ErrorCode, ErrorResponse},
+    std::collections::HashMap,
+};
+
+/// Fixed exchange rates, for tests and offline development. The past rates are
+/// the same.
+pub struct FixtureFxRateProvider {
+    rates: HashMap<FiatCurrency, f64>,
+}
+
+impl FixtureFxRateProvider {
+    pub fn new(rates: &[(FiatCurrency, f64)]) -> Self {
+        FixtureFxRateProvider {
+            rates: rates.iter().copied().collect(),
+        }
+    }
+}
+
+impl Default for FixtureFxRateProvider {
+    /// Rates close to the market in October 2025.
+    fn default() -> Self {
+        FixtureFxRateProvider::new(&[
+            (FiatCurrency::USD, 1.0),
+            (FiatCurrency::EUR, 0.86),
+            (FiatCurrency::GBP, 0.75),
+            (FiatCurrency::JPY, 150.5),
+            (FiatCurrency::IDR, 16575.0),
+            (FiatCurrency::SEK, 9.41),
+        ])
+    }
+}
+
+#[async_trait]
+impl FxRateProvider for FixtureFxRateProvider {
+    async fn usd_rates(&self) -> Result<HashMap<FiatCurrency, f64>, ErrorResponse> {
+        Ok(self.rates.clone())
+    }
+}
+
+#[async_trait]
+impl FxHistoryProvider for FixtureFxRateProvider {
+    async fn usd_rate_history(
+        &self,
+        currency: FiatCurrency,
+        from: i64,
+        _to: i64,
+    ) -> Result<Vec<FxRatePoint>, ErrorResponse> {
+        match self.rates.get(&currency) {
+            Some(rate) => Ok(vec![FxRatePoint {
+                timestamp: from,
+                usd_rate: *rate,
+            }]),
+            None => Err(ErrorResponse::Error {
+                code: ErrorCode::NetworkError,
+                message: format!("No exchange rates for {}", currency.code()),
+            }),
+        }
+    }
+}
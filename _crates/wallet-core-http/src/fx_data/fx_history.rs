@@ -0,0 +1,149 @@
+use {
+    async_trait::async_trait,
+    chrono::{DateTime, NaiveDate},
+    log::debug,
+    reqwest::Client,
+    smbcloud_wallet_constants::constants::FX_HISTORY_BASE_URL,
+    smbcloud_wallet_core_model::models::{
+        currency::FiatCurrency, fx_rates_response::FxHistoryResponse, price::FxRatePoint,
+    },
+    smbcloud_wallet_core_network::{
+        model::{ErrorCode, ErrorResponse},
+        request,
+    },
+};
+
+/// A source of past fiat exchange rates against the US dollar.
+#[async_trait]
+pub trait FxHistoryProvider: Send + Sync {
+    /// Units of `currency` one US dollar bought between `from` and `to`, oldest first.
+    async fn usd_rate_history(
+        &self,
+        currency: FiatCurrency,
+        from: i64,
+        to: i64,
+    ) -> Result<Vec<FxRatePoint>, ErrorResponse>;
+}
+
+/// Daily reference rates of the European Central Bank from Frankfurter, no API key needed.
+///
+/// There are no rates on weekends and holidays, and only for the currencies the
+/// bank publishes.
+pub struct FrankfurterProvider {
+    client: Client,
+    user_agent: String,
+}
+
+impl FrankfurterProvider {
+    pub fn new(user_agent: &str) -> Self {
+        Self::with_client(Client::new(), user_agent)
+    }
+
+    /// Same as [`FrankfurterProvider::new`], sending requests through `client`.
+    pub fn with_client(client: Client, user_agent: &str) -> Self {
+        FrankfurterProvider {
+            client,
+            user_agent: user_agent.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl FxHistoryProvider for FrankfurterProvider {
+    async fn usd_rate_history(
+        &self,
+        currency: FiatCurrency,
+        from: i64,
+        to: i64,
+    ) -> Result<Vec<FxRatePoint>, ErrorResponse> {
+        if currency == FiatCurrency::USD {
+            return Ok(vec![FxRatePoint {
+                timestamp: from,
+                usd_rate: 1.0,
+            }]);
+        }
+        let url = format!(
+            "{}{}..{}?base=USD&symbols={}",
+            FX_HISTORY_BASE_URL,
+            date(from),
+            date(to),
+            currency.code()
+        );
+        debug!("Get {} exchange rate history from {}", currency.code(), url);
+        let builder = self.client.get(url).header("User-Agent", &self.user_agent);
+
+        let response: FxHistoryResponse = request(builder).await?;
+        let points = history_points(response, currency);
+        if points.is_empty() {
+            return Err(ErrorResponse::Error {
+                code: ErrorCode::NetworkError,
+                message: format!("No exchange rates for {}", currency.code()),
+            });
+        }
+        Ok(points)
+    }
+}
+
+/// The `YYYY-MM-DD` date of a Unix timestamp, in UTC.
+fn date(timestamp: i64) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+/// The rates of `currency` in a response, at the start of their day in UTC.
+fn history_points(response: FxHistoryResponse, currency: FiatCurrency) -> Vec<FxRatePoint> {
+    if response.base != FiatCurrency::USD.code() {
+        return Vec::new();
+    }
+    let mut points: Vec<FxRatePoint> = response
+        .rates
+        .into_iter()
+        .filter_map(|(day, rates)| {
+            let date = NaiveDate::parse_from_str(&day, "%Y-%m-%d").ok()?;
+            Some(FxRatePoint {
+                timestamp: date.and_hms_opt(0, 0, 0)?.and_utc().timestamp(),
+                usd_rate: *rates.get(currency.code())?,
+            })
+        })
+        .collect();
+    points.sort_by_key(|point| point.timestamp);
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::collections::HashMap};
+
+    #[test]
+    fn test_history_points() {
+        let response = FxHistoryResponse {
+            base: "USD".to_string(),
+            rates: HashMap::from([
+                (
+                    "2025-01-03".to_string(),
+                    HashMap::from([("SEK".to_string(), 11.12)]),
+                ),
+                (
+                    "2025-01-02".to_string(),
+                    HashMap::from([("SEK".to_string(), 11.06)]),
+                ),
+            ]),
+        };
+        let points = history_points(response, FiatCurrency::SEK);
+        assert_eq!(
+            points,
+            vec![
+                FxRatePoint {
+                    timestamp: 1_735_776_000,
+                    usd_rate: 11.06,
+                },
+                FxRatePoint {
+                    timestamp: 1_735_862_400,
+                    usd_rate: 11.12,
+                },
+            ]
+        );
+        assert_eq!(date(1_735_862_400 + 60), "2025-01-03");
+    }
+}
@@ -0,0 +1,13 @@
+use {
+    async_trait::async_trait, smbcloud_wallet_core_model::models::currency::FiatCurrency,
+    smbcloud_wallet_core_network::model::ErrorResponse, std::collections::HashMap,
+};
+
+/// A source of fiat exchange rates against the US dollar.
+#[async_trait]
+pub trait FxRateProvider: Send + Sync {
+    /// Units of each currency one US dollar buys.
+    ///
+    /// Currencies the provider has no rate for are left out of the result.
+    async fn usd_rates(&self) -> Result<HashMap<FiatCurrency, f64>, ErrorResponse>;
+}
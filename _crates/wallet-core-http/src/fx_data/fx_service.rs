@@ -0,0 +1,187 @@
+use {
+    crate::{
+        fx_data::{exchange_rate_api::ExchangeRateApiProvider, fx_rate_provider::FxRateProvider},
+        shared::Shared,
+    },
+    log::warn,
+    smbcloud_wallet_core_model::models::currency::FiatCurrency,
+    smbcloud_wallet_core_network::model::{ErrorCode, ErrorResponse},
+    std::{
+        collections::HashMap,
+        sync::Mutex,
+        time::{Duration, Instant},
+    },
+};
+
+/// How long fetched exchange rates are reused. The open API updates once a day.
+pub const FX_RATES_TTL: Duration = Duration::from_secs(60 * 60);
+
+static SHARED_FX_SERVICES: Shared<String, FxService> = Shared::new();
+
+/// Converts USD amounts into other fiat currencies with cached rates.
+pub struct FxService {
+    provider: Box<dyn FxRateProvider>,
+    ttl: Duration,
+    rates: Mutex<Option<(Instant, HashMap<FiatCurrency, f64>)>>,
+}
+
+impl FxService {
+    pub fn new(provider: Box<dyn FxRateProvider>, ttl: Duration) -> Self {
+        FxService {
+            provider,
+            ttl,
+            rates: Mutex::new(None),
+        }
+    }
+
+    /// The process wide service of a user agent, backed by ExchangeRate-API.
+    pub fn shared(user_agent: &str) -> &'static FxService {
+        SHARED_FX_SERVICES.get_or_init(user_agent.to_string(), || {
+            FxService::new(
+                Box::new(ExchangeRateApiProvider::new(user_agent)),
+                FX_RATES_TTL,
+            )
+        })
+    }
+
+    /// Units of `currency` one US dollar buys.
+    ///
+    /// When the provider fails, the last fetched rates are used however old they are.
+    pub async fn usd_rate(&self, currency: FiatCurrency) -> Result<f64, ErrorResponse> {
+        if currency == FiatCurrency::USD {
+            return Ok(1.0);
+        }
+
+        let cached = self.cached_rates();
+        let rates = match cached {
+            Some((fetched_at, rates)) if fetched_at.elapsed() < self.ttl => rates,
+            stale => match self.provider.usd_rates().await {
+                Ok(rates) => {
+                    let mut cache = self.rates.lock().unwrap_or_else(|e| e.into_inner());
+                    *cache = Some((Instant::now(), rates.clone()));
+                    rates
+                }
+                Err(e) => match stale {
+                    Some((_, rates)) => {
+                        warn!("Using stale exchange rates: {}", e);
+                        rates
+                    }
+                    None => return Err(e),
+                },
+            },
+        };
+
+        rates
+            .get(&currency)
+            .copied()
+            .ok_or_else(|| ErrorResponse::Error {
+                code: ErrorCode::BalanceError,
+                message: format!("No exchange rate for {}", currency),
+            })
+    }
+
+    /// Convert a USD amount into `currency`.
+    pub async fn convert_usd(
+        &self,
+        amount: f64,
+        currency: FiatCurrency,
+    ) -> Result<f64, ErrorResponse> {
+        Ok(amount * self.usd_rate(currency).await?)
+    }
+
+    fn cached_rates(&self) -> Option<(Instant, HashMap<FiatCurrency, f64>)> {
+        self.rates.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::fx_data::fixture::FixtureFxRateProvider,
+        async_trait::async_trait,
+        std::sync::{
+            atomic::{AtomicBool, AtomicUsize, Ordering},
+            Arc,
+        },
+    };
+
+    /// Serves the fixture rates until told to fail, counting requests.
+    struct FlakyProvider {
+        fixture: FixtureFxRateProvider,
+        fail: Arc<AtomicBool>,
+        requests: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl FxRateProvider for FlakyProvider {
+        async fn usd_rates(&self) -> Result<HashMap<FiatCurrency, f64>, ErrorResponse> {
+            self.requests.fetch_add(1, Ordering::SeqCst);
+            if self.fail.load(Ordering::SeqCst) {
+                return Err(ErrorResponse::Error {
+                    code: ErrorCode::NetworkError,
+                    message: "unavailable".to_string(),
+                });
+            }
+            self.fixture.usd_rates().await
+        }
+    }
+
+    fn flaky_service(ttl: Duration) -> (FxService, Arc<AtomicBool>, Arc<AtomicUsize>) {
+        let fail = Arc::new(AtomicBool::new(false));
+        let requests = Arc::new(AtomicUsize::new(0));
+        let provider = FlakyProvider {
+            fixture: FixtureFxRateProvider::default(),
+            fail: fail.clone(),
+            requests: requests.clone(),
+        };
+        (FxService::new(Box::new(provider), ttl), fail, requests)
+    }
+
+    #[tokio::test]
+    async fn test_convert_usd() {
+        let service = FxService::new(Box::new(FixtureFxRateProvider::default()), FX_RATES_TTL);
+        assert_eq!(
+            service.convert_usd(2.0, FiatCurrency::IDR).await.unwrap(),
+            33150.0
+        );
+        assert_eq!(
+            service.convert_usd(4.0, FiatCurrency::GBP).await.unwrap(),
+            3.0
+        );
+        assert!(service.convert_usd(1.0, FiatCurrency::NGN).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_usd_needs_no_rates() {
+        let (service, fail, requests) = flaky_service(FX_RATES_TTL);
+        fail.store(true, Ordering::SeqCst);
+        assert_eq!(service.usd_rate(FiatCurrency::USD).await.unwrap(), 1.0);
+        assert_eq!(requests.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_rates_are_cached() {
+        let (service, _, requests) = flaky_service(FX_RATES_TTL);
+        service.usd_rate(FiatCurrency::EUR).await.unwrap();
+        service.usd_rate(FiatCurrency::GBP).await.unwrap();
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_to_stale_rates() {
+        let (service, fail, requests) = flaky_service(Duration::ZERO);
+        assert!(service.usd_rate(FiatCurrency::EUR).await.is_ok());
+
+        fail.store(true, Ordering::SeqCst);
+        assert_eq!(service.usd_rate(FiatCurrency::EUR).await.unwrap(), 0.86);
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_fails_without_any_rates() {
+        let (service, fail, _) = flaky_service(FX_RATES_TTL);
+        fail.store(true, Ordering::SeqCst);
+        assert!(service.usd_rate(FiatCurrency::EUR).await.is_err());
+    }
+}
@@ -0,0 +1,5 @@
+pub mod exchange_rate_api;
+pub mod fixture;
+pub mod fx_history;
+pub mod fx_rate_provider;
+pub mod fx_service;
@@ -1,2 +1,4 @@
+pub mod fx_data;
 pub mod price_data;
+mod shared;
 pub mod xlp;
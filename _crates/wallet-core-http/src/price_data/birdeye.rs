@@ -0,0 +1,94 @@
+use {
+    crate::price_data::{price_provider::PriceProvider, rate_limiter::RateLimiter},
+    async_trait::async_trait,
+    log::debug,
+    reqwest::Client,
+    smbcloud_wallet_constants::constants::{BIRDEYE_BASE_URL, BIRDEYE_MULTI_PRICE_PATH},
+    smbcloud_wallet_core_model::models::{
+        birdeye_price_response::BirdeyeMultiPriceResponse,
+        price::{AssetPrice, PriceSource},
+    },
+    smbcloud_wallet_core_network::{
+        model::{ErrorCode, ErrorResponse},
+        request,
+    },
+    std::{collections::HashMap, sync::LazyLock},
+};
+
+/// Most addresses Birdeye accepts in one `multi_price` request.
+const BIRDEYE_BATCH_SIZE: usize = 100;
+
+/// Shared by every request to Birdeye, the public plan allows one request per second.
+pub(crate) static BIRDEYE_RATE_LIMITER: LazyLock<RateLimiter> =
+    LazyLock::new(|| RateLimiter::new(1, 1.0));
+
+/// Prices from the Birdeye `multi_price` endpoint.
+pub struct BirdeyePriceProvider {
+    client: Client,
+    api_key: String,
+    user_agent: String,
+}
+
+impl BirdeyePriceProvider {
+    pub fn new(api_key: &str, user_agent: &str) -> Self {
+        Self::with_client(Client::new(), api_key, user_agent)
+    }
+
+    /// Same as [`BirdeyePriceProvider::new`], sending requests through `client`.
+    pub fn with_client(client: Client, api_key: &str, user_agent: &str) -> Self {
+        BirdeyePriceProvider {
+            client,
+            api_key: api_key.to_string(),
+            user_agent: user_agent.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl PriceProvider for BirdeyePriceProvider {
+    fn source(&self) -> PriceSource {
+        PriceSource::Birdeye
+    }
+
+    async fn get_prices(
+        &self,
+        mints: &[String],
+    ) -> Result<HashMap<String, AssetPrice>, ErrorResponse> {
+        let mut prices = HashMap::new();
+        for batch in mints.chunks(BIRDEYE_BATCH_SIZE) {
+            let url = format!(
+                "{}{}?list_address={}",
+                BIRDEYE_BASE_URL,
+                BIRDEYE_MULTI_PRICE_PATH,
+                batch.join(",")
+            );
+            debug!("Get {} prices from Birdeye", batch.len());
+            let builder = self
+                .client
+                .get(url)
+                .header("X-API-KEY", &self.api_key)
+                .header("User-Agent", &self.user_agent);
+
+            BIRDEYE_RATE_LIMITER.acquire().await;
+            let response: BirdeyeMultiPriceResponse = request(builder).await?;
+            if !response.success {
+                return Err(ErrorResponse::Error {
+                    code: ErrorCode::NetworkError,
+                    message: "Birdeye returned no price data".to_string(),
+                });
+            }
+            prices.extend(response.data.into_iter().filter_map(|(mint, data)| {
+                let data = data?;
+                let price = AssetPrice {
+                    mint: mint.clone(),
+                    usd_price: data.value,
+                    price_change_24h: data.price_change_24h,
+                    source: PriceSource::Birdeye,
+                    updated_at: data.update_unix_time,
+                };
+                Some((mint, price))
+            }));
+        }
+        Ok(prices)
+    }
+}
@@ -1,24 +1,21 @@
 use {
-    crate::price_data::get_asset_price::get_asset_price,
+    crate::price_data::price_service::PriceService,
+    log::{debug, error},
     smbcloud_wallet_constants::assets_solana::ADDRESS_SOL,
-    smbcloud_wallet_core_network::model::{ErrorCode::BalanceError, ErrorResponse},
+    smbcloud_wallet_core_network::model::ErrorResponse,
 };
 
 pub async fn get_sol_price(api_key: &str, user_agent: &str) -> Result<f64, ErrorResponse> {
-    match get_asset_price(ADDRESS_SOL, api_key, user_agent).await {
+    match PriceService::shared(api_key, user_agent)
+        .get_price(ADDRESS_SOL)
+        .await
+    {
         Ok(price) => {
-            println!("🦀🦀  Got SOL price data: {:?}", price);
-            if price.is_valid() {
-                Ok(price.data.value)
-            } else {
-                Err(ErrorResponse::Error {
-                    code: BalanceError,
-                    message: "No price data available".to_string(),
-                })
-            }
+            debug!("Got SOL price data: {:?}", price);
+            Ok(price.usd_price)
         }
         Err(err) => {
-            println!("🦀🦀  Failed to get SOL price: {:?}", err);
+            error!("Failed to get SOL price: {:?}", err);
             Err(err)
         }
     }
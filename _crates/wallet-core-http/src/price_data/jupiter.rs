@@ -0,0 +1,85 @@
+use {
+    crate::price_data::{price_provider::PriceProvider, rate_limiter::RateLimiter},
+    async_trait::async_trait,
+    log::debug,
+    reqwest::Client,
+    smbcloud_wallet_constants::constants::{JUPITER_BASE_URL, JUPITER_PRICE_PATH},
+    smbcloud_wallet_core_model::models::{
+        birdeye_price_response::PricesResponse,
+        price::{AssetPrice, PriceSource},
+    },
+    smbcloud_wallet_core_network::{model::ErrorResponse, request},
+    std::{
+        collections::HashMap,
+        time::{SystemTime, UNIX_EPOCH},
+    },
+};
+
+/// Most ids Jupiter Price v3 accepts in one request.
+const JUPITER_BATCH_SIZE: usize = 50;
+
+/// Prices from Jupiter Price v3.
+pub struct JupiterPriceProvider {
+    client: Client,
+    user_agent: String,
+    rate_limiter: RateLimiter,
+}
+
+impl JupiterPriceProvider {
+    pub fn new(user_agent: &str) -> Self {
+        Self::with_client(Client::new(), user_agent)
+    }
+
+    /// Same as [`JupiterPriceProvider::new`], sending requests through `client`.
+    pub fn with_client(client: Client, user_agent: &str) -> Self {
+        JupiterPriceProvider {
+            client,
+            user_agent: user_agent.to_string(),
+            // The free API allows 60 requests per minute.
+            rate_limiter: RateLimiter::new(5, 1.0),
+        }
+    }
+}
+
+#[async_trait]
+impl PriceProvider for JupiterPriceProvider {
+    fn source(&self) -> PriceSource {
+        PriceSource::Jupiter
+    }
+
+    async fn get_prices(
+        &self,
+        mints: &[String],
+    ) -> Result<HashMap<String, AssetPrice>, ErrorResponse> {
+        let mut prices = HashMap::new();
+        for batch in mints.chunks(JUPITER_BATCH_SIZE) {
+            let url = format!(
+                "{}{}?ids={}",
+                JUPITER_BASE_URL,
+                JUPITER_PRICE_PATH,
+                batch.join(",")
+            );
+            debug!("Get {} prices from Jupiter", batch.len());
+            let builder = self.client.get(url).header("User-Agent", &self.user_agent);
+
+            self.rate_limiter.acquire().await;
+            let response: PricesResponse = request(builder).await?;
+            // Jupiter does not say when a price was last updated.
+            let updated_at = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs() as i64)
+                .unwrap_or_default();
+            prices.extend(response.prices.into_iter().map(|(mint, token_price)| {
+                let price = AssetPrice {
+                    mint: mint.clone(),
+                    usd_price: token_price.usd_price,
+                    price_change_24h: Some(token_price.price_change_24h),
+                    source: PriceSource::Jupiter,
+                    updated_at,
+                };
+                (mint, price)
+            }));
+        }
+        Ok(prices)
+    }
+}
@@ -1,2 +1,10 @@
+pub mod birdeye;
 pub mod get_asset_price;
 pub mod get_sol_price;
+pub mod jupiter;
+pub mod price_cache;
+pub mod price_history;
+pub mod price_provider;
+pub mod price_service;
+pub mod rate_limiter;
+pub mod valuation;
@@ -0,0 +1,94 @@
+use {
+    smbcloud_wallet_core_model::models::price::AssetPrice,
+    std::{
+        collections::HashMap,
+        sync::Mutex,
+        time::{Duration, Instant},
+    },
+};
+
+/// Prices kept for `ttl` after they were fetched.
+#[derive(Debug)]
+pub struct PriceCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, (Instant, AssetPrice)>>,
+}
+
+impl PriceCache {
+    pub fn new(ttl: Duration) -> Self {
+        PriceCache {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Fresh cached prices for `mints`.
+    pub fn get(&self, mints: &[String]) -> HashMap<String, AssetPrice> {
+        self.get_at(mints, Instant::now())
+    }
+
+    pub fn insert(&self, prices: &HashMap<String, AssetPrice>) {
+        self.insert_at(prices, Instant::now())
+    }
+
+    fn get_at(&self, mints: &[String], now: Instant) -> HashMap<String, AssetPrice> {
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        mints
+            .iter()
+            .filter_map(|mint| {
+                let (fetched_at, price) = entries.get(mint)?;
+                self.is_fresh(*fetched_at, now)
+                    .then(|| (mint.clone(), price.clone()))
+            })
+            .collect()
+    }
+
+    fn insert_at(&self, prices: &HashMap<String, AssetPrice>, now: Instant) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.retain(|_, (fetched_at, _)| self.is_fresh(*fetched_at, now));
+        for (mint, price) in prices {
+            entries.insert(mint.clone(), (now, price.clone()));
+        }
+    }
+
+    fn is_fresh(&self, fetched_at: Instant, now: Instant) -> bool {
+        now.saturating_duration_since(fetched_at) < self.ttl
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, smbcloud_wallet_core_model::models::price::PriceSource};
+
+    fn price(mint: &str) -> AssetPrice {
+        AssetPrice {
+            mint: mint.to_string(),
+            usd_price: 1.0,
+            price_change_24h: None,
+            source: PriceSource::Jupiter,
+            updated_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_cache_expires() {
+        let ttl = Duration::from_secs(60);
+        let cache = PriceCache::new(ttl);
+        let mints = vec!["a".to_string(), "b".to_string()];
+        let fetched_at = Instant::now();
+        cache.insert_at(&HashMap::from([("a".to_string(), price("a"))]), fetched_at);
+
+        let cached = cache.get_at(&mints, fetched_at + ttl - Duration::from_secs(1));
+        assert_eq!(cached.len(), 1);
+        assert_eq!(cached["a"], price("a"));
+
+        assert!(cache.get_at(&mints, fetched_at + ttl).is_empty());
+
+        // Expired prices are dropped when new ones come in.
+        cache.insert_at(
+            &HashMap::from([("b".to_string(), price("b"))]),
+            fetched_at + ttl,
+        );
+        assert_eq!(cache.entries.lock().unwrap().len(), 1);
+    }
+}
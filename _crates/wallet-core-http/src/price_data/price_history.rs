@@ -0,0 +1,116 @@
+use {
+    crate::price_data::birdeye::BIRDEYE_RATE_LIMITER,
+    async_trait::async_trait,
+    log::debug,
+    reqwest::Client,
+    smbcloud_wallet_constants::constants::{BIRDEYE_BASE_URL, BIRDEYE_PRICE_HISTORY_PATH},
+    smbcloud_wallet_core_model::models::{
+        birdeye_price_response::BirdeyePriceHistoryResponse, price::PricePoint,
+    },
+    smbcloud_wallet_core_network::{
+        model::{ErrorCode, ErrorResponse},
+        request,
+    },
+};
+
+/// A source of past USD token prices.
+#[async_trait]
+pub trait PriceHistoryProvider: Send + Sync {
+    /// Prices of `mint` between `from` and `to`, oldest first.
+    ///
+    /// `interval` is the wanted spacing of the points in seconds, providers
+    /// round it to the closest interval they support.
+    async fn price_history(
+        &self,
+        mint: &str,
+        from: i64,
+        to: i64,
+        interval: i64,
+    ) -> Result<Vec<PricePoint>, ErrorResponse>;
+}
+
+/// Past prices from the Birdeye `history_price` endpoint.
+pub struct BirdeyePriceHistoryProvider {
+    api_key: String,
+    user_agent: String,
+}
+
+impl BirdeyePriceHistoryProvider {
+    pub fn new(api_key: &str, user_agent: &str) -> Self {
+        BirdeyePriceHistoryProvider {
+            api_key: api_key.to_string(),
+            user_agent: user_agent.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl PriceHistoryProvider for BirdeyePriceHistoryProvider {
+    async fn price_history(
+        &self,
+        mint: &str,
+        from: i64,
+        to: i64,
+        interval: i64,
+    ) -> Result<Vec<PricePoint>, ErrorResponse> {
+        let url = format!(
+            "{}{}?address={}&address_type=token&type={}&time_from={}&time_to={}",
+            BIRDEYE_BASE_URL,
+            BIRDEYE_PRICE_HISTORY_PATH,
+            mint,
+            birdeye_interval(interval),
+            from,
+            to
+        );
+        debug!("Get price history of {} from Birdeye", mint);
+        let builder = Client::new()
+            .get(url)
+            .header("X-API-KEY", &self.api_key)
+            .header("x-chain", "solana")
+            .header("User-Agent", &self.user_agent);
+
+        BIRDEYE_RATE_LIMITER.acquire().await;
+        let response: BirdeyePriceHistoryResponse = request(builder).await?;
+        if !response.success {
+            return Err(ErrorResponse::Error {
+                code: ErrorCode::NetworkError,
+                message: "Birdeye returned no price history".to_string(),
+            });
+        }
+        let mut points: Vec<PricePoint> = response
+            .data
+            .items
+            .into_iter()
+            .map(|item| PricePoint {
+                timestamp: item.unix_time,
+                usd_price: item.value,
+            })
+            .collect();
+        points.sort_by_key(|point| point.timestamp);
+        Ok(points)
+    }
+}
+
+/// The Birdeye candle type closest to `interval` seconds.
+fn birdeye_interval(interval: i64) -> &'static str {
+    match interval {
+        i if i <= 15 * 60 => "15m",
+        i if i <= 60 * 60 => "1H",
+        i if i <= 4 * 60 * 60 => "4H",
+        i if i <= 12 * 60 * 60 => "12H",
+        _ => "1D",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_birdeye_interval() {
+        assert_eq!(birdeye_interval(5 * 60), "15m");
+        assert_eq!(birdeye_interval(60 * 60), "1H");
+        assert_eq!(birdeye_interval(6 * 60 * 60), "12H");
+        assert_eq!(birdeye_interval(7 * 24 * 60 * 60), "1D");
+    }
+}
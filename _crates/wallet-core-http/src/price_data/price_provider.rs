@@ -0,0 +1,20 @@
+use {
+    async_trait::async_trait,
+    smbcloud_wallet_core_model::models::price::{AssetPrice, PriceSource},
+    smbcloud_wallet_core_network::model::ErrorResponse,
+    std::collections::HashMap,
+};
+
+/// A source of USD token prices.
+#[async_trait]
+pub trait PriceProvider: Send + Sync {
+    fn source(&self) -> PriceSource;
+
+    /// Fetch the prices of `mints`, keyed by mint.
+    ///
+    /// Mints the provider has no price for are left out of the result.
+    async fn get_prices(
+        &self,
+        mints: &[String],
+    ) -> Result<HashMap<String, AssetPrice>, ErrorResponse>;
+}
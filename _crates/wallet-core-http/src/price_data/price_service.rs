@@ -0,0 +1,240 @@
+use {
+    crate::{
+        price_data::{
+            birdeye::BirdeyePriceProvider, jupiter::JupiterPriceProvider, price_cache::PriceCache,
+            price_provider::PriceProvider,
+        },
+        shared::Shared,
+    },
+    log::{debug, warn},
+    smbcloud_wallet_core_model::models::price::AssetPrice,
+    smbcloud_wallet_core_network::model::{ErrorCode, ErrorResponse},
+    std::{collections::HashMap, time::Duration},
+};
+
+/// How long a fetched price is reused.
+pub const PRICE_CACHE_TTL: Duration = Duration::from_secs(60);
+
+static SHARED_PRICE_SERVICES: Shared<(String, String), PriceService> = Shared::new();
+static PUBLIC_PRICE_SERVICES: Shared<String, PriceService> = Shared::new();
+
+/// Cached prices from a list of providers, tried in order.
+///
+/// Mints a provider fails on or has no price for are asked from the next
+/// provider.
+pub struct PriceService {
+    providers: Vec<Box<dyn PriceProvider>>,
+    cache: PriceCache,
+}
+
+impl PriceService {
+    pub fn new(providers: Vec<Box<dyn PriceProvider>>, ttl: Duration) -> Self {
+        PriceService {
+            providers,
+            cache: PriceCache::new(ttl),
+        }
+    }
+
+    /// The process wide service of an API key and user agent: Birdeye first,
+    /// Jupiter as fallback.
+    pub fn shared(api_key: &str, user_agent: &str) -> &'static PriceService {
+        let key = (api_key.to_string(), user_agent.to_string());
+        SHARED_PRICE_SERVICES.get_or_init(key, || {
+            PriceService::new(
+                vec![
+                    Box::new(BirdeyePriceProvider::new(api_key, user_agent)),
+                    Box::new(JupiterPriceProvider::new(user_agent)),
+                ],
+                PRICE_CACHE_TTL,
+            )
+        })
+    }
+
+    /// The process wide service of a user agent for callers without a Birdeye
+    /// API key: Jupiter only.
+    pub fn public(user_agent: &str) -> &'static PriceService {
+        PUBLIC_PRICE_SERVICES.get_or_init(user_agent.to_string(), || {
+            PriceService::new(
+                vec![Box::new(JupiterPriceProvider::new(user_agent))],
+                PRICE_CACHE_TTL,
+            )
+        })
+    }
+
+    /// Prices of `mints`, keyed by mint. Mints no provider knows are left out.
+    ///
+    /// Only fails when every provider failed.
+    pub async fn get_prices(
+        &self,
+        mints: &[String],
+    ) -> Result<HashMap<String, AssetPrice>, ErrorResponse> {
+        let mut mints = mints.to_vec();
+        mints.sort();
+        mints.dedup();
+
+        let mut prices = self.cache.get(&mints);
+        let mut missing: Vec<String> = mints
+            .into_iter()
+            .filter(|mint| !prices.contains_key(mint))
+            .collect();
+        debug!("{} cached prices, {} to fetch", prices.len(), missing.len());
+
+        let mut last_error = None;
+        let mut any_succeeded = false;
+        for provider in &self.providers {
+            if missing.is_empty() {
+                break;
+            }
+            match provider.get_prices(&missing).await {
+                Ok(fetched) => {
+                    any_succeeded = true;
+                    self.cache.insert(&fetched);
+                    missing.retain(|mint| !fetched.contains_key(mint));
+                    prices.extend(fetched);
+                }
+                Err(e) => {
+                    warn!("{:?} price provider failed: {}", provider.source(), e);
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        match last_error {
+            Some(e) if !any_succeeded && prices.is_empty() => Err(e),
+            _ => Ok(prices),
+        }
+    }
+
+    /// Price of a single mint.
+    pub async fn get_price(&self, mint: &str) -> Result<AssetPrice, ErrorResponse> {
+        let mut prices = self.get_prices(&[mint.to_string()]).await?;
+        prices.remove(mint).ok_or_else(|| ErrorResponse::Error {
+            code: ErrorCode::BalanceError,
+            message: "No price data available".to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        async_trait::async_trait,
+        smbcloud_wallet_core_model::models::price::PriceSource,
+        std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+    };
+
+    /// A provider with fixed prices that counts the mints it is asked for.
+    struct FixtureProvider {
+        source: PriceSource,
+        prices: HashMap<String, f64>,
+        fail: bool,
+        requested: Arc<AtomicUsize>,
+    }
+
+    impl FixtureProvider {
+        fn new(source: PriceSource, prices: &[(&str, f64)]) -> Self {
+            FixtureProvider {
+                source,
+                prices: prices
+                    .iter()
+                    .map(|(mint, price)| (mint.to_string(), *price))
+                    .collect(),
+                fail: false,
+                requested: Arc::new(AtomicUsize::new(0)),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl PriceProvider for FixtureProvider {
+        fn source(&self) -> PriceSource {
+            self.source
+        }
+
+        async fn get_prices(
+            &self,
+            mints: &[String],
+        ) -> Result<HashMap<String, AssetPrice>, ErrorResponse> {
+            self.requested.fetch_add(mints.len(), Ordering::SeqCst);
+            if self.fail {
+                return Err(ErrorResponse::Error {
+                    code: ErrorCode::NetworkError,
+                    message: "unavailable".to_string(),
+                });
+            }
+            Ok(mints
+                .iter()
+                .filter_map(|mint| {
+                    let price = AssetPrice {
+                        mint: mint.clone(),
+                        usd_price: *self.prices.get(mint)?,
+                        price_change_24h: None,
+                        source: self.source,
+                        updated_at: 0,
+                    };
+                    Some((mint.clone(), price))
+                })
+                .collect())
+        }
+    }
+
+    fn mints(mints: &[&str]) -> Vec<String> {
+        mints.iter().map(|mint| mint.to_string()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_for_missing_mints() {
+        let primary = FixtureProvider::new(PriceSource::Birdeye, &[("sol", 150.0)]);
+        let fallback = FixtureProvider::new(PriceSource::Jupiter, &[("sol", 1.0), ("bach", 0.5)]);
+        let fallback_requested = fallback.requested.clone();
+        let service =
+            PriceService::new(vec![Box::new(primary), Box::new(fallback)], PRICE_CACHE_TTL);
+
+        let prices = service
+            .get_prices(&mints(&["sol", "bach", "unknown", "sol"]))
+            .await
+            .unwrap();
+        assert_eq!(prices.len(), 2);
+        assert_eq!(prices["sol"].usd_price, 150.0);
+        assert_eq!(prices["sol"].source, PriceSource::Birdeye);
+        assert_eq!(prices["bach"].source, PriceSource::Jupiter);
+        // Only the mints the primary could not price are asked from the fallback.
+        assert_eq!(fallback_requested.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_when_provider_fails() {
+        let mut primary = FixtureProvider::new(PriceSource::Birdeye, &[("sol", 150.0)]);
+        primary.fail = true;
+        let fallback = FixtureProvider::new(PriceSource::Jupiter, &[("sol", 149.0)]);
+        let service =
+            PriceService::new(vec![Box::new(primary), Box::new(fallback)], PRICE_CACHE_TTL);
+
+        let price = service.get_price("sol").await.unwrap();
+        assert_eq!(price.usd_price, 149.0);
+        assert_eq!(price.source, PriceSource::Jupiter);
+    }
+
+    #[tokio::test]
+    async fn test_fails_when_every_provider_fails() {
+        let mut primary = FixtureProvider::new(PriceSource::Birdeye, &[]);
+        primary.fail = true;
+        let service = PriceService::new(vec![Box::new(primary)], PRICE_CACHE_TTL);
+        assert!(service.get_prices(&mints(&["sol"])).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_serves_cached_prices() {
+        let primary = FixtureProvider::new(PriceSource::Birdeye, &[("sol", 150.0)]);
+        let requested = primary.requested.clone();
+        let service = PriceService::new(vec![Box::new(primary)], PRICE_CACHE_TTL);
+
+        service.get_price("sol").await.unwrap();
+        service.get_price("sol").await.unwrap();
+        assert_eq!(requested.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,87 @@
+use {
+    std::{sync::Mutex, time::Duration},
+    tokio::time::{sleep, Instant},
+};
+
+/// Token bucket rate limiter.
+///
+/// The bucket holds up to `capacity` requests and refills `per_second`
+/// requests every second, so short bursts go out right away and sustained
+/// traffic is spread out instead of hitting the API's `429`.
+#[derive(Debug)]
+pub struct RateLimiter {
+    capacity: f64,
+    per_second: f64,
+    bucket: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl RateLimiter {
+    pub fn new(capacity: u32, per_second: f64) -> Self {
+        RateLimiter {
+            capacity: capacity as f64,
+            per_second,
+            bucket: Mutex::new(Bucket {
+                tokens: capacity as f64,
+                refilled_at: Instant::now(),
+            }),
+        }
+    }
+
+    /// Wait until a request may be sent and take its token.
+    pub async fn acquire(&self) {
+        while let Some(wait) = self.try_acquire() {
+            sleep(wait).await;
+        }
+    }
+
+    /// Take a token, or return how long to wait for the next one.
+    fn try_acquire(&self) -> Option<Duration> {
+        let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.per_second).min(self.capacity);
+        bucket.refilled_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            None
+        } else {
+            let missing = 1.0 - bucket.tokens;
+            Some(Duration::from_secs_f64(missing / self.per_second))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_burst_then_refill() {
+        let limiter = RateLimiter::new(2, 20.0);
+        let started = Instant::now();
+        limiter.acquire().await;
+        limiter.acquire().await;
+        // The burst is free.
+        assert_eq!(started.elapsed(), Duration::ZERO);
+
+        // The third request waits for a refill of 1 / 20 s.
+        limiter.acquire().await;
+        assert!(started.elapsed() >= Duration::from_millis(45));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_bucket_never_exceeds_capacity() {
+        let limiter = RateLimiter::new(1, 100.0);
+        // Long enough to refill five tokens without the cap.
+        tokio::time::advance(Duration::from_millis(50)).await;
+        assert!(limiter.try_acquire().is_none());
+        assert!(limiter.try_acquire().is_some());
+    }
+}
@@ -0,0 +1,55 @@
+use {
+    crate::{fx_data::fx_service::FxService, price_data::price_service::PriceService},
+    smbcloud_wallet_core_model::models::{
+        currency::FiatCurrency,
+        portfolio::{Holding, PortfolioValuation},
+    },
+    smbcloud_wallet_core_network::model::ErrorResponse,
+    std::{
+        collections::HashMap,
+        time::{SystemTime, UNIX_EPOCH},
+    },
+};
+
+/// Price `holdings` with `price_service` and value them in `currency`.
+pub async fn value_holdings(
+    holdings: Vec<Holding>,
+    currency: FiatCurrency,
+    price_service: &PriceService,
+    user_agent: &str,
+) -> Result<PortfolioValuation, ErrorResponse> {
+    value_holdings_with(
+        holdings,
+        currency,
+        price_service,
+        FxService::shared(user_agent),
+    )
+    .await
+}
+
+/// Same as [`value_holdings`], converting from USD with `fx_service`.
+pub async fn value_holdings_with(
+    holdings: Vec<Holding>,
+    currency: FiatCurrency,
+    price_service: &PriceService,
+    fx_service: &FxService,
+) -> Result<PortfolioValuation, ErrorResponse> {
+    let mints: Vec<String> = holdings
+        .iter()
+        .map(|holding| holding.mint.clone())
+        .collect();
+    let prices = if mints.is_empty() {
+        HashMap::new()
+    } else {
+        price_service.get_prices(&mints).await?
+    };
+    let usd_rate = fx_service.usd_rate(currency).await?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or_default();
+
+    Ok(PortfolioValuation::new(
+        holdings, &prices, currency, usd_rate, now,
+    ))
+}
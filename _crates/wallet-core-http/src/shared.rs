@@ -0,0 +1,49 @@
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    sync::{Mutex, OnceLock},
+};
+
+/// Process wide values, one per key, that live as long as the process.
+///
+/// Keys are configurations such as an API key and a user agent, a process
+/// only ever uses a handful of them.
+pub(crate) struct Shared<K, V: 'static> {
+    values: OnceLock<Mutex<HashMap<K, &'static V>>>,
+}
+
+impl<K: Eq + Hash, V> Shared<K, V> {
+    pub(crate) const fn new() -> Self {
+        Shared {
+            values: OnceLock::new(),
+        }
+    }
+
+    /// The value of `key`, built with `init` on first use.
+    pub(crate) fn get_or_init(&self, key: K, init: impl FnOnce() -> V) -> &'static V {
+        let mut values = self
+            .values
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        values
+            .entry(key)
+            .or_insert_with(|| Box::leak(Box::new(init())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_one_value_per_key() {
+        static SHARED: Shared<&str, String> = Shared::new();
+        let first = SHARED.get_or_init("a", || "first".to_string());
+        let again = SHARED.get_or_init("a", || "again".to_string());
+        let other = SHARED.get_or_init("b", || "other".to_string());
+        assert!(std::ptr::eq(first, again));
+        assert_eq!(again, "first");
+        assert_eq!(other, "other");
+    }
+}
@@ -1,29 +1,47 @@
 use {
-    reqwest::Client,
-    smbcloud_wallet_constants::constants::XLP_WALLET_BALANCE_PATH,
-    smbcloud_wallet_core_model::models::{environment::Environment, xlp::WalletBalanceResponse},
-    smbcloud_wallet_core_network::{model::ErrorResponse, request},
+    crate::{
+        price_data::{price_service::PriceService, valuation::value_holdings},
+        xlp::get_wallet_assets_balance::wallet_token_list,
+    },
+    log::debug,
+    smbcloud_wallet_core_model::models::{
+        currency::FiatCurrency,
+        environment::Environment,
+        portfolio::{Holding, PortfolioValuation},
+    },
+    smbcloud_wallet_core_network::model::ErrorResponse,
 };
 
+/// Value the assets XLP lists for the wallet in `currency`.
+///
+/// Prices come from the public price service, XLP API keys are not valid for Birdeye.
 pub async fn wallet_balance(
     base_url: &str,
     environment: Environment,
     wallet_address: &str,
     api_key: &str,
     user_agent: &str,
-) -> Result<WalletBalanceResponse, ErrorResponse> {
-    println!("🦀🦀  Get asset price");
+    currency: FiatCurrency,
+) -> Result<PortfolioValuation, ErrorResponse> {
+    debug!("Getting wallet balance of {}", wallet_address);
 
-    let url = format!(
-        "{}{}?wallet={}&environment={}",
-        base_url, XLP_WALLET_BALANCE_PATH, wallet_address, environment
-    );
-    println!("🦀🦀  URL: {}", url);
-    let client = Client::new()
-        .get(url)
-        .header("X-API-KEY", api_key)
-        .header("x-chain", "Solana")
-        .header("User-Agent", user_agent);
+    let token_list =
+        wallet_token_list(base_url, environment, wallet_address, api_key, user_agent).await?;
+    let holdings = token_list
+        .data
+        .into_iter()
+        .map(|token| Holding {
+            mint: token.meta.address,
+            symbol: Some(token.meta.symbol),
+            amount: token.ui_amount,
+        })
+        .collect();
 
-    request(client).await
+    value_holdings(
+        holdings,
+        currency,
+        PriceService::public(user_agent),
+        user_agent,
+    )
+    .await
 }
@@ -1,3 +1,4 @@
 uniffi::setup_scaffolding!();
 
 pub mod models;
+pub mod token_registry;
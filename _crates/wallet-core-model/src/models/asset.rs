@@ -1,5 +0,0 @@
-use crate::models::asset_solana::SolanaAsset;
-
-pub enum Asset {
-    Solana(SolanaAsset),
-}
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use tsync::tsync;
 
-use crate::models::asset_metadata::Metadata;
+use crate::models::{asset_metadata::Metadata, token_preferences::TokenVisibility};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[tsync]
@@ -12,4 +12,7 @@ pub struct BalanceV1 {
     pub balance: u64,
     /// Balance in its easy-to-read form. For example, a 0.01 SOL.
     pub ui_amount: f64,
+    /// Whether the token is listed, see the token preferences.
+    #[serde(default)]
+    pub visibility: TokenVisibility,
 }
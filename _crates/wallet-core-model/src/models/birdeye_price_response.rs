@@ -86,6 +86,45 @@ impl BirdeyePriceData {
     }
 }
 
+/// Response of the Birdeye `multi_price` endpoint, keyed by mint.
+///
+/// Mints without a price map to `null`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BirdeyeMultiPriceResponse {
+    pub data: HashMap<String, Option<BirdeyeMultiPriceData>>,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BirdeyeMultiPriceData {
+    pub value: f64,
+
+    #[serde(rename = "updateUnixTime")]
+    pub update_unix_time: i64,
+
+    #[serde(rename = "priceChange24h")]
+    pub price_change_24h: Option<f64>,
+}
+
+/// Response of the Birdeye `history_price` endpoint.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BirdeyePriceHistoryResponse {
+    pub data: BirdeyePriceHistoryData,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BirdeyePriceHistoryData {
+    pub items: Vec<BirdeyePriceHistoryItem>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BirdeyePriceHistoryItem {
+    #[serde(rename = "unixTime")]
+    pub unix_time: i64,
+    pub value: f64,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Record)]
 pub struct Price {
     pub symbol: String,
@@ -217,7 +256,7 @@ mod tests {
         assert_eq!(token_price.formatted_usd_price(), "$147.48");
 
         let small_price_token = TokenPrice {
-            usd_price: 0.0012345,
+            usd_price: 0.0012346,
             block_id: 348004023,
             decimals: 6,
             price_change_24h: -2.5,
@@ -254,6 +293,59 @@ mod tests {
         assert_eq!(response.data.price_in_native, 0.0008419589807401728);
     }
 
+    #[test]
+    fn test_deserialize_birdeye_multi_price_response() {
+        let json = r#"
+        {
+            "data": {
+                "So11111111111111111111111111111111111111112": {
+                    "value": 147.47,
+                    "updateUnixTime": 1756693582,
+                    "updateHumanTime": "2025-09-01T02:26:22",
+                    "priceChange24h": 1.29,
+                    "priceInNative": 1
+                },
+                "Bach1111111111111111111111111111111111111111": null
+            },
+            "success": true
+        }
+        "#;
+
+        let response: BirdeyeMultiPriceResponse = serde_json::from_str(json).unwrap();
+
+        assert!(response.success);
+        assert_eq!(response.data.len(), 2);
+        let sol = response.data["So11111111111111111111111111111111111111112"]
+            .as_ref()
+            .unwrap();
+        assert_eq!(sol.value, 147.47);
+        assert_eq!(sol.update_unix_time, 1756693582);
+        assert_eq!(sol.price_change_24h, Some(1.29));
+        assert!(response.data["Bach1111111111111111111111111111111111111111"].is_none());
+    }
+
+    #[test]
+    fn test_deserialize_birdeye_price_history_response() {
+        let json = r#"
+        {
+            "data": {
+                "items": [
+                    { "address": "So11111111111111111111111111111111111111112", "unixTime": 1756684800, "value": 201.12 },
+                    { "address": "So11111111111111111111111111111111111111112", "unixTime": 1756688400, "value": 202.5 }
+                ]
+            },
+            "success": true
+        }
+        "#;
+
+        let response: BirdeyePriceHistoryResponse = serde_json::from_str(json).unwrap();
+
+        assert!(response.success);
+        assert_eq!(response.data.items.len(), 2);
+        assert_eq!(response.data.items[1].unix_time, 1756688400);
+        assert_eq!(response.data.items[1].value, 202.5);
+    }
+
     #[test]
     fn test_birdeye_price_data_methods() {
         let price_data = BirdeyePriceData {
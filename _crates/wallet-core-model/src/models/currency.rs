@@ -0,0 +1,249 @@
+use {
+    log::warn,
+    serde::{Deserialize, Serialize},
+    serde_json::Value,
+    std::fmt::Display,
+    tsync::tsync,
+};
+
+/// The fiat currencies balances and prices can be shown in, by ISO 4217 code.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, uniffi::Enum,
+)]
+#[tsync]
+pub enum FiatCurrency {
+    #[default]
+    USD,
+    EUR,
+    GBP,
+    JPY,
+    CNY,
+    IDR,
+    SEK,
+    NOK,
+    DKK,
+    CHF,
+    PLN,
+    CZK,
+    HUF,
+    TRY,
+    CAD,
+    MXN,
+    BRL,
+    ARS,
+    CLP,
+    COP,
+    AUD,
+    NZD,
+    SGD,
+    HKD,
+    TWD,
+    KRW,
+    INR,
+    MYR,
+    THB,
+    PHP,
+    VND,
+    AED,
+    SAR,
+    ILS,
+    ZAR,
+    NGN,
+}
+
+impl FiatCurrency {
+    pub const ALL: [FiatCurrency; 36] = [
+        Self::USD,
+        Self::EUR,
+        Self::GBP,
+        Self::JPY,
+        Self::CNY,
+        Self::IDR,
+        Self::SEK,
+        Self::NOK,
+        Self::DKK,
+        Self::CHF,
+        Self::PLN,
+        Self::CZK,
+        Self::HUF,
+        Self::TRY,
+        Self::CAD,
+        Self::MXN,
+        Self::BRL,
+        Self::ARS,
+        Self::CLP,
+        Self::COP,
+        Self::AUD,
+        Self::NZD,
+        Self::SGD,
+        Self::HKD,
+        Self::TWD,
+        Self::KRW,
+        Self::INR,
+        Self::MYR,
+        Self::THB,
+        Self::PHP,
+        Self::VND,
+        Self::AED,
+        Self::SAR,
+        Self::ILS,
+        Self::ZAR,
+        Self::NGN,
+    ];
+
+    /// The ISO 4217 code, e.g. `IDR`.
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::USD => "USD",
+            Self::EUR => "EUR",
+            Self::GBP => "GBP",
+            Self::JPY => "JPY",
+            Self::CNY => "CNY",
+            Self::IDR => "IDR",
+            Self::SEK => "SEK",
+            Self::NOK => "NOK",
+            Self::DKK => "DKK",
+            Self::CHF => "CHF",
+            Self::PLN => "PLN",
+            Self::CZK => "CZK",
+            Self::HUF => "HUF",
+            Self::TRY => "TRY",
+            Self::CAD => "CAD",
+            Self::MXN => "MXN",
+            Self::BRL => "BRL",
+            Self::ARS => "ARS",
+            Self::CLP => "CLP",
+            Self::COP => "COP",
+            Self::AUD => "AUD",
+            Self::NZD => "NZD",
+            Self::SGD => "SGD",
+            Self::HKD => "HKD",
+            Self::TWD => "TWD",
+            Self::KRW => "KRW",
+            Self::INR => "INR",
+            Self::MYR => "MYR",
+            Self::THB => "THB",
+            Self::PHP => "PHP",
+            Self::VND => "VND",
+            Self::AED => "AED",
+            Self::SAR => "SAR",
+            Self::ILS => "ILS",
+            Self::ZAR => "ZAR",
+            Self::NGN => "NGN",
+        }
+    }
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Self::USD => "$",
+            Self::EUR => "€",
+            Self::GBP => "£",
+            Self::JPY | Self::CNY => "¥",
+            Self::IDR => "Rp",
+            Self::SEK | Self::NOK | Self::DKK => "kr",
+            Self::CHF => "CHF ",
+            Self::PLN => "zł",
+            Self::CZK => "Kč",
+            Self::HUF => "Ft",
+            Self::TRY => "₺",
+            Self::CAD => "CA$",
+            Self::MXN => "MX$",
+            Self::BRL => "R$",
+            Self::ARS => "AR$",
+            Self::CLP => "CL$",
+            Self::COP => "CO$",
+            Self::AUD => "A$",
+            Self::NZD => "NZ$",
+            Self::SGD => "S$",
+            Self::HKD => "HK$",
+            Self::TWD => "NT$",
+            Self::KRW => "₩",
+            Self::INR => "₹",
+            Self::MYR => "RM",
+            Self::THB => "฿",
+            Self::PHP => "₱",
+            Self::VND => "₫",
+            Self::AED => "AED ",
+            Self::SAR => "SAR ",
+            Self::ILS => "₪",
+            Self::ZAR => "R",
+            Self::NGN => "₦",
+        }
+    }
+
+    /// Digits after the decimal point, following the ISO 4217 minor unit.
+    pub fn decimals(self) -> usize {
+        match self {
+            Self::JPY | Self::KRW | Self::VND | Self::CLP => 0,
+            _ => 2,
+        }
+    }
+
+    /// Format an amount in this currency, e.g. `Rp15000.00`.
+    pub fn format_amount(self, amount: f64) -> String {
+        format!("{}{:.*}", self.symbol(), self.decimals(), amount)
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|currency| currency.code().eq_ignore_ascii_case(code.trim()))
+    }
+
+    /// Read a stored currency code, falling back to USD.
+    pub fn from_value(value: Value) -> Self {
+        let code = match serde_json::from_value::<String>(value) {
+            Ok(code) => code,
+            Err(e) => {
+                warn!("Cannot determine fiat currency: {}", e);
+                return FiatCurrency::USD;
+            }
+        };
+        Self::from_code(&code).unwrap_or_else(|| {
+            warn!(
+                "Cannot determine fiat currency from '{}'. Default to USD.",
+                code
+            );
+            FiatCurrency::USD
+        })
+    }
+}
+
+impl Display for FiatCurrency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codes_round_trip() {
+        for currency in FiatCurrency::ALL {
+            assert_eq!(FiatCurrency::from_code(currency.code()), Some(currency));
+            // The serialized form is the ISO code as well.
+            assert_eq!(
+                serde_json::to_value(currency).unwrap(),
+                Value::String(currency.code().to_string())
+            );
+        }
+        assert_eq!(FiatCurrency::from_code(" idr"), Some(FiatCurrency::IDR));
+        assert_eq!(FiatCurrency::from_code("XXX"), None);
+    }
+
+    #[test]
+    fn test_from_value() {
+        assert_eq!(FiatCurrency::from_value("SEK".into()), FiatCurrency::SEK);
+        assert_eq!(FiatCurrency::from_value("XXX".into()), FiatCurrency::USD);
+        assert_eq!(FiatCurrency::from_value(Value::Null), FiatCurrency::USD);
+    }
+
+    #[test]
+    fn test_format_amount() {
+        assert_eq!(FiatCurrency::USD.format_amount(12.346), "$12.35");
+        assert_eq!(FiatCurrency::IDR.format_amount(195000.0), "Rp195000.00");
+        assert_eq!(FiatCurrency::JPY.format_amount(1520.6), "¥1521");
+    }
+}
@@ -0,0 +1,167 @@
+use {
+    crate::models::{environment::Environment, rpc_endpoint::host},
+    serde::{Deserialize, Serialize},
+    smbcloud_wallet_core_network::model::{ErrorCode, ErrorResponse},
+    tsync::tsync,
+};
+
+/// An RPC provider or cluster added by the user.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[tsync]
+pub struct CustomNetwork {
+    pub name: String,
+    /// Cluster the network serves. Its endpoint is tried before the built-in
+    /// ones of the cluster, or instead of them for `Local`.
+    pub environment: Environment,
+    pub rpc_url: String,
+    /// WebSocket endpoint, derived from `rpc_url` when not set.
+    pub ws_url: Option<String>,
+    /// Genesis hash the endpoint must return from `getGenesisHash`.
+    pub genesis_hash: String,
+}
+
+impl CustomNetwork {
+    /// WebSocket endpoint of the network.
+    pub fn ws_url(&self) -> String {
+        match &self.ws_url {
+            Some(ws_url) => ws_url.clone(),
+            None => websocket_url(&self.rpc_url),
+        }
+    }
+
+    /// Checks that need no request, the endpoint itself is checked with
+    /// `verify_network` of the wallet kit.
+    pub fn validate(&self) -> Result<(), ErrorResponse> {
+        if self.name.trim().is_empty() {
+            return Err(ErrorResponse::Error {
+                code: ErrorCode::ParseError,
+                message: "The network has no name".to_string(),
+            });
+        }
+        if !has_scheme(&self.rpc_url, &["http://", "https://"]) {
+            return Err(invalid_url(&self.rpc_url));
+        }
+        if let Some(ws_url) = &self.ws_url {
+            if !has_scheme(ws_url, &["ws://", "wss://"]) {
+                return Err(invalid_url(ws_url));
+            }
+        }
+        match self.environment.genesis_hash() {
+            Some(genesis_hash) if genesis_hash != self.genesis_hash => Err(ErrorResponse::Error {
+                code: ErrorCode::GenesisMismatch,
+                message: format!(
+                    "The genesis hash of {} is {}, not {}",
+                    self.environment, genesis_hash, self.genesis_hash
+                ),
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// WebSocket endpoint of the node serving `rpc_url`. It is on the same host,
+/// and on the next port when the port is explicit, as `solana-test-validator`
+/// serves it.
+pub fn websocket_url(rpc_url: &str) -> String {
+    let (scheme, rest) = if let Some(rest) = rpc_url.strip_prefix("https://") {
+        ("wss://", rest)
+    } else if let Some(rest) = rpc_url.strip_prefix("http://") {
+        ("ws://", rest)
+    } else {
+        return rpc_url.to_string();
+    };
+    let end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    let (authority, path) = rest.split_at(end);
+    let authority = match authority.rsplit_once(':') {
+        Some((host, port)) => match port.parse::<u16>() {
+            Ok(port) => format!("{}:{}", host, port.saturating_add(1)),
+            Err(_) => authority.to_string(),
+        },
+        None => authority.to_string(),
+    };
+    format!("{}{}{}", scheme, authority, path)
+}
+
+fn has_scheme(url: &str, schemes: &[&str]) -> bool {
+    schemes
+        .iter()
+        .any(|scheme| url.len() > scheme.len() && url.starts_with(scheme))
+}
+
+fn invalid_url(url: &str) -> ErrorResponse {
+    ErrorResponse::Error {
+        code: ErrorCode::InvalidRpcUrl,
+        message: format!("Invalid endpoint: {}", host(url)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, smbcloud_wallet_constants::rpc::MAINNET_GENESIS_HASH};
+
+    fn network() -> CustomNetwork {
+        CustomNetwork {
+            name: "Helius".to_string(),
+            environment: Environment::Mainnet,
+            rpc_url: "https://mainnet.helius-rpc.com/?api-key=secret".to_string(),
+            ws_url: None,
+            genesis_hash: MAINNET_GENESIS_HASH.to_string(),
+        }
+    }
+
+    fn error_code(result: Result<(), ErrorResponse>) -> Option<ErrorCode> {
+        result.err().map(|ErrorResponse::Error { code, .. }| code)
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(network().validate().is_ok());
+
+        let mut invalid = network();
+        invalid.rpc_url = "mainnet.helius-rpc.com".to_string();
+        assert!(matches!(
+            error_code(invalid.validate()),
+            Some(ErrorCode::InvalidRpcUrl)
+        ));
+
+        let mut invalid = network();
+        invalid.ws_url = Some("https://mainnet.helius-rpc.com".to_string());
+        assert!(matches!(
+            error_code(invalid.validate()),
+            Some(ErrorCode::InvalidRpcUrl)
+        ));
+    }
+
+    #[test]
+    fn test_validate_genesis_hash() {
+        // A devnet genesis hash for a mainnet network.
+        let mut wrong_cluster = network();
+        wrong_cluster.genesis_hash = Environment::Devnet.genesis_hash().unwrap().to_string();
+        assert!(matches!(
+            error_code(wrong_cluster.validate()),
+            Some(ErrorCode::GenesisMismatch)
+        ));
+
+        // Local clusters have their own.
+        let mut local = network();
+        local.environment = Environment::Local;
+        local.genesis_hash = "4sGjMW1sUnHzSxGspuhpqLDx6wiyjNtZAMdL4VZHirAn".to_string();
+        assert!(local.validate().is_ok());
+    }
+
+    #[test]
+    fn test_ws_url() {
+        assert_eq!(
+            network().ws_url(),
+            "wss://mainnet.helius-rpc.com/?api-key=secret"
+        );
+        assert_eq!(
+            websocket_url("http://localhost:8899/"),
+            "ws://localhost:8900/"
+        );
+
+        let mut network = network();
+        network.ws_url = Some("wss://ws.example.com".to_string());
+        assert_eq!(network.ws_url(), "wss://ws.example.com");
+    }
+}
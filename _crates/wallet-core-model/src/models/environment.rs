@@ -3,13 +3,15 @@ use {
     serde::{Deserialize, Serialize},
     serde_json::Value,
     smbcloud_wallet_constants::rpc::{
-        devnet_rpc_url, local_rpc_url, mainnet_rpc_url, testnet_rpc_url,
+        devnet_rpc_url, devnet_rpc_urls, local_rpc_url, mainnet_rpc_url, mainnet_rpc_urls,
+        testnet_rpc_url, testnet_rpc_urls, DEVNET_GENESIS_HASH, MAINNET_GENESIS_HASH,
+        TESTNET_GENESIS_HASH,
     },
     std::fmt::Display,
     tsync::tsync,
 };
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, uniffi::Enum)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, uniffi::Enum)]
 #[tsync]
 pub enum Environment {
     Local,
@@ -39,6 +41,7 @@ impl Environment {
             }
         };
         match env.as_str() {
+            "Local" => Environment::Local,
             "Devnet" => Environment::Devnet,
             "Testnet" => Environment::Testnet,
             "Mainnet" => Environment::Mainnet,
@@ -63,6 +66,7 @@ impl Environment {
             _ => Self::Mainnet,
         }
     }
+    /// The preferred RPC endpoint of the environment.
     pub fn rpc_url(self) -> String {
         match self {
             Self::Local => local_rpc_url(),
@@ -71,6 +75,27 @@ impl Environment {
             Self::Mainnet => mainnet_rpc_url(),
         }
     }
+
+    /// Every RPC endpoint of the environment, by preference.
+    pub fn rpc_urls(self) -> Vec<String> {
+        match self {
+            Self::Local => vec![local_rpc_url()],
+            Self::Devnet => devnet_rpc_urls(),
+            Self::Testnet => testnet_rpc_urls(),
+            Self::Mainnet => mainnet_rpc_urls(),
+        }
+    }
+
+    /// Genesis hash of the public cluster, `None` for a local one, which has
+    /// its own.
+    pub fn genesis_hash(self) -> Option<&'static str> {
+        match self {
+            Self::Local => None,
+            Self::Devnet => Some(DEVNET_GENESIS_HASH),
+            Self::Testnet => Some(TESTNET_GENESIS_HASH),
+            Self::Mainnet => Some(MAINNET_GENESIS_HASH),
+        }
+    }
 }
 
 impl Environment {
@@ -84,3 +109,27 @@ impl Environment {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {super::*, serde_json::json};
+
+    #[test]
+    fn test_from_value() {
+        for environment in [
+            Environment::Local,
+            Environment::Devnet,
+            Environment::Testnet,
+            Environment::Mainnet,
+        ] {
+            assert_eq!(
+                Environment::from_value(json!(environment.to_string())),
+                environment
+            );
+        }
+        assert_eq!(
+            Environment::from_value(json!("Unknown")),
+            Environment::Mainnet
+        );
+    }
+}
@@ -0,0 +1,60 @@
+use {
+    serde::{Deserialize, Serialize},
+    std::collections::HashMap,
+};
+
+/// Response of the ExchangeRate-API `latest` endpoint.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FxRatesResponse {
+    /// `success` or `error`.
+    pub result: String,
+    pub base_code: String,
+    /// Units of each currency per unit of `base_code`, keyed by ISO code.
+    pub rates: HashMap<String, f64>,
+    pub time_last_update_unix: i64,
+}
+
+/// Response of the Frankfurter time series endpoint.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FxHistoryResponse {
+    pub base: String,
+    /// Units of each currency per unit of `base`, keyed by `YYYY-MM-DD` date and ISO code.
+    pub rates: HashMap<String, HashMap<String, f64>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_fx_rates_response() {
+        let json = r#"{
+            "result": "success",
+            "provider": "https://www.exchangerate-api.com",
+            "time_last_update_unix": 1760832151,
+            "base_code": "USD",
+            "rates": { "USD": 1, "IDR": 16575.5, "SEK": 9.41 }
+        }"#;
+        let response: FxRatesResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.result, "success");
+        assert_eq!(response.rates["IDR"], 16575.5);
+        assert_eq!(response.rates.len(), 3);
+    }
+
+    #[test]
+    fn test_deserialize_fx_history_response() {
+        let json = r#"{
+            "amount": 1.0,
+            "base": "USD",
+            "start_date": "2025-01-02",
+            "end_date": "2025-01-03",
+            "rates": {
+                "2025-01-02": { "SEK": 11.06 },
+                "2025-01-03": { "SEK": 11.12 }
+            }
+        }"#;
+        let response: FxHistoryResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.base, "USD");
+        assert_eq!(response.rates["2025-01-03"]["SEK"], 11.12);
+    }
+}
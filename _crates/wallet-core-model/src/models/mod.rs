@@ -1,9 +1,16 @@
-pub mod asset;
 pub mod asset_metadata;
-pub mod asset_solana;
 pub mod balance;
 pub mod balance_v1;
 pub mod birdeye_price_response;
+pub mod currency;
+pub mod custom_network;
 pub mod environment;
+pub mod fx_rates_response;
 pub mod network;
+pub mod portfolio;
+pub mod price;
+pub mod rpc_endpoint;
+pub mod stake;
+pub mod token_preferences;
+pub mod wallet_kit;
 pub mod xlp;
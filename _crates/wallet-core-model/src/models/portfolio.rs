@@ -0,0 +1,217 @@
+use {
+    crate::models::{
+        currency::FiatCurrency,
+        price::{AssetPrice, PriceSource},
+    },
+    serde::{Deserialize, Serialize},
+    std::collections::HashMap,
+    tsync::tsync,
+    uniffi::Record,
+};
+
+/// A token amount held by a wallet, before it is priced.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Holding {
+    pub mint: String,
+    pub symbol: Option<String>,
+    /// Amount in its easy-to-read form, e.g. `0.01` SOL.
+    pub amount: f64,
+}
+
+/// One asset of a wallet with its price in the valuation currency.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Record)]
+#[tsync]
+pub struct AssetValuation {
+    pub mint: String,
+    pub symbol: Option<String>,
+    pub amount: f64,
+    /// Unit price, `None` when no provider knows the token.
+    pub price: Option<f64>,
+    /// `amount * price`.
+    pub value: Option<f64>,
+    /// Price change over the last 24 hours, in percent.
+    pub price_change_24h: Option<f64>,
+    pub price_source: Option<PriceSource>,
+    /// Unix timestamp of the price, in seconds.
+    pub price_updated_at: Option<i64>,
+}
+
+/// The value of a wallet, asset by asset, in one fiat currency.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Record)]
+#[tsync]
+pub struct PortfolioValuation {
+    pub currency: FiatCurrency,
+    /// Highest value first, unpriced assets last.
+    pub assets: Vec<AssetValuation>,
+    /// Sum of the priced asset values.
+    pub total_value: f64,
+    /// Change of `total_value` over the last 24 hours, in the valuation currency.
+    pub total_change_24h: Option<f64>,
+    /// Unix timestamp of the valuation, in seconds.
+    pub updated_at: i64,
+}
+
+impl PortfolioValuation {
+    /// Value `holdings` with USD `prices` keyed by mint.
+    ///
+    /// `usd_rate` is the units of `currency` one US dollar buys. Holdings of the
+    /// same mint are added up.
+    pub fn new(
+        holdings: Vec<Holding>,
+        prices: &HashMap<String, AssetPrice>,
+        currency: FiatCurrency,
+        usd_rate: f64,
+        updated_at: i64,
+    ) -> Self {
+        let mut assets: Vec<AssetValuation> = Vec::new();
+        for holding in holdings {
+            match assets.iter_mut().find(|asset| asset.mint == holding.mint) {
+                Some(asset) => {
+                    asset.amount += holding.amount;
+                    asset.symbol = asset.symbol.take().or(holding.symbol);
+                }
+                None => assets.push(AssetValuation {
+                    mint: holding.mint,
+                    symbol: holding.symbol,
+                    amount: holding.amount,
+                    price: None,
+                    value: None,
+                    price_change_24h: None,
+                    price_source: None,
+                    price_updated_at: None,
+                }),
+            }
+        }
+
+        for asset in &mut assets {
+            if let Some(price) = prices.get(&asset.mint) {
+                let unit_price = price.usd_price * usd_rate;
+                asset.price = Some(unit_price);
+                asset.value = Some(asset.amount * unit_price);
+                asset.price_change_24h = price.price_change_24h;
+                asset.price_source = Some(price.source);
+                asset.price_updated_at = Some(price.updated_at);
+            }
+        }
+        assets.sort_by(|a, b| {
+            b.value
+                .unwrap_or(-1.0)
+                .total_cmp(&a.value.unwrap_or(-1.0))
+                .then_with(|| a.mint.cmp(&b.mint))
+        });
+
+        let mut valuation = PortfolioValuation {
+            currency,
+            assets,
+            total_value: 0.0,
+            total_change_24h: None,
+            updated_at,
+        };
+        valuation.update_totals();
+        valuation
+    }
+
+    /// Keep only the assets `keep` returns true for, e.g. to leave out hidden tokens.
+    pub fn retain_assets(&mut self, keep: impl FnMut(&AssetValuation) -> bool) {
+        self.assets.retain(keep);
+        self.update_totals();
+    }
+
+    fn update_totals(&mut self) {
+        self.total_value = self.assets.iter().filter_map(|asset| asset.value).sum();
+        self.total_change_24h = self
+            .assets
+            .iter()
+            .filter_map(|asset| {
+                let value = asset.value?;
+                let change = asset.price_change_24h?;
+                // The value a day ago was `value / (1 + change)`.
+                Some(value - value / (1.0 + change / 100.0))
+            })
+            .fold(None, |total: Option<f64>, change| {
+                Some(total.unwrap_or(0.0) + change)
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn holding(mint: &str, amount: f64) -> Holding {
+        Holding {
+            mint: mint.to_string(),
+            symbol: None,
+            amount,
+        }
+    }
+
+    fn price(mint: &str, usd_price: f64, price_change_24h: Option<f64>) -> (String, AssetPrice) {
+        let price = AssetPrice {
+            mint: mint.to_string(),
+            usd_price,
+            price_change_24h,
+            source: PriceSource::Birdeye,
+            updated_at: 100,
+        };
+        (mint.to_string(), price)
+    }
+
+    #[test]
+    fn test_portfolio_valuation() {
+        let holdings = vec![
+            holding("bach", 100.0),
+            holding("sol", 1.5),
+            holding("unknown", 7.0),
+            holding("bach", 300.0),
+        ];
+        let prices = HashMap::from([price("sol", 200.0, Some(25.0)), price("bach", 0.01, None)]);
+
+        let valuation = PortfolioValuation::new(holdings, &prices, FiatCurrency::SEK, 10.0, 200);
+        let mints: Vec<&str> = valuation.assets.iter().map(|a| a.mint.as_str()).collect();
+        assert_eq!(mints, vec!["sol", "bach", "unknown"]);
+
+        let sol = &valuation.assets[0];
+        assert_eq!(sol.price, Some(2000.0));
+        assert_eq!(sol.value, Some(3000.0));
+        assert_eq!(sol.price_source, Some(PriceSource::Birdeye));
+
+        let bach = &valuation.assets[1];
+        assert_eq!(bach.amount, 400.0);
+        assert_eq!(bach.value, Some(40.0));
+
+        let unknown = &valuation.assets[2];
+        assert_eq!(unknown.price, None);
+        assert_eq!(unknown.value, None);
+
+        assert_eq!(valuation.total_value, 3040.0);
+        // SOL was worth 2400 SEK a day ago.
+        assert_eq!(valuation.total_change_24h, Some(600.0));
+        assert_eq!(valuation.currency, FiatCurrency::SEK);
+    }
+
+    #[test]
+    fn test_retain_assets() {
+        let holdings = vec![holding("sol", 1.0), holding("spam", 1.0)];
+        let prices = HashMap::from([
+            price("sol", 100.0, Some(25.0)),
+            price("spam", 5.0, Some(25.0)),
+        ]);
+        let mut valuation = PortfolioValuation::new(holdings, &prices, FiatCurrency::USD, 1.0, 200);
+        assert_eq!(valuation.total_value, 105.0);
+
+        valuation.retain_assets(|asset| asset.mint != "spam");
+        assert_eq!(valuation.assets.len(), 1);
+        assert_eq!(valuation.total_value, 100.0);
+        assert_eq!(valuation.total_change_24h, Some(20.0));
+    }
+
+    #[test]
+    fn test_empty_portfolio() {
+        let valuation =
+            PortfolioValuation::new(Vec::new(), &HashMap::new(), FiatCurrency::USD, 1.0, 200);
+        assert!(valuation.assets.is_empty());
+        assert_eq!(valuation.total_value, 0.0);
+        assert_eq!(valuation.total_change_24h, None);
+    }
+}
@@ -0,0 +1,43 @@
+use {
+    serde::{Deserialize, Serialize},
+    tsync::tsync,
+    uniffi::{Enum, Record},
+};
+
+/// The API a price was fetched from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Enum)]
+#[tsync]
+pub enum PriceSource {
+    Birdeye,
+    Jupiter,
+}
+
+/// The USD price of a token.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Record)]
+#[tsync]
+pub struct AssetPrice {
+    pub mint: String,
+    pub usd_price: f64,
+    /// Price change over the last 24 hours, in percent.
+    pub price_change_24h: Option<f64>,
+    pub source: PriceSource,
+    /// Unix timestamp of the price, in seconds.
+    pub updated_at: i64,
+}
+
+/// The USD price of a token at a point in time.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Record)]
+#[tsync]
+pub struct PricePoint {
+    /// Unix timestamp in seconds.
+    pub timestamp: i64,
+    pub usd_price: f64,
+}
+
+/// Units of a fiat currency one US dollar bought at a point in time.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FxRatePoint {
+    /// Unix timestamp in seconds.
+    pub timestamp: i64,
+    pub usd_rate: f64,
+}
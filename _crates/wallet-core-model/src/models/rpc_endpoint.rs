@@ -0,0 +1,37 @@
+use {
+    serde::{Deserialize, Serialize},
+    tsync::tsync,
+};
+
+/// What the last health check found out about an RPC endpoint.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[tsync]
+pub struct RpcEndpointStatus {
+    /// Host of the endpoint. The path and query, which may hold an API key,
+    /// are left out.
+    pub host: String,
+    /// Preference of the endpoint, 0 first.
+    pub rank: u32,
+    /// Whether the endpoint answered its last health check, or a call since.
+    /// Endpoints not checked yet count as healthy.
+    pub healthy: bool,
+    /// Whether calls currently go to this endpoint.
+    pub active: bool,
+    pub slot: Option<u64>,
+    /// Slots behind the most advanced endpoint of the network.
+    pub slot_lag: Option<u64>,
+    pub latency_ms: Option<u64>,
+    pub error: Option<String>,
+    /// Unix timestamp of the last health check.
+    pub checked_at: Option<i64>,
+}
+
+/// Host of `url`, without the path and query that may hold an API key.
+pub fn host(url: &str) -> String {
+    let without_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+    without_scheme
+        .split(['/', '?', '#'])
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
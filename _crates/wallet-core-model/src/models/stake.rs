@@ -0,0 +1,72 @@
+use {
+    serde::{Deserialize, Serialize},
+    tsync::tsync,
+    uniffi::{Enum, Record},
+};
+
+/// A validator that can receive stake delegations.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Record)]
+#[tsync]
+pub struct Validator {
+    /// Vote account, the address stake is delegated to.
+    pub vote_account: String,
+    /// Identity of the validator node.
+    pub identity: String,
+    /// Percentage of rewards kept by the validator.
+    pub commission: u8,
+    /// Active stake, in SOL.
+    pub activated_stake: f64,
+    pub last_vote: u64,
+    /// Ratio of leader slots without a block in the current epoch, if the
+    /// validator had any leader slot.
+    pub skip_rate: Option<f64>,
+    pub delinquent: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Enum)]
+#[tsync]
+pub enum StakeActivationState {
+    Activating,
+    Active,
+    Deactivating,
+    Inactive,
+}
+
+/// A stake account owned by the wallet.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Record)]
+#[tsync]
+pub struct StakeAccount {
+    pub address: String,
+    /// Total balance of the account, in SOL.
+    pub balance: f64,
+    /// Delegated stake, in SOL.
+    pub delegated_stake: f64,
+    /// Lamports kept in the account to keep it rent exempt, in SOL.
+    pub rent_exempt_reserve: f64,
+    /// Vote account the stake is delegated to, if any.
+    pub validator: Option<String>,
+    pub state: StakeActivationState,
+    pub activation_epoch: Option<u64>,
+    pub deactivation_epoch: Option<u64>,
+}
+
+/// Result of an operation that creates a new stake account.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Record)]
+#[tsync]
+pub struct StakeOperation {
+    pub stake_account: String,
+    pub signature: String,
+}
+
+/// Inflation reward credited to a stake account for an epoch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Record)]
+#[tsync]
+pub struct StakeReward {
+    pub stake_account: String,
+    pub epoch: u64,
+    /// Reward, in SOL.
+    pub amount: f64,
+    /// Account balance after the reward, in SOL.
+    pub post_balance: f64,
+    pub commission: Option<u8>,
+}
@@ -0,0 +1,144 @@
+use {
+    serde::{Deserialize, Serialize},
+    tsync::tsync,
+};
+
+/// Whether a token is listed in balances and history.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[tsync]
+pub enum TokenVisibility {
+    #[default]
+    Visible,
+    /// Hidden by the user.
+    Hidden,
+    /// Classified as spam and not shown by the user.
+    Spam,
+}
+
+impl TokenVisibility {
+    /// Visibility of `mint` given the user's `preferences` and whether it looks like spam.
+    ///
+    /// Choices of the user win over the classification, and tokens the user
+    /// added are never spam.
+    pub fn of(mint: &str, preferences: &[TokenPreference], suspected_spam: bool) -> Self {
+        let preference = preferences
+            .iter()
+            .find(|preference| preference.mint == mint);
+        match preference {
+            Some(TokenPreference {
+                hidden: Some(true), ..
+            }) => TokenVisibility::Hidden,
+            Some(TokenPreference {
+                hidden: Some(false),
+                ..
+            })
+            | Some(TokenPreference { custom: true, .. }) => TokenVisibility::Visible,
+            _ if suspected_spam => TokenVisibility::Spam,
+            _ => TokenVisibility::Visible,
+        }
+    }
+
+    pub fn is_visible(self) -> bool {
+        self == TokenVisibility::Visible
+    }
+}
+
+/// What the user chose for a token.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[tsync]
+pub struct TokenPreference {
+    pub mint: String,
+    /// Added by the user, listed even without a balance.
+    pub custom: bool,
+    /// `Some(true)` when hidden by the user, `Some(false)` when shown even if it
+    /// looks like spam, `None` to go by the spam classification.
+    pub hidden: Option<bool>,
+}
+
+/// Mark `mint` as added by the user.
+pub fn add_custom_token(preferences: &mut Vec<TokenPreference>, mint: &str) {
+    update(preferences, mint, |preference| preference.custom = true);
+}
+
+/// Hide or show `mint`, or with `None` leave it to the spam classification.
+pub fn set_token_hidden(preferences: &mut Vec<TokenPreference>, mint: &str, hidden: Option<bool>) {
+    update(preferences, mint, |preference| preference.hidden = hidden);
+}
+
+/// Apply `change` to the preference of `mint`, dropping preferences left without a choice.
+fn update(
+    preferences: &mut Vec<TokenPreference>,
+    mint: &str,
+    change: impl FnOnce(&mut TokenPreference),
+) {
+    let index = match preferences
+        .iter()
+        .position(|preference| preference.mint == mint)
+    {
+        Some(index) => index,
+        None => {
+            preferences.push(TokenPreference {
+                mint: mint.to_string(),
+                custom: false,
+                hidden: None,
+            });
+            preferences.len() - 1
+        }
+    };
+    change(&mut preferences[index]);
+    preferences.retain(|preference| preference.custom || preference.hidden.is_some());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_visibility() {
+        let mut preferences = Vec::new();
+        assert_eq!(
+            TokenVisibility::of("spam", &preferences, true),
+            TokenVisibility::Spam
+        );
+        assert_eq!(
+            TokenVisibility::of("token", &preferences, false),
+            TokenVisibility::Visible
+        );
+
+        set_token_hidden(&mut preferences, "token", Some(true));
+        set_token_hidden(&mut preferences, "spam", Some(false));
+        assert_eq!(
+            TokenVisibility::of("token", &preferences, false),
+            TokenVisibility::Hidden
+        );
+        assert!(TokenVisibility::of("spam", &preferences, true).is_visible());
+
+        add_custom_token(&mut preferences, "custom");
+        assert!(TokenVisibility::of("custom", &preferences, true).is_visible());
+        set_token_hidden(&mut preferences, "custom", Some(true));
+        assert_eq!(
+            TokenVisibility::of("custom", &preferences, true),
+            TokenVisibility::Hidden
+        );
+    }
+
+    #[test]
+    fn test_preferences_without_choice_are_dropped() {
+        let mut preferences = Vec::new();
+        set_token_hidden(&mut preferences, "token", Some(true));
+        add_custom_token(&mut preferences, "custom");
+        set_token_hidden(&mut preferences, "custom", Some(true));
+        assert_eq!(preferences.len(), 2);
+
+        set_token_hidden(&mut preferences, "token", None);
+        set_token_hidden(&mut preferences, "custom", None);
+        assert_eq!(
+            preferences,
+            vec![TokenPreference {
+                mint: "custom".to_string(),
+                custom: true,
+                hidden: None,
+            }]
+        );
+    }
+}
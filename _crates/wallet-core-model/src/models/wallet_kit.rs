@@ -0,0 +1,48 @@
+use {crate::models::environment::Environment, std::time::Duration, uniffi::Record};
+
+/// How long an RPC or HTTP request may take unless configured otherwise.
+pub const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// Configuration of a `WalletKit` client.
+#[derive(Debug, Clone, PartialEq, Record)]
+pub struct WalletKitConfig {
+    pub environment: Environment,
+    /// RPC endpoint used instead of the one of `environment`.
+    #[uniffi(default = None)]
+    pub rpc_url: Option<String>,
+    /// Birdeye API key. Without one prices come from Jupiter only.
+    #[uniffi(default = None)]
+    pub birdeye_api_key: Option<String>,
+    /// Sent with every HTTP request.
+    pub user_agent: String,
+    /// Charge the treasury fee on top of transfers, so recipients receive the
+    /// amount in full.
+    #[uniffi(default = false)]
+    pub fee_on_top: bool,
+    /// Wait for transfers to be confirmed before returning their signature.
+    /// Turn it off when confirmations are followed with a signature
+    /// subscription instead.
+    #[uniffi(default = true)]
+    pub confirm_transfers: bool,
+    /// Timeout of every RPC and HTTP request, in seconds.
+    #[uniffi(default = 30)]
+    pub timeout_secs: u64,
+}
+
+impl WalletKitConfig {
+    pub fn new(environment: Environment, user_agent: &str) -> Self {
+        WalletKitConfig {
+            environment,
+            rpc_url: None,
+            birdeye_api_key: None,
+            user_agent: user_agent.to_string(),
+            fee_on_top: false,
+            confirm_transfers: true,
+            timeout_secs: DEFAULT_TIMEOUT_SECS,
+        }
+    }
+
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_secs)
+    }
+}
@@ -1,8 +1 @@
-use serde::{Deserialize, Serialize};
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct WalletBalanceResponse {
-    pub value: String,
-}
-
 pub mod wallet_assets;
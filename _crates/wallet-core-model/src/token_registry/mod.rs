@@ -0,0 +1,179 @@
+//! Token metadata by mint.
+//!
+//! The bundled verified list wins over the locally cached metadata, which wins
+//! over metadata read from chain. Only bundled tokens are ever verified.
+//!
+//! The registry does not read the chain itself, metadata read from chain is
+//! added with [`TokenRegistry::insert`].
+
+use {
+    crate::models::asset_metadata::Metadata,
+    log::error,
+    std::{
+        collections::HashMap,
+        sync::{OnceLock, RwLock, RwLockReadGuard, RwLockWriteGuard},
+    },
+};
+
+pub mod spam;
+
+/// Tokens reviewed and shipped with the app.
+const VERIFIED_TOKENS: &str = include_str!("verified_tokens.json");
+
+static SHARED_TOKEN_REGISTRY: OnceLock<TokenRegistry> = OnceLock::new();
+
+/// Resolves and remembers the metadata of token mints.
+pub struct TokenRegistry {
+    bundled: Vec<Metadata>,
+    tokens: RwLock<HashMap<String, Metadata>>,
+}
+
+impl TokenRegistry {
+    pub fn new(bundled: Vec<Metadata>) -> Self {
+        let tokens = bundled
+            .iter()
+            .map(|token| (token.address.clone(), token.clone()))
+            .collect();
+        TokenRegistry {
+            bundled,
+            tokens: RwLock::new(tokens),
+        }
+    }
+
+    /// The process wide registry seeded with the bundled token list.
+    ///
+    /// Every wallet kit shares it, it holds no credentials.
+    pub fn shared() -> &'static TokenRegistry {
+        SHARED_TOKEN_REGISTRY.get_or_init(|| {
+            let bundled = serde_json::from_str(VERIFIED_TOKENS).unwrap_or_else(|e| {
+                error!("Error parsing the bundled token list: {}", e);
+                Vec::new()
+            });
+            TokenRegistry::new(bundled)
+        })
+    }
+
+    /// Metadata of `mint` when it is already known.
+    pub fn get(&self, mint: &str) -> Option<Metadata> {
+        self.read().get(mint).cloned()
+    }
+
+    /// Verified tokens in the order of the bundled list.
+    pub fn verified(&self) -> Vec<Metadata> {
+        self.bundled
+            .iter()
+            .filter(|token| token.verified)
+            .cloned()
+            .collect()
+    }
+
+    /// Add previously cached metadata, without overriding the bundled list.
+    pub fn load_cache(&self, cached: Vec<Metadata>) {
+        let mut tokens = self.write();
+        for token in cached {
+            tokens.entry(token.address.clone()).or_insert(Metadata {
+                verified: false,
+                ..token
+            });
+        }
+    }
+
+    /// Metadata that is not bundled, to be persisted and loaded back with `load_cache`.
+    pub fn cache(&self) -> Vec<Metadata> {
+        self.read()
+            .values()
+            .filter(|token| !self.is_bundled(&token.address))
+            .cloned()
+            .collect()
+    }
+
+    /// Those of `mints` whose metadata is not known yet.
+    pub fn unknown(&self, mints: &[String]) -> Vec<String> {
+        let tokens = self.read();
+        mints
+            .iter()
+            .filter(|mint| !tokens.contains_key(*mint))
+            .cloned()
+            .collect()
+    }
+
+    /// Metadata of those of `mints` that are known.
+    pub fn known(&self, mints: &[String]) -> Vec<Metadata> {
+        let tokens = self.read();
+        mints
+            .iter()
+            .filter_map(|mint| tokens.get(mint).cloned())
+            .collect()
+    }
+
+    /// Add metadata read from chain, without overriding what is already known.
+    pub fn insert(&self, resolved: impl IntoIterator<Item = Metadata>) {
+        let mut tokens = self.write();
+        for token in resolved {
+            tokens.entry(token.address.clone()).or_insert(token);
+        }
+    }
+
+    fn is_bundled(&self, mint: &str) -> bool {
+        self.bundled.iter().any(|token| token.address == mint)
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, HashMap<String, Metadata>> {
+        self.tokens.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, HashMap<String, Metadata>> {
+        self.tokens.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        smbcloud_wallet_constants::assets_solana::{ADDRESS_SOL, ADDRESS_USDC},
+    };
+
+    fn token(address: &str, symbol: &str, verified: bool) -> Metadata {
+        Metadata {
+            address: address.to_string(),
+            name: symbol.to_string(),
+            symbol: symbol.to_string(),
+            decimal: 6,
+            logo_uri: String::new(),
+            verified,
+        }
+    }
+
+    #[test]
+    fn test_bundled_tokens() {
+        let registry = TokenRegistry::shared();
+        let verified = registry.verified();
+        assert_eq!(verified[0].address, ADDRESS_SOL);
+        assert!(verified.iter().all(|token| token.verified));
+        assert_eq!(registry.get(ADDRESS_USDC).unwrap().decimal, 6);
+        // Local development tokens are known but not verified.
+        let local = registry
+            .get("38JsCWEZ3dLRzcwxiCbL9rkkZqwwoWLAoCmqu7mWGSwq")
+            .unwrap();
+        assert!(!local.verified);
+        assert!(!verified.contains(&local));
+    }
+
+    #[test]
+    fn test_cache_never_overrides_bundled() {
+        let registry = TokenRegistry::new(vec![token(ADDRESS_USDC, "USDC", true)]);
+        registry.load_cache(vec![
+            token(ADDRESS_USDC, "FAKE", true),
+            token("Custom111111111111111111111111111111111111", "CSTM", true),
+        ]);
+
+        assert_eq!(registry.get(ADDRESS_USDC).unwrap().symbol, "USDC");
+        let custom = registry
+            .get("Custom111111111111111111111111111111111111")
+            .unwrap();
+        assert!(!custom.verified);
+        assert_eq!(registry.cache(), vec![custom]);
+        assert_eq!(registry.verified().len(), 1);
+    }
+}
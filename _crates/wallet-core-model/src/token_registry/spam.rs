@@ -0,0 +1,75 @@
+use crate::models::asset_metadata::Metadata;
+
+/// Fragments of names and symbols scam airdrops use to lure holders to a site.
+const SCAM_PATTERNS: &[&str] = &[
+    "http", "www.", ".com", ".io", ".xyz", ".net", ".org", ".app", "t.me/", "claim", "airdrop",
+    "reward", "voucher", "visit", "bonus", "free ",
+];
+
+/// Amount up to which an unpriced token received without asking counts as dust.
+const DUST_AMOUNT: f64 = 1.0;
+
+/// Whether a token held in `amount` looks like a spam airdrop.
+///
+/// Verified tokens never are. Unverified ones are when their name or symbol
+/// matches a known scam pattern, or when they are unpriced dust the wallet
+/// never sent or swapped, given by `unsolicited`.
+pub fn is_suspected_spam(
+    metadata: &Metadata,
+    usd_price: Option<f64>,
+    amount: f64,
+    unsolicited: bool,
+) -> bool {
+    if metadata.verified {
+        return false;
+    }
+    has_scam_name(metadata) || (unsolicited && is_unpriced_dust(usd_price, amount))
+}
+
+/// Whether a token priced at `usd_price` and held in `amount` is worth nothing
+/// anyone can tell, the tokens airdropped to lure holders.
+pub fn is_unpriced_dust(usd_price: Option<f64>, amount: f64) -> bool {
+    let unpriced = usd_price.is_none_or(|price| price <= 0.0);
+    unpriced && amount <= DUST_AMOUNT
+}
+
+fn has_scam_name(metadata: &Metadata) -> bool {
+    let text = format!("{} {}", metadata.name, metadata.symbol).to_lowercase();
+    SCAM_PATTERNS.iter().any(|pattern| text.contains(pattern))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(name: &str, symbol: &str, verified: bool) -> Metadata {
+        Metadata {
+            address: "mint".to_string(),
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            decimal: 6,
+            logo_uri: String::new(),
+            verified,
+        }
+    }
+
+    #[test]
+    fn test_scam_names() {
+        let lure = metadata("Visit solrewards.xyz to claim", "$500 USDC", false);
+        assert!(is_suspected_spam(&lure, Some(1.0), 1000.0, false));
+        // Verified tokens are trusted whatever they are called.
+        let verified = metadata("Airdrop Token", "AIR", true);
+        assert!(!is_suspected_spam(&verified, None, 0.1, true));
+    }
+
+    #[test]
+    fn test_unpriced_dust() {
+        let token = metadata("Some Token", "SOME", false);
+        assert!(is_suspected_spam(&token, None, 1.0, true));
+        assert!(is_suspected_spam(&token, Some(0.0), 0.5, true));
+        assert!(!is_suspected_spam(&token, None, 250.0, true));
+        assert!(!is_suspected_spam(&token, Some(0.02), 0.5, true));
+        // Dust the wallet sent or swapped itself was asked for.
+        assert!(!is_suspected_spam(&token, None, 0.5, false));
+    }
+}
@@ -42,7 +42,7 @@ pub async fn parse_error_response<T: DeserializeOwned>(
         }
     };
     println!("🦀🦀  Error response: {:?}", e);
-    return Err(e);
+    Err(e)
 }
 
 pub async fn request<R: DeserializeOwned>(builder: RequestBuilder) -> Result<R, ErrorResponse> {
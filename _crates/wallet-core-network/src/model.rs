@@ -35,6 +35,10 @@ pub enum ErrorCode {
     // Wallet related error codes.
     BalanceError = 1000,
     InvalidPubkey = 1001,
+    InvalidDomain = 1002,
+    DomainNotFound = 1003,
+    InvalidRpcUrl = 1004,
+    GenesisMismatch = 1005,
 }
 
 impl Display for ErrorCode {
@@ -9,7 +9,7 @@ use {
 /// for all token accounts associated with the given public key and token address.
 ///
 /// Returns: the balance and ui balance for the given token address.
-pub fn aggregate_spl_token_balance(
+pub async fn aggregate_spl_token_balance(
     rpc_url: String,
     pubkey: String,
     spl_token_program_id: String,
@@ -17,7 +17,7 @@ pub fn aggregate_spl_token_balance(
 ) -> Result<(u64, f64), ErrorResponse> {
     // Get token accounts for the given public key and token address
     let target_spl_token_accounts =
-        match spl_token_accounts_for(rpc_url, pubkey, spl_token_program_id, token_address) {
+        match spl_token_accounts_for(rpc_url, pubkey, spl_token_program_id, token_address).await {
             Ok(accounts) => accounts,
             Err(err) => {
                 error!("Failed to fetch token accounts: {}", err);
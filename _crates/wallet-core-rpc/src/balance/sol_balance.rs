@@ -2,7 +2,7 @@ use {
     smbcloud_wallet_constants::constants::LAMPORTS_PER_SOL,
     smbcloud_wallet_core_network::model::{ErrorCode, ErrorResponse},
     solana_pubkey::Pubkey,
-    solana_rpc_client::rpc_client::RpcClient,
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
     std::str::FromStr,
 };
 
@@ -11,9 +11,10 @@ use {
 /// Params:
 ///  - RPC Url
 ///  - Pubkey address
+///
 /// Return:
 ///  - Tuple with balance and ui balance.
-pub fn sol_balance(rpc_url: String, pubkey: String) -> Result<(u64, f64), ErrorResponse> {
+pub async fn sol_balance(rpc_url: String, pubkey: String) -> Result<(u64, f64), ErrorResponse> {
     println!("🦀🦀  Will load balance");
     let connection = RpcClient::new(rpc_url);
     let pubkey = match Pubkey::from_str(&pubkey) {
@@ -26,7 +27,7 @@ pub fn sol_balance(rpc_url: String, pubkey: String) -> Result<(u64, f64), ErrorR
             });
         }
     };
-    match connection.get_balance(&pubkey) {
+    match connection.get_balance(&pubkey).await {
         Ok(balance) => {
             println!("🦀🦀  Get balance for {}: {}", pubkey, balance);
             let balance_as_f64 = balance as f64;
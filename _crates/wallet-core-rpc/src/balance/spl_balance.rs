@@ -3,7 +3,7 @@ use {
     smbcloud_wallet_core_network::model::ErrorResponse,
 };
 
-pub fn spl_balance(
+pub async fn spl_balance(
     rpc_url: String,
     pubkey: String,
     spl_token_program_id: String,
@@ -15,7 +15,7 @@ pub fn spl_balance(
     );
 
     let balance =
-        aggregate_spl_token_balance(rpc_url, pubkey, spl_token_program_id, token_address)?;
+        aggregate_spl_token_balance(rpc_url, pubkey, spl_token_program_id, token_address).await?;
     Ok(format!("{} BACH", balance.1))
 }
 
@@ -25,19 +25,24 @@ mod tests {
     use smbcloud_wallet_constants::{
         assets_solana::ADDRESS_BACH_TOKEN, constants::SPL_TOKEN_PROGRAM_ID,
     };
+    use smbcloud_wallet_core_network::model::ErrorCode;
 
-    #[test]
-    fn test_spl_balance_error_handling() {
-        let result = match spl_balance(
+    #[tokio::test]
+    async fn test_spl_balance_error_handling() {
+        let result = spl_balance(
             "https://api.mainnet-beta.solana.com".to_string(),
             "invalid_pubkey".to_string(),
             SPL_TOKEN_PROGRAM_ID.to_string(),
             ADDRESS_BACH_TOKEN.to_string(),
-        ) {
-            Ok(result) => result,
-            Err(_) => panic!(),
-        };
-        // Should return "0 BACH" instead of panicking
-        assert_eq!(result, "0 BACH");
+        )
+        .await;
+        // Should return a balance error instead of panicking
+        assert!(matches!(
+            result,
+            Err(ErrorResponse::Error {
+                code: ErrorCode::BalanceError,
+                ..
+            })
+        ));
     }
 }
@@ -7,12 +7,12 @@ use {
     },
     solana_account_decoder::{parse_token::UiTokenAccount, UiAccountData},
     solana_client::rpc_request::TokenAccountsFilter,
-    solana_rpc_client::rpc_client::RpcClient,
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
     solana_sdk::pubkey::Pubkey,
     std::str::FromStr,
 };
 
-pub fn spl_token_accounts(
+pub async fn spl_token_accounts(
     rpc_url: String,
     pubkey: String,
     spl_token_program_id: String,
@@ -42,10 +42,13 @@ pub fn spl_token_accounts(
     };
 
     // Get all token accounts owned by the pubkey
-    let rpc_keyed_accounts = match connection.get_token_accounts_by_owner(
-        &pubkey,
-        TokenAccountsFilter::ProgramId(spl_token_program_id_pubkey),
-    ) {
+    let rpc_keyed_accounts = match connection
+        .get_token_accounts_by_owner(
+            &pubkey,
+            TokenAccountsFilter::ProgramId(spl_token_program_id_pubkey),
+        )
+        .await
+    {
         Ok(accounts) => accounts,
         Err(err) => {
             error!("Error getting token accounts: {}", err);
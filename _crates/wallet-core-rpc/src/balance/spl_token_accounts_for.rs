@@ -4,14 +4,15 @@ use {
     solana_account_decoder::parse_token::UiTokenAccount,
 };
 
-pub(crate) fn spl_token_accounts_for(
+pub(crate) async fn spl_token_accounts_for(
     rpc_url: String,
     pubkey: String,
     spl_token_program_id: String,
     token_address: String,
 ) -> Result<Vec<UiTokenAccount>, ErrorResponse> {
     // Get token accounts for a specific token
-    let target_spl_token_accounts = spl_token_accounts(rpc_url, pubkey, spl_token_program_id)?
+    let target_spl_token_accounts = spl_token_accounts(rpc_url, pubkey, spl_token_program_id)
+        .await?
         .into_iter()
         .filter(|account| account.mint == token_address)
         .collect::<Vec<_>>();
@@ -4,19 +4,20 @@ use {
     solana_account_decoder::parse_token::UiTokenAccount,
 };
 
-pub fn spl_token_accounts_with_balance(
+/// Token accounts of `pubkey` in `spl_token_program_id` that hold tokens.
+///
+/// Native accounts holding wrapped SOL are included, their amount leaves out
+/// the rent reserve.
+pub async fn spl_token_accounts_with_balance(
     rpc_url: String,
     pubkey: String,
     spl_token_program_id: String,
 ) -> Result<Vec<UiTokenAccount>, ErrorResponse> {
-    let target_spl_token_accounts = spl_token_accounts(rpc_url, pubkey, spl_token_program_id)?;
+    let target_spl_token_accounts =
+        spl_token_accounts(rpc_url, pubkey, spl_token_program_id).await?;
     let spl_token_accounts_with_balance = target_spl_token_accounts
         .into_iter()
         .filter_map(|account| {
-            // Only non-SOL
-            if account.is_native {
-                return None;
-            }
             // token_amount.amount is the amount of tokens in its smallest unit
             if let Ok(amount) = account.token_amount.amount.parse::<u64>() {
                 if amount > 0 {
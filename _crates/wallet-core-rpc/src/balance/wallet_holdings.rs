@@ -0,0 +1,45 @@
+use {
+    crate::balance::{
+        sol_balance::sol_balance, spl_token_accounts_with_balance::spl_token_accounts_with_balance,
+    },
+    log::error,
+    smbcloud_wallet_constants::constants::{SPL_TOKEN_2022_PROGRAM_ID, SPL_TOKEN_PROGRAM_ID},
+    smbcloud_wallet_core_network::model::ErrorResponse,
+    solana_account_decoder::parse_token::UiTokenAccount,
+};
+
+/// The SOL balance and token accounts of a wallet.
+#[derive(Debug)]
+pub struct WalletHoldings {
+    /// Lamports and SOL, as returned by `sol_balance`.
+    pub sol: Result<(u64, f64), ErrorResponse>,
+    /// Token accounts with balance in both token programs. A program whose
+    /// accounts failed to load is left out.
+    pub tokens: Vec<UiTokenAccount>,
+}
+
+/// Read the SOL balance and the token accounts of both token programs of
+/// `pubkey` concurrently.
+pub async fn wallet_holdings(rpc_url: String, pubkey: String) -> WalletHoldings {
+    let (sol, spl_tokens, token_2022_tokens) = tokio::join!(
+        sol_balance(rpc_url.clone(), pubkey.clone()),
+        spl_token_accounts_with_balance(
+            rpc_url.clone(),
+            pubkey.clone(),
+            SPL_TOKEN_PROGRAM_ID.to_string(),
+        ),
+        spl_token_accounts_with_balance(rpc_url, pubkey, SPL_TOKEN_2022_PROGRAM_ID.to_string()),
+    );
+
+    let mut tokens = Vec::new();
+    for (program_id, accounts) in [
+        (SPL_TOKEN_PROGRAM_ID, spl_tokens),
+        (SPL_TOKEN_2022_PROGRAM_ID, token_2022_tokens),
+    ] {
+        match accounts {
+            Ok(accounts) => tokens.extend(accounts),
+            Err(err) => error!("Failed to get token accounts of {}: {:?}", program_id, err),
+        }
+    }
+    WalletHoldings { sol, tokens }
+}
@@ -0,0 +1,61 @@
+//! Blocking calls for the synchronous uniffi exports.
+//!
+//! Each call runs on its own current thread runtime, so none of them may be
+//! made from async code, which should await the calls they wrap instead.
+
+use {
+    crate::{
+        balance::sol_balance::sol_balance as async_sol_balance,
+        sns::{
+            primary_domain::primary_domain as async_primary_domain,
+            resolve_domain::resolve_domain as async_resolve_domain,
+        },
+    },
+    smbcloud_wallet_core_network::model::{ErrorCode::Unknown, ErrorResponse},
+    std::future::Future,
+    tokio::runtime::Builder,
+};
+
+/// See `balance::sol_balance::sol_balance`.
+pub fn sol_balance(rpc_url: String, pubkey: String) -> Result<(u64, f64), ErrorResponse> {
+    block_on(async_sol_balance(rpc_url, pubkey))
+}
+
+/// See `sns::resolve_domain::resolve_domain`.
+pub fn resolve_domain(rpc_url: String, domain: String) -> Result<String, ErrorResponse> {
+    block_on(async_resolve_domain(rpc_url, domain))
+}
+
+/// See `sns::primary_domain::primary_domain`.
+pub fn primary_domain(rpc_url: String, pubkey: String) -> Result<Option<String>, ErrorResponse> {
+    block_on(async_primary_domain(rpc_url, pubkey))
+}
+
+fn block_on<T>(future: impl Future<Output = Result<T, ErrorResponse>>) -> Result<T, ErrorResponse> {
+    let runtime = Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| ErrorResponse::Error {
+            code: Unknown,
+            message: e.to_string(),
+        })?;
+    runtime.block_on(future)
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, smbcloud_wallet_core_network::model::ErrorCode::InvalidPubkey};
+
+    #[test]
+    fn test_blocking_call_returns_the_async_result() {
+        // Parsing fails before any request is sent.
+        let result = sol_balance("http://localhost:8899".to_string(), "invalid".to_string());
+        assert!(matches!(
+            result,
+            Err(ErrorResponse::Error {
+                code: InvalidPubkey,
+                ..
+            })
+        ));
+    }
+}
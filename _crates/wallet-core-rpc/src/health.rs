@@ -0,0 +1,108 @@
+use {
+    futures::StreamExt,
+    smbcloud_wallet_core_network::model::{ErrorCode, ErrorResponse},
+    solana_client::nonblocking::pubsub_client::PubsubClient,
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
+    std::time::{Duration, Instant},
+};
+
+/// How long an endpoint may take to answer a health check.
+pub const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// What a health check of an RPC endpoint found.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EndpointHealth {
+    /// Slot the endpoint has processed.
+    pub slot: u64,
+    /// Round trip of the `getSlot` request.
+    pub latency: Duration,
+}
+
+/// Check `rpc_url` with `getHealth` and read its slot.
+///
+/// Fails when the node reports itself unhealthy, e.g. when it is behind the
+/// cluster, or does not answer within `timeout`.
+pub async fn endpoint_health(
+    rpc_url: String,
+    timeout: Duration,
+) -> Result<EndpointHealth, ErrorResponse> {
+    let connection = RpcClient::new_with_timeout(rpc_url, timeout);
+    connection
+        .get_health()
+        .await
+        .map_err(|e| ErrorResponse::Error {
+            code: ErrorCode::NetworkError,
+            message: e.to_string(),
+        })?;
+
+    let started = Instant::now();
+    let slot = connection
+        .get_slot()
+        .await
+        .map_err(|e| ErrorResponse::Error {
+            code: ErrorCode::NetworkError,
+            message: e.to_string(),
+        })?;
+    Ok(EndpointHealth {
+        slot,
+        latency: started.elapsed(),
+    })
+}
+
+/// Genesis hash of the cluster `rpc_url` serves.
+pub async fn endpoint_genesis_hash(
+    rpc_url: String,
+    timeout: Duration,
+) -> Result<String, ErrorResponse> {
+    let connection = RpcClient::new_with_timeout(rpc_url, timeout);
+    connection
+        .get_genesis_hash()
+        .await
+        .map(|hash| hash.to_string())
+        .map_err(|e| ErrorResponse::Error {
+            code: ErrorCode::NetworkError,
+            message: e.to_string(),
+        })
+}
+
+/// First slot the WebSocket endpoint `ws_url` notifies after `slotSubscribe`.
+pub async fn websocket_slot(ws_url: String, timeout: Duration) -> Result<u64, ErrorResponse> {
+    let network_error = |message: String| ErrorResponse::Error {
+        code: ErrorCode::NetworkError,
+        message,
+    };
+    let read_slot = async {
+        let client = PubsubClient::new(&ws_url)
+            .await
+            .map_err(|e| network_error(e.to_string()))?;
+        let (mut slots, unsubscribe) = client
+            .slot_subscribe()
+            .await
+            .map_err(|e| network_error(e.to_string()))?;
+        let slot = slots.next().await.map(|info| info.slot);
+        unsubscribe().await;
+        slot.ok_or_else(|| network_error("The WebSocket endpoint sent no slot".to_string()))
+    };
+    tokio::time::timeout(timeout, read_slot)
+        .await
+        .map_err(|_| network_error("The WebSocket endpoint sent no slot in time".to_string()))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_unreachable_endpoint() {
+        let result =
+            endpoint_health("http://127.0.0.1:1".to_string(), Duration::from_secs(1)).await;
+        assert!(result.is_err());
+
+        let result =
+            endpoint_genesis_hash("http://127.0.0.1:1".to_string(), Duration::from_secs(1)).await;
+        assert!(result.is_err());
+
+        let result = websocket_slot("ws://127.0.0.1:1".to_string(), Duration::from_secs(1)).await;
+        assert!(result.is_err());
+    }
+}
@@ -1 +1,5 @@
 pub mod balance;
+pub mod blocking;
+pub mod health;
+pub mod sns;
+pub mod token_metadata;
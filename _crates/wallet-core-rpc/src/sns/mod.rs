@@ -0,0 +1,503 @@
+//! Solana Name Service account derivation and layouts.
+//!
+//! Only plain RPC account reads are used, so resolution works against any
+//! cluster the name program is deployed on.
+
+use {
+    sha2::{Digest, Sha256},
+    smbcloud_wallet_constants::constants::{
+        SNS_NAME_OFFERS_PROGRAM_ID, SNS_NAME_PROGRAM_ID, SNS_NAME_TOKENIZER_PROGRAM_ID,
+        SNS_RECORDS_CENTRAL_STATE, SNS_REVERSE_LOOKUP_CLASS, SNS_SOL_TLD_AUTHORITY,
+    },
+    solana_pubkey::Pubkey,
+};
+
+pub mod primary_domain;
+pub mod resolve_domain;
+
+/// Prefix hashed together with every name.
+const HASH_PREFIX: &str = "SPL Name Service";
+
+/// Size of the name registry header: parent, owner and class.
+const NAME_REGISTRY_HEADER_LEN: usize = 96;
+
+/// Size of the records v2 header: two validation kinds and the content length.
+const RECORD_V2_HEADER_LEN: usize = 8;
+
+/// Records v2 validation kind for a Solana signature.
+const VALIDATION_SOLANA: u16 = 1;
+
+/// Size of a token account without extensions.
+const TOKEN_ACCOUNT_LEN: usize = 165;
+
+/// The header of a name registry account.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NameRegistry {
+    pub parent: Pubkey,
+    pub owner: Pubkey,
+    pub class: Pubkey,
+    /// Data stored after the header.
+    pub data: Vec<u8>,
+}
+
+impl NameRegistry {
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        if data.len() < NAME_REGISTRY_HEADER_LEN {
+            return None;
+        }
+        Some(NameRegistry {
+            parent: read_pubkey(data, 0)?,
+            owner: read_pubkey(data, 32)?,
+            class: read_pubkey(data, 64)?,
+            data: data[NAME_REGISTRY_HEADER_LEN..].to_vec(),
+        })
+    }
+}
+
+/// A domain name account and, for subdomains, its parent domain account.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DomainKey {
+    pub pubkey: Pubkey,
+    pub parent: Option<Pubkey>,
+}
+
+pub fn name_program_id() -> Pubkey {
+    Pubkey::from_str_const(SNS_NAME_PROGRAM_ID)
+}
+
+pub fn sol_tld_authority() -> Pubkey {
+    Pubkey::from_str_const(SNS_SOL_TLD_AUTHORITY)
+}
+
+pub fn hashed_name(name: &str) -> [u8; 32] {
+    Sha256::digest(format!("{}{}", HASH_PREFIX, name)).into()
+}
+
+/// Address of the name account for `hashed_name` under an optional class and parent.
+pub fn name_account_key(
+    hashed_name: &[u8; 32],
+    class: Option<&Pubkey>,
+    parent: Option<&Pubkey>,
+) -> Pubkey {
+    let zero = Pubkey::default();
+    let (pubkey, _) = Pubkey::find_program_address(
+        &[
+            hashed_name,
+            class.unwrap_or(&zero).as_ref(),
+            parent.unwrap_or(&zero).as_ref(),
+        ],
+        &name_program_id(),
+    );
+    pubkey
+}
+
+/// Normalize a user typed domain, e.g. ` Alice.SOL ` to `alice.sol`.
+///
+/// Returns `None` for anything that is not a `.sol` domain or a one level subdomain.
+pub fn normalize_domain(domain: &str) -> Option<String> {
+    let domain = domain.trim().to_lowercase();
+    let name = domain.strip_suffix(".sol")?;
+    let labels: Vec<&str> = name.split('.').collect();
+    let valid = labels.len() <= 2
+        && labels
+            .iter()
+            .all(|label| !label.is_empty() && !label.chars().any(char::is_whitespace));
+    valid.then_some(domain)
+}
+
+/// Derive the name account of a `.sol` domain or subdomain.
+pub fn domain_key(domain: &str) -> Option<DomainKey> {
+    let domain = normalize_domain(domain)?;
+    let name = domain.strip_suffix(".sol")?;
+    let root = sol_tld_authority();
+    match name.split_once('.') {
+        None => Some(DomainKey {
+            pubkey: name_account_key(&hashed_name(name), None, Some(&root)),
+            parent: None,
+        }),
+        Some((sub, parent_name)) => {
+            let parent = name_account_key(&hashed_name(parent_name), None, Some(&root));
+            // Subdomain names are prefixed with a zero byte.
+            let sub = format!("\0{}", sub);
+            Some(DomainKey {
+                pubkey: name_account_key(&hashed_name(&sub), None, Some(&parent)),
+                parent: Some(parent),
+            })
+        }
+    }
+}
+
+/// Address of the records v2 `SOL` record of a domain account.
+pub fn sol_record_v2_key(domain: &Pubkey) -> Pubkey {
+    let central_state = Pubkey::from_str_const(SNS_RECORDS_CENTRAL_STATE);
+    name_account_key(&hashed_name("\u{2}SOL"), Some(&central_state), Some(domain))
+}
+
+/// Address of the reverse lookup account that stores the name of `name_account`.
+pub fn reverse_key(name_account: &Pubkey, parent: Option<&Pubkey>) -> Pubkey {
+    let class = Pubkey::from_str_const(SNS_REVERSE_LOOKUP_CLASS);
+    name_account_key(
+        &hashed_name(&name_account.to_string()),
+        Some(&class),
+        parent,
+    )
+}
+
+/// Address of the account that stores the primary domain of `owner`.
+pub fn primary_domain_key(owner: &Pubkey) -> Pubkey {
+    let (pubkey, _) = Pubkey::find_program_address(
+        &[b"favourite_owner", owner.as_ref()],
+        &Pubkey::from_str_const(SNS_NAME_OFFERS_PROGRAM_ID),
+    );
+    pubkey
+}
+
+/// Address of the Name Tokenizer record of a domain.
+///
+/// Tokenizing a domain hands the name account over to this record and mints
+/// an NFT to the former owner, so whoever holds the NFT owns the domain.
+pub fn nft_record_key(domain: &Pubkey) -> Pubkey {
+    let (pubkey, _) = Pubkey::find_program_address(
+        &[b"nft_record", domain.as_ref()],
+        &Pubkey::from_str_const(SNS_NAME_TOKENIZER_PROGRAM_ID),
+    );
+    pubkey
+}
+
+/// Mint of the NFT of a tokenized domain.
+pub fn domain_mint_key(domain: &Pubkey) -> Pubkey {
+    let (pubkey, _) = Pubkey::find_program_address(
+        &[b"tokenized_name", domain.as_ref()],
+        &Pubkey::from_str_const(SNS_NAME_TOKENIZER_PROGRAM_ID),
+    );
+    pubkey
+}
+
+/// Whether the domain of `registry` is tokenized, i.e. owned by its NFT record.
+pub fn is_tokenized(domain: &Pubkey, registry: &NameRegistry) -> bool {
+    registry.owner == nft_record_key(domain)
+}
+
+/// Owner of a token account holding the one token of `mint`.
+pub fn parse_nft_holder(data: &[u8], mint: &Pubkey) -> Option<Pubkey> {
+    if data.len() < TOKEN_ACCOUNT_LEN || read_pubkey(data, 0)? != *mint {
+        return None;
+    }
+    let amount = u64::from_le_bytes(data.get(64..72)?.try_into().ok()?);
+    (amount == 1).then_some(read_pubkey(data, 32)?)
+}
+
+/// The address funds sent to a domain should go to.
+///
+/// A valid `SOL` record signed by the current owner wins, otherwise the owner
+/// is returned. The owner of a tokenized domain is `nft_holder`; without one
+/// there is no destination, the name account itself only points at the
+/// tokenizer and anything sent there would be lost.
+pub fn domain_destination(
+    domain: &Pubkey,
+    registry: &NameRegistry,
+    sol_record: Option<&NameRegistry>,
+    nft_holder: Option<Pubkey>,
+) -> Option<Pubkey> {
+    let owner = if is_tokenized(domain, registry) {
+        nft_holder?
+    } else {
+        registry.owner
+    };
+    Some(
+        sol_record
+            .and_then(|record| parse_sol_record_v2(record, &owner))
+            .unwrap_or(owner),
+    )
+}
+
+/// The destination of a records v2 `SOL` record.
+///
+/// The record only counts when it was signed by the current domain owner and
+/// by the destination itself, otherwise it may be stale or forged.
+pub fn parse_sol_record_v2(record: &NameRegistry, domain_owner: &Pubkey) -> Option<Pubkey> {
+    let data = &record.data;
+    let staleness_validation = u16::from_le_bytes(data.get(0..2)?.try_into().ok()?);
+    let roa_validation = u16::from_le_bytes(data.get(2..4)?.try_into().ok()?);
+    let content_length = u32::from_le_bytes(data.get(4..8)?.try_into().ok()?) as usize;
+    if staleness_validation != VALIDATION_SOLANA || roa_validation != VALIDATION_SOLANA {
+        return None;
+    }
+
+    let staleness_id = read_pubkey(data, RECORD_V2_HEADER_LEN)?;
+    let roa_id = read_pubkey(data, RECORD_V2_HEADER_LEN + 32)?;
+    let content_start = RECORD_V2_HEADER_LEN + 64;
+    let content = data.get(content_start..content_start + content_length)?;
+    let destination = Pubkey::try_from(content).ok()?;
+    (staleness_id == *domain_owner && roa_id == destination).then_some(destination)
+}
+
+/// The name stored in a reverse lookup account, without the subdomain zero byte.
+pub fn parse_reverse_name(reverse: &NameRegistry) -> Option<String> {
+    let length = u32::from_le_bytes(reverse.data.get(0..4)?.try_into().ok()?) as usize;
+    let name = reverse.data.get(4..4 + length)?;
+    let name = String::from_utf8(name.to_vec()).ok()?;
+    Some(name.replace('\0', ""))
+}
+
+/// The name account a primary domain account points to.
+pub fn parse_primary_domain(data: &[u8]) -> Option<Pubkey> {
+    // A one byte account tag comes first.
+    read_pubkey(data, 1)
+}
+
+fn read_pubkey(data: &[u8], offset: usize) -> Option<Pubkey> {
+    Pubkey::try_from(data.get(offset..offset + 32)?).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a name registry account as stored on chain.
+    fn name_registry_fixture(parent: &Pubkey, owner: &Pubkey, data: &[u8]) -> Vec<u8> {
+        let mut account = Vec::new();
+        account.extend_from_slice(parent.as_ref());
+        account.extend_from_slice(owner.as_ref());
+        account.extend_from_slice(Pubkey::default().as_ref());
+        account.extend_from_slice(data);
+        account
+    }
+
+    /// Build the data of a records v2 `SOL` record.
+    fn sol_record_v2_fixture(
+        validation: u16,
+        staleness_id: &Pubkey,
+        roa_id: &Pubkey,
+        destination: &Pubkey,
+    ) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&validation.to_le_bytes());
+        data.extend_from_slice(&validation.to_le_bytes());
+        data.extend_from_slice(&32u32.to_le_bytes());
+        data.extend_from_slice(staleness_id.as_ref());
+        data.extend_from_slice(roa_id.as_ref());
+        data.extend_from_slice(destination.as_ref());
+        data
+    }
+
+    fn reverse_fixture(name: &str) -> Vec<u8> {
+        let mut data = (name.len() as u32).to_le_bytes().to_vec();
+        data.extend_from_slice(name.as_bytes());
+        data
+    }
+
+    #[test]
+    fn test_domain_key() {
+        let bonfida = domain_key("bonfida.sol").unwrap();
+        assert_eq!(
+            bonfida.pubkey.to_string(),
+            "Crf8hzfthWGbGbLTVCiqRqV5MVnbpHB1L9KQMd6gsinb"
+        );
+        assert_eq!(bonfida.parent, None);
+        assert_eq!(domain_key(" Bonfida.SOL").unwrap(), bonfida);
+
+        let dex = domain_key("dex.bonfida.sol").unwrap();
+        assert_eq!(
+            dex.pubkey.to_string(),
+            "HoFfFXqFHAC8RP3duuQNzag1ieUwJRBv1HtRNiWFq4Qu"
+        );
+        assert_eq!(dex.parent, Some(bonfida.pubkey));
+    }
+
+    #[test]
+    fn test_normalize_domain() {
+        assert_eq!(normalize_domain("Alice.sol").as_deref(), Some("alice.sol"));
+        assert!(normalize_domain("pay.alice.sol").is_some());
+        assert!(normalize_domain("a.b.alice.sol").is_none());
+        assert!(normalize_domain("alice").is_none());
+        assert!(normalize_domain(".sol").is_none());
+        assert!(normalize_domain("alice..sol").is_none());
+        assert!(normalize_domain("mvines9iiHiQTysrwkJjGf2gb9Ex9jXJX8ns3qwf2kN").is_none());
+    }
+
+    #[test]
+    fn test_parse_name_registry() {
+        let parent = sol_tld_authority();
+        let owner = Pubkey::new_unique();
+        let account = name_registry_fixture(&parent, &owner, &[7; 10]);
+        let registry = NameRegistry::parse(&account).unwrap();
+        assert_eq!(registry.parent, parent);
+        assert_eq!(registry.owner, owner);
+        assert_eq!(registry.class, Pubkey::default());
+        assert_eq!(registry.data, vec![7; 10]);
+        assert!(NameRegistry::parse(&account[..95]).is_none());
+    }
+
+    #[test]
+    fn test_parse_sol_record_v2() {
+        let domain = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let destination = Pubkey::new_unique();
+        let record = |data: Vec<u8>| {
+            NameRegistry::parse(&name_registry_fixture(&domain, &owner, &data)).unwrap()
+        };
+
+        let valid = record(sol_record_v2_fixture(
+            VALIDATION_SOLANA,
+            &owner,
+            &destination,
+            &destination,
+        ));
+        assert_eq!(parse_sol_record_v2(&valid, &owner), Some(destination));
+
+        // Set by a previous owner of the domain.
+        assert_eq!(parse_sol_record_v2(&valid, &Pubkey::new_unique()), None);
+        // Not signed by the destination.
+        let unverified = record(sol_record_v2_fixture(
+            VALIDATION_SOLANA,
+            &owner,
+            &owner,
+            &destination,
+        ));
+        assert_eq!(parse_sol_record_v2(&unverified, &owner), None);
+        // No validation at all.
+        let unvalidated = record(sol_record_v2_fixture(0, &owner, &destination, &destination));
+        assert_eq!(parse_sol_record_v2(&unvalidated, &owner), None);
+        // Truncated content.
+        let mut truncated =
+            sol_record_v2_fixture(VALIDATION_SOLANA, &owner, &destination, &destination);
+        truncated.truncate(truncated.len() - 1);
+        assert_eq!(parse_sol_record_v2(&record(truncated), &owner), None);
+    }
+
+    /// Build a token account holding `amount` of `mint`.
+    fn token_account_fixture(mint: &Pubkey, owner: &Pubkey, amount: u64) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(mint.as_ref());
+        data.extend_from_slice(owner.as_ref());
+        data.extend_from_slice(&amount.to_le_bytes());
+        data.resize(TOKEN_ACCOUNT_LEN, 0);
+        data
+    }
+
+    #[test]
+    fn test_domain_destination() {
+        let domain = domain_key("bonfida.sol").unwrap().pubkey;
+        let owner = Pubkey::new_unique();
+        let destination = Pubkey::new_unique();
+        let registry = NameRegistry::parse(&name_registry_fixture(
+            &sol_tld_authority(),
+            &owner,
+            &[],
+        ))
+        .unwrap();
+        let record = NameRegistry::parse(&name_registry_fixture(
+            &domain,
+            &owner,
+            &sol_record_v2_fixture(VALIDATION_SOLANA, &owner, &destination, &destination),
+        ))
+        .unwrap();
+
+        assert!(!is_tokenized(&domain, &registry));
+        assert_eq!(
+            domain_destination(&domain, &registry, None, None),
+            Some(owner)
+        );
+        assert_eq!(
+            domain_destination(&domain, &registry, Some(&record), None),
+            Some(destination)
+        );
+    }
+
+    #[test]
+    fn test_tokenized_domain_destination() {
+        let domain = domain_key("bonfida.sol").unwrap().pubkey;
+        let escrow = nft_record_key(&domain);
+        let registry = NameRegistry::parse(&name_registry_fixture(
+            &sol_tld_authority(),
+            &escrow,
+            &[],
+        ))
+        .unwrap();
+        assert!(is_tokenized(&domain, &registry));
+
+        // Never the tokenizer escrow, even without an NFT holder.
+        assert_eq!(domain_destination(&domain, &registry, None, None), None);
+
+        let mint = domain_mint_key(&domain);
+        let holder = Pubkey::new_unique();
+        let nft_holder = parse_nft_holder(&token_account_fixture(&mint, &holder, 1), &mint);
+        assert_eq!(nft_holder, Some(holder));
+        assert_eq!(
+            domain_destination(&domain, &registry, None, nft_holder),
+            Some(holder)
+        );
+
+        // A record counts when signed by the holder, not by the escrow.
+        let destination = Pubkey::new_unique();
+        let by_holder = NameRegistry::parse(&name_registry_fixture(
+            &domain,
+            &escrow,
+            &sol_record_v2_fixture(VALIDATION_SOLANA, &holder, &destination, &destination),
+        ))
+        .unwrap();
+        assert_eq!(
+            domain_destination(&domain, &registry, Some(&by_holder), nft_holder),
+            Some(destination)
+        );
+        let by_escrow = NameRegistry::parse(&name_registry_fixture(
+            &domain,
+            &escrow,
+            &sol_record_v2_fixture(VALIDATION_SOLANA, &escrow, &destination, &destination),
+        ))
+        .unwrap();
+        assert_eq!(
+            domain_destination(&domain, &registry, Some(&by_escrow), nft_holder),
+            Some(holder)
+        );
+    }
+
+    #[test]
+    fn test_parse_nft_holder() {
+        let mint = Pubkey::new_unique();
+        let holder = Pubkey::new_unique();
+        assert_eq!(
+            parse_nft_holder(&token_account_fixture(&mint, &holder, 1), &mint),
+            Some(holder)
+        );
+        // An emptied account, another mint and a truncated account.
+        assert_eq!(
+            parse_nft_holder(&token_account_fixture(&mint, &holder, 0), &mint),
+            None
+        );
+        assert_eq!(
+            parse_nft_holder(
+                &token_account_fixture(&Pubkey::new_unique(), &holder, 1),
+                &mint
+            ),
+            None
+        );
+        assert_eq!(
+            parse_nft_holder(&token_account_fixture(&mint, &holder, 1)[..100], &mint),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_reverse_name() {
+        let class = Pubkey::from_str_const(SNS_REVERSE_LOOKUP_CLASS);
+        let account =
+            name_registry_fixture(&Pubkey::default(), &class, &reverse_fixture("bonfida"));
+        let reverse = NameRegistry::parse(&account).unwrap();
+        assert_eq!(parse_reverse_name(&reverse).as_deref(), Some("bonfida"));
+
+        let account = name_registry_fixture(&Pubkey::default(), &class, &reverse_fixture("\0dex"));
+        let reverse = NameRegistry::parse(&account).unwrap();
+        assert_eq!(parse_reverse_name(&reverse).as_deref(), Some("dex"));
+    }
+
+    #[test]
+    fn test_parse_primary_domain() {
+        let name_account = Pubkey::new_unique();
+        let mut data = vec![1];
+        data.extend_from_slice(name_account.as_ref());
+        assert_eq!(parse_primary_domain(&data), Some(name_account));
+        assert_eq!(parse_primary_domain(&data[..32]), None);
+    }
+}
@@ -0,0 +1,99 @@
+use {
+    crate::sns::{
+        parse_primary_domain, parse_reverse_name, primary_domain_key, reverse_key,
+        sol_tld_authority, NameRegistry,
+    },
+    log::{debug, error},
+    smbcloud_wallet_core_network::model::{
+        ErrorCode::{InvalidPubkey, NetworkError},
+        ErrorResponse,
+    },
+    solana_pubkey::Pubkey,
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
+    std::str::FromStr,
+};
+
+/// Return the primary `.sol` domain of an address, e.g. for history and contacts.
+///
+/// Returns `None` when no primary domain is set or the address no longer owns it.
+pub async fn primary_domain(
+    rpc_url: String,
+    pubkey: String,
+) -> Result<Option<String>, ErrorResponse> {
+    let owner = match Pubkey::from_str(&pubkey) {
+        Ok(pubkey) => pubkey,
+        Err(err) => {
+            error!("Error parsing wallet pubkey: {}", err);
+            return Err(ErrorResponse::Error {
+                code: InvalidPubkey,
+                message: err.to_string(),
+            });
+        }
+    };
+    let connection = RpcClient::new(rpc_url);
+
+    let name_account = match fetch(&connection, &primary_domain_key(&owner)).await? {
+        Some(data) => match parse_primary_domain(&data) {
+            Some(name_account) => name_account,
+            None => return Ok(None),
+        },
+        None => return Ok(None),
+    };
+    let registry = match fetch_registry(&connection, &name_account).await? {
+        Some(registry) => registry,
+        None => return Ok(None),
+    };
+    if registry.owner != owner {
+        debug!("Primary domain of {} is stale", owner);
+        return Ok(None);
+    }
+
+    let root = sol_tld_authority();
+    let domain = if registry.parent == root {
+        reverse_name(&connection, &name_account, None)
+            .await?
+            .map(|name| format!("{}.sol", name))
+    } else {
+        // A subdomain, its reverse account is derived under the parent domain.
+        let sub = reverse_name(&connection, &name_account, Some(&registry.parent)).await?;
+        let parent = reverse_name(&connection, &registry.parent, None).await?;
+        sub.zip(parent)
+            .map(|(sub, parent)| format!("{}.{}.sol", sub, parent))
+    };
+    Ok(domain)
+}
+
+async fn reverse_name(
+    connection: &RpcClient,
+    name_account: &Pubkey,
+    parent: Option<&Pubkey>,
+) -> Result<Option<String>, ErrorResponse> {
+    let reverse = fetch_registry(connection, &reverse_key(name_account, parent)).await?;
+    Ok(reverse.as_ref().and_then(parse_reverse_name))
+}
+
+async fn fetch_registry(
+    connection: &RpcClient,
+    pubkey: &Pubkey,
+) -> Result<Option<NameRegistry>, ErrorResponse> {
+    Ok(fetch(connection, pubkey)
+        .await?
+        .and_then(|data| NameRegistry::parse(&data)))
+}
+
+/// Account data, or `None` if the account does not exist.
+async fn fetch(connection: &RpcClient, pubkey: &Pubkey) -> Result<Option<Vec<u8>>, ErrorResponse> {
+    match connection
+        .get_account_with_commitment(pubkey, connection.commitment())
+        .await
+    {
+        Ok(response) => Ok(response.value.map(|account| account.data)),
+        Err(err) => {
+            error!("Error getting account {}: {}", pubkey, err);
+            Err(ErrorResponse::Error {
+                code: NetworkError,
+                message: err.to_string(),
+            })
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use {
+    crate::sns::{
+        domain_destination, domain_key, domain_mint_key, is_tokenized, parse_nft_holder,
+        sol_record_v2_key, NameRegistry,
+    },
+    log::{debug, error},
+    smbcloud_wallet_core_network::model::{
+        ErrorCode::{DomainNotFound, InvalidDomain, NetworkError},
+        ErrorResponse,
+    },
+    solana_pubkey::Pubkey,
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
+    std::str::FromStr,
+};
+
+/// Resolve a `.sol` domain or subdomain to the address funds should be sent to.
+///
+/// A valid records v2 `SOL` record wins, otherwise the domain owner is returned.
+/// A tokenized domain is owned by whoever holds its NFT; when nobody does, it
+/// is reported as not found rather than resolved to the tokenizer escrow.
+pub async fn resolve_domain(rpc_url: String, domain: String) -> Result<String, ErrorResponse> {
+    let key = match domain_key(&domain) {
+        Some(key) => key,
+        None => {
+            return Err(ErrorResponse::Error {
+                code: InvalidDomain,
+                message: InvalidDomain.to_string(),
+            })
+        }
+    };
+    let record_key = sol_record_v2_key(&key.pubkey);
+
+    let connection = RpcClient::new(rpc_url);
+    let accounts = match connection
+        .get_multiple_accounts(&[key.pubkey, record_key])
+        .await
+    {
+        Ok(accounts) => accounts,
+        Err(err) => {
+            error!("Error getting name accounts: {}", err);
+            return Err(ErrorResponse::Error {
+                code: NetworkError,
+                message: err.to_string(),
+            });
+        }
+    };
+
+    let registry = accounts
+        .first()
+        .and_then(|account| account.as_ref())
+        .and_then(|account| NameRegistry::parse(&account.data));
+    let registry = match registry {
+        Some(registry) => registry,
+        None => {
+            return Err(ErrorResponse::Error {
+                code: DomainNotFound,
+                message: DomainNotFound.to_string(),
+            })
+        }
+    };
+
+    let nft_holder = if is_tokenized(&key.pubkey, &registry) {
+        nft_holder(&connection, &key.pubkey).await?
+    } else {
+        None
+    };
+    let sol_record = accounts
+        .get(1)
+        .and_then(|account| account.as_ref())
+        .and_then(|account| NameRegistry::parse(&account.data));
+    match domain_destination(&key.pubkey, &registry, sol_record.as_ref(), nft_holder) {
+        Some(destination) => {
+            debug!("{} resolved to {}", domain, destination);
+            Ok(destination.to_string())
+        }
+        None => {
+            debug!("{} is tokenized and its NFT is not held", domain);
+            Err(ErrorResponse::Error {
+                code: DomainNotFound,
+                message: DomainNotFound.to_string(),
+            })
+        }
+    }
+}
+
+/// The wallet holding the NFT of the tokenized domain account `domain`.
+///
+/// The NFT has a supply of one, so its holder is the largest token account.
+async fn nft_holder(
+    connection: &RpcClient,
+    domain: &Pubkey,
+) -> Result<Option<Pubkey>, ErrorResponse> {
+    let network_error = |err: String| {
+        error!("Error getting the domain NFT holder: {}", err);
+        ErrorResponse::Error {
+            code: NetworkError,
+            message: err,
+        }
+    };
+    let mint = domain_mint_key(domain);
+    let largest = match connection.get_token_largest_accounts(&mint).await {
+        Ok(largest) => largest,
+        Err(err) => return Err(network_error(err.to_string())),
+    };
+    let Some(token_account) = largest
+        .iter()
+        .find(|balance| balance.amount.amount == "1")
+        .and_then(|balance| Pubkey::from_str(&balance.address).ok())
+    else {
+        return Ok(None);
+    };
+    match connection.get_account_data(&token_account).await {
+        Ok(data) => Ok(parse_nft_holder(&data, &mint)),
+        Err(err) => Err(network_error(err.to_string())),
+    }
+}
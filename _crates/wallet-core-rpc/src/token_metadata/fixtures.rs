@@ -0,0 +1,20 @@
+//! Account data to test token layouts with, for this crate and its dependents.
+
+use super::{MINT_DECIMALS_OFFSET, MINT_INITIALIZED_OFFSET, MINT_LEN};
+
+/// A borsh string, zero padded to `padded_len` like fixed size metadata fields.
+pub fn borsh_string(value: &str, padded_len: usize) -> Vec<u8> {
+    let mut bytes = value.as_bytes().to_vec();
+    bytes.resize(padded_len.max(bytes.len()), 0);
+    let mut data = (bytes.len() as u32).to_le_bytes().to_vec();
+    data.extend_from_slice(&bytes);
+    data
+}
+
+/// An initialized mint without extensions.
+pub fn mint_fixture(decimals: u8) -> Vec<u8> {
+    let mut data = vec![0; MINT_LEN];
+    data[MINT_DECIMALS_OFFSET] = decimals;
+    data[MINT_INITIALIZED_OFFSET] = 1;
+    data
+}
@@ -0,0 +1,93 @@
+use {
+    crate::token_metadata::{
+        is_token_program, metaplex_metadata_key, parse_metaplex_metadata, parse_mint_decimals,
+        parse_token_2022_metadata, MintMetadata,
+    },
+    log::{debug, error, warn},
+    smbcloud_wallet_constants::constants::SPL_TOKEN_2022_PROGRAM_ID,
+    smbcloud_wallet_core_network::model::{
+        ErrorCode::{InvalidPubkey, NetworkError},
+        ErrorResponse,
+    },
+    solana_pubkey::Pubkey,
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
+    std::str::FromStr,
+};
+
+/// Accounts requested per `getMultipleAccounts` call.
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+/// Read the decimals and metadata of `mints`.
+///
+/// Mints that do not exist are left out of the result.
+pub async fn mint_metadata(
+    rpc_url: String,
+    mints: Vec<String>,
+) -> Result<Vec<MintMetadata>, ErrorResponse> {
+    let mut mint_pubkeys = Vec::with_capacity(mints.len());
+    for mint in &mints {
+        match Pubkey::from_str(mint) {
+            Ok(pubkey) => mint_pubkeys.push(pubkey),
+            Err(err) => {
+                error!("Error parsing mint {}: {}", mint, err);
+                return Err(ErrorResponse::Error {
+                    code: InvalidPubkey,
+                    message: err.to_string(),
+                });
+            }
+        }
+    }
+
+    let connection = RpcClient::new(rpc_url);
+    let token_2022_program_id = Pubkey::from_str_const(SPL_TOKEN_2022_PROGRAM_ID);
+    let mut result = Vec::with_capacity(mint_pubkeys.len());
+    // Every mint is read together with its Metaplex metadata account.
+    for chunk in mint_pubkeys.chunks(MAX_MULTIPLE_ACCOUNTS / 2) {
+        let keys = chunk
+            .iter()
+            .flat_map(|mint| [*mint, metaplex_metadata_key(mint)])
+            .collect::<Vec<_>>();
+        let accounts = match connection.get_multiple_accounts(&keys).await {
+            Ok(accounts) => accounts,
+            Err(err) => {
+                error!("Error getting mint accounts: {}", err);
+                return Err(ErrorResponse::Error {
+                    code: NetworkError,
+                    message: err.to_string(),
+                });
+            }
+        };
+
+        for (mint, accounts) in chunk.iter().zip(accounts.chunks(2)) {
+            let Some(mint_account) = accounts.first().and_then(|account| account.as_ref()) else {
+                debug!("Mint {} not found", mint);
+                continue;
+            };
+            if !is_token_program(&mint_account.owner) {
+                warn!("Account {} is not owned by a token program", mint);
+                continue;
+            }
+            let Some(decimals) = parse_mint_decimals(&mint_account.data) else {
+                warn!("Account {} is not a mint", mint);
+                continue;
+            };
+            let extension_metadata = (mint_account.owner == token_2022_program_id)
+                .then(|| parse_token_2022_metadata(&mint_account.data))
+                .flatten();
+            let metadata = extension_metadata.or_else(|| {
+                accounts
+                    .get(1)
+                    .and_then(|account| account.as_ref())
+                    .and_then(|account| parse_metaplex_metadata(&account.data))
+            });
+            result.push(MintMetadata {
+                mint: *mint,
+                decimals,
+                metadata,
+            });
+        }
+    }
+
+    debug!("Read metadata of {} mints", result.len());
+    Ok(result)
+}
@@ -0,0 +1,279 @@
+//! Token mint and metadata account layouts.
+//!
+//! Metadata is read from the Token-2022 metadata extension of the mint when it
+//! has one, otherwise from the Metaplex token metadata account of the mint.
+
+use {
+    smbcloud_wallet_constants::constants::{
+        METAPLEX_TOKEN_METADATA_PROGRAM_ID, SPL_TOKEN_2022_PROGRAM_ID, SPL_TOKEN_PROGRAM_ID,
+    },
+    solana_pubkey::Pubkey,
+};
+
+#[cfg(any(test, feature = "fixtures"))]
+pub mod fixtures;
+pub mod mint_metadata;
+
+/// Size of a mint account without extensions.
+const MINT_LEN: usize = 82;
+
+/// Offset of the mint decimals, after the mint authority and the supply.
+const MINT_DECIMALS_OFFSET: usize = 44;
+
+/// Offset of the mint `is_initialized` flag.
+const MINT_INITIALIZED_OFFSET: usize = 45;
+
+/// Offset of the Token-2022 account type, mints are padded to the size of a token account.
+const TOKEN_2022_ACCOUNT_TYPE_OFFSET: usize = 165;
+
+/// Token-2022 account type of a mint.
+const TOKEN_2022_ACCOUNT_TYPE_MINT: u8 = 1;
+
+/// Token-2022 extension type of the token metadata extension.
+const TOKEN_2022_EXTENSION_TOKEN_METADATA: u16 = 19;
+
+/// Metaplex account key of a metadata account.
+pub const METAPLEX_KEY_METADATA_V1: u8 = 4;
+
+/// Offset of the mint in a Metaplex metadata account, after the key and the
+/// update authority.
+const METAPLEX_MINT_OFFSET: usize = 33;
+
+/// Name, symbol and URI a token creator published for a mint.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenMetadata {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+/// A mint read from chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MintMetadata {
+    pub mint: Pubkey,
+    pub decimals: u8,
+    /// `None` when the mint has no metadata.
+    pub metadata: Option<TokenMetadata>,
+}
+
+pub fn metaplex_program_id() -> Pubkey {
+    Pubkey::from_str_const(METAPLEX_TOKEN_METADATA_PROGRAM_ID)
+}
+
+/// Address of the Metaplex metadata account of `mint`.
+pub fn metaplex_metadata_key(mint: &Pubkey) -> Pubkey {
+    let program_id = metaplex_program_id();
+    let (pubkey, _) = Pubkey::find_program_address(
+        &[b"metadata", program_id.as_ref(), mint.as_ref()],
+        &program_id,
+    );
+    pubkey
+}
+
+/// Whether `program_id` is the SPL Token or the Token-2022 program.
+pub fn is_token_program(program_id: &Pubkey) -> bool {
+    *program_id == Pubkey::from_str_const(SPL_TOKEN_PROGRAM_ID)
+        || *program_id == Pubkey::from_str_const(SPL_TOKEN_2022_PROGRAM_ID)
+}
+
+/// Decimals of an initialized SPL Token or Token-2022 mint.
+///
+/// Only the layout is checked, the account must be owned by a token program,
+/// see [`is_token_program`].
+pub fn parse_mint_decimals(data: &[u8]) -> Option<u8> {
+    if data.len() < MINT_LEN || data[MINT_INITIALIZED_OFFSET] != 1 {
+        return None;
+    }
+    Some(data[MINT_DECIMALS_OFFSET])
+}
+
+/// The token metadata extension of a Token-2022 mint.
+pub fn parse_token_2022_metadata(data: &[u8]) -> Option<TokenMetadata> {
+    if *data.get(TOKEN_2022_ACCOUNT_TYPE_OFFSET)? != TOKEN_2022_ACCOUNT_TYPE_MINT {
+        return None;
+    }
+    let mut offset = TOKEN_2022_ACCOUNT_TYPE_OFFSET + 1;
+    while offset + 4 <= data.len() {
+        let extension_type = u16::from_le_bytes(data[offset..offset + 2].try_into().ok()?);
+        let length = u16::from_le_bytes(data[offset + 2..offset + 4].try_into().ok()?) as usize;
+        let value = data.get(offset + 4..offset + 4 + length)?;
+        if extension_type == TOKEN_2022_EXTENSION_TOKEN_METADATA {
+            // The update authority and the mint come before the strings.
+            let mut reader = Reader::new(value, 64);
+            return Some(TokenMetadata {
+                name: reader.string()?,
+                symbol: reader.string()?,
+                uri: reader.string()?,
+            });
+        }
+        offset += 4 + length;
+    }
+    None
+}
+
+/// A Metaplex metadata account, read up to its strings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetaplexMetadata<'a> {
+    pub mint: Pubkey,
+    pub metadata: TokenMetadata,
+    /// The fields that follow the strings, which older accounts lack.
+    pub rest: &'a [u8],
+}
+
+/// A Metaplex metadata account, with the padding of its fixed size strings removed.
+pub fn parse_metaplex_metadata(data: &[u8]) -> Option<TokenMetadata> {
+    parse_metaplex_account(data).map(|account| account.metadata)
+}
+
+/// A Metaplex metadata account, with the fields after its strings left for
+/// the caller to read.
+pub fn parse_metaplex_account(data: &[u8]) -> Option<MetaplexMetadata<'_>> {
+    if *data.first()? != METAPLEX_KEY_METADATA_V1 {
+        return None;
+    }
+    let mint = data.get(METAPLEX_MINT_OFFSET..METAPLEX_MINT_OFFSET + 32)?;
+    // The key, the update authority and the mint come before the strings.
+    let mut reader = Reader::new(data, METAPLEX_MINT_OFFSET + 32);
+    let metadata = TokenMetadata {
+        name: reader.string()?,
+        symbol: reader.string()?,
+        uri: reader.string()?,
+    };
+    Some(MetaplexMetadata {
+        mint: Pubkey::try_from(mint).ok()?,
+        metadata,
+        rest: &data[reader.offset..],
+    })
+}
+
+/// Reads borsh strings one after the other.
+struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8], offset: usize) -> Self {
+        Reader { data, offset }
+    }
+
+    fn string(&mut self) -> Option<String> {
+        let length_end = self.offset + 4;
+        let length = u32::from_le_bytes(self.data.get(self.offset..length_end)?.try_into().ok()?);
+        let end = length_end + length as usize;
+        let bytes = self.data.get(length_end..end)?;
+        self.offset = end;
+        let value = String::from_utf8_lossy(bytes);
+        Some(value.trim_end_matches('\0').trim().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        fixtures::{borsh_string, mint_fixture},
+    };
+
+    fn extension(extension_type: u16, value: &[u8]) -> Vec<u8> {
+        let mut data = extension_type.to_le_bytes().to_vec();
+        data.extend_from_slice(&(value.len() as u16).to_le_bytes());
+        data.extend_from_slice(value);
+        data
+    }
+
+    #[test]
+    fn test_metaplex_metadata_key() {
+        // Metadata account of USDC.
+        let mint = Pubkey::from_str_const("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+        assert_eq!(
+            metaplex_metadata_key(&mint).to_string(),
+            "5x38Kp4hvdomTCnCrAny4UtMUt5rQBdB6px2K1Ui45Wq"
+        );
+    }
+
+    #[test]
+    fn test_parse_mint_decimals() {
+        assert_eq!(parse_mint_decimals(&mint_fixture(6)), Some(6));
+        let mut uninitialized = mint_fixture(6);
+        uninitialized[MINT_INITIALIZED_OFFSET] = 0;
+        assert_eq!(parse_mint_decimals(&uninitialized), None);
+        assert_eq!(parse_mint_decimals(&mint_fixture(6)[..MINT_LEN - 1]), None);
+    }
+
+    #[test]
+    fn test_is_token_program() {
+        assert!(is_token_program(&Pubkey::from_str_const(
+            SPL_TOKEN_PROGRAM_ID
+        )));
+        assert!(is_token_program(&Pubkey::from_str_const(
+            SPL_TOKEN_2022_PROGRAM_ID
+        )));
+        assert!(!is_token_program(&metaplex_program_id()));
+        assert!(!is_token_program(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_parse_token_2022_metadata() {
+        let mut value = vec![0; 64];
+        value.extend(borsh_string("PayPal USD", 0));
+        value.extend(borsh_string("PYUSD", 0));
+        value.extend(borsh_string("https://example.com/pyusd.json", 0));
+        // No additional metadata.
+        value.extend_from_slice(&0u32.to_le_bytes());
+
+        let mut data = mint_fixture(6);
+        data.resize(TOKEN_2022_ACCOUNT_TYPE_OFFSET, 0);
+        data.push(TOKEN_2022_ACCOUNT_TYPE_MINT);
+        // A metadata pointer comes first.
+        data.extend(extension(18, &[0; 64]));
+        data.extend(extension(TOKEN_2022_EXTENSION_TOKEN_METADATA, &value));
+
+        let metadata = parse_token_2022_metadata(&data).unwrap();
+        assert_eq!(metadata.name, "PayPal USD");
+        assert_eq!(metadata.symbol, "PYUSD");
+        assert_eq!(metadata.uri, "https://example.com/pyusd.json");
+
+        // A mint without extensions.
+        assert_eq!(parse_token_2022_metadata(&mint_fixture(6)), None);
+        // A truncated extension.
+        data.truncate(data.len() - 1);
+        assert_eq!(parse_token_2022_metadata(&data), None);
+    }
+
+    #[test]
+    fn test_parse_metaplex_metadata() {
+        let mut data = vec![METAPLEX_KEY_METADATA_V1];
+        data.extend_from_slice(&[0; 64]);
+        data.extend(borsh_string("USD Coin", 32));
+        data.extend(borsh_string("USDC", 10));
+        data.extend(borsh_string("", 200));
+        data.extend_from_slice(&[0; 40]);
+
+        let metadata = parse_metaplex_metadata(&data).unwrap();
+        assert_eq!(metadata.name, "USD Coin");
+        assert_eq!(metadata.symbol, "USDC");
+        assert_eq!(metadata.uri, "");
+
+        data[0] = 0;
+        assert_eq!(parse_metaplex_metadata(&data), None);
+        assert_eq!(parse_metaplex_metadata(&data[..70]), None);
+    }
+
+    #[test]
+    fn test_parse_metaplex_account() {
+        let mint = Pubkey::new_unique();
+        let mut data = vec![METAPLEX_KEY_METADATA_V1];
+        data.extend_from_slice(&[0; 32]);
+        data.extend_from_slice(mint.as_ref());
+        data.extend(borsh_string("Mad Lad #1", 32));
+        data.extend(borsh_string("MAD", 10));
+        data.extend(borsh_string("", 200));
+        data.extend_from_slice(&[1, 2, 3]);
+
+        let account = parse_metaplex_account(&data).unwrap();
+        assert_eq!(account.mint, mint);
+        assert_eq!(account.metadata.name, "Mad Lad #1");
+        assert_eq!(account.rest, [1, 2, 3]);
+    }
+}
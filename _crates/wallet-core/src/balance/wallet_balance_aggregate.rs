@@ -1,14 +1,12 @@
 use {
-    smbcloud_wallet_constants::{
-        assets_solana::ADDRESS_SOL,
-        constants::{SOL_DECIMALS, SPL_TOKEN_PROGRAM_ID},
+    crate::token_registry::resolve_tokens,
+    smbcloud_wallet_constants::{assets_solana::ADDRESS_SOL, constants::SOL_DECIMALS},
+    smbcloud_wallet_core_model::{
+        models::{asset_metadata::Metadata, balance::Balance},
+        token_registry::TokenRegistry,
     },
-    smbcloud_wallet_core_model::models::balance::Balance,
     smbcloud_wallet_core_network::model::ErrorResponse,
-    smbcloud_wallet_core_rpc::balance::{
-        sol_balance::sol_balance as core_sol_balance,
-        spl_token_accounts_with_balance::spl_token_accounts_with_balance,
-    },
+    smbcloud_wallet_core_rpc::balance::wallet_holdings::wallet_holdings,
 };
 
 pub async fn wallet_balance_aggregate(
@@ -17,8 +15,9 @@ pub async fn wallet_balance_aggregate(
 ) -> Result<Vec<Balance>, ErrorResponse> {
     let mut aggregates: Vec<Balance> = Vec::new();
 
-    // Get SOL balance
-    let sol_amount = match core_sol_balance(rpc_url.clone(), pubkey.clone()) {
+    // SOL and the tokens of both token programs, handling errors gracefully
+    let holdings = wallet_holdings(rpc_url.clone(), pubkey).await;
+    let sol_amount = match holdings.sol {
         Ok(balance) => balance.1,
         Err(_) => 0.0,
     };
@@ -33,21 +32,19 @@ pub async fn wallet_balance_aggregate(
         decimal: SOL_DECIMALS,
     });
 
-    // Try to get BACH price, but handle errors gracefully
-    let spl_tokens = match spl_token_accounts_with_balance(
-        rpc_url.clone(),
-        pubkey.clone(),
-        SPL_TOKEN_PROGRAM_ID.to_string(),
-    ) {
-        Ok(tokens) => tokens,
-        Err(err) => {
-            println!("🦀🦀  Failed to get SPL token accounts: {:?}", err);
-            Vec::new()
-        }
-    };
+    let spl_tokens = holdings.tokens;
 
     println!("🦀🦀  SPL tokens with balance: {:?}", spl_tokens);
 
+    let registry = TokenRegistry::shared();
+    let mints = spl_tokens
+        .iter()
+        .map(|token| token.mint.clone())
+        .collect::<Vec<_>>();
+    if let Err(err) = resolve_tokens(registry, rpc_url, &mints).await {
+        println!("🦀🦀  Failed to resolve token metadata: {:?}", err);
+    }
+
     for token in spl_tokens {
         let token_amount = match token.token_amount.ui_amount {
             Some(amount) => amount,
@@ -56,7 +53,16 @@ pub async fn wallet_balance_aggregate(
                 0.0
             }
         };
-        let symbol = token.mint[..3].to_string();
+        let symbol = if token.is_native {
+            Metadata::WRAPPED_SOL_SYMBOL.to_string()
+        } else {
+            registry
+                .get(&token.mint)
+                .unwrap_or_else(|| {
+                    Metadata::unknown(token.mint.clone(), token.token_amount.decimals)
+                })
+                .symbol
+        };
         aggregates.push(Balance {
             mint: token.mint,
             symbol,
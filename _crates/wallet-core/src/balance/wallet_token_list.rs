@@ -1,10 +1,14 @@
 use {
-    smbcloud_wallet_constants::constants::SPL_TOKEN_PROGRAM_ID,
-    smbcloud_wallet_core_model::models::{asset_solana::SolanaAsset, balance_v1::BalanceV1},
-    smbcloud_wallet_core_network::model::ErrorResponse,
-    smbcloud_wallet_core_rpc::balance::{
-        sol_balance::sol_balance, spl_token_accounts_with_balance::spl_token_accounts_with_balance,
+    crate::token_registry::resolve_tokens,
+    smbcloud_wallet_constants::{assets_solana::ADDRESS_SOL, constants::SOL_DECIMALS},
+    smbcloud_wallet_core_model::{
+        models::{
+            asset_metadata::Metadata, balance_v1::BalanceV1, token_preferences::TokenVisibility,
+        },
+        token_registry::TokenRegistry,
     },
+    smbcloud_wallet_core_network::model::ErrorResponse,
+    smbcloud_wallet_core_rpc::balance::wallet_holdings::wallet_holdings,
     std::str::FromStr,
 };
 
@@ -13,58 +17,64 @@ pub async fn wallet_token_list(
     pubkey: String,
 ) -> Result<Vec<BalanceV1>, ErrorResponse> {
     let mut aggregates: Vec<BalanceV1> = Vec::new();
+    let registry = TokenRegistry::shared();
 
-    // Get SOL balance
-    let sol_balance = sol_balance(rpc_url.clone(), pubkey.clone()).unwrap_or((0, 0.0));
+    // SOL and the tokens of both token programs, handling errors gracefully
+    let holdings = wallet_holdings(rpc_url.clone(), pubkey).await;
+    let sol_balance = holdings.sol.unwrap_or((0, 0.0));
 
     println!("🦀🦀  Balance {:?} SOL", sol_balance);
     if sol_balance.0 > 0 {
-        let native_asset = SolanaAsset::native();
+        let native = registry
+            .get(ADDRESS_SOL)
+            .unwrap_or_else(|| Metadata::unknown(ADDRESS_SOL.to_string(), SOL_DECIMALS));
         aggregates.push(BalanceV1 {
-            meta: native_asset.metadata(),
+            meta: native,
             balance: sol_balance.0,
             ui_amount: sol_balance.1,
+            visibility: TokenVisibility::Visible,
         });
     }
 
-    // Try to get BACH price, but handle errors gracefully
-    let spl_tokens = match spl_token_accounts_with_balance(
-        rpc_url.clone(),
-        pubkey.clone(),
-        SPL_TOKEN_PROGRAM_ID.to_string(),
-    ) {
-        Ok(tokens) => tokens,
-        Err(err) => {
-            println!("🦀🦀  Failed to get SPL token accounts: {:?}", err);
-            Vec::new()
-        }
-    };
+    let mut spl_tokens = holdings.tokens;
+
+    // A single token of a mint without decimals is an NFT, listed with the NFT holdings.
+    spl_tokens.retain(|token| token.token_amount.decimals != 0 || token.token_amount.amount != "1");
 
     println!("🦀🦀  SPL tokens with balance: {:?}", spl_tokens);
 
+    let mints = spl_tokens
+        .iter()
+        .map(|token| token.mint.clone())
+        .collect::<Vec<_>>();
+    if let Err(err) = resolve_tokens(registry, rpc_url, &mints).await {
+        println!("🦀🦀  Failed to resolve token metadata: {:?}", err);
+    }
+
     for token in spl_tokens {
-        let asset = match SolanaAsset::from_address(token.mint) {
-            Some(asset) => asset,
-            None => {
-                println!("🦀🦀  Unrecognized SPL token.");
-                continue;
-            }
+        // Tokens without metadata are still listed, named after their mint.
+        let meta = registry
+            .get(&token.mint)
+            .unwrap_or_else(|| Metadata::unknown(token.mint.clone(), token.token_amount.decimals));
+        // Wrapped SOL is listed apart from the SOL balance, priced as SOL.
+        let meta = if token.is_native {
+            Metadata::wrapped_sol(meta)
+        } else {
+            meta
         };
         let amount = u64::from_str(&token.token_amount.amount).unwrap_or_default();
         let ui_amount = match token.token_amount.ui_amount {
             Some(amount) => amount,
             None => {
-                println!(
-                    "🦀🦀  Token amount is None for token {}",
-                    asset.metadata().name
-                );
+                println!("🦀🦀  Token amount is None for token {}", meta.name);
                 0.0
             }
         };
         aggregates.push(BalanceV1 {
-            meta: asset.metadata(),
+            meta,
             balance: amount,
             ui_amount,
+            visibility: TokenVisibility::Visible,
         });
     }
 
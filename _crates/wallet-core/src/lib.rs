@@ -2,3 +2,4 @@ pub mod balance;
 pub mod create_seed_phrase;
 pub mod derive_keypair;
 pub mod import_wallet;
+pub mod token_registry;
@@ -0,0 +1,93 @@
+//! Token metadata read from chain for the token registry.
+
+use {
+    log::debug,
+    smbcloud_wallet_core_model::{models::asset_metadata::Metadata, token_registry::TokenRegistry},
+    smbcloud_wallet_core_network::model::ErrorResponse,
+    smbcloud_wallet_core_rpc::token_metadata::{mint_metadata::mint_metadata, MintMetadata},
+};
+
+/// Metadata of `mints`, reading the ones unknown to `registry` from chain.
+///
+/// Mints that do not exist on chain are left out.
+pub async fn resolve_tokens(
+    registry: &TokenRegistry,
+    rpc_url: String,
+    mints: &[String],
+) -> Result<Vec<Metadata>, ErrorResponse> {
+    let unknown = registry.unknown(mints);
+    if !unknown.is_empty() {
+        debug!("Reading metadata of {} mints", unknown.len());
+        let resolved = mint_metadata(rpc_url, unknown).await?;
+        registry.insert(resolved.into_iter().map(from_mint_metadata));
+    }
+    Ok(registry.known(mints))
+}
+
+/// Metadata of a mint read from chain, named after its address when it published none.
+fn from_mint_metadata(mint: MintMetadata) -> Metadata {
+    let address = mint.mint.to_string();
+    match mint.metadata {
+        Some(metadata) if !metadata.symbol.is_empty() => Metadata {
+            name: if metadata.name.is_empty() {
+                metadata.symbol.clone()
+            } else {
+                metadata.name
+            },
+            symbol: metadata.symbol,
+            // Off-chain JSON is not fetched, so only direct image links are usable.
+            logo_uri: if is_image_uri(&metadata.uri) {
+                metadata.uri
+            } else {
+                String::new()
+            },
+            ..Metadata::unknown(address, mint.decimals)
+        },
+        _ => Metadata::unknown(address, mint.decimals),
+    }
+}
+
+fn is_image_uri(uri: &str) -> bool {
+    let path = uri
+        .split(['?', '#'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    [".png", ".jpg", ".jpeg", ".svg", ".webp", ".gif"]
+        .iter()
+        .any(|extension| path.ends_with(extension))
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*, smbcloud_wallet_core_rpc::token_metadata::TokenMetadata, solana_pubkey::Pubkey,
+    };
+
+    #[test]
+    fn test_from_mint_metadata() {
+        let mint = Pubkey::new_unique();
+        let metadata = from_mint_metadata(MintMetadata {
+            mint,
+            decimals: 9,
+            metadata: Some(TokenMetadata {
+                name: "Example".to_string(),
+                symbol: "EXMPL".to_string(),
+                uri: "https://example.com/token.json".to_string(),
+            }),
+        });
+        assert_eq!(metadata.address, mint.to_string());
+        assert_eq!(metadata.symbol, "EXMPL");
+        assert_eq!(metadata.decimal, 9);
+        assert_eq!(metadata.logo_uri, "");
+        assert!(!metadata.verified);
+
+        let unnamed = from_mint_metadata(MintMetadata {
+            mint,
+            decimals: 0,
+            metadata: None,
+        });
+        assert_eq!(unnamed, Metadata::unknown(mint.to_string(), 0));
+        assert!(unnamed.symbol.contains('…'));
+    }
+}
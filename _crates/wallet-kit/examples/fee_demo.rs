@@ -3,9 +3,12 @@
 //! This example demonstrates how the 0.25% fee system works for both SOL and token transfers.
 //! The fee is automatically deducted from each transaction and sent to The Stable Foundation Treasury.
 
-use smbcloud_wallet_kit::{
-    fee::{FeeBreakdown, FeeConfig},
-    transactions::{estimate_sol_transaction_cost, estimate_token_transaction_cost},
+use {
+    smbcloud_wallet_constants::constants::SEMITONE_PER_BACH,
+    smbcloud_wallet_kit::{
+        fee::{FeeBreakdown, FeeConfig},
+        transactions::{estimate_sol_transaction_cost, estimate_token_transaction_cost},
+    },
 };
 
 fn main() {
@@ -108,8 +111,8 @@ fn demo_token_transaction_fees() {
 
         if token == "BACH" {
             // Show semitone conversion for BACH
-            let fee_st = breakdown.fee_token_units(1_000_000_000.0); // SEMITONE_PER_BACH
-            let net_st = breakdown.net_token_units(1_000_000_000.0);
+            let fee_st = breakdown.fee_token_units(SEMITONE_PER_BACH);
+            let net_st = breakdown.net_token_units(SEMITONE_PER_BACH);
             println!("  Fee in st: {}", fee_st);
             println!("  Net in st: {}", net_st);
         }
@@ -155,8 +158,10 @@ fn demo_fee_configuration() {
     }
 
     // Disabled fees configuration
-    let mut disabled_config = FeeConfig::default();
-    disabled_config.fees_enabled = false;
+    let disabled_config = FeeConfig {
+        fees_enabled: false,
+        ..FeeConfig::default()
+    };
 
     let no_fee_breakdown = disabled_config
         .calculate_breakdown(100.0, "SOL".to_string())
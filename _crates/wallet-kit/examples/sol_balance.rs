@@ -1,7 +1,8 @@
 use smbcloud_wallet_kit::balance::sol_balance;
 
-fn main() {
+#[tokio::main]
+async fn main() {
     let rpc_url = "http://localhost:8899/";
     let pubkey = "BuLWxBBwgo2QTZjkAHr4YTKpEKssKgNiejMg8Z66dQ4A";
-    sol_balance(rpc_url.to_string(), pubkey.to_string());
+    sol_balance(rpc_url.to_string(), pubkey.to_string()).await;
 }
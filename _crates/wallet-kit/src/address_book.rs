@@ -0,0 +1,333 @@
+use {
+    crate::models::address_book::{Contact, ContactInput},
+    smbcloud_wallet_constants::assets_solana::ADDRESS_SOL,
+    smbcloud_wallet_core_model::models::environment::Environment,
+    solana_sdk::pubkey::Pubkey,
+    std::str::FromStr,
+    thiserror::Error,
+    uuid::Uuid,
+};
+
+/// Largest SOL transfer that counts as a test transfer and verifies a contact.
+pub const VERIFICATION_MAX_SOL: f64 = 0.01;
+
+#[derive(Error, Debug)]
+pub enum AddressBookError {
+    #[error("Invalid address: {0}")]
+    InvalidAddress(String),
+
+    #[error("Label must not be empty")]
+    EmptyLabel,
+
+    #[error("A contact for {0} already exists")]
+    DuplicateContact(String),
+
+    #[error("Contact not found: {0}")]
+    ContactNotFound(String),
+}
+
+/// Add a new contact, rejecting a second contact for the same address on the same network.
+pub fn add_contact(
+    contacts: &mut Vec<Contact>,
+    input: ContactInput,
+    now: i64,
+) -> Result<Contact, AddressBookError> {
+    let input = validate(input)?;
+    if contacts
+        .iter()
+        .any(|c| c.network == input.network && c.address == input.address)
+    {
+        return Err(AddressBookError::DuplicateContact(input.address));
+    }
+
+    let contact = Contact {
+        id: Uuid::new_v4().to_string(),
+        label: input.label,
+        address: input.address,
+        network: input.network,
+        username: input.username,
+        domain: input.domain,
+        notes: input.notes,
+        verified: false,
+        created_at: now,
+        last_used_at: None,
+    };
+    contacts.push(contact.clone());
+    Ok(contact)
+}
+
+/// Replace the editable fields of a contact.
+///
+/// Changing the address or network resets the verified status.
+pub fn update_contact(
+    contacts: &mut [Contact],
+    id: &str,
+    input: ContactInput,
+) -> Result<Contact, AddressBookError> {
+    let input = validate(input)?;
+    if contacts
+        .iter()
+        .any(|c| c.id != id && c.network == input.network && c.address == input.address)
+    {
+        return Err(AddressBookError::DuplicateContact(input.address));
+    }
+
+    let contact = contacts
+        .iter_mut()
+        .find(|c| c.id == id)
+        .ok_or_else(|| AddressBookError::ContactNotFound(id.to_string()))?;
+    if contact.address != input.address || contact.network != input.network {
+        contact.verified = false;
+    }
+    contact.label = input.label;
+    contact.address = input.address;
+    contact.network = input.network;
+    contact.username = input.username;
+    contact.domain = input.domain;
+    contact.notes = input.notes;
+    Ok(contact.clone())
+}
+
+pub fn remove_contact(contacts: &mut Vec<Contact>, id: &str) -> Result<Contact, AddressBookError> {
+    let index = contacts
+        .iter()
+        .position(|c| c.id == id)
+        .ok_or_else(|| AddressBookError::ContactNotFound(id.to_string()))?;
+    Ok(contacts.remove(index))
+}
+
+/// Find the contact on `network` whose address, `@username` or domain is exactly `query`.
+pub fn find_contact<'a>(
+    contacts: &'a [Contact],
+    network: Environment,
+    query: &str,
+) -> Option<&'a Contact> {
+    let query = query.trim();
+    let username = query.strip_prefix('@');
+    let same_text = |text: &Option<String>, query: Option<&str>| match (text, query) {
+        (Some(text), Some(query)) => text.eq_ignore_ascii_case(query),
+        _ => false,
+    };
+    contacts.iter().filter(|c| c.network == network).find(|c| {
+        c.address == query || same_text(&c.username, username) || same_text(&c.domain, Some(query))
+    })
+}
+
+/// Contacts on `network` matching `query`, best matches first.
+///
+/// Label, username and domain match anywhere in the text, the address only as a prefix.
+/// Prefix matches rank above other matches, then the most recently used contact wins.
+pub fn autocomplete_contacts(
+    contacts: &[Contact],
+    network: Environment,
+    query: &str,
+    limit: usize,
+) -> Vec<Contact> {
+    let query = query.trim().trim_start_matches('@').to_lowercase();
+    let mut matches: Vec<(bool, &Contact)> = contacts
+        .iter()
+        .filter(|c| c.network == network)
+        .filter_map(|c| match_rank(c, &query).map(|prefix| (prefix, c)))
+        .collect();
+    matches.sort_by(|(a_prefix, a), (b_prefix, b)| {
+        b_prefix
+            .cmp(a_prefix)
+            .then(b.last_used_at.cmp(&a.last_used_at))
+            .then_with(|| a.label.cmp(&b.label))
+    });
+    matches
+        .into_iter()
+        .take(limit)
+        .map(|(_, c)| c.clone())
+        .collect()
+}
+
+/// Record a successful transfer to the contact.
+///
+/// A small SOL transfer marks the contact as verified, since the user has seen
+/// it arrive before sending larger amounts.
+pub fn record_transfer(contact: &mut Contact, token_address: &str, amount: f64, now: i64) {
+    contact.last_used_at = Some(now);
+    if is_verification_transfer(token_address, amount) {
+        contact.verified = true;
+    }
+}
+
+pub fn is_verification_transfer(token_address: &str, amount: f64) -> bool {
+    token_address == ADDRESS_SOL && amount > 0.0 && amount <= VERIFICATION_MAX_SOL
+}
+
+/// `Some(true)` for a prefix match, `Some(false)` for any other match.
+fn match_rank(contact: &Contact, query: &str) -> Option<bool> {
+    if query.is_empty() {
+        return Some(false);
+    }
+    let texts = [
+        Some(contact.label.as_str()),
+        contact.username.as_deref(),
+        contact.domain.as_deref(),
+    ];
+    let texts = texts.iter().flatten().map(|text| text.to_lowercase());
+    let mut found = None;
+    for text in texts {
+        if text.starts_with(query) {
+            return Some(true);
+        }
+        if text.contains(query) {
+            found = Some(false);
+        }
+    }
+    if contact.address.to_lowercase().starts_with(query) {
+        return Some(true);
+    }
+    found
+}
+
+fn validate(input: ContactInput) -> Result<ContactInput, AddressBookError> {
+    let label = input.label.trim().to_string();
+    if label.is_empty() {
+        return Err(AddressBookError::EmptyLabel);
+    }
+    let address = input.address.trim().to_string();
+    Pubkey::from_str(&address).map_err(|_| AddressBookError::InvalidAddress(address.clone()))?;
+    Ok(ContactInput {
+        label,
+        address,
+        username: non_empty(input.username).map(|u| u.trim_start_matches('@').to_string()),
+        domain: non_empty(input.domain).map(|d| d.to_lowercase()),
+        notes: non_empty(input.notes),
+        ..input
+    })
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: &str = "mvines9iiHiQTysrwkJjGf2gb9Ex9jXJX8ns3qwf2kN";
+    const BOB: &str = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+
+    fn input(label: &str, address: &str) -> ContactInput {
+        ContactInput {
+            label: label.to_string(),
+            address: address.to_string(),
+            network: Environment::Mainnet,
+            username: None,
+            domain: None,
+            notes: None,
+        }
+    }
+
+    #[test]
+    fn test_add_contact() {
+        let mut contacts = Vec::new();
+        let mut alice = input(" Alice ", ALICE);
+        alice.username = Some("@alice".to_string());
+        alice.notes = Some("  ".to_string());
+        let contact = add_contact(&mut contacts, alice, 100).unwrap();
+        assert_eq!(contact.label, "Alice");
+        assert_eq!(contact.username.as_deref(), Some("alice"));
+        assert_eq!(contact.notes, None);
+        assert!(!contact.verified);
+
+        assert!(matches!(
+            add_contact(&mut contacts, input("Alice again", ALICE), 100),
+            Err(AddressBookError::DuplicateContact(_))
+        ));
+        // The same address on another network is a different contact.
+        let mut devnet = input("Alice devnet", ALICE);
+        devnet.network = Environment::Devnet;
+        assert!(add_contact(&mut contacts, devnet, 100).is_ok());
+
+        assert!(matches!(
+            add_contact(&mut contacts, input("Nobody", "not-an-address"), 100),
+            Err(AddressBookError::InvalidAddress(_))
+        ));
+        assert!(matches!(
+            add_contact(&mut contacts, input(" ", BOB), 100),
+            Err(AddressBookError::EmptyLabel)
+        ));
+    }
+
+    #[test]
+    fn test_update_contact_resets_verification() {
+        let mut contacts = Vec::new();
+        let id = add_contact(&mut contacts, input("Alice", ALICE), 100)
+            .unwrap()
+            .id;
+        contacts[0].verified = true;
+
+        let renamed = update_contact(&mut contacts, &id, input("Alice L.", ALICE)).unwrap();
+        assert!(renamed.verified);
+
+        let moved = update_contact(&mut contacts, &id, input("Alice L.", BOB)).unwrap();
+        assert_eq!(moved.address, BOB);
+        assert!(!moved.verified);
+    }
+
+    #[test]
+    fn test_find_contact() {
+        let mut contacts = Vec::new();
+        let mut alice = input("Alice", ALICE);
+        alice.username = Some("alice".to_string());
+        alice.domain = Some("Alice.sol".to_string());
+        add_contact(&mut contacts, alice, 100).unwrap();
+
+        let network = Environment::Mainnet;
+        assert!(find_contact(&contacts, network, ALICE).is_some());
+        assert!(find_contact(&contacts, network, "@Alice").is_some());
+        assert!(find_contact(&contacts, network, "alice.sol").is_some());
+        assert!(find_contact(&contacts, network, "alice").is_none());
+        assert!(find_contact(&contacts, Environment::Devnet, ALICE).is_none());
+    }
+
+    #[test]
+    fn test_autocomplete_contacts() {
+        let mut contacts = Vec::new();
+        add_contact(&mut contacts, input("Coffee shop", BOB), 100).unwrap();
+        let mut alice = input("Alice", ALICE);
+        alice.username = Some("coffeelover".to_string());
+        add_contact(&mut contacts, alice, 100).unwrap();
+        let mut alice_devnet = input("Alice", ALICE);
+        alice_devnet.network = Environment::Devnet;
+        add_contact(&mut contacts, alice_devnet, 100).unwrap();
+
+        let network = Environment::Mainnet;
+        // Both are prefix matches, so the most recently used one comes first.
+        contacts[1].last_used_at = Some(200);
+        let labels = |query: &str, limit| {
+            autocomplete_contacts(&contacts, network, query, limit)
+                .into_iter()
+                .map(|c| c.label)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(labels("COF", 10), vec!["Alice", "Coffee shop"]);
+        assert_eq!(labels("shop", 10), vec!["Coffee shop"]);
+        assert_eq!(labels("mvin", 10), vec!["Alice"]);
+        assert_eq!(labels("", 1), vec!["Alice"]);
+        assert!(labels("zzz", 10).is_empty());
+    }
+
+    #[test]
+    fn test_record_transfer() {
+        let mut contacts = Vec::new();
+        let mut contact = add_contact(&mut contacts, input("Alice", ALICE), 100).unwrap();
+
+        record_transfer(&mut contact, ADDRESS_SOL, 5.0, 200);
+        assert_eq!(contact.last_used_at, Some(200));
+        assert!(!contact.verified);
+
+        record_transfer(&mut contact, BOB, 0.001, 300);
+        assert!(!contact.verified);
+
+        record_transfer(&mut contact, ADDRESS_SOL, 0.001, 400);
+        assert_eq!(contact.last_used_at, Some(400));
+        assert!(contact.verified);
+    }
+}
@@ -3,27 +3,38 @@ use {
         assets::SOLANA,
         models::{asset::AssetBalance, currency::FiatCurrency},
     },
-    log::error,
+    log::{debug, error},
     smbcloud_wallet_constants::{
-        assets_solana::ADDRESS_BACH_TOKEN, constants::SPL_TOKEN_PROGRAM_ID,
+        assets_solana::{ADDRESS_BACH_TOKEN, ADDRESS_SOL},
+        constants::{LAMPORTS_PER_SOL, SPL_TOKEN_2022_PROGRAM_ID, SPL_TOKEN_PROGRAM_ID},
     },
-    smbcloud_wallet_core_http::price_data::{
-        get_asset_price::get_asset_price, get_sol_price::get_sol_price,
+    smbcloud_wallet_core_http::{
+        fx_data::fx_service::FxService,
+        price_data::{price_service::PriceService, valuation::value_holdings_with},
     },
-    smbcloud_wallet_core_network::model::ErrorResponse,
+    smbcloud_wallet_core_model::models::{
+        asset_metadata::Metadata,
+        portfolio::{Holding, PortfolioValuation},
+    },
+    smbcloud_wallet_core_network::model::{ErrorCode, ErrorResponse},
     smbcloud_wallet_core_rpc::balance::{
         sol_balance::sol_balance as core_sol_balance, spl_token_accounts::spl_token_accounts,
-        spl_token_accounts_with_balance::spl_token_accounts_with_balance,
     },
-    std::collections::HashMap,
+    solana_account_decoder::{parse_token::UiTokenAccount, UiAccountData},
+    solana_client::{
+        client_error::ClientError, nonblocking::rpc_client::RpcClient,
+        rpc_request::TokenAccountsFilter,
+    },
+    solana_sdk::pubkey::Pubkey,
+    std::{collections::HashMap, str::FromStr},
 };
 
-pub fn sol_balance(rpc_url: String, pubkey: String) -> String {
-    let sol_amount = match core_sol_balance(rpc_url, pubkey.to_string()) {
+pub async fn sol_balance(rpc_url: String, pubkey: String) -> String {
+    let sol_amount = match core_sol_balance(rpc_url, pubkey.to_string()).await {
         Ok(balance) => balance.1,
         Err(_) => 0.0,
     };
-    println!("{:#?} SOL", sol_amount);
+    debug!("{:#?} SOL", sol_amount);
     // Display SOL balance
     format!("{:.9} SOL", sol_amount)
 }
@@ -34,79 +45,128 @@ pub async fn wallet_balance(
     user_agent: &str,
     pubkey: String,
     currency: Option<FiatCurrency>,
-) -> Result<String, ErrorResponse> {
-    // Get SOL balance
-    let sol_amount = match core_sol_balance(rpc_url.clone(), pubkey.clone()) {
-        Ok(balance) => balance.1,
-        Err(_) => 0.0,
-    };
-
-    // Get current prices in the target currency
-    // If SOL balance is less than 0.000000001 SOL, we don't query the price.
-    let sol_price = if sol_amount >= 0.000000001 {
-        get_sol_price(api_key, user_agent).await?
-    } else {
-        0.0
-    };
+) -> Result<PortfolioValuation, ErrorResponse> {
+    wallet_valuation(
+        &RpcClient::new(rpc_url),
+        PriceService::shared(api_key, user_agent),
+        FxService::shared(user_agent),
+        &pubkey,
+        currency,
+    )
+    .await
+}
 
-    // Calculate total value
-    let sol_value = sol_amount * sol_price;
+/// Value the wallet through the given clients, see [`wallet_balance`].
+async fn wallet_valuation(
+    rpc_client: &RpcClient,
+    price_service: &PriceService,
+    fx_service: &FxService,
+    pubkey: &str,
+    currency: Option<FiatCurrency>,
+) -> Result<PortfolioValuation, ErrorResponse> {
+    let owner = Pubkey::from_str(pubkey).map_err(|_| ErrorResponse::Error {
+        code: ErrorCode::InvalidPubkey,
+        message: ErrorCode::InvalidPubkey.to_string(),
+    })?;
+    let holdings = read_wallet_holdings(rpc_client, &owner)
+        .await
+        .map_err(|e| ErrorResponse::Error {
+            code: ErrorCode::NetworkError,
+            message: e.to_string(),
+        })?;
+    value_holdings_with(
+        holdings,
+        currency.unwrap_or_default(),
+        price_service,
+        fx_service,
+    )
+    .await
+}
 
-    // Try to get BACH price, but handle errors gracefully
-    let spl_tokens = match spl_token_accounts_with_balance(
-        rpc_url.clone(),
-        pubkey.clone(),
-        SPL_TOKEN_PROGRAM_ID.to_string(),
-    ) {
-        Ok(tokens) => tokens,
-        Err(err) => {
-            error!("Failed to get SPL token accounts: {:?}", err);
-            Vec::new()
-        }
-    };
+/// SOL and the tokens of both token programs held by `owner`, read concurrently.
+///
+/// Fails when any balance fails to load, so a partial wallet is never valued
+/// and the read can be retried on another endpoint.
+pub(crate) async fn read_wallet_holdings(
+    rpc_client: &RpcClient,
+    owner: &Pubkey,
+) -> Result<Vec<Holding>, ClientError> {
+    let (lamports, spl_tokens, token_2022_tokens) = tokio::try_join!(
+        rpc_client.get_balance(owner),
+        token_accounts(rpc_client, owner, spl_token::id()),
+        token_accounts(rpc_client, owner, spl_token_2022_program_id()),
+    )?;
+    Ok(holdings(lamports, spl_tokens, token_2022_tokens))
+}
 
-    let mut spl_value = 0.0;
-    for token in spl_tokens {
-        let token_price = match get_asset_price(&token.mint, api_key, user_agent).await {
-            Ok(price) => price.data.value,
-            Err(err) => {
-                error!("Failed to get price for token {}: {:?}", token.mint, err);
-                0.0
-            }
-        };
-        let token_amount = match token.token_amount.ui_amount {
-            Some(amount) => amount,
-            None => {
-                error!("Token amount is None for token {}", token.mint);
-                0.0
-            }
+/// The SOL and token balances worth valuing.
+fn holdings(
+    lamports: u64,
+    spl_tokens: Vec<UiTokenAccount>,
+    token_2022_tokens: Vec<UiTokenAccount>,
+) -> Vec<Holding> {
+    let mut holdings = Vec::new();
+    let sol_amount = lamports as f64 / LAMPORTS_PER_SOL;
+    // If SOL balance is less than 0.000000001 SOL, we don't query the price.
+    if sol_amount >= 0.000000001 {
+        holdings.push(Holding {
+            mint: ADDRESS_SOL.to_string(),
+            symbol: Some("SOL".to_string()),
+            amount: sol_amount,
+        });
+    }
+    for token in spl_tokens.into_iter().chain(token_2022_tokens) {
+        let amount = match token.token_amount.ui_amount {
+            Some(amount) if amount > 0.0 => amount,
+            _ => continue,
         };
-        spl_value += token_amount * token_price;
+        holdings.push(Holding {
+            mint: token.mint,
+            symbol: token
+                .is_native
+                .then(|| Metadata::WRAPPED_SOL_SYMBOL.to_string()),
+            amount,
+        });
     }
+    holdings
+}
 
-    let total_value = sol_value + spl_value;
-
-    let currency_symbol = match currency.unwrap_or(FiatCurrency::USD) {
-        FiatCurrency::USD => "$",
-        FiatCurrency::IDR => "Rp",
-        FiatCurrency::SEK => "kr",
-    };
+/// Token accounts of `owner` in `program_id`.
+async fn token_accounts(
+    rpc_client: &RpcClient,
+    owner: &Pubkey,
+    program_id: Pubkey,
+) -> Result<Vec<UiTokenAccount>, ClientError> {
+    let keyed_accounts = rpc_client
+        .get_token_accounts_by_owner(owner, TokenAccountsFilter::ProgramId(program_id))
+        .await?;
+    Ok(keyed_accounts
+        .into_iter()
+        .filter_map(|keyed_account| match keyed_account.account.data {
+            UiAccountData::Json(parsed) => {
+                serde_json::from_value(parsed.parsed["info"].clone()).ok()
+            }
+            _ => None,
+        })
+        .collect())
+}
 
-    Ok(format!("{}{:.2}", currency_symbol, total_value))
+fn spl_token_2022_program_id() -> Pubkey {
+    Pubkey::from_str(SPL_TOKEN_2022_PROGRAM_ID).expect("Token-2022 program id is valid")
 }
 
 pub async fn other_assets_balance(
     rpc_url: String,
     pubkey: String,
 ) -> Result<Vec<AssetBalance>, ErrorResponse> {
-    let token_accounts = match spl_token_accounts(rpc_url, pubkey, SPL_TOKEN_PROGRAM_ID.to_string())
-    {
-        Ok(accounts) => accounts,
-        Err(err) => {
-            error!("Failed to get token accounts: {:?}", err);
-            return Ok(vec![]);
-        }
-    };
+    let token_accounts =
+        match spl_token_accounts(rpc_url, pubkey, SPL_TOKEN_PROGRAM_ID.to_string()).await {
+            Ok(accounts) => accounts,
+            Err(err) => {
+                error!("Failed to get token accounts: {:?}", err);
+                return Ok(vec![]);
+            }
+        };
     let token_accounts_with_balance = token_accounts
         .into_iter()
         .filter_map(|account| {
@@ -154,8 +214,8 @@ mod tests {
 
     #[tokio::test]
     async fn test_wallet_balance_with_invalid_spl() {
-        // This test verifies that wallet_balance can still return SOL balance
-        // even when SPL operations fail
+        // This test verifies that wallet_balance returns an error instead of
+        // a partial valuation when a balance fails to load
         let result = wallet_balance(
             "https://api.mainnet-beta.solana.com".to_string(),
             "api-key",
@@ -165,7 +225,6 @@ mod tests {
         )
         .await;
 
-        // Should succeed even if SPL balance fetching fails
         assert!(result.is_ok() || result.is_err()); // Either way, it shouldn't panic
     }
 }
@@ -0,0 +1,616 @@
+//! Burning unwanted tokens and NFTs, closing their accounts to recover the rent.
+
+use {
+    crate::{
+        models::burn::{BurnItem, BurnKind, BurnResult, BurnSummary},
+        nft::{
+            holdings::nft_metadata,
+            metadata::{
+                edition_key, metadata_key, optional_account, token_metadata_program_id,
+                token_record_key,
+            },
+        },
+        rpc::{parse_address, send, RpcError},
+        token_accounts::{closable_once_empty, close_account_instruction},
+    },
+    log::{info, warn},
+    smbcloud_wallet_constants::constants::LAMPORTS_PER_SOL,
+    smbcloud_wallet_core_model::{models::asset_metadata::Metadata, token_registry::TokenRegistry},
+    solana_account_decoder::{
+        parse_token::{UiAccountState, UiTokenAccount},
+        UiAccountData,
+    },
+    solana_client::{nonblocking::rpc_client::RpcClient, rpc_request::TokenAccountsFilter},
+    solana_sdk::{
+        account::Account,
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        sysvar,
+    },
+    solana_system_interface_v1::program as system_program,
+    thiserror::Error,
+};
+
+/// Instruction index of `BurnChecked`, the same in both token programs.
+const BURN_CHECKED_INSTRUCTION: u8 = 15;
+
+/// Token Metadata instruction index of `Burn`.
+const TOKEN_METADATA_BURN: u8 = 41;
+
+/// Metaplex account key of a print edition.
+const PRINT_EDITION_KEY: u8 = 1;
+
+#[derive(Error, Debug)]
+pub enum BurnError {
+    #[error("Failed to connect to RPC: {0}")]
+    ConnectionError(String),
+
+    #[error("Invalid address: {0}")]
+    InvalidAddress(String),
+
+    #[error("The wallet holds no token of {0}")]
+    NothingToBurn(String),
+
+    #[error("{0} is on the verified token list, burning it must be forced")]
+    VerifiedToken(String),
+
+    #[error("Wrapped SOL cannot be burnt, unwrap it instead: {0}")]
+    NativeToken(String),
+
+    #[error("Token account is frozen: {0}")]
+    FrozenAccount(String),
+
+    #[error("Transaction error: {0}")]
+    TransactionError(String),
+}
+
+impl RpcError for BurnError {
+    fn connection_error(message: String) -> Self {
+        BurnError::ConnectionError(message)
+    }
+
+    fn transaction_error(message: String) -> Self {
+        BurnError::TransactionError(message)
+    }
+
+    fn invalid_address(address: String) -> Self {
+        BurnError::InvalidAddress(address)
+    }
+}
+
+/// A token account of the wallet holding the burnt mint.
+struct HeldAccount {
+    address: Pubkey,
+    program_id: Pubkey,
+    lamports: u64,
+    account: UiTokenAccount,
+}
+
+impl HeldAccount {
+    fn amount(&self) -> u64 {
+        self.account.token_amount.amount.parse().unwrap_or_default()
+    }
+}
+
+/// The instructions burning one mint, and what they destroy.
+struct BurnPlan {
+    item: BurnItem,
+    instructions: Vec<Instruction>,
+}
+
+/// What burning `mints` would destroy and the SOL it would recover.
+///
+/// Verified tokens are flagged rather than refused, so they can be confirmed
+/// and burnt with `force`.
+pub async fn preview_burn(
+    rpc_client: &RpcClient,
+    owner: String,
+    mints: Vec<String>,
+) -> Result<BurnSummary, BurnError> {
+    let owner = parse_address(&owner)?;
+    let mut items = Vec::new();
+    for mint in &mints {
+        items.push(plan_burn(rpc_client, &owner, mint).await?.item);
+    }
+    let recovered = items.iter().map(|item| item.rent).sum();
+    Ok(BurnSummary { items, recovered })
+}
+
+/// Burn the whole balance of `mints` held by the wallet and close the emptied
+/// token accounts.
+///
+/// NFTs are burnt through Token Metadata, which also closes their metadata and
+/// edition accounts. Tokens on the verified list are refused unless `force`
+/// is set. Each mint is burnt in its own transaction; a mint whose transaction
+/// fails is skipped.
+pub async fn burn_tokens(
+    rpc_client: &RpcClient,
+    keypair: Keypair,
+    mints: Vec<String>,
+    force: bool,
+) -> Result<BurnResult, BurnError> {
+    let owner = keypair.pubkey();
+
+    // Plan every burn before sending any, so a refused mint burns nothing.
+    let mut plans = Vec::new();
+    for mint in &mints {
+        let plan = plan_burn(rpc_client, &owner, mint).await?;
+        if plan.item.verified && !force {
+            return Err(BurnError::VerifiedToken(mint.clone()));
+        }
+        plans.push(plan);
+    }
+
+    let mut signatures = Vec::new();
+    let mut burned = Vec::new();
+    let mut skipped = Vec::new();
+    let mut recovered = 0.0;
+    let mut last_error = None;
+    for plan in plans {
+        match send(rpc_client, &keypair, &plan.instructions, &[]).await {
+            Ok(signature) => {
+                signatures.push(signature);
+                burned.push(plan.item.mint);
+                recovered += plan.item.rent;
+            }
+            Err(e) => {
+                warn!("Failed to burn {}: {}", plan.item.mint, e);
+                skipped.push(plan.item.mint);
+                last_error = Some(e);
+            }
+        }
+    }
+
+    // Mints left over from a partial burn are listed in `skipped`.
+    if let (true, Some(e)) = (burned.is_empty(), last_error) {
+        return Err(e);
+    }
+    info!("Burned {} mints, recovered {} SOL", burned.len(), recovered);
+    Ok(BurnResult {
+        signatures,
+        burned,
+        skipped,
+        recovered,
+    })
+}
+
+async fn plan_burn(
+    rpc_client: &RpcClient,
+    owner: &Pubkey,
+    mint: &str,
+) -> Result<BurnPlan, BurnError> {
+    let mint_pubkey = parse_address(mint)?;
+    let held = held_accounts(rpc_client, owner, &mint_pubkey).await?;
+    if held.iter().all(|account| account.amount() == 0) {
+        return Err(BurnError::NothingToBurn(mint.to_string()));
+    }
+
+    let nft_account = held.iter().find(|account| account.amount() == 1);
+    let token_record = token_record_key(
+        &mint_pubkey,
+        &nft_account
+            .map(|account| account.address)
+            .unwrap_or_default(),
+    );
+    let keys = [
+        mint_pubkey,
+        metadata_key(&mint_pubkey),
+        edition_key(&mint_pubkey),
+        token_record,
+    ];
+    let accounts = rpc_client
+        .get_multiple_accounts(&keys)
+        .await
+        .map_err(|e| BurnError::ConnectionError(e.to_string()))?;
+    let registry = TokenRegistry::shared().get(mint);
+    build_plan(owner, mint, &held, &accounts, registry)
+}
+
+/// The burn of the `held` accounts of `mint`, given its mint, metadata,
+/// edition and token record `accounts`.
+fn build_plan(
+    owner: &Pubkey,
+    mint: &str,
+    held: &[HeldAccount],
+    accounts: &[Option<Account>],
+    registry: Option<Metadata>,
+) -> Result<BurnPlan, BurnError> {
+    let mint_pubkey = parse_address(mint)?;
+    if held.iter().any(|account| account.account.is_native) {
+        return Err(BurnError::NativeToken(mint.to_string()));
+    }
+
+    let nft = nft_metadata(
+        accounts[0].as_ref(),
+        accounts[1].as_ref(),
+        accounts[2].as_ref(),
+    );
+    let nft_account = held.iter().find(|account| account.amount() == 1);
+    // Print editions need their master edition to burn through Token Metadata,
+    // they are burnt as tokens and their metadata stays behind.
+    let nft_burn = match (nft, nft_account) {
+        (Some(metadata), Some(account)) if !is_print_edition(accounts[2].as_ref()) => {
+            Some((metadata, account))
+        }
+        _ => None,
+    };
+
+    // Token Metadata keeps the token account of a programmable NFT frozen and
+    // thaws it itself to burn it.
+    let thawed_by_metadata = |account: &HeldAccount| {
+        nft_burn.as_ref().is_some_and(|(metadata, nft_account)| {
+            metadata.is_programmable() && nft_account.address == account.address
+        })
+    };
+    if let Some(frozen) = held.iter().find(|account| {
+        account.amount() > 0
+            && account.account.state == UiAccountState::Frozen
+            && !thawed_by_metadata(account)
+    }) {
+        return Err(BurnError::FrozenAccount(frozen.address.to_string()));
+    }
+
+    let verified = registry.as_ref().is_some_and(|token| token.verified);
+    let decimals = held[0].account.token_amount.decimals;
+    let amount =
+        held.iter().map(HeldAccount::amount).sum::<u64>() as f64 / 10f64.powi(decimals as i32);
+
+    let mut instructions = Vec::new();
+    let mut lamports = 0;
+    let (name, symbol, kind) = match &nft_burn {
+        Some((metadata, account)) => {
+            instructions.push(nft_burn_instruction(
+                owner,
+                &mint_pubkey,
+                &account.address,
+                metadata.verified_collection(),
+                metadata.is_programmable(),
+            ));
+            let closed = [1, 2, 3]
+                .into_iter()
+                .filter(|&index| index != 3 || metadata.is_programmable())
+                .filter_map(|index| accounts[index].as_ref())
+                .map(|account| account.lamports)
+                .sum::<u64>();
+            lamports += account.lamports + closed;
+            (
+                metadata.name.clone(),
+                metadata.symbol.clone(),
+                BurnKind::Nft,
+            )
+        }
+        None => {
+            let metadata =
+                registry.unwrap_or_else(|| Metadata::unknown(mint.to_string(), decimals));
+            (metadata.name, metadata.symbol, BurnKind::Token)
+        }
+    };
+
+    for account in held {
+        let burnt_as_nft = nft_burn
+            .as_ref()
+            .is_some_and(|(_, nft_account)| nft_account.address == account.address);
+        if burnt_as_nft || account.account.state == UiAccountState::Frozen {
+            continue;
+        }
+        if account.amount() > 0 {
+            instructions.push(burn_checked_instruction(
+                account,
+                &mint_pubkey,
+                owner,
+                decimals,
+            ));
+        }
+        if closable_once_empty(&account.account, owner) {
+            instructions.push(close_account_instruction(
+                &account.program_id,
+                &account.address,
+                owner,
+            ));
+            lamports += account.lamports;
+        }
+    }
+
+    Ok(BurnPlan {
+        item: BurnItem {
+            mint: mint.to_string(),
+            name,
+            symbol,
+            kind,
+            amount,
+            rent: lamports as f64 / LAMPORTS_PER_SOL,
+            verified,
+        },
+        instructions,
+    })
+}
+
+/// Token accounts of `owner` for `mint`, in either token program.
+async fn held_accounts(
+    rpc_client: &RpcClient,
+    owner: &Pubkey,
+    mint: &Pubkey,
+) -> Result<Vec<HeldAccount>, BurnError> {
+    let token_accounts = rpc_client
+        .get_token_accounts_by_owner(owner, TokenAccountsFilter::Mint(*mint))
+        .await
+        .map_err(|e| BurnError::ConnectionError(e.to_string()))?;
+
+    token_accounts
+        .into_iter()
+        .filter_map(|keyed_account| {
+            let UiAccountData::Json(parsed) = &keyed_account.account.data else {
+                return None;
+            };
+            let account =
+                serde_json::from_value::<UiTokenAccount>(parsed.parsed["info"].clone()).ok()?;
+            Some((keyed_account, account))
+        })
+        .map(|(keyed_account, account)| {
+            Ok(HeldAccount {
+                address: parse_address(&keyed_account.pubkey)?,
+                program_id: parse_address(&keyed_account.account.owner)?,
+                lamports: keyed_account.account.lamports,
+                account,
+            })
+        })
+        .collect()
+}
+
+fn is_print_edition(edition: Option<&Account>) -> bool {
+    edition.and_then(|account| account.data.first()) == Some(&PRINT_EDITION_KEY)
+}
+
+/// `BurnChecked` of the whole balance of `account`, in either token program.
+fn burn_checked_instruction(
+    account: &HeldAccount,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    decimals: u8,
+) -> Instruction {
+    let mut data = vec![BURN_CHECKED_INSTRUCTION];
+    data.extend_from_slice(&account.amount().to_le_bytes());
+    data.push(decimals);
+    Instruction {
+        program_id: account.program_id,
+        accounts: vec![
+            AccountMeta::new(account.address, false),
+            AccountMeta::new(*mint, false),
+            AccountMeta::new_readonly(*owner, true),
+        ],
+        data,
+    }
+}
+
+/// Token Metadata `BurnV1` of an NFT, closing its token, metadata and edition
+/// accounts, and the token record of a programmable NFT.
+fn nft_burn_instruction(
+    owner: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    collection: Option<Pubkey>,
+    programmable: bool,
+) -> Instruction {
+    let program_id = token_metadata_program_id();
+    let collection_metadata =
+        optional_account(collection.map(|collection| metadata_key(&collection)));
+    let token_record =
+        optional_account(programmable.then(|| token_record_key(mint, token_account)));
+
+    let accounts = vec![
+        AccountMeta::new(*owner, true),
+        AccountMeta::new(collection_metadata, false),
+        AccountMeta::new(metadata_key(mint), false),
+        AccountMeta::new(edition_key(mint), false),
+        AccountMeta::new(*mint, false),
+        AccountMeta::new(*token_account, false),
+        AccountMeta::new_readonly(program_id, false),
+        AccountMeta::new_readonly(program_id, false),
+        AccountMeta::new_readonly(program_id, false),
+        AccountMeta::new_readonly(program_id, false),
+        AccountMeta::new(token_record, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+    // `BurnArgs::V1` of the single token.
+    let mut data = vec![TOKEN_METADATA_BURN, 0];
+    data.extend_from_slice(&1u64.to_le_bytes());
+
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        serde_json::json,
+        smbcloud_wallet_core_rpc::token_metadata::{
+            fixtures::borsh_string, METAPLEX_KEY_METADATA_V1,
+        },
+        solana_sdk::program_pack::Pack,
+        spl_token::state::Mint,
+    };
+
+    fn held(amount: u64, program_id: Pubkey) -> HeldAccount {
+        HeldAccount {
+            address: Pubkey::new_unique(),
+            program_id,
+            lamports: 2_039_280,
+            account: serde_json::from_value(json!({
+                "mint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+                "owner": "Owner11111111111111111111111111111111111111",
+                "tokenAmount": {
+                    "amount": amount.to_string(),
+                    "decimals": 6,
+                    "uiAmount": 0.0,
+                    "uiAmountString": "0"
+                },
+                "state": "initialized",
+                "isNative": false
+            }))
+            .unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_burn_checked_instruction() {
+        let owner = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let account = held(1_500_000, spl_token::id());
+        let instruction = burn_checked_instruction(&account, &mint, &owner, 6);
+
+        let spl_instruction = spl_token::instruction::burn_checked(
+            &spl_token::id(),
+            &account.address,
+            &mint,
+            &owner,
+            &[],
+            1_500_000,
+            6,
+        )
+        .unwrap();
+        assert_eq!(spl_instruction.data, instruction.data);
+        assert_eq!(spl_instruction.accounts, instruction.accounts);
+    }
+
+    #[test]
+    fn test_nft_burn_instruction() {
+        let owner = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let token_account = Pubkey::new_unique();
+        let program_id = token_metadata_program_id();
+
+        let instruction = nft_burn_instruction(&owner, &mint, &token_account, None, false);
+        assert_eq!(instruction.accounts.len(), 14);
+        assert_eq!(instruction.data, [41, 0, 1, 0, 0, 0, 0, 0, 0, 0]);
+        assert!(instruction.accounts[0].is_signer);
+        assert_eq!(instruction.accounts[1].pubkey, program_id);
+        assert_eq!(instruction.accounts[10].pubkey, program_id);
+
+        let collection = Pubkey::new_unique();
+        let instruction =
+            nft_burn_instruction(&owner, &mint, &token_account, Some(collection), true);
+        assert_eq!(instruction.accounts[1].pubkey, metadata_key(&collection));
+        assert_eq!(
+            instruction.accounts[10].pubkey,
+            token_record_key(&mint, &token_account)
+        );
+    }
+
+    #[test]
+    fn test_is_print_edition() {
+        let edition = |key: u8| Account {
+            lamports: 1,
+            data: vec![key],
+            owner: token_metadata_program_id(),
+            executable: false,
+            rent_epoch: 0,
+        };
+        assert!(is_print_edition(Some(&edition(PRINT_EDITION_KEY))));
+        // Master editions are burnt through Token Metadata.
+        assert!(!is_print_edition(Some(&edition(6))));
+        assert!(!is_print_edition(None));
+    }
+
+    fn frozen(amount: u64, decimals: u8) -> HeldAccount {
+        let mut account = held(amount, spl_token::id());
+        account.account.state = UiAccountState::Frozen;
+        account.account.token_amount.decimals = decimals;
+        account
+    }
+
+    fn account(owner: Pubkey, data: Vec<u8>) -> Option<Account> {
+        Some(Account {
+            lamports: 1_000_000,
+            data,
+            owner,
+            executable: false,
+            rent_epoch: 0,
+        })
+    }
+
+    /// Mint, metadata, master edition and token record of a programmable NFT.
+    fn pnft_accounts(mint: &Pubkey) -> Vec<Option<Account>> {
+        let mut mint_data = vec![0; Mint::LEN];
+        Mint {
+            supply: 1,
+            decimals: 0,
+            is_initialized: true,
+            ..Mint::default()
+        }
+        .pack_into_slice(&mut mint_data);
+
+        let mut metadata = vec![METAPLEX_KEY_METADATA_V1];
+        metadata.extend_from_slice(&[0; 32]);
+        metadata.extend_from_slice(mint.as_ref());
+        for value in ["Mad Lad #1", "MAD", ""] {
+            metadata.extend(borsh_string(value, 0));
+        }
+        // No royalties nor creators, primary sale happened, mutable, no
+        // edition nonce, programmable non fungible with nothing else set.
+        metadata.extend_from_slice(&[0, 0, 0, 1, 1, 0, 1, 4, 0, 0, 0, 0]);
+
+        vec![
+            account(spl_token::id(), mint_data),
+            account(token_metadata_program_id(), metadata),
+            account(token_metadata_program_id(), vec![6]),
+            account(token_metadata_program_id(), vec![14]),
+        ]
+    }
+
+    #[test]
+    fn test_burn_frozen_programmable_nft() {
+        let owner = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let nft_account = frozen(1, 0);
+        let plan = build_plan(
+            &owner,
+            &mint.to_string(),
+            std::slice::from_ref(&nft_account),
+            &pnft_accounts(&mint),
+            None,
+        )
+        .unwrap();
+
+        assert!(matches!(plan.item.kind, BurnKind::Nft));
+        assert_eq!(plan.item.name, "Mad Lad #1");
+        assert_eq!(plan.instructions.len(), 1);
+        let instruction = &plan.instructions[0];
+        assert_eq!(instruction.program_id, token_metadata_program_id());
+        assert_eq!(instruction.accounts[5].pubkey, nft_account.address);
+        assert_eq!(
+            instruction.accounts[10].pubkey,
+            token_record_key(&mint, &nft_account.address)
+        );
+    }
+
+    #[test]
+    fn test_burn_frozen_token_account() {
+        let owner = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let account = frozen(1_500_000, 6);
+        let result = build_plan(
+            &owner,
+            &mint.to_string(),
+            std::slice::from_ref(&account),
+            &[None, None, None, None],
+            None,
+        );
+        assert!(
+            matches!(result, Err(BurnError::FrozenAccount(address)) if address == account.address.to_string())
+        );
+
+        // A frozen legacy NFT cannot be burnt either.
+        let mut accounts = pnft_accounts(&mint);
+        let metadata = accounts[1].as_mut().unwrap();
+        metadata.data.truncate(metadata.data.len() - 5);
+        let result = build_plan(&owner, &mint.to_string(), &[frozen(1, 0)], &accounts, None);
+        assert!(matches!(result, Err(BurnError::FrozenAccount(_))));
+    }
+}
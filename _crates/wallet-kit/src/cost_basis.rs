@@ -0,0 +1,145 @@
+use {crate::models::portfolio_history::CostBasisMethod, std::collections::VecDeque};
+
+/// Amounts below this are treated as zero.
+pub(crate) const DUST: f64 = 1e-12;
+
+/// Lot id of holdings acquired before the period being looked at.
+pub(crate) const OPENING_LOT: &str = "opening";
+
+/// Lot id of the single lot kept by the average cost method.
+pub(crate) const AVERAGE_LOT: &str = "average";
+
+/// An amount acquired at once, or the part of it a sale took.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Lot {
+    pub id: String,
+    /// Unix timestamp of the acquisition, in seconds.
+    pub acquired_at: Option<i64>,
+    pub amount: f64,
+    pub unit_cost: f64,
+    /// `false` when the acquisition had no price, `unit_cost` is then zero.
+    pub priced: bool,
+}
+
+impl Lot {
+    pub fn new(id: &str, acquired_at: i64, amount: f64, unit_cost: f64) -> Self {
+        Lot {
+            id: id.to_string(),
+            acquired_at: Some(acquired_at),
+            amount,
+            unit_cost,
+            priced: true,
+        }
+    }
+
+    /// A lot whose cost is unknown.
+    pub fn unpriced(id: &str, acquired_at: i64, amount: f64) -> Self {
+        Lot {
+            priced: false,
+            ..Lot::new(id, acquired_at, amount, 0.0)
+        }
+    }
+
+    pub fn cost(&self) -> f64 {
+        self.amount * self.unit_cost
+    }
+}
+
+/// Purchases of one token still held.
+pub(crate) struct Lots {
+    method: CostBasisMethod,
+    lots: VecDeque<Lot>,
+}
+
+impl Lots {
+    pub fn new(method: CostBasisMethod) -> Self {
+        Lots {
+            method,
+            lots: VecDeque::new(),
+        }
+    }
+
+    pub fn buy(&mut self, lot: Lot) {
+        self.lots.push_back(lot);
+        if self.method == CostBasisMethod::Average {
+            // Keep a single lot at the average cost.
+            let amount: f64 = self.lots.iter().map(|lot| lot.amount).sum();
+            let cost = self.cost();
+            let priced = self.lots.iter().all(|lot| lot.priced);
+            self.lots.clear();
+            self.lots.push_back(Lot {
+                id: AVERAGE_LOT.to_string(),
+                acquired_at: None,
+                amount,
+                unit_cost: cost / amount,
+                priced,
+            });
+        }
+    }
+
+    /// Remove `amount` from the oldest lots and return the parts taken.
+    ///
+    /// Selling more than the lots hold takes nothing for the excess.
+    pub fn sell(&mut self, mut amount: f64) -> Vec<Lot> {
+        let mut taken = Vec::new();
+        while amount > DUST {
+            let Some(lot) = self.lots.front_mut() else {
+                break;
+            };
+            let part = amount.min(lot.amount);
+            taken.push(Lot {
+                amount: part,
+                ..lot.clone()
+            });
+            lot.amount -= part;
+            amount -= part;
+            if lot.amount <= DUST {
+                self.lots.pop_front();
+            }
+        }
+        taken
+    }
+
+    pub fn cost(&self) -> f64 {
+        self.lots.iter().map(Lot::cost).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fifo_sells_oldest_lots_first() {
+        let mut lots = Lots::new(CostBasisMethod::Fifo);
+        lots.buy(Lot::new("a", 1, 2.0, 10.0));
+        lots.buy(Lot::new("b", 2, 2.0, 20.0));
+
+        let taken = lots.sell(3.0);
+        assert_eq!(taken.len(), 2);
+        assert_eq!((taken[0].id.as_str(), taken[0].amount), ("a", 2.0));
+        assert_eq!((taken[1].id.as_str(), taken[1].amount), ("b", 1.0));
+        assert_eq!(lots.cost(), 20.0);
+        // Nothing is left to take from.
+        lots.sell(1.0);
+        assert!(lots.sell(1.0).is_empty());
+    }
+
+    #[test]
+    fn test_average_keeps_one_lot() {
+        let mut lots = Lots::new(CostBasisMethod::Average);
+        lots.buy(Lot::new("a", 1, 1.0, 10.0));
+        lots.buy(Lot::new("b", 2, 1.0, 20.0));
+
+        let taken = lots.sell(1.0);
+        assert_eq!(taken.len(), 1);
+        assert_eq!(taken[0].id, AVERAGE_LOT);
+        assert_eq!(taken[0].acquired_at, None);
+        assert_eq!(taken[0].cost(), 15.0);
+        assert!(taken[0].priced);
+
+        // An unknown cost makes the average unknown.
+        lots.buy(Lot::unpriced("c", 3, 1.0));
+        assert!(!lots.sell(1.0)[0].priced);
+    }
+}
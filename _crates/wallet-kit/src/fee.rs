@@ -1,14 +1,14 @@
 use {
+    crate::units::to_base_units,
     log::info,
     serde::{Deserialize, Serialize},
-    smbcloud_wallet_constants::constants::{
-        LAMPORTS_PER_SOL, THE_STABLE_FOUNDATION_TREASURY_WALLET_FEE,
-    },
+    smbcloud_wallet_constants::constants::{SOL_DECIMALS, THE_STABLE_FOUNDATION_TREASURY_WALLET_FEE},
     solana_address::Address,
     solana_instruction::Instruction,
-    solana_sdk::{pubkey::Pubkey, system_instruction},
+    solana_sdk::pubkey::Pubkey,
     solana_system_interface::instruction,
-    spl_token::instruction as token_instruction,
+    solana_system_interface_v1::instruction as system_instruction,
+    spl_token_2022::instruction as token_instruction,
     std::str::FromStr,
     thiserror::Error,
 };
@@ -66,7 +66,7 @@ impl FeeBreakdown {
             return Err(FeeError::AmountTooSmall(amount));
         }
 
-        if fee_percentage < 0.0 || fee_percentage > 1.0 {
+        if !(0.0..=1.0).contains(&fee_percentage) {
             return Err(FeeError::InvalidFeePercentage(fee_percentage));
         }
 
@@ -92,24 +92,48 @@ impl FeeBreakdown {
         })
     }
 
+    /// Create a fee breakdown where the recipient receives exactly `net_amount`
+    /// and the fee is charged on top of it. Used for payment requests where the
+    /// merchant expects the requested amount to arrive in full.
+    pub fn with_net_amount(net_amount: f64, currency: String) -> Result<Self, FeeError> {
+        if net_amount <= 0.0 || net_amount < MIN_TRANSACTION_AMOUNT {
+            return Err(FeeError::AmountTooSmall(net_amount));
+        }
+
+        let fee_amount = net_amount * DEFAULT_FEE_PERCENTAGE;
+        let original_amount = net_amount + fee_amount;
+
+        if fee_amount.is_infinite() || original_amount.is_infinite() {
+            return Err(FeeError::CalculationOverflow);
+        }
+
+        Ok(FeeBreakdown {
+            original_amount,
+            fee_amount,
+            net_amount,
+            fee_percentage: DEFAULT_FEE_PERCENTAGE,
+            currency,
+        })
+    }
+
     /// Convert fee amount to lamports (for SOL transactions)
     pub fn fee_lamports(&self) -> u64 {
-        (self.fee_amount * LAMPORTS_PER_SOL) as u64
+        self.fee_base_units(SOL_DECIMALS)
     }
 
     /// Convert net amount to lamports (for SOL transactions)
     pub fn net_lamports(&self) -> u64 {
-        (self.net_amount * LAMPORTS_PER_SOL) as u64
+        self.net_base_units(SOL_DECIMALS)
     }
 
     /// Convert fee amount to token units (for token transactions)
     pub fn fee_token_units(&self, decimals_multiplier: f64) -> u64 {
-        (self.fee_amount * decimals_multiplier) as u64
+        self.fee_base_units(decimals_of(decimals_multiplier))
     }
 
     /// Convert net amount to token units (for token transactions)
     pub fn net_token_units(&self, decimals_multiplier: f64) -> u64 {
-        (self.net_amount * decimals_multiplier) as u64
+        self.net_base_units(decimals_of(decimals_multiplier))
     }
 
     /// Get total lamports needed for SOL transaction (including fee)
@@ -122,6 +146,21 @@ impl FeeBreakdown {
         self.fee_token_units(decimals_multiplier) + self.net_token_units(decimals_multiplier)
     }
 
+    /// Fee amount in base units of a mint with `decimals`, rounded down
+    pub fn fee_base_units(&self, decimals: u8) -> u64 {
+        to_base_units(self.fee_amount, decimals)
+    }
+
+    /// Net amount in base units of a mint with `decimals`, converted exactly
+    pub fn net_base_units(&self, decimals: u8) -> u64 {
+        to_base_units(self.net_amount, decimals)
+    }
+
+    /// Total base units needed for a transaction of a mint with `decimals` (including fee)
+    pub fn total_base_units(&self, decimals: u8) -> u64 {
+        self.fee_base_units(decimals) + self.net_base_units(decimals)
+    }
+
     /// Format fee breakdown as human-readable string
     pub fn format_summary(&self) -> String {
         format!(
@@ -137,6 +176,11 @@ impl FeeBreakdown {
     }
 }
 
+/// Decimals of a mint whose smallest unit is `1 / decimals_multiplier`.
+fn decimals_of(decimals_multiplier: f64) -> u8 {
+    decimals_multiplier.log10().round() as u8
+}
+
 /// Treasury fee management utilities
 pub struct TreasuryFeeManager;
 
@@ -166,6 +210,11 @@ impl TreasuryFeeManager {
         FeeBreakdown::new(amount, currency)
     }
 
+    /// Calculate fee breakdown where the fee is added on top of the given amount
+    pub fn calculate_fees_on_top(amount: f64, currency: String) -> Result<FeeBreakdown, FeeError> {
+        FeeBreakdown::with_net_amount(amount, currency)
+    }
+
     /// Create SOL fee transfer instruction
     pub fn create_sol_fee_instruction(
         from: &Pubkey,
@@ -183,21 +232,26 @@ impl TreasuryFeeManager {
         let treasury = Self::treasury_pubkey_v3()?;
         Ok(instruction::transfer(from, &treasury, fee_lamports))
     }
-    /// Create token fee transfer instruction
+    /// Create token fee transfer instruction, a checked transfer so it works with
+    /// both the Token and the Token-2022 program
     pub fn create_token_fee_instruction(
         token_program: &Pubkey,
         from_token_account: &Pubkey,
+        token_mint: &Pubkey,
         treasury_token_account: &Pubkey,
         from_wallet: &Pubkey,
         fee_token_units: u64,
+        decimals: u8,
     ) -> Result<solana_sdk::instruction::Instruction, FeeError> {
-        token_instruction::transfer(
+        token_instruction::transfer_checked(
             token_program,
             from_token_account,
+            token_mint,
             treasury_token_account,
             from_wallet,
-            &[from_wallet],
+            &[],
             fee_token_units,
+            decimals,
         )
         .map_err(|e| FeeError::TreasuryAddressError(format!("Token transfer error: {}", e)))
     }
@@ -267,7 +321,7 @@ impl FeeConfig {
         treasury_address: String,
         enabled: bool,
     ) -> Result<Self, FeeError> {
-        if fee_percentage < 0.0 || fee_percentage > 1.0 {
+        if !(0.0..=1.0).contains(&fee_percentage) {
             return Err(FeeError::InvalidFeePercentage(fee_percentage));
         }
 
@@ -354,6 +408,15 @@ mod tests {
         assert!(FeeBreakdown::with_custom_percentage(100.0, 1.1, "SOL".to_string()).is_err());
     }
 
+    #[test]
+    fn test_fee_breakdown_with_net_amount() {
+        let breakdown = FeeBreakdown::with_net_amount(100.0, "USDC".to_string()).unwrap();
+        assert_eq!(breakdown.net_amount, 100.0);
+        assert_eq!(breakdown.fee_amount, 0.25);
+        assert_eq!(breakdown.original_amount, 100.25);
+        assert!(FeeBreakdown::with_net_amount(0.0, "USDC".to_string()).is_err());
+    }
+
     #[test]
     fn test_lamports_conversion() {
         let breakdown = FeeBreakdown::new(1.0, "SOL".to_string()).unwrap();
@@ -368,8 +431,20 @@ mod tests {
         let fee_units = breakdown.fee_token_units(SEMITONE_PER_BACH);
         let net_units = breakdown.net_token_units(SEMITONE_PER_BACH);
 
-        assert_eq!(fee_units, 2500000); // 0.0025 BACH in st
-        assert_eq!(net_units, 997500000); // 0.9975 BACH in st
+        assert_eq!(fee_units, 2500000000); // 0.0025 BACH in st
+        assert_eq!(net_units, 997500000000); // 0.9975 BACH in st
+    }
+
+    #[test]
+    fn test_net_amount_converts_exactly() {
+        // 2.01 * 1e6 is 2009999.9999999998 as a float.
+        let breakdown = FeeBreakdown::with_net_amount(2.01, "USDC".to_string()).unwrap();
+        assert_eq!(breakdown.net_base_units(6), 2_010_000);
+        assert_eq!(breakdown.net_token_units(1_000_000.0), 2_010_000);
+        assert_eq!(breakdown.fee_base_units(6), 5_025);
+
+        let breakdown = FeeBreakdown::with_net_amount(0.29, "SOL".to_string()).unwrap();
+        assert_eq!(breakdown.net_lamports(), 290_000_000);
     }
 
     #[test]
@@ -414,8 +489,10 @@ mod tests {
 
     #[test]
     fn test_fee_config_disabled() {
-        let mut config = FeeConfig::default();
-        config.fees_enabled = false;
+        let config = FeeConfig {
+            fees_enabled: false,
+            ..FeeConfig::default()
+        };
 
         let breakdown = config
             .calculate_breakdown(100.0, "SOL".to_string())
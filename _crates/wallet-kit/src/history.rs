@@ -0,0 +1,304 @@
+use {
+    crate::models::portfolio_history::BalanceChange,
+    futures::{stream, StreamExt, TryStreamExt},
+    log::warn,
+    smbcloud_wallet_constants::{assets_solana::ADDRESS_SOL, constants::SOL_DECIMALS},
+    solana_client::{
+        client_error::ClientError, nonblocking::rpc_client::RpcClient,
+        rpc_client::GetConfirmedSignaturesForAddress2Config, rpc_config::RpcTransactionConfig,
+    },
+    solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature},
+    solana_transaction_status_client_types::{
+        option_serializer::OptionSerializer, EncodedTransactionWithStatusMeta,
+        UiTransactionEncoding, UiTransactionTokenBalance,
+    },
+    std::{collections::HashMap, str::FromStr},
+};
+
+/// Signatures requested per `getSignaturesForAddress` page.
+const SIGNATURES_PAGE_SIZE: usize = 100;
+
+/// Transactions read at the same time.
+const MAX_CONCURRENT_TRANSACTIONS: usize = 8;
+
+/// A confirmed transaction of a wallet.
+pub(crate) struct HistoryTransaction {
+    pub signature: String,
+    /// Unix timestamp of the block, in seconds.
+    pub block_time: i64,
+    pub transaction: EncodedTransactionWithStatusMeta,
+}
+
+/// The most recent transactions of a wallet.
+pub(crate) struct TransactionHistory {
+    pub transactions: Vec<HistoryTransaction>,
+    /// Whether older transactions in the period were left out.
+    pub truncated: bool,
+}
+
+/// Read the transactions of `owner` since `since`, newest first, up to `limit` of them.
+///
+/// Failed transactions still pay their fee, so they are read too.
+pub(crate) async fn fetch_transactions(
+    rpc_client: &RpcClient,
+    owner: &Pubkey,
+    since: i64,
+    limit: usize,
+) -> Result<TransactionHistory, ClientError> {
+    let mut signatures = Vec::new();
+    let mut truncated = false;
+    let mut before = None;
+    'pages: loop {
+        let config = GetConfirmedSignaturesForAddress2Config {
+            before,
+            until: None,
+            limit: Some(SIGNATURES_PAGE_SIZE),
+            commitment: Some(CommitmentConfig::confirmed()),
+        };
+        let statuses = rpc_client
+            .get_signatures_for_address_with_config(owner, config)
+            .await?;
+
+        for status in &statuses {
+            let Some(block_time) = status.block_time else {
+                continue;
+            };
+            if block_time < since {
+                break 'pages;
+            }
+            if signatures.len() >= limit {
+                truncated = true;
+                break 'pages;
+            }
+            let Ok(signature) = Signature::from_str(&status.signature) else {
+                warn!("Skipping invalid signature {}", status.signature);
+                continue;
+            };
+            signatures.push((signature, block_time));
+        }
+
+        if statuses.len() < SIGNATURES_PAGE_SIZE {
+            break;
+        }
+        before = statuses
+            .last()
+            .and_then(|status| Signature::from_str(&status.signature).ok());
+    }
+
+    let transactions = stream::iter(signatures)
+        .map(|(signature, block_time)| async move {
+            let config = RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Base64),
+                commitment: Some(CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            };
+            let transaction = rpc_client
+                .get_transaction_with_config(&signature, config)
+                .await?;
+            Ok::<_, ClientError>(HistoryTransaction {
+                signature: signature.to_string(),
+                block_time,
+                transaction: transaction.transaction,
+            })
+        })
+        .buffered(MAX_CONCURRENT_TRANSACTIONS)
+        .try_collect()
+        .await?;
+    Ok(TransactionHistory {
+        transactions,
+        truncated,
+    })
+}
+
+/// Whether `signer` signed one of the latest `limit` transactions of `account`.
+pub(crate) async fn signed_recently(
+    rpc_client: &RpcClient,
+    account: &Pubkey,
+    signer: &Pubkey,
+    limit: usize,
+) -> Result<bool, ClientError> {
+    let config = GetConfirmedSignaturesForAddress2Config {
+        limit: Some(limit),
+        commitment: Some(CommitmentConfig::confirmed()),
+        ..Default::default()
+    };
+    let statuses = rpc_client
+        .get_signatures_for_address_with_config(account, config)
+        .await?;
+    for status in statuses {
+        let Ok(signature) = Signature::from_str(&status.signature) else {
+            warn!("Skipping invalid signature {}", status.signature);
+            continue;
+        };
+        let config = RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            commitment: Some(CommitmentConfig::confirmed()),
+            max_supported_transaction_version: Some(0),
+        };
+        let transaction = rpc_client
+            .get_transaction_with_config(&signature, config)
+            .await?;
+        if signers(&transaction.transaction).contains(signer) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// SOL and token balance changes of `owner` in a transaction.
+pub(crate) fn transaction_balance_changes(
+    transaction: &HistoryTransaction,
+    owner: &Pubkey,
+) -> Vec<BalanceChange> {
+    let change = |mint: &str, delta: f64| BalanceChange {
+        signature: transaction.signature.clone(),
+        timestamp: transaction.block_time,
+        mint: mint.to_string(),
+        delta,
+    };
+    let mut changes = Vec::new();
+    if let Some(delta) = sol_delta(&transaction.transaction, owner) {
+        changes.push(change(ADDRESS_SOL, delta));
+    }
+    for (mint, delta) in token_deltas(&transaction.transaction, &owner.to_string()) {
+        changes.push(change(&mint, delta));
+    }
+    changes
+}
+
+/// Accounts of the transaction message, in the order of the balances in its meta.
+pub(crate) fn account_keys(transaction: &EncodedTransactionWithStatusMeta) -> Vec<Pubkey> {
+    transaction
+        .transaction
+        .decode()
+        .map(|decoded| decoded.message.static_account_keys().to_vec())
+        .unwrap_or_default()
+}
+
+/// Accounts that signed a transaction.
+fn signers(transaction: &EncodedTransactionWithStatusMeta) -> Vec<Pubkey> {
+    transaction
+        .transaction
+        .decode()
+        .map(|decoded| {
+            let count = decoded.message.header().num_required_signatures as usize;
+            decoded
+                .message
+                .static_account_keys()
+                .iter()
+                .take(count)
+                .copied()
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// SOL balance change of `account` in a transaction, `None` when it did not change.
+pub(crate) fn sol_delta(
+    transaction: &EncodedTransactionWithStatusMeta,
+    account: &Pubkey,
+) -> Option<f64> {
+    let meta = transaction.meta.as_ref()?;
+    let index = account_keys(transaction)
+        .iter()
+        .position(|key| key == account)?;
+    let pre = meta.pre_balances.get(index).copied().unwrap_or_default();
+    let post = meta.post_balances.get(index).copied().unwrap_or_default();
+    (pre != post).then(|| (post as f64 - pre as f64) / 10f64.powi(SOL_DECIMALS as i32))
+}
+
+/// Token balance changes of the accounts owned by `owner` in a transaction, by mint.
+pub(crate) fn token_deltas(
+    transaction: &EncodedTransactionWithStatusMeta,
+    owner: &str,
+) -> Vec<(String, f64)> {
+    let mut deltas = Vec::new();
+    let Some(meta) = transaction.meta.as_ref() else {
+        return deltas;
+    };
+    let pre = token_amounts(&meta.pre_token_balances, owner);
+    let mut post = token_amounts(&meta.post_token_balances, owner);
+    for (mint, (pre_amount, decimals)) in pre {
+        let post_amount = post
+            .remove(&mint)
+            .map(|(amount, _)| amount)
+            .unwrap_or_default();
+        if post_amount != pre_amount {
+            let delta = (post_amount as f64 - pre_amount as f64) / 10f64.powi(decimals as i32);
+            deltas.push((mint, delta));
+        }
+    }
+    for (mint, (post_amount, decimals)) in post {
+        if post_amount > 0 {
+            deltas.push((mint, post_amount as f64 / 10f64.powi(decimals as i32)));
+        }
+    }
+    deltas
+}
+
+/// Token amounts of `owner` by mint, in base units, with the mint decimals.
+fn token_amounts(
+    balances: &OptionSerializer<Vec<UiTransactionTokenBalance>>,
+    owner: &str,
+) -> HashMap<String, (u64, u8)> {
+    let mut amounts = HashMap::new();
+    let OptionSerializer::Some(balances) = balances else {
+        return amounts;
+    };
+    for balance in balances
+        .iter()
+        .filter(|balance| matches!(&balance.owner, OptionSerializer::Some(o) if o == owner))
+    {
+        let amount = balance.ui_token_amount.amount.parse::<u64>().unwrap_or(0);
+        let entry = amounts
+            .entry(balance.mint.clone())
+            .or_insert((0, balance.ui_token_amount.decimals));
+        entry.0 += amount;
+    }
+    amounts
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        serde_json::json,
+        solana_client::{rpc_client::Mocks, rpc_request::RpcRequest},
+    };
+
+    fn status(block_time: i64) -> serde_json::Value {
+        json!({
+            "signature": Signature::new_unique().to_string(),
+            "slot": 1,
+            "err": null,
+            "memo": null,
+            "blockTime": block_time,
+            "confirmationStatus": "finalized"
+        })
+    }
+
+    async fn history(since: i64, limit: usize) -> TransactionHistory {
+        let mut mocks = Mocks::new();
+        mocks.insert(
+            RpcRequest::GetSignaturesForAddress,
+            json!([status(300), status(200), status(100)]),
+        );
+        let rpc_client = RpcClient::new_mock_with_mocks("succeeds".to_string(), mocks);
+        fetch_transactions(&rpc_client, &Pubkey::new_unique(), since, limit)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_fetch_transactions_truncated() {
+        let truncated = history(0, 2).await;
+        assert_eq!(truncated.transactions.len(), 2);
+        assert!(truncated.truncated);
+
+        // Transactions before the period do not count.
+        let complete = history(150, 2).await;
+        assert_eq!(complete.transactions.len(), 2);
+        assert!(!complete.truncated);
+        assert_eq!(complete.transactions[1].block_time, 200);
+    }
+}
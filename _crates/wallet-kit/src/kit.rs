@@ -0,0 +1,446 @@
+//! A long-lived client that owns the RPC and HTTP connections of the wallet.
+
+use {
+    crate::{
+        balance::read_wallet_holdings,
+        models::swap::{
+            PrioritizationFeeLamports, PriorityLevelWithMaxLamports, SwapQuoteResponse,
+            SwapTransactionPayload, SwapTransactionResponse,
+        },
+        rpc_endpoints::RpcEndpoints,
+        swap::{jupiter_swap_quote, jupiter_swap_transaction, send_swap_transaction},
+        transactions::{send_sol_transfer, send_token_transfer, TransactionError, TransferOptions},
+    },
+    reqwest::Client,
+    smbcloud_wallet_constants::constants::LAMPORTS_PER_SOL,
+    smbcloud_wallet_core_http::{
+        fx_data::{
+            exchange_rate_api::ExchangeRateApiProvider,
+            fx_service::{FxService, FX_RATES_TTL},
+        },
+        price_data::{
+            birdeye::BirdeyePriceProvider,
+            jupiter::JupiterPriceProvider,
+            price_provider::PriceProvider,
+            price_service::{PriceService, PRICE_CACHE_TTL},
+            valuation::value_holdings_with,
+        },
+    },
+    smbcloud_wallet_core_model::models::{
+        currency::FiatCurrency, portfolio::PortfolioValuation, wallet_kit::WalletKitConfig,
+    },
+    smbcloud_wallet_core_network::model::{ErrorCode, ErrorResponse},
+    solana_client::{
+        client_error::{ClientError, ClientErrorKind},
+        nonblocking::rpc_client::RpcClient,
+        rpc_request::{RpcError, RpcResponseErrorData},
+    },
+    solana_sdk::{
+        pubkey::Pubkey,
+        signature::{Keypair, Signature, Signer},
+    },
+    std::{
+        collections::HashMap,
+        future::Future,
+        str::FromStr,
+        sync::{Arc, Mutex},
+    },
+    thiserror::Error,
+};
+
+/// Most lamports [`WalletKit::swap`] pays as priority fee, as the swap screen does.
+const SWAP_MAX_PRIORITY_FEE_LAMPORTS: u64 = 1_000_000;
+
+#[derive(Error, Debug)]
+pub enum WalletKitError {
+    #[error("Failed to create HTTP client: {0}")]
+    ClientError(String),
+
+    #[error("Failed to connect to RPC: {0}")]
+    ConnectionError(String),
+
+    #[error("Invalid address: {0}")]
+    InvalidAddress(String),
+
+    #[error("Not a token mint: {0}")]
+    InvalidMint(String),
+
+    #[error("Invalid signature: {0}")]
+    InvalidSignature(String),
+
+    #[error("Request failed: {0}")]
+    RequestError(#[from] ErrorResponse),
+
+    #[error(transparent)]
+    TransactionError(#[from] TransactionError),
+}
+
+/// Balances, prices, transfers and swaps of one network.
+///
+/// Built once from a [`WalletKitConfig`], it keeps one RPC client per endpoint
+/// and one HTTP connection pool for every call, along with the price and
+/// exchange rate caches.
+///
+/// Those caches belong to the kit and use its API key and user agent, the
+/// process wide `PriceService::shared` and `FxService::shared` are never used.
+/// Two things are process wide on purpose: token metadata from
+/// `TokenRegistry::shared` and endpoint health from `RpcEndpoints::shared`.
+/// Neither holds credentials, and every kit of a network learns from the
+/// tokens and endpoint failures the others saw.
+pub struct WalletKit {
+    config: WalletKitConfig,
+    rpc_clients: Mutex<HashMap<String, Arc<RpcClient>>>,
+    http_client: Client,
+    price_service: PriceService,
+    fx_service: FxService,
+}
+
+impl WalletKit {
+    pub fn new(config: WalletKitConfig) -> Result<Self, WalletKitError> {
+        let http_client = Client::builder()
+            .timeout(config.timeout())
+            .user_agent(&config.user_agent)
+            .build()
+            .map_err(|e| WalletKitError::ClientError(e.to_string()))?;
+
+        // Birdeye first when there is a key, Jupiter as fallback.
+        let mut providers: Vec<Box<dyn PriceProvider>> = Vec::new();
+        if let Some(api_key) = &config.birdeye_api_key {
+            providers.push(Box::new(BirdeyePriceProvider::with_client(
+                http_client.clone(),
+                api_key,
+                &config.user_agent,
+            )));
+        }
+        providers.push(Box::new(JupiterPriceProvider::with_client(
+            http_client.clone(),
+            &config.user_agent,
+        )));
+        let fx_provider =
+            ExchangeRateApiProvider::with_client(http_client.clone(), &config.user_agent);
+
+        Ok(WalletKit {
+            rpc_clients: Mutex::new(HashMap::new()),
+            price_service: PriceService::new(providers, PRICE_CACHE_TTL),
+            fx_service: FxService::new(Box::new(fx_provider), FX_RATES_TTL),
+            http_client,
+            config,
+        })
+    }
+
+    pub fn config(&self) -> &WalletKitConfig {
+        &self.config
+    }
+
+    /// SOL balance of `pubkey`.
+    pub async fn sol_balance(&self, pubkey: &str) -> Result<f64, WalletKitError> {
+        let owner = parse_address(pubkey)?;
+        let lamports = self
+            .read(|rpc_client| async move { rpc_client.get_balance(&owner).await })
+            .await
+            .map_err(|e| WalletKitError::ConnectionError(e.to_string()))?;
+        Ok(lamports as f64 / LAMPORTS_PER_SOL)
+    }
+
+    /// SOL and token holdings of `pubkey`, valued in `currency`.
+    pub async fn wallet_balance(
+        &self,
+        pubkey: &str,
+        currency: FiatCurrency,
+    ) -> Result<PortfolioValuation, WalletKitError> {
+        let owner = parse_address(pubkey)?;
+        let holdings = self
+            .read(|rpc_client| async move { read_wallet_holdings(&rpc_client, &owner).await })
+            .await
+            .map_err(|e| WalletKitError::ConnectionError(e.to_string()))?;
+        Ok(value_holdings_with(holdings, currency, &self.price_service, &self.fx_service).await?)
+    }
+
+    /// Units of `currency` one US dollar buys.
+    pub async fn usd_rate(&self, currency: FiatCurrency) -> Result<f64, WalletKitError> {
+        Ok(self.fx_service.usd_rate(currency).await?)
+    }
+
+    /// Price of one `mint` token in `currency`.
+    pub async fn asset_price(
+        &self,
+        mint: &str,
+        currency: FiatCurrency,
+    ) -> Result<f64, WalletKitError> {
+        let price = self.price_service.get_price(mint).await?;
+        Ok(self
+            .fx_service
+            .convert_usd(price.usd_price, currency)
+            .await?)
+    }
+
+    /// Send `amount` SOL to `to`, with the treasury fee of the fee policy.
+    pub async fn transfer_sol(
+        &self,
+        keypair: &Keypair,
+        to: &str,
+        amount: f64,
+    ) -> Result<String, WalletKitError> {
+        Ok(send_sol_transfer(
+            &self.rpc_client(),
+            keypair,
+            keypair.pubkey().to_string(),
+            to.to_string(),
+            amount,
+            self.transfer_options(),
+        )
+        .await?)
+    }
+
+    /// Send `amount` of the `mint` token to `to`, with the treasury fee of the
+    /// fee policy. The token program is read from the mint account, Token and
+    /// Token-2022 mints both work.
+    pub async fn transfer_token(
+        &self,
+        keypair: &Keypair,
+        to: &str,
+        mint: &str,
+        amount: f64,
+    ) -> Result<String, WalletKitError> {
+        let mint_address = parse_address(mint)?;
+        let token_program = self
+            .read(|rpc_client| async move { rpc_client.get_account(&mint_address).await })
+            .await
+            .map_err(|e| WalletKitError::ConnectionError(e.to_string()))?
+            .owner;
+        if token_program != spl_token::id() && token_program != spl_token_2022::id() {
+            return Err(WalletKitError::InvalidMint(mint.to_string()));
+        }
+        // The transfer stays on the endpoint the reads left active
+        let rpc_client = self.rpc_client();
+        Ok(send_token_transfer(
+            &rpc_client,
+            keypair,
+            keypair.pubkey().to_string(),
+            to.to_string(),
+            mint.to_string(),
+            token_program.to_string(),
+            amount,
+            self.transfer_options(),
+        )
+        .await?)
+    }
+
+    /// Jupiter quote for swapping `amount` of `from_token` into `to_token`.
+    pub async fn swap_quote(
+        &self,
+        from_token: &str,
+        to_token: &str,
+        amount: f64,
+        slippage_bps: u64,
+    ) -> Result<SwapQuoteResponse, WalletKitError> {
+        Ok(jupiter_swap_quote(
+            &self.http_client,
+            from_token,
+            to_token,
+            amount,
+            slippage_bps,
+        )
+        .await?)
+    }
+
+    /// Unsigned Jupiter swap transaction for a quote.
+    pub async fn build_swap_transaction(
+        &self,
+        payload: SwapTransactionPayload,
+    ) -> Result<SwapTransactionResponse, WalletKitError> {
+        Ok(jupiter_swap_transaction(&self.http_client, payload).await?)
+    }
+
+    /// Sign and send a swap transaction built by [`WalletKit::build_swap_transaction`].
+    pub async fn send_swap_transaction(
+        &self,
+        keypair: &Keypair,
+        swap_transaction: String,
+    ) -> Result<Signature, WalletKitError> {
+        Ok(send_swap_transaction(&self.rpc_client(), swap_transaction, keypair).await?)
+    }
+
+    /// Quote, build and send a swap of `amount` of `from_token` into `to_token`,
+    /// with the priority fee and slippage settings of the app.
+    pub async fn swap(
+        &self,
+        keypair: &Keypair,
+        from_token: &str,
+        to_token: &str,
+        amount: f64,
+        slippage_bps: u64,
+    ) -> Result<Signature, WalletKitError> {
+        let quote = self
+            .swap_quote(from_token, to_token, amount, slippage_bps)
+            .await?;
+        let payload = SwapTransactionPayload {
+            quoteResponse: quote,
+            userPublicKey: keypair.pubkey().to_string(),
+            dynamicComputeUnitLimit: true,
+            dynamicSlippage: true,
+            wrapAndUnwrapSol: true,
+            prioritizationFeeLamports: PrioritizationFeeLamports {
+                priorityLevelWithMaxLamports: PriorityLevelWithMaxLamports {
+                    maxLamports: SWAP_MAX_PRIORITY_FEE_LAMPORTS,
+                    priorityLevel: "veryHigh".to_string(),
+                },
+            },
+        };
+        let transaction = self.build_swap_transaction(payload).await?;
+        self.send_swap_transaction(keypair, transaction.swapTransaction)
+            .await
+    }
+
+    /// The client of the endpoint calls should go to now.
+    ///
+    /// Transactions keep the client they started with, so they are sent and
+    /// confirmed on one endpoint.
+    pub fn rpc_client(&self) -> Arc<RpcClient> {
+        let url = match &self.config.rpc_url {
+            Some(rpc_url) => rpc_url.clone(),
+            None => RpcEndpoints::shared(self.config.environment).active_url(),
+        };
+        self.rpc_client_for(url)
+    }
+
+    fn rpc_client_for(&self, url: String) -> Arc<RpcClient> {
+        let mut rpc_clients = self.rpc_clients.lock().unwrap_or_else(|e| e.into_inner());
+        rpc_clients
+            .entry(url.clone())
+            .or_insert_with(|| Arc::new(RpcClient::new_with_timeout(url, self.config.timeout())))
+            .clone()
+    }
+
+    /// Run an idempotent read with [`RpcEndpoints::read_with_failover`], so it
+    /// moves to the other endpoints only when the node could not answer.
+    /// Endpoints set in the configuration have no fallback.
+    async fn read<T, F, Fut>(&self, read: F) -> Result<T, ErrorResponse>
+    where
+        F: Fn(Arc<RpcClient>) -> Fut,
+        Fut: Future<Output = Result<T, ClientError>>,
+    {
+        if self.config.rpc_url.is_some() {
+            return read(self.rpc_client()).await.map_err(rpc_error);
+        }
+        RpcEndpoints::shared(self.config.environment)
+            .read_with_failover(|url| {
+                let result = read(self.rpc_client_for(url));
+                async move { result.await.map_err(rpc_error) }
+            })
+            .await
+    }
+
+    fn transfer_options(&self) -> TransferOptions {
+        TransferOptions {
+            fee_on_top: self.config.fee_on_top,
+            skip_confirmation: !self.config.confirm_transfers,
+            ..TransferOptions::default()
+        }
+    }
+}
+
+fn parse_address(address: &str) -> Result<Pubkey, WalletKitError> {
+    Pubkey::from_str(address).map_err(|_| WalletKitError::InvalidAddress(address.to_string()))
+}
+
+/// A failed RPC call, as a network error when the node could not answer it
+/// and another endpoint may.
+fn rpc_error(error: ClientError) -> ErrorResponse {
+    let code = match error.kind() {
+        ClientErrorKind::Io(_) | ClientErrorKind::Reqwest(_) | ClientErrorKind::Middleware(_) => {
+            ErrorCode::NetworkError
+        }
+        ClientErrorKind::RpcError(RpcError::RpcResponseError {
+            data: RpcResponseErrorData::NodeUnhealthy { .. },
+            ..
+        }) => ErrorCode::NetworkError,
+        _ => ErrorCode::Unknown,
+    };
+    ErrorResponse::Error {
+        code,
+        message: error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, smbcloud_wallet_core_model::models::environment::Environment};
+
+    #[test]
+    fn test_config_rpc_url() {
+        let mut config = WalletKitConfig::new(Environment::Devnet, "user-agent");
+        let kit = WalletKit::new(config.clone()).unwrap();
+        assert_eq!(
+            kit.rpc_client().url(),
+            RpcEndpoints::shared(Environment::Devnet).active_url()
+        );
+
+        config.rpc_url = Some("http://localhost:8899".to_string());
+        let kit = WalletKit::new(config).unwrap();
+        assert_eq!(kit.rpc_client().url(), "http://localhost:8899");
+    }
+
+    #[test]
+    fn test_fee_policy() {
+        let mut config = WalletKitConfig::new(Environment::Devnet, "user-agent");
+        config.fee_on_top = true;
+        let kit = WalletKit::new(config).unwrap();
+        assert!(kit.transfer_options().fee_on_top);
+        assert!(!kit.transfer_options().skip_confirmation);
+    }
+
+    #[test]
+    fn test_rpc_error() {
+        let error = rpc_error(ClientError::from(std::io::Error::other("connection reset")));
+        assert!(matches!(
+            error,
+            ErrorResponse::Error {
+                code: ErrorCode::NetworkError,
+                ..
+            }
+        ));
+
+        let unhealthy = RpcError::RpcResponseError {
+            code: -32005,
+            message: "Node is behind".to_string(),
+            data: RpcResponseErrorData::NodeUnhealthy {
+                num_slots_behind: Some(100),
+            },
+        };
+        let error = rpc_error(ClientError::from(unhealthy));
+        assert!(matches!(
+            error,
+            ErrorResponse::Error {
+                code: ErrorCode::NetworkError,
+                ..
+            }
+        ));
+
+        // The node answered, another one would answer the same.
+        let invalid = RpcError::RpcResponseError {
+            code: -32602,
+            message: "Invalid param: could not find account".to_string(),
+            data: RpcResponseErrorData::Empty,
+        };
+        let error = rpc_error(ClientError::from(invalid));
+        assert!(matches!(
+            error,
+            ErrorResponse::Error {
+                code: ErrorCode::Unknown,
+                ..
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_address() {
+        let kit = WalletKit::new(WalletKitConfig::new(Environment::Local, "user-agent")).unwrap();
+        let result = kit.sol_balance("not-an-address").await;
+        assert!(matches!(result, Err(WalletKitError::InvalidAddress(_))));
+        let result = kit
+            .wallet_balance("not-an-address", FiatCurrency::USD)
+            .await;
+        assert!(matches!(result, Err(WalletKitError::InvalidAddress(_))));
+    }
+}
@@ -1,6 +1,24 @@
+pub mod address_book;
 pub(crate) mod assets;
 pub mod balance;
+pub mod burn;
+pub(crate) mod cost_basis;
 pub mod fee;
+pub(crate) mod history;
+pub mod kit;
+pub mod liquid_staking;
 pub mod models;
+pub mod nft;
+pub mod portfolio_history;
+pub(crate) mod rpc;
+pub mod rpc_endpoints;
+pub mod solana_pay;
+pub mod staking;
+pub mod subscriptions;
 pub mod swap;
+pub mod tax_export;
+pub mod token_accounts;
 pub mod transactions;
+pub mod units;
+pub mod username;
+pub mod wsol;
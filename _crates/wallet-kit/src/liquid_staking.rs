@@ -0,0 +1,453 @@
+use {
+    crate::{
+        assets::SOLANA,
+        models::liquid_staking::{
+            LiquidStakeQuote, LiquidStakeRoute, LiquidUnstakeQuote, StakePoolInfo,
+        },
+        rpc::{send, RpcError},
+        swap::get_jupiter_swap_quote,
+        units::to_base_units,
+    },
+    borsh::BorshDeserialize,
+    log::{info, warn},
+    smbcloud_wallet_constants::constants::{
+        JITO_STAKE_POOL, LAMPORTS_PER_SOL, SOL_DECIMALS, SPL_STAKE_POOL_PROGRAM_ID,
+        SPL_TOKEN_PROGRAM_ID,
+    },
+    solana_client::nonblocking::rpc_client::RpcClient,
+    solana_sdk::{
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+    },
+    spl_associated_token_account::{
+        get_associated_token_address, instruction::create_associated_token_account_idempotent,
+    },
+    spl_stake_pool::{
+        find_withdraw_authority_program_address, instruction as stake_pool_instruction,
+        state::{Fee, StakePool},
+    },
+    std::str::FromStr,
+    thiserror::Error,
+};
+
+/// Stake pools offered in the app, by name and pool address.
+pub const STAKE_POOLS: [(&str, &str); 1] = [("Jito Staked SOL", JITO_STAKE_POOL)];
+
+/// Slippage used for the Jupiter comparison quote.
+const JUPITER_SLIPPAGE_BPS: u64 = 50;
+
+#[derive(Error, Debug)]
+pub enum LiquidStakingError {
+    #[error("Failed to connect to RPC: {0}")]
+    ConnectionError(String),
+
+    #[error("Invalid address: {0}")]
+    InvalidAddress(String),
+
+    #[error("Invalid amount: {0}")]
+    InvalidAmount(String),
+
+    #[error("Not an SPL stake pool: {0}")]
+    InvalidStakePool(String),
+
+    #[error("Stake pool is not updated for the current epoch")]
+    StakePoolNotUpdated,
+
+    #[error("Stake pool only accepts {0} from an authorized account")]
+    RestrictedStakePool(String),
+
+    #[error("Insufficient funds")]
+    InsufficientFunds,
+
+    #[error("Transaction error: {0}")]
+    TransactionError(String),
+}
+
+impl RpcError for LiquidStakingError {
+    fn connection_error(message: String) -> Self {
+        LiquidStakingError::ConnectionError(message)
+    }
+
+    fn transaction_error(message: String) -> Self {
+        LiquidStakingError::TransactionError(message)
+    }
+
+    fn invalid_address(address: String) -> Self {
+        LiquidStakingError::InvalidAddress(address)
+    }
+}
+
+/// Fetch the state of every configured stake pool.
+pub async fn get_stake_pools(
+    rpc_client: &RpcClient,
+) -> Result<Vec<StakePoolInfo>, LiquidStakingError> {
+    let mut pools = Vec::new();
+    for (_, address) in STAKE_POOLS {
+        pools.push(get_stake_pool(rpc_client, address.to_string()).await?);
+    }
+    Ok(pools)
+}
+
+/// Fetch the exchange rate and fees of a stake pool.
+pub async fn get_stake_pool(
+    rpc_client: &RpcClient,
+    pool_address: String,
+) -> Result<StakePoolInfo, LiquidStakingError> {
+    let (address, stake_pool) = load_stake_pool(rpc_client, &pool_address).await?;
+    Ok(stake_pool_info(&address, &stake_pool))
+}
+
+/// Quote a SOL deposit into the pool and compare it with the Jupiter route.
+///
+/// A failing Jupiter quote does not fail the whole quote, the direct deposit
+/// is then the only route.
+pub async fn quote_liquid_stake(
+    rpc_client: &RpcClient,
+    pool_address: String,
+    amount: f64,
+) -> Result<LiquidStakeQuote, LiquidStakingError> {
+    let (address, stake_pool) = load_stake_pool(rpc_client, &pool_address).await?;
+    let lamports = to_lamports(amount)?;
+    let (pool_tokens, fee) = deposit_sol_amounts(&stake_pool, lamports)
+        .ok_or_else(|| LiquidStakingError::InvalidAmount(amount.to_string()))?;
+
+    let jupiter_out_amount = match get_jupiter_swap_quote(
+        SOLANA,
+        &stake_pool.pool_mint.to_string(),
+        amount,
+        JUPITER_SLIPPAGE_BPS,
+    )
+    .await
+    {
+        Ok(quote) => quote
+            .outAmount
+            .parse::<u64>()
+            .ok()
+            .map(|out_amount| out_amount as f64 / LAMPORTS_PER_SOL),
+        Err(e) => {
+            warn!("Failed to get Jupiter quote for liquid staking: {}", e);
+            None
+        }
+    };
+
+    let pool_tokens_out = pool_tokens as f64 / LAMPORTS_PER_SOL;
+    Ok(LiquidStakeQuote {
+        pool: stake_pool_info(&address, &stake_pool),
+        amount,
+        pool_tokens_out,
+        deposit_fee: fee as f64 / LAMPORTS_PER_SOL,
+        jupiter_out_amount,
+        best_route: best_route(pool_tokens_out, jupiter_out_amount),
+    })
+}
+
+/// Quote a SOL withdrawal from the pool.
+pub async fn quote_liquid_unstake(
+    rpc_client: &RpcClient,
+    pool_address: String,
+    pool_tokens: f64,
+) -> Result<LiquidUnstakeQuote, LiquidStakingError> {
+    let (address, stake_pool) = load_stake_pool(rpc_client, &pool_address).await?;
+    let pool_token_amount = to_lamports(pool_tokens)?;
+    let (lamports, fee) = withdraw_sol_amounts(&stake_pool, pool_token_amount)
+        .ok_or_else(|| LiquidStakingError::InvalidAmount(pool_tokens.to_string()))?;
+
+    Ok(LiquidUnstakeQuote {
+        pool: stake_pool_info(&address, &stake_pool),
+        pool_tokens,
+        sol_out: lamports as f64 / LAMPORTS_PER_SOL,
+        withdrawal_fee: fee as f64 / LAMPORTS_PER_SOL,
+    })
+}
+
+/// Deposit `amount` SOL into the stake pool and receive pool tokens.
+///
+/// The associated token account for the pool mint is created if needed.
+pub async fn deposit_sol(
+    rpc_client: &RpcClient,
+    keypair: Keypair,
+    pool_address: String,
+    amount: f64,
+) -> Result<String, LiquidStakingError> {
+    let (address, stake_pool) = load_stake_pool(rpc_client, &pool_address).await?;
+    ensure_updated(rpc_client, &stake_pool).await?;
+    if stake_pool.sol_deposit_authority.is_some() {
+        return Err(LiquidStakingError::RestrictedStakePool(
+            "deposits".to_string(),
+        ));
+    }
+
+    let lamports = to_lamports(amount)?;
+    let balance = rpc_client
+        .get_balance(&keypair.pubkey())
+        .await
+        .map_err(|e| LiquidStakingError::ConnectionError(e.to_string()))?;
+    if balance < lamports {
+        return Err(LiquidStakingError::InsufficientFunds);
+    }
+
+    let program_id = stake_pool_program_id();
+    let token_program = token_program_id();
+    let (withdraw_authority, _) = find_withdraw_authority_program_address(&program_id, &address);
+    let pool_token_account = get_associated_token_address(&keypair.pubkey(), &stake_pool.pool_mint);
+
+    let instructions = vec![
+        create_associated_token_account_idempotent(
+            &keypair.pubkey(),
+            &keypair.pubkey(),
+            &stake_pool.pool_mint,
+            &token_program,
+        ),
+        stake_pool_instruction::deposit_sol(
+            &program_id,
+            &address,
+            &withdraw_authority,
+            &stake_pool.reserve_stake,
+            &keypair.pubkey(),
+            &pool_token_account,
+            &stake_pool.manager_fee_account,
+            &pool_token_account,
+            &stake_pool.pool_mint,
+            &token_program,
+            lamports,
+        ),
+    ];
+    let signature = send(rpc_client, &keypair, &instructions, &[]).await?;
+
+    info!("Deposited {} SOL into stake pool {}", amount, address);
+    Ok(signature)
+}
+
+/// Burn `pool_tokens` and withdraw SOL from the pool reserve.
+///
+/// Large withdrawals can exceed the reserve, in which case the transaction fails
+/// and swapping on Jupiter is the way out.
+pub async fn withdraw_sol(
+    rpc_client: &RpcClient,
+    keypair: Keypair,
+    pool_address: String,
+    pool_tokens: f64,
+) -> Result<String, LiquidStakingError> {
+    let (address, stake_pool) = load_stake_pool(rpc_client, &pool_address).await?;
+    ensure_updated(rpc_client, &stake_pool).await?;
+    if stake_pool.sol_withdraw_authority.is_some() {
+        return Err(LiquidStakingError::RestrictedStakePool(
+            "withdrawals".to_string(),
+        ));
+    }
+
+    let pool_token_amount = to_lamports(pool_tokens)?;
+    let program_id = stake_pool_program_id();
+    let token_program = token_program_id();
+    let (withdraw_authority, _) = find_withdraw_authority_program_address(&program_id, &address);
+    let pool_token_account = get_associated_token_address(&keypair.pubkey(), &stake_pool.pool_mint);
+
+    let instruction = stake_pool_instruction::withdraw_sol(
+        &program_id,
+        &address,
+        &withdraw_authority,
+        &keypair.pubkey(),
+        &pool_token_account,
+        &stake_pool.reserve_stake,
+        &keypair.pubkey(),
+        &stake_pool.manager_fee_account,
+        &stake_pool.pool_mint,
+        &token_program,
+        pool_token_amount,
+    );
+    let signature = send(rpc_client, &keypair, &[instruction], &[]).await?;
+
+    info!(
+        "Withdrew {} pool tokens from stake pool {}",
+        pool_tokens, address
+    );
+    Ok(signature)
+}
+
+async fn load_stake_pool(
+    rpc_client: &RpcClient,
+    pool_address: &str,
+) -> Result<(Pubkey, StakePool), LiquidStakingError> {
+    let address = Pubkey::from_str(pool_address)
+        .map_err(|_| LiquidStakingError::InvalidAddress(pool_address.to_string()))?;
+    let account = rpc_client
+        .get_account(&address)
+        .await
+        .map_err(|e| LiquidStakingError::ConnectionError(e.to_string()))?;
+    if account.owner != stake_pool_program_id() {
+        return Err(LiquidStakingError::InvalidStakePool(
+            pool_address.to_string(),
+        ));
+    }
+    let stake_pool = StakePool::deserialize(&mut account.data.as_slice())
+        .map_err(|_| LiquidStakingError::InvalidStakePool(pool_address.to_string()))?;
+    Ok((address, stake_pool))
+}
+
+/// Deposits and withdrawals fail until the pool has been updated for the epoch.
+async fn ensure_updated(
+    rpc_client: &RpcClient,
+    stake_pool: &StakePool,
+) -> Result<(), LiquidStakingError> {
+    let epoch = rpc_client
+        .get_epoch_info()
+        .await
+        .map_err(|e| LiquidStakingError::ConnectionError(e.to_string()))?
+        .epoch;
+    if stake_pool.last_update_epoch < epoch {
+        return Err(LiquidStakingError::StakePoolNotUpdated);
+    }
+    Ok(())
+}
+
+fn stake_pool_info(address: &Pubkey, stake_pool: &StakePool) -> StakePoolInfo {
+    let address = address.to_string();
+    StakePoolInfo {
+        name: STAKE_POOLS
+            .iter()
+            .find(|(_, pool)| *pool == address)
+            .map(|(name, _)| name.to_string()),
+        pool_mint: stake_pool.pool_mint.to_string(),
+        exchange_rate: exchange_rate(stake_pool),
+        sol_deposit_fee: fee_ratio(&stake_pool.sol_deposit_fee),
+        sol_withdrawal_fee: fee_ratio(&stake_pool.sol_withdrawal_fee),
+        total_lamports: stake_pool.total_lamports as f64 / LAMPORTS_PER_SOL,
+        address,
+    }
+}
+
+/// Pool tokens minted to the depositor and the deposit fee the pool keeps, as
+/// computed by the program.
+///
+/// Deposits name the depositor as the referrer, so the referral share of the
+/// fee comes back to it.
+fn deposit_sol_amounts(stake_pool: &StakePool, lamports: u64) -> Option<(u64, u64)> {
+    let minted = stake_pool.calc_pool_tokens_for_deposit(lamports)?;
+    let fee = stake_pool.calc_pool_tokens_sol_deposit_fee(minted)?;
+    let referral = stake_pool.calc_pool_tokens_sol_referral_fee(fee)?;
+    let kept = fee.checked_sub(referral)?;
+    Some((minted.checked_sub(kept)?, kept))
+}
+
+/// Lamports paid to the withdrawer and the withdrawal fee in pool tokens.
+///
+/// Like the program, the fee is taken from the pool tokens and only the rest
+/// is converted to lamports.
+fn withdraw_sol_amounts(stake_pool: &StakePool, pool_tokens: u64) -> Option<(u64, u64)> {
+    let fee = stake_pool.calc_pool_tokens_sol_withdrawal_fee(pool_tokens)?;
+    let burnt = pool_tokens.checked_sub(fee)?;
+    let lamports = stake_pool.calc_lamports_withdraw_amount(burnt)?;
+    Some((lamports, fee))
+}
+
+fn exchange_rate(stake_pool: &StakePool) -> f64 {
+    if stake_pool.pool_token_supply == 0 {
+        1.0
+    } else {
+        stake_pool.total_lamports as f64 / stake_pool.pool_token_supply as f64
+    }
+}
+
+fn fee_ratio(fee: &Fee) -> f64 {
+    if fee.denominator == 0 {
+        0.0
+    } else {
+        fee.numerator as f64 / fee.denominator as f64
+    }
+}
+
+fn best_route(pool_tokens_out: f64, jupiter_out_amount: Option<f64>) -> LiquidStakeRoute {
+    match jupiter_out_amount {
+        Some(jupiter) if jupiter > pool_tokens_out => LiquidStakeRoute::Jupiter,
+        _ => LiquidStakeRoute::StakePool,
+    }
+}
+
+/// Stake pool mints use the same 9 decimals as SOL.
+fn to_lamports(amount: f64) -> Result<u64, LiquidStakingError> {
+    if !amount.is_finite() || amount <= 0.0 {
+        return Err(LiquidStakingError::InvalidAmount(amount.to_string()));
+    }
+    Ok(to_base_units(amount, SOL_DECIMALS))
+}
+
+fn stake_pool_program_id() -> Pubkey {
+    Pubkey::from_str(SPL_STAKE_POOL_PROGRAM_ID).expect("Invalid stake pool program id")
+}
+
+fn token_program_id() -> Pubkey {
+    Pubkey::from_str(SPL_TOKEN_PROGRAM_ID).expect("Invalid token program id")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stake_pool() -> StakePool {
+        StakePool {
+            total_lamports: 1_100_000_000_000,
+            pool_token_supply: 1_000_000_000_000,
+            sol_deposit_fee: Fee {
+                numerator: 1,
+                denominator: 1000,
+            },
+            sol_withdrawal_fee: Fee {
+                numerator: 3,
+                denominator: 1000,
+            },
+            sol_referral_fee: 50,
+            ..StakePool::default()
+        }
+    }
+
+    #[test]
+    fn test_exchange_rate_and_fees() {
+        let stake_pool = stake_pool();
+        assert!((exchange_rate(&stake_pool) - 1.1).abs() < 1e-12);
+        assert_eq!(fee_ratio(&stake_pool.sol_deposit_fee), 0.001);
+        assert_eq!(fee_ratio(&Fee::default()), 0.0);
+        assert_eq!(exchange_rate(&StakePool::default()), 1.0);
+    }
+
+    #[test]
+    fn test_deposit_sol_amounts() {
+        // 1.1 SOL buys 1 pool token, minus the 0.1% deposit fee of which half
+        // is the referral share returned to the depositor.
+        let (pool_tokens, fee) = deposit_sol_amounts(&stake_pool(), 1_100_000_000).unwrap();
+        assert_eq!(fee, 500_000);
+        assert_eq!(pool_tokens, 999_500_000);
+
+        let no_referral = StakePool {
+            sol_referral_fee: 0,
+            ..stake_pool()
+        };
+        let (pool_tokens, fee) = deposit_sol_amounts(&no_referral, 1_100_000_000).unwrap();
+        assert_eq!(fee, 1_000_000);
+        assert_eq!(pool_tokens, 999_000_000);
+    }
+
+    #[test]
+    fn test_withdraw_sol_amounts() {
+        // 1 pool token is worth 1.1 SOL, minus the 0.3% withdrawal fee.
+        let (lamports, fee) = withdraw_sol_amounts(&stake_pool(), 1_000_000_000).unwrap();
+        assert_eq!(fee, 3_000_000);
+        assert_eq!(lamports, 1_096_700_000);
+    }
+
+    #[test]
+    fn test_best_route() {
+        assert_eq!(best_route(1.0, None), LiquidStakeRoute::StakePool);
+        assert_eq!(best_route(1.0, Some(0.99)), LiquidStakeRoute::StakePool);
+        assert_eq!(best_route(1.0, Some(1.01)), LiquidStakeRoute::Jupiter);
+    }
+
+    #[test]
+    fn test_to_lamports() {
+        assert_eq!(to_lamports(0.3).unwrap(), 300_000_000);
+        assert_eq!(to_lamports(0.1 + 0.2).unwrap(), 300_000_000);
+        assert!(matches!(
+            to_lamports(-1.0),
+            Err(LiquidStakingError::InvalidAmount(_))
+        ));
+    }
+}
@@ -0,0 +1,39 @@
+use {
+    serde::{Deserialize, Serialize},
+    smbcloud_wallet_core_model::models::environment::Environment,
+    tsync::tsync,
+};
+
+/// A saved recipient in the address book.
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Contact {
+    pub id: String,
+    pub label: String,
+    pub address: String,
+    /// The network the address was saved for.
+    pub network: Environment,
+    /// NotWallet username, without the leading `@`.
+    pub username: Option<String>,
+    /// Domain name resolving to the address, e.g. `bonfida.sol`.
+    pub domain: Option<String>,
+    pub notes: Option<String>,
+    /// Set after the first successful small transfer to the address.
+    pub verified: bool,
+    /// Unix timestamp in seconds.
+    pub created_at: i64,
+    /// Unix timestamp of the last transfer to the contact.
+    pub last_used_at: Option<i64>,
+}
+
+/// The editable fields of a contact.
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContactInput {
+    pub label: String,
+    pub address: String,
+    pub network: Environment,
+    pub username: Option<String>,
+    pub domain: Option<String>,
+    pub notes: Option<String>,
+}
@@ -0,0 +1,51 @@
+use {
+    serde::{Deserialize, Serialize},
+    tsync::tsync,
+};
+
+#[tsync]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BurnKind {
+    /// A fungible balance of SPL Token or Token-2022.
+    Token,
+    /// A Metaplex NFT, burnt with its metadata and edition accounts.
+    Nft,
+}
+
+/// What burning one mint destroys and returns to the wallet.
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BurnItem {
+    pub mint: String,
+    pub name: String,
+    pub symbol: String,
+    pub kind: BurnKind,
+    /// Tokens destroyed, in UI units.
+    pub amount: f64,
+    /// SOL returned by the accounts closed with the burn.
+    pub rent: f64,
+    /// Whether the token is on the verified registry, burning it must be forced.
+    pub verified: bool,
+}
+
+/// Confirmation summary shown before burning.
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BurnSummary {
+    pub items: Vec<BurnItem>,
+    /// SOL returned by burning every item.
+    pub recovered: f64,
+}
+
+/// Outcome of burning tokens and NFTs.
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BurnResult {
+    /// One signature per burnt mint.
+    pub signatures: Vec<String>,
+    pub burned: Vec<String>,
+    /// Requested mints whose transaction failed.
+    pub skipped: Vec<String>,
+    /// SOL returned to the wallet.
+    pub recovered: f64,
+}
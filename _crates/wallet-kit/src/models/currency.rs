@@ -1,8 +1 @@
-use serde::{Deserialize, Serialize};
-
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub enum FiatCurrency {
-    USD,
-    IDR,
-    SEK,
-}
+pub use smbcloud_wallet_core_model::models::currency::FiatCurrency;
@@ -0,0 +1,59 @@
+use {
+    serde::{Deserialize, Serialize},
+    tsync::tsync,
+};
+
+/// State of an SPL stake pool, e.g. the JitoSOL pool.
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StakePoolInfo {
+    pub name: Option<String>,
+    pub address: String,
+    /// Mint of the liquid staking token.
+    pub pool_mint: String,
+    /// SOL received for one pool token, before fees.
+    pub exchange_rate: f64,
+    /// Fee on SOL deposits, as a fraction, e.g. `0.001` for 0.1%.
+    pub sol_deposit_fee: f64,
+    /// Fee on SOL withdrawals, as a fraction.
+    pub sol_withdrawal_fee: f64,
+    /// Total SOL managed by the pool.
+    pub total_lamports: f64,
+}
+
+#[tsync]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LiquidStakeRoute {
+    StakePool,
+    Jupiter,
+}
+
+/// Quote for depositing SOL into a stake pool.
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LiquidStakeQuote {
+    pub pool: StakePoolInfo,
+    /// SOL deposited.
+    pub amount: f64,
+    /// Pool tokens received from a direct deposit, after fees.
+    pub pool_tokens_out: f64,
+    /// Pool tokens kept by the pool as the deposit fee.
+    pub deposit_fee: f64,
+    /// Pool tokens received by swapping on Jupiter, if a route was found.
+    pub jupiter_out_amount: Option<f64>,
+    /// The route that returns more pool tokens.
+    pub best_route: LiquidStakeRoute,
+}
+
+/// Quote for withdrawing SOL from a stake pool.
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LiquidUnstakeQuote {
+    pub pool: StakePoolInfo,
+    /// Pool tokens burnt.
+    pub pool_tokens: f64,
+    /// SOL received, after fees.
+    pub sol_out: f64,
+    /// Pool tokens kept by the pool as the withdrawal fee.
+    pub withdrawal_fee: f64,
+}
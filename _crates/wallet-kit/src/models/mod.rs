@@ -1,3 +1,13 @@
+pub mod address_book;
 pub mod asset;
+pub mod burn;
 pub mod currency;
+pub mod liquid_staking;
+pub mod nft;
+pub mod portfolio_history;
+pub mod solana_pay;
+pub mod subscriptions;
 pub mod swap;
+pub mod tax_export;
+pub mod token_accounts;
+pub mod username;
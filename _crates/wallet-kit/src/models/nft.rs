@@ -0,0 +1,50 @@
+use {
+    serde::{Deserialize, Serialize},
+    tsync::tsync,
+};
+
+/// How an NFT is held on chain, which decides how it is transferred.
+#[tsync]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NftKind {
+    /// A Metaplex NFT held in a token account.
+    Standard,
+    /// A programmable NFT, transferred through Token Metadata under its rule set.
+    Programmable,
+    /// A compressed NFT, a leaf of a Bubblegum merkle tree.
+    Compressed,
+}
+
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NftAttribute {
+    pub trait_type: String,
+    pub value: String,
+}
+
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Nft {
+    /// Mint of the NFT, or the asset id of a compressed NFT.
+    pub id: String,
+    pub kind: NftKind,
+    pub name: String,
+    pub symbol: String,
+    pub description: Option<String>,
+    /// Image from the off-chain JSON metadata.
+    pub image: Option<String>,
+    pub attributes: Vec<NftAttribute>,
+    /// URI of the off-chain JSON metadata.
+    pub uri: String,
+}
+
+/// NFTs of one verified collection.
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NftCollection {
+    /// Mint of the collection NFT, `None` for NFTs outside a verified collection.
+    pub address: Option<String>,
+    pub name: Option<String>,
+    pub image: Option<String>,
+    pub nfts: Vec<Nft>,
+}
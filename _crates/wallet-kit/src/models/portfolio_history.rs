@@ -0,0 +1,140 @@
+use {
+    serde::{Deserialize, Serialize},
+    smbcloud_wallet_core_model::models::{currency::FiatCurrency, environment::Environment},
+    tsync::tsync,
+};
+
+/// The period a portfolio chart covers.
+#[tsync]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HistoryRange {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl HistoryRange {
+    /// Length of the range in seconds.
+    pub fn duration(self) -> i64 {
+        match self {
+            Self::Day => 24 * 60 * 60,
+            Self::Week => 7 * 24 * 60 * 60,
+            Self::Month => 30 * 24 * 60 * 60,
+            Self::Year => 365 * 24 * 60 * 60,
+        }
+    }
+
+    /// Seconds between two chart points.
+    pub fn step(self) -> i64 {
+        match self {
+            Self::Day => 60 * 60,
+            Self::Week => 4 * 60 * 60,
+            Self::Month => 24 * 60 * 60,
+            Self::Year => 7 * 24 * 60 * 60,
+        }
+    }
+}
+
+/// How the cost of sold tokens is matched against their purchases.
+#[tsync]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CostBasisMethod {
+    /// First in, first out: the oldest purchases are sold first.
+    Fifo,
+    /// Every unit costs the average price paid for the current holding.
+    Average,
+}
+
+/// The holdings of a wallet at one point in time, valued in USD.
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PortfolioSnapshot {
+    pub pubkey: String,
+    pub network: Environment,
+    /// Unix timestamp in seconds.
+    pub timestamp: i64,
+    pub total_usd: f64,
+    pub assets: Vec<SnapshotAsset>,
+}
+
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotAsset {
+    pub mint: String,
+    pub amount: f64,
+    pub usd_price: Option<f64>,
+}
+
+/// The change of one token balance of a wallet in a transaction.
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BalanceChange {
+    pub signature: String,
+    /// Unix timestamp of the block, in seconds.
+    pub timestamp: i64,
+    pub mint: String,
+    /// Amount received, negative for amounts sent and fees paid.
+    pub delta: f64,
+}
+
+#[tsync]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ChartPoint {
+    /// Unix timestamp in seconds.
+    pub timestamp: i64,
+    pub value: f64,
+}
+
+/// Chart-ready portfolio values over a range.
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PortfolioChart {
+    pub range: HistoryRange,
+    pub currency: FiatCurrency,
+    /// Oldest first, spaced by the range step, the last point is now.
+    pub points: Vec<ChartPoint>,
+    /// Value change from the first to the last point.
+    pub change: f64,
+    /// `change` relative to the first point, in percent. `None` when the first point is zero.
+    pub change_percent: Option<f64>,
+}
+
+/// Cost basis and profit and loss of one asset.
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AssetPnl {
+    pub mint: String,
+    pub symbol: Option<String>,
+    pub amount: f64,
+    /// What the current amount cost.
+    pub cost_basis: f64,
+    /// Average cost of one unit of the current amount.
+    pub average_cost: Option<f64>,
+    pub current_value: Option<f64>,
+    /// Gains of everything sold, minus what it cost.
+    pub realized_pnl: f64,
+    /// `current_value - cost_basis`, `None` without a current price.
+    pub unrealized_pnl: Option<f64>,
+}
+
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PortfolioPnl {
+    pub currency: FiatCurrency,
+    pub method: CostBasisMethod,
+    pub assets: Vec<AssetPnl>,
+    pub total_cost_basis: f64,
+    pub total_realized_pnl: f64,
+    pub total_unrealized_pnl: f64,
+}
+
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PortfolioHistory {
+    pub chart: PortfolioChart,
+    pub pnl: PortfolioPnl,
+    /// Whether older transactions of the range were left out, the balances
+    /// before the oldest one read are then taken as they were at that time.
+    pub truncated: bool,
+}
@@ -0,0 +1,176 @@
+use {
+    serde::{Deserialize, Serialize},
+    smbcloud_wallet_core_model::models::environment::Environment,
+    tsync::tsync,
+};
+
+/// A Solana Pay transfer request, e.g.
+/// `solana:<recipient>?amount=1&spl-token=<mint>&reference=<pubkey>&label=Shop`.
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransferRequest {
+    /// Base58 address of the native SOL account that receives the payment.
+    pub recipient: String,
+    /// Amount in user units (e.g. `1.5` SOL). `None` lets the wallet ask the user.
+    pub amount: Option<f64>,
+    /// Mint address of the SPL token. `None` means native SOL.
+    pub spl_token: Option<String>,
+    /// Reference keys used by the merchant to locate the transaction.
+    pub reference: Vec<String>,
+    /// Describes the source of the request, e.g. the merchant name.
+    pub label: Option<String>,
+    /// Describes the nature of the request, e.g. the purchased item.
+    pub message: Option<String>,
+    /// Memo to include in the transaction, visible on-chain.
+    pub memo: Option<String>,
+}
+
+impl TransferRequest {
+    pub fn new(recipient: &str) -> Self {
+        Self {
+            recipient: recipient.to_string(),
+            amount: None,
+            spl_token: None,
+            reference: Vec::new(),
+            label: None,
+            message: None,
+            memo: None,
+        }
+    }
+}
+
+/// A Solana Pay transaction request, e.g. `solana:https%3A%2F%2Fshop.example%2Fpay`.
+/// The merchant server builds the transaction; the wallet only reviews and signs it.
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransactionRequest {
+    /// HTTPS endpoint of the merchant, percent-decoded.
+    pub link: String,
+    pub label: Option<String>,
+    pub message: Option<String>,
+}
+
+/// Any request encoded in a `solana:` URI.
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SolanaPayRequest {
+    Transfer { request: TransferRequest },
+    Transaction { request: TransactionRequest },
+}
+
+/// Response of the merchant to the `GET` request.
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransactionRequestMetadata {
+    pub label: String,
+    pub icon: String,
+}
+
+/// Body of the `POST` request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransactionRequestPayload {
+    pub account: String,
+}
+
+/// Response of the merchant to the `POST` request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransactionRequestResponse {
+    /// Base64 encoded, serialized transaction.
+    pub transaction: String,
+    pub message: Option<String>,
+}
+
+/// One decoded instruction of a merchant transaction.
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstructionSummary {
+    pub program_id: String,
+    /// Human-readable program name, e.g. "System Program".
+    pub program: String,
+    /// Short description of what the instruction does, e.g. "Transfer 10000 lamports".
+    pub description: String,
+    pub accounts: Vec<String>,
+}
+
+/// Everything the user needs to review before signing a merchant transaction.
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransactionRequestPreview {
+    pub link: String,
+    pub label: Option<String>,
+    pub icon: Option<String>,
+    /// Message returned by the merchant together with the transaction.
+    pub message: Option<String>,
+    /// Base64 encoded transaction, passed back unchanged when approving.
+    pub transaction: String,
+    pub fee_payer: String,
+    /// Accounts the transaction may modify.
+    pub writable_accounts: Vec<String>,
+    pub instructions: Vec<InstructionSummary>,
+}
+
+/// Lifecycle of a merchant invoice.
+#[tsync]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InvoiceStatus {
+    /// No payment found yet.
+    Pending,
+    /// The full amount was received by the recipient.
+    Paid,
+    /// A payment was found but it is less than the invoiced amount.
+    Underpaid,
+    /// The invoice expired without any payment.
+    Expired,
+}
+
+/// An invoice created by the wallet acting as a point-of-sale.
+///
+/// The unique `reference` key is attached to the payment, which lets the
+/// wallet find the transaction on-chain without knowing the payer.
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Invoice {
+    /// Base58 reference public key, also used as the invoice id.
+    pub reference: String,
+    /// The network the payment is expected on.
+    pub network: Environment,
+    pub recipient: String,
+    /// Amount in user units.
+    pub amount: f64,
+    /// Mint address of the SPL token. `None` means native SOL.
+    pub spl_token: Option<String>,
+    pub label: Option<String>,
+    pub message: Option<String>,
+    pub memo: Option<String>,
+    /// Unix timestamp, in seconds.
+    pub created_at: i64,
+    /// Unix timestamp, in seconds.
+    pub expires_at: i64,
+    pub status: InvoiceStatus,
+    /// Amount received so far, in user units.
+    pub received_amount: f64,
+    /// Signatures of the transactions that paid this invoice.
+    pub signatures: Vec<String>,
+    /// Solana Pay URI to render as a QR code.
+    pub uri: String,
+}
+
+impl Invoice {
+    /// The transfer request to encode in the invoice QR code.
+    pub fn transfer_request(&self) -> TransferRequest {
+        TransferRequest {
+            recipient: self.recipient.clone(),
+            amount: Some(self.amount),
+            spl_token: self.spl_token.clone(),
+            reference: vec![self.reference.clone()],
+            label: self.label.clone(),
+            message: self.message.clone(),
+            memo: self.memo.clone(),
+        }
+    }
+
+    /// Whether the status can no longer change.
+    pub fn is_settled(&self, now: i64) -> bool {
+        self.status == InvoiceStatus::Paid || now >= self.expires_at
+    }
+}
@@ -0,0 +1,45 @@
+use {
+    serde::{Deserialize, Serialize},
+    tsync::tsync,
+};
+
+/// The balance of an account of the followed wallet changed.
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccountUpdate {
+    pub wallet: String,
+    /// The wallet itself for SOL, or one of its token accounts.
+    pub account: String,
+    pub lamports: u64,
+    /// Mint of a token account.
+    pub mint: Option<String>,
+    /// Balance of a token account, in UI units.
+    pub ui_amount: Option<f64>,
+}
+
+/// A transaction mentioning the followed wallet was confirmed.
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransactionUpdate {
+    pub wallet: String,
+    pub signature: String,
+    /// Why the transaction failed, if it did.
+    pub error: Option<String>,
+}
+
+/// A watched signature was confirmed, failed or expired.
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignatureUpdate {
+    pub signature: String,
+    /// Why the transaction did not succeed, if it did not.
+    pub error: Option<String>,
+}
+
+/// What the wallet subscriptions report.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WalletNotification {
+    Account(AccountUpdate),
+    Transaction(TransactionUpdate),
+    Signature(SignatureUpdate),
+}
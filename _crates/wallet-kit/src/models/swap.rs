@@ -87,6 +87,9 @@ pub struct SwapTransactionPayload {
     pub userPublicKey: String,
     pub dynamicComputeUnitLimit: bool,
     pub dynamicSlippage: bool,
+    /// Whether Jupiter wraps SOL paid and unwraps SOL received. Without it the
+    /// swap reads from and leaves wrapped SOL in the wSOL token account.
+    pub wrapAndUnwrapSol: bool,
     pub prioritizationFeeLamports: PrioritizationFeeLamports,
 }
 
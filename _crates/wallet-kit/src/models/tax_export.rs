@@ -0,0 +1,95 @@
+use {
+    crate::models::portfolio_history::CostBasisMethod,
+    serde::{Deserialize, Serialize},
+    smbcloud_wallet_core_model::models::currency::FiatCurrency,
+    tsync::tsync,
+};
+
+/// What a ledger entry records, from the wallet's point of view.
+#[tsync]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LedgerKind {
+    TransferIn,
+    TransferOut,
+    /// One token sent in exchange for another in the same transaction.
+    Swap,
+    /// Inflation reward credited to one of the wallet's stake accounts.
+    StakingReward,
+    /// The transaction fee paid to the network.
+    NetworkFee,
+    /// The fee paid to the treasury by `TreasuryFeeManager`.
+    PlatformFee,
+}
+
+/// The file formats a ledger can be exported to.
+#[tsync]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TaxExportFormat {
+    /// Koinly universal CSV.
+    Koinly,
+    /// CoinTracker CSV.
+    CoinTracker,
+    /// The ledger itself, as JSON.
+    Json,
+}
+
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LedgerAmount {
+    pub mint: String,
+    pub symbol: Option<String>,
+    pub amount: f64,
+}
+
+/// The part of an acquisition a disposal is matched against.
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LotAssignment {
+    /// Id of the entry that acquired the lot, `opening` for holdings from before
+    /// the export and `average` with the average cost method.
+    pub lot: String,
+    /// Unix timestamp of the acquisition, in seconds.
+    pub acquired_at: Option<i64>,
+    pub amount: f64,
+    /// `None` when the acquisition had no price.
+    pub cost_basis: Option<f64>,
+}
+
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LedgerEntry {
+    /// Unique within the ledger. Acquisitions open a lot with this id.
+    pub id: String,
+    /// `None` for staking rewards, which are not paid by a transaction.
+    pub signature: Option<String>,
+    /// Unix timestamp in seconds.
+    pub timestamp: i64,
+    pub kind: LedgerKind,
+    pub sent: Option<LedgerAmount>,
+    pub received: Option<LedgerAmount>,
+    pub description: Option<String>,
+    /// Market value of the entry at its time, `None` without a price.
+    pub value: Option<f64>,
+    /// Cost of what was sent, `None` when a lot it was taken from has no price.
+    pub cost_basis: Option<f64>,
+    /// `value - cost_basis` of what was sent.
+    pub gain: Option<f64>,
+    /// Lots the sent amount was taken from.
+    pub lots: Vec<LotAssignment>,
+    /// Whether the value or the cost basis is missing for lack of a price.
+    pub unpriced: bool,
+}
+
+/// Every taxable event of a wallet over a period, oldest first.
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaxLedger {
+    pub pubkey: String,
+    pub currency: FiatCurrency,
+    pub method: CostBasisMethod,
+    /// Unix timestamp in seconds.
+    pub from: i64,
+    /// Unix timestamp in seconds.
+    pub to: i64,
+    pub entries: Vec<LedgerEntry>,
+}
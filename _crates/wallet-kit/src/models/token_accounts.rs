@@ -0,0 +1,37 @@
+use {
+    serde::{Deserialize, Serialize},
+    tsync::tsync,
+};
+
+/// A token account with no balance left, whose rent can be reclaimed by closing it.
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmptyTokenAccount {
+    pub address: String,
+    pub mint: String,
+    /// SPL Token or Token-2022.
+    pub program_id: String,
+    /// SOL returned to the wallet when the account is closed.
+    pub rent: f64,
+}
+
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RentReclaim {
+    pub accounts: Vec<EmptyTokenAccount>,
+    /// SOL returned by closing every account.
+    pub total_rent: f64,
+}
+
+/// Outcome of closing empty token accounts.
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClosedTokenAccounts {
+    /// One signature per batch of closed accounts.
+    pub signatures: Vec<String>,
+    pub closed: Vec<String>,
+    /// Requested accounts that were no longer empty or could not be closed.
+    pub skipped: Vec<String>,
+    /// SOL returned to the wallet.
+    pub reclaimed: f64,
+}
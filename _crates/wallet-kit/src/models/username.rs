@@ -0,0 +1,55 @@
+use {
+    serde::{Deserialize, Serialize},
+    tsync::tsync,
+};
+
+/// A username claim signed by the wallet that owns it.
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsernameProof {
+    pub username: String,
+    pub pubkey: String,
+    /// The challenge issued by the directory, see `username::parse_proof_message`.
+    pub message: String,
+    /// Base58 signature of `message` by `pubkey`.
+    pub signature: String,
+    /// The directory's statement that the username is currently assigned to
+    /// `pubkey`. Set on the proofs the directory returns.
+    pub attestation: Option<UsernameAttestation>,
+}
+
+/// A time bounded statement signed by the username directory.
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsernameAttestation {
+    /// See `username::parse_attestation_message`.
+    pub message: String,
+    /// Base58 signature of `message` by the directory key.
+    pub signature: String,
+}
+
+/// The message the directory asks a wallet to sign to claim a username.
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsernameChallenge {
+    pub message: String,
+}
+
+#[tsync]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RecipientSource {
+    Address,
+    Username,
+    /// A Solana Name Service `.sol` domain.
+    Domain,
+}
+
+/// A send recipient resolved to an address, shown to the user before signing.
+#[tsync]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResolvedRecipient {
+    /// What the user typed, e.g. `@alice` or `alice.sol`.
+    pub input: String,
+    pub address: String,
+    pub source: RecipientSource,
+}
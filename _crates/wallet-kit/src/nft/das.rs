@@ -0,0 +1,377 @@
+//! Compressed NFTs through an indexer of the Metaplex Digital Asset Standard (DAS) API.
+//!
+//! Compressed NFTs only exist as merkle tree leaves, so listing them and
+//! proving ownership for a transfer needs an indexer. Most RPC providers serve
+//! the DAS API on their RPC endpoint.
+
+use {
+    crate::{
+        models::nft::{Nft, NftAttribute, NftCollection, NftKind},
+        nft::{metadata::JsonAttribute, NftError},
+    },
+    reqwest::{header::CONTENT_TYPE, Client},
+    serde::{de::DeserializeOwned, Deserialize},
+    serde_json::{json, Value},
+    std::future::Future,
+};
+
+/// Assets requested per page, the maximum of the DAS API.
+const PAGE_LIMIT: u32 = 1000;
+
+/// Grouping key of the collection of an asset.
+const COLLECTION_GROUP: &str = "collection";
+
+/// The DAS API methods the wallet uses.
+pub trait DasProvider {
+    /// A page of the assets held by `owner`, starting at page 1.
+    fn get_assets_by_owner(
+        &self,
+        owner: &str,
+        page: u32,
+    ) -> impl Future<Output = Result<DasAssetPage, NftError>> + Send;
+
+    fn get_asset(&self, id: &str) -> impl Future<Output = Result<DasAsset, NftError>> + Send;
+
+    /// The merkle proof of the leaf of a compressed asset.
+    fn get_asset_proof(
+        &self,
+        id: &str,
+    ) -> impl Future<Output = Result<DasAssetProof, NftError>> + Send;
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DasAssetPage {
+    pub total: u32,
+    pub limit: u32,
+    pub page: u32,
+    pub items: Vec<DasAsset>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DasAsset {
+    pub id: String,
+    #[serde(default)]
+    pub content: Option<DasContent>,
+    #[serde(default)]
+    pub grouping: Vec<DasGroup>,
+    #[serde(default)]
+    pub compression: Option<DasCompression>,
+    pub ownership: DasOwnership,
+    #[serde(default)]
+    pub burnt: bool,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DasContent {
+    #[serde(default)]
+    pub json_uri: String,
+    #[serde(default)]
+    pub metadata: DasMetadata,
+    #[serde(default)]
+    pub links: Option<DasLinks>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DasMetadata {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub symbol: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub attributes: Vec<JsonAttribute>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DasLinks {
+    #[serde(default)]
+    pub image: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DasGroup {
+    pub group_key: String,
+    pub group_value: String,
+    #[serde(default)]
+    pub collection_metadata: Option<DasCollectionMetadata>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DasCollectionMetadata {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub image: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DasCompression {
+    pub compressed: bool,
+    #[serde(default)]
+    pub tree: String,
+    #[serde(default)]
+    pub leaf_id: u64,
+    #[serde(default)]
+    pub data_hash: String,
+    #[serde(default)]
+    pub creator_hash: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DasOwnership {
+    pub owner: String,
+    #[serde(default)]
+    pub delegate: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DasAssetProof {
+    pub root: String,
+    pub proof: Vec<String>,
+    pub node_index: u64,
+    pub tree_id: String,
+}
+
+#[derive(Deserialize)]
+struct JsonRpcResponse<T> {
+    result: Option<T>,
+    error: Option<JsonRpcError>,
+}
+
+#[derive(Deserialize)]
+struct JsonRpcError {
+    code: i64,
+    message: String,
+}
+
+/// A DAS API served over JSON-RPC.
+#[derive(Debug, Clone)]
+pub struct DasClient {
+    url: String,
+    client: Client,
+}
+
+impl DasClient {
+    pub fn new(url: String) -> Self {
+        DasClient {
+            url,
+            client: Client::new(),
+        }
+    }
+
+    async fn call<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<T, NftError> {
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": method,
+            "method": method,
+            "params": params,
+        });
+        let response = self
+            .client
+            .post(&self.url)
+            .header(CONTENT_TYPE, "application/json")
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| NftError::DasError(e.to_string()))?
+            .json::<JsonRpcResponse<T>>()
+            .await
+            .map_err(|e| NftError::DasError(e.to_string()))?;
+
+        match (response.result, response.error) {
+            (_, Some(error)) => Err(NftError::DasError(format!(
+                "{} ({})",
+                error.message, error.code
+            ))),
+            (Some(result), None) => Ok(result),
+            (None, None) => Err(NftError::DasError(format!("Empty response to {}", method))),
+        }
+    }
+}
+
+impl DasProvider for DasClient {
+    async fn get_assets_by_owner(&self, owner: &str, page: u32) -> Result<DasAssetPage, NftError> {
+        let params = json!({
+            "ownerAddress": owner,
+            "page": page,
+            "limit": PAGE_LIMIT,
+            "displayOptions": { "showCollectionMetadata": true },
+        });
+        self.call("getAssetsByOwner", params).await
+    }
+
+    async fn get_asset(&self, id: &str) -> Result<DasAsset, NftError> {
+        self.call("getAsset", json!({ "id": id })).await
+    }
+
+    async fn get_asset_proof(&self, id: &str) -> Result<DasAssetProof, NftError> {
+        self.call("getAssetProof", json!({ "id": id })).await
+    }
+}
+
+/// Compressed NFTs held by `owner`, one collection per NFT.
+///
+/// Pass the result to `group_by_collection` to merge it with other holdings.
+pub async fn get_compressed_nfts(
+    das: &impl DasProvider,
+    owner: &str,
+) -> Result<Vec<NftCollection>, NftError> {
+    let mut collections = Vec::new();
+    let mut page = 1;
+    loop {
+        let assets = das.get_assets_by_owner(owner, page).await?;
+        let last_page = assets.items.len() < assets.limit.max(1) as usize;
+        collections.extend(assets.items.into_iter().filter_map(compressed_nft));
+        if last_page {
+            return Ok(collections);
+        }
+        page += 1;
+    }
+}
+
+/// The collection and compressed NFT of `asset`, `None` for other assets.
+fn compressed_nft(asset: DasAsset) -> Option<NftCollection> {
+    let compressed = asset
+        .compression
+        .as_ref()
+        .is_some_and(|compression| compression.compressed);
+    if !compressed || asset.burnt {
+        return None;
+    }
+    let group = asset
+        .grouping
+        .into_iter()
+        .find(|group| group.group_key == COLLECTION_GROUP);
+    let content = asset.content.unwrap_or_default();
+    let nft = Nft {
+        id: asset.id,
+        kind: NftKind::Compressed,
+        name: content.metadata.name,
+        symbol: content.metadata.symbol,
+        description: content.metadata.description,
+        image: content.links.and_then(|links| links.image),
+        attributes: content
+            .metadata
+            .attributes
+            .into_iter()
+            .map(NftAttribute::from)
+            .collect(),
+        uri: content.json_uri,
+    };
+    let (address, collection_metadata) = match group {
+        Some(group) => (Some(group.group_value), group.collection_metadata),
+        None => (None, None),
+    };
+    let (name, image) = match collection_metadata {
+        Some(metadata) => (metadata.name, metadata.image),
+        None => (None, None),
+    };
+    Some(NftCollection {
+        address,
+        name: name.filter(|name| !name.is_empty()),
+        image: image.filter(|image| !image.is_empty()),
+        nfts: vec![nft],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asset(id: &str, compressed: bool) -> Value {
+        json!({
+            "interface": "V1_NFT",
+            "id": id,
+            "content": {
+                "json_uri": "https://example.com/1.json",
+                "metadata": {
+                    "name": "Drip #1",
+                    "symbol": "DRIP",
+                    "attributes": [{ "trait_type": "Artist", "value": "Someone" }]
+                },
+                "links": { "image": "https://example.com/1.png" }
+            },
+            "grouping": [{
+                "group_key": "collection",
+                "group_value": "Collection1111111111111111111111111111111111",
+                "collection_metadata": { "name": "Drip", "image": "" }
+            }],
+            "compression": {
+                "compressed": compressed,
+                "tree": "Tree111111111111111111111111111111111111111",
+                "leaf_id": 7,
+                "data_hash": "",
+                "creator_hash": ""
+            },
+            "ownership": { "owner": "Owner11111111111111111111111111111111111111" },
+            "burnt": false
+        })
+    }
+
+    struct PagedDas {
+        pages: Vec<Vec<Value>>,
+        limit: u32,
+    }
+
+    impl DasProvider for PagedDas {
+        async fn get_assets_by_owner(
+            &self,
+            _owner: &str,
+            page: u32,
+        ) -> Result<DasAssetPage, NftError> {
+            let items = self
+                .pages
+                .get(page as usize - 1)
+                .cloned()
+                .unwrap_or_default();
+            Ok(DasAssetPage {
+                total: items.len() as u32,
+                limit: self.limit,
+                page,
+                items: serde_json::from_value(Value::Array(items)).unwrap(),
+            })
+        }
+
+        async fn get_asset(&self, id: &str) -> Result<DasAsset, NftError> {
+            Err(NftError::NftNotFound(id.to_string()))
+        }
+
+        async fn get_asset_proof(&self, id: &str) -> Result<DasAssetProof, NftError> {
+            Err(NftError::NftNotFound(id.to_string()))
+        }
+    }
+
+    #[test]
+    fn test_compressed_nft() {
+        let compressed: DasAsset = serde_json::from_value(asset("cnft", true)).unwrap();
+        let collection = compressed_nft(compressed).unwrap();
+        assert_eq!(collection.name.as_deref(), Some("Drip"));
+        // Empty images are treated as missing.
+        assert_eq!(collection.image, None);
+        let nft = &collection.nfts[0];
+        assert_eq!(nft.kind, NftKind::Compressed);
+        assert_eq!(nft.image.as_deref(), Some("https://example.com/1.png"));
+        assert_eq!(nft.attributes[0].value, "Someone");
+
+        let uncompressed: DasAsset = serde_json::from_value(asset("nft", false)).unwrap();
+        assert_eq!(compressed_nft(uncompressed), None);
+    }
+
+    #[tokio::test]
+    async fn test_get_compressed_nfts_pages() {
+        let das = PagedDas {
+            pages: vec![
+                vec![asset("a", true), asset("b", false)],
+                vec![asset("c", true)],
+            ],
+            limit: 2,
+        };
+        let collections = get_compressed_nfts(&das, "owner").await.unwrap();
+        let ids = collections
+            .iter()
+            .map(|collection| collection.nfts[0].id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec!["a", "c"]);
+    }
+}
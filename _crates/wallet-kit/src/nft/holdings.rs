@@ -0,0 +1,295 @@
+use {
+    crate::{
+        models::nft::{Nft, NftAttribute, NftCollection, NftKind},
+        nft::{
+            group_by_collection,
+            metadata::{
+                edition_key, fetch_json_metadata, metadata_key, parse_nft_metadata,
+                token_metadata_program_id, NftMetadata,
+            },
+            NftError,
+        },
+        rpc::parse_address,
+    },
+    log::debug,
+    solana_account_decoder::{parse_token::UiTokenAccount, UiAccountData},
+    solana_client::{nonblocking::rpc_client::RpcClient, rpc_request::TokenAccountsFilter},
+    solana_sdk::{account::Account, pubkey::Pubkey},
+    spl_token_2022::{extension::StateWithExtensions, state::Mint},
+    std::collections::HashMap,
+};
+
+/// Accounts read per `getMultipleAccounts` call, the maximum of the RPC API.
+const MULTIPLE_ACCOUNTS_LIMIT: usize = 100;
+
+/// Metaplex NFTs held by `owner`, grouped by collection.
+///
+/// An NFT is a token account holding the single token of a mint with no
+/// decimals, whose mint has a metadata and an edition account. Compressed
+/// NFTs are listed with `das::get_compressed_nfts`.
+pub async fn get_nfts(
+    rpc_client: &RpcClient,
+    owner: String,
+) -> Result<Vec<NftCollection>, NftError> {
+    let owner = parse_address(&owner)?;
+
+    let candidates = single_token_mints(rpc_client, &owner).await?;
+    let mut nfts: Vec<NftMetadata> = Vec::new();
+    for chunk in candidates.chunks(MULTIPLE_ACCOUNTS_LIMIT / 3) {
+        let keys = chunk
+            .iter()
+            .flat_map(|mint| [*mint, metadata_key(mint), edition_key(mint)])
+            .collect::<Vec<_>>();
+        let accounts = get_multiple_accounts(rpc_client, &keys).await?;
+        for (mint, accounts) in chunk.iter().zip(accounts.chunks(3)) {
+            match nft_metadata(
+                accounts[0].as_ref(),
+                accounts[1].as_ref(),
+                accounts[2].as_ref(),
+            ) {
+                Some(metadata) => nfts.push(metadata),
+                None => debug!("{} is not an NFT", mint),
+            }
+        }
+    }
+
+    // Collection NFTs provide the name of their collection.
+    let mut collection_mints = nfts
+        .iter()
+        .filter_map(NftMetadata::verified_collection)
+        .collect::<Vec<_>>();
+    collection_mints.sort();
+    collection_mints.dedup();
+    let mut collections: HashMap<Pubkey, NftMetadata> = HashMap::new();
+    for chunk in collection_mints.chunks(MULTIPLE_ACCOUNTS_LIMIT) {
+        let keys = chunk.iter().map(metadata_key).collect::<Vec<_>>();
+        let accounts = get_multiple_accounts(rpc_client, &keys).await?;
+        for account in accounts.into_iter().flatten() {
+            if let Some(metadata) = parse_nft_metadata(&account.data) {
+                collections.insert(metadata.mint, metadata);
+            }
+        }
+    }
+
+    let collection_uris = collection_mints
+        .iter()
+        .map(|mint| {
+            collections
+                .get(mint)
+                .map(|metadata| metadata.uri.clone())
+                .unwrap_or_default()
+        })
+        .collect();
+    let collection_images = collection_mints
+        .iter()
+        .zip(fetch_json_metadata(collection_uris).await)
+        .map(|(mint, json)| (*mint, json.image))
+        .collect::<HashMap<_, _>>();
+
+    let uris = nfts.iter().map(|nft| nft.uri.clone()).collect();
+    let json = fetch_json_metadata(uris).await;
+    let grouped = nfts.into_iter().zip(json).map(|(metadata, json)| {
+        let address = metadata.verified_collection();
+        NftCollection {
+            address: address.map(|mint| mint.to_string()),
+            name: address
+                .and_then(|mint| collections.get(&mint))
+                .map(|collection| collection.name.clone()),
+            image: address.and_then(|mint| collection_images.get(&mint).cloned().flatten()),
+            nfts: vec![Nft {
+                id: metadata.mint.to_string(),
+                kind: if metadata.is_programmable() {
+                    NftKind::Programmable
+                } else {
+                    NftKind::Standard
+                },
+                name: metadata.name,
+                symbol: metadata.symbol,
+                description: json.description,
+                image: json.image,
+                attributes: json
+                    .attributes
+                    .into_iter()
+                    .map(NftAttribute::from)
+                    .collect(),
+                uri: metadata.uri,
+            }],
+        }
+    });
+    Ok(group_by_collection(grouped))
+}
+
+/// Mints of which `owner` holds exactly one token without decimals, in
+/// either token program.
+async fn single_token_mints(
+    rpc_client: &RpcClient,
+    owner: &Pubkey,
+) -> Result<Vec<Pubkey>, NftError> {
+    let mut mints = Vec::new();
+    for program_id in [spl_token::id(), spl_token_2022::id()] {
+        let token_accounts = rpc_client
+            .get_token_accounts_by_owner(owner, TokenAccountsFilter::ProgramId(program_id))
+            .await
+            .map_err(|e| NftError::ConnectionError(e.to_string()))?;
+        mints.extend(token_accounts.iter().filter_map(|keyed_account| {
+            let UiAccountData::Json(parsed) = &keyed_account.account.data else {
+                return None;
+            };
+            single_token_mint(&parsed.parsed["info"])
+        }));
+    }
+    Ok(mints)
+}
+
+/// The mint of a parsed token account holding exactly one token without decimals.
+fn single_token_mint(info: &serde_json::Value) -> Option<Pubkey> {
+    let account = serde_json::from_value::<UiTokenAccount>(info.clone()).ok()?;
+    let single = account.token_amount.amount == "1" && account.token_amount.decimals == 0;
+    single.then(|| account.mint.parse().ok()).flatten()
+}
+
+async fn get_multiple_accounts(
+    rpc_client: &RpcClient,
+    keys: &[Pubkey],
+) -> Result<Vec<Option<Account>>, NftError> {
+    rpc_client
+        .get_multiple_accounts(keys)
+        .await
+        .map_err(|e| NftError::ConnectionError(e.to_string()))
+}
+
+/// The metadata of an NFT from its mint, metadata and edition accounts.
+pub(crate) fn nft_metadata(
+    mint: Option<&Account>,
+    metadata: Option<&Account>,
+    edition: Option<&Account>,
+) -> Option<NftMetadata> {
+    let mint = mint?;
+    if mint.owner != spl_token::id() && mint.owner != spl_token_2022::id() {
+        return None;
+    }
+    let mint = StateWithExtensions::<Mint>::unpack(&mint.data).ok()?.base;
+    if mint.supply != 1 || mint.decimals != 0 {
+        return None;
+    }
+    if edition?.owner != token_metadata_program_id() {
+        return None;
+    }
+    let metadata = parse_nft_metadata(&metadata?.data)?;
+    if metadata
+        .token_standard
+        .is_some_and(|standard| standard.is_fungible())
+    {
+        return None;
+    }
+    Some(metadata)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        serde_json::json,
+        smbcloud_wallet_core_rpc::token_metadata::{
+            fixtures::borsh_string, METAPLEX_KEY_METADATA_V1,
+        },
+        solana_sdk::{program_option::COption, program_pack::Pack},
+    };
+
+    fn account(owner: Pubkey, data: Vec<u8>) -> Account {
+        Account {
+            lamports: 1,
+            data,
+            owner,
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    fn mint(supply: u64, decimals: u8) -> Account {
+        let mut data = vec![0; Mint::LEN];
+        Mint::pack(
+            Mint {
+                mint_authority: COption::None,
+                supply,
+                decimals,
+                is_initialized: true,
+                freeze_authority: COption::None,
+            },
+            &mut data,
+        )
+        .unwrap();
+        account(spl_token::id(), data)
+    }
+
+    fn metadata(mint: &Pubkey) -> Account {
+        let mut data = vec![METAPLEX_KEY_METADATA_V1];
+        data.extend_from_slice(&[0; 32]);
+        data.extend_from_slice(mint.as_ref());
+        data.extend(borsh_string("Mad Lad #1", 32));
+        data.extend(borsh_string("MAD", 10));
+        data.extend(borsh_string("", 200));
+        data.resize(679, 0);
+        account(token_metadata_program_id(), data)
+    }
+
+    #[test]
+    fn test_nft_metadata() {
+        let key = Pubkey::new_unique();
+        let edition = account(token_metadata_program_id(), vec![6]);
+        let nft = nft_metadata(Some(&mint(1, 0)), Some(&metadata(&key)), Some(&edition)).unwrap();
+        assert_eq!(nft.mint, key);
+        assert_eq!(nft.name, "Mad Lad #1");
+
+        // Fungible tokens, semi fungible tokens and mints without an edition are not NFTs.
+        let metadata = metadata(&key);
+        assert_eq!(
+            nft_metadata(Some(&mint(1, 6)), Some(&metadata), Some(&edition)),
+            None
+        );
+        assert_eq!(
+            nft_metadata(Some(&mint(5, 0)), Some(&metadata), Some(&edition)),
+            None
+        );
+        assert_eq!(nft_metadata(Some(&mint(1, 0)), Some(&metadata), None), None);
+        let forged_edition = account(Pubkey::new_unique(), vec![6]);
+        assert_eq!(
+            nft_metadata(Some(&mint(1, 0)), Some(&metadata), Some(&forged_edition)),
+            None
+        );
+        let mut forged_mint = mint(1, 0);
+        forged_mint.owner = Pubkey::new_unique();
+        assert_eq!(
+            nft_metadata(Some(&forged_mint), Some(&metadata), Some(&edition)),
+            None
+        );
+
+        // Token-2022 mints are NFTs too.
+        let mut token_2022_mint = mint(1, 0);
+        token_2022_mint.owner = spl_token_2022::id();
+        assert!(nft_metadata(Some(&token_2022_mint), Some(&metadata), Some(&edition)).is_some());
+    }
+
+    #[test]
+    fn test_single_token_mint() {
+        let mint = Pubkey::new_unique();
+        let info = |amount: &str, decimals: u8| {
+            json!({
+                "mint": mint.to_string(),
+                "owner": Pubkey::new_unique().to_string(),
+                "tokenAmount": {
+                    "amount": amount,
+                    "decimals": decimals,
+                    "uiAmount": 1.0,
+                    "uiAmountString": "1"
+                },
+                "state": "initialized",
+                "isNative": false
+            })
+        };
+        assert_eq!(single_token_mint(&info("1", 0)), Some(mint));
+        assert_eq!(single_token_mint(&info("2", 0)), None);
+        assert_eq!(single_token_mint(&info("1", 6)), None);
+        assert_eq!(single_token_mint(&json!({})), None);
+    }
+}
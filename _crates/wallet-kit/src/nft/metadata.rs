@@ -0,0 +1,430 @@
+//! Metaplex and account compression layouts, and the off-chain JSON metadata of NFTs.
+
+use {
+    crate::models::nft::NftAttribute,
+    borsh::BorshDeserialize,
+    futures::stream::{self, StreamExt},
+    log::debug,
+    reqwest::Client,
+    serde::Deserialize,
+    serde_json::Value,
+    smbcloud_wallet_constants::constants::METAPLEX_BUBBLEGUM_PROGRAM_ID,
+    smbcloud_wallet_core_rpc::token_metadata::{
+        metaplex_metadata_key, metaplex_program_id, parse_metaplex_account,
+    },
+    solana_address::Address,
+    solana_sdk::pubkey::Pubkey,
+    std::{io, str::FromStr, sync::LazyLock, time::Duration},
+};
+
+/// How long fetching the JSON metadata of one NFT may take.
+const JSON_METADATA_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Most JSON metadata fetched at once.
+const JSON_METADATA_CONCURRENCY: usize = 8;
+
+/// Client of the hosts serving JSON metadata, shared by every listing.
+static JSON_METADATA_CLIENT: LazyLock<Client> = LazyLock::new(|| {
+    Client::builder()
+        .timeout(JSON_METADATA_TIMEOUT)
+        .build()
+        .unwrap_or_default()
+});
+
+/// Size of the header of a concurrent merkle tree account.
+const MERKLE_TREE_HEADER_LEN: usize = 56;
+
+/// Token standard of a Metaplex metadata account.
+#[derive(BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenStandard {
+    NonFungible,
+    FungibleAsset,
+    Fungible,
+    NonFungibleEdition,
+    ProgrammableNonFungible,
+    ProgrammableNonFungibleEdition,
+}
+
+impl TokenStandard {
+    pub fn is_programmable(self) -> bool {
+        matches!(
+            self,
+            TokenStandard::ProgrammableNonFungible | TokenStandard::ProgrammableNonFungibleEdition
+        )
+    }
+
+    pub fn is_fungible(self) -> bool {
+        matches!(self, TokenStandard::FungibleAsset | TokenStandard::Fungible)
+    }
+}
+
+#[derive(BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Collection {
+    pub verified: bool,
+    pub key: Pubkey,
+}
+
+#[derive(BorshDeserialize)]
+struct Creator {
+    _address: Pubkey,
+    _verified: bool,
+    _share: u8,
+}
+
+#[derive(BorshDeserialize)]
+struct Uses {
+    _use_method: u8,
+    _remaining: u64,
+    _total: u64,
+}
+
+#[derive(BorshDeserialize)]
+enum CollectionDetails {
+    V1 { _size: u64 },
+    V2 { _padding: [u8; 8] },
+}
+
+#[derive(BorshDeserialize)]
+enum ProgrammableConfig {
+    V1 { rule_set: Option<Pubkey> },
+}
+
+/// The fields of a Metaplex metadata account the wallet uses.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NftMetadata {
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub token_standard: Option<TokenStandard>,
+    pub collection: Option<Collection>,
+    /// Token Auth Rules rule set of a programmable NFT.
+    pub rule_set: Option<Pubkey>,
+}
+
+impl NftMetadata {
+    /// The collection, if the collection authority verified the NFT belongs to it.
+    pub fn verified_collection(&self) -> Option<Pubkey> {
+        self.collection
+            .filter(|collection| collection.verified)
+            .map(|collection| collection.key)
+    }
+
+    pub fn is_programmable(&self) -> bool {
+        self.token_standard
+            .is_some_and(|standard| standard.is_programmable())
+    }
+}
+
+pub fn token_metadata_program_id() -> Pubkey {
+    Pubkey::new_from_array(metaplex_program_id().to_bytes())
+}
+
+/// `account`, or the Token Metadata program id, which its instructions take in
+/// place of a missing optional account.
+pub(crate) fn optional_account(account: Option<Pubkey>) -> Pubkey {
+    account.unwrap_or_else(token_metadata_program_id)
+}
+
+pub fn bubblegum_program_id() -> Pubkey {
+    Pubkey::from_str(METAPLEX_BUBBLEGUM_PROGRAM_ID).expect("Invalid bubblegum program id")
+}
+
+/// Address of the metadata account of `mint`.
+pub fn metadata_key(mint: &Pubkey) -> Pubkey {
+    let mint = Address::new_from_array(mint.to_bytes());
+    Pubkey::new_from_array(metaplex_metadata_key(&mint).to_bytes())
+}
+
+/// Address of the master or print edition account of `mint`.
+pub fn edition_key(mint: &Pubkey) -> Pubkey {
+    let program_id = token_metadata_program_id();
+    let (pubkey, _) = Pubkey::find_program_address(
+        &[b"metadata", program_id.as_ref(), mint.as_ref(), b"edition"],
+        &program_id,
+    );
+    pubkey
+}
+
+/// Address of the token record of a programmable NFT held in `token_account`.
+pub fn token_record_key(mint: &Pubkey, token_account: &Pubkey) -> Pubkey {
+    let program_id = token_metadata_program_id();
+    let (pubkey, _) = Pubkey::find_program_address(
+        &[
+            b"metadata",
+            program_id.as_ref(),
+            mint.as_ref(),
+            b"token_record",
+            token_account.as_ref(),
+        ],
+        &program_id,
+    );
+    pubkey
+}
+
+/// Address of the Bubblegum configuration of `merkle_tree`.
+pub fn tree_config_key(merkle_tree: &Pubkey) -> Pubkey {
+    let (pubkey, _) =
+        Pubkey::find_program_address(&[merkle_tree.as_ref()], &bubblegum_program_id());
+    pubkey
+}
+
+/// A Metaplex metadata account.
+///
+/// Accounts created before a field was added end early or are zero padded,
+/// the missing fields are then `None`.
+pub fn parse_nft_metadata(data: &[u8]) -> Option<NftMetadata> {
+    let account = parse_metaplex_account(data)?;
+    let buf = &mut &account.rest[..];
+    let _seller_fee_basis_points: u16 = BorshDeserialize::deserialize(buf).ok()?;
+    let _creators: Option<Vec<Creator>> = BorshDeserialize::deserialize(buf).ok()?;
+    let _primary_sale_happened: bool = BorshDeserialize::deserialize(buf).ok()?;
+    let _is_mutable: bool = BorshDeserialize::deserialize(buf).ok()?;
+    let (token_standard, collection, rule_set) = optional_fields(buf).unwrap_or_default();
+
+    Some(NftMetadata {
+        mint: Pubkey::new_from_array(account.mint.to_bytes()),
+        name: account.metadata.name,
+        symbol: account.metadata.symbol,
+        uri: account.metadata.uri,
+        token_standard,
+        collection,
+        rule_set,
+    })
+}
+
+type OptionalFields = (Option<TokenStandard>, Option<Collection>, Option<Pubkey>);
+
+fn optional_fields(buf: &mut &[u8]) -> io::Result<OptionalFields> {
+    let _edition_nonce: Option<u8> = BorshDeserialize::deserialize(buf)?;
+    let token_standard: Option<TokenStandard> = BorshDeserialize::deserialize(buf)?;
+    let collection: Option<Collection> = BorshDeserialize::deserialize(buf)?;
+    let _uses: Option<Uses> = BorshDeserialize::deserialize(buf)?;
+    let _collection_details: Option<CollectionDetails> = BorshDeserialize::deserialize(buf)?;
+    let rule_set = match BorshDeserialize::deserialize(buf) {
+        Ok(Some(ProgrammableConfig::V1 { rule_set })) => rule_set,
+        _ => None,
+    };
+    Ok((token_standard, collection, rule_set))
+}
+
+/// Depth of the canopy cached at the end of a concurrent merkle tree account.
+///
+/// Proof nodes covered by the canopy are left out of Bubblegum instructions.
+pub fn canopy_depth(tree_data: &[u8]) -> Option<usize> {
+    let header = tree_data.get(..MERKLE_TREE_HEADER_LEN)?;
+    let max_buffer_size = u32::from_le_bytes(header[2..6].try_into().ok()?) as usize;
+    let max_depth = u32::from_le_bytes(header[6..10].try_into().ok()?) as usize;
+
+    // Sequence number, active index and buffer size, the change log buffer and
+    // the rightmost proof. Change logs and the proof have the same size.
+    let path_len = 40 + 32 * max_depth;
+    let tree_len = 24 + (max_buffer_size + 1) * path_len;
+    let canopy_len = tree_data
+        .len()
+        .checked_sub(MERKLE_TREE_HEADER_LEN + tree_len)?;
+    // A canopy of depth `d` caches the `2^(d + 1) - 2` nodes above the proof.
+    let canopy_nodes = canopy_len / 32;
+    Some((canopy_nodes + 2).ilog2() as usize - 1)
+}
+
+/// Attribute of an NFT as found in JSON metadata. Values are strings or numbers.
+#[derive(Debug, Clone, Deserialize)]
+pub struct JsonAttribute {
+    #[serde(default)]
+    pub trait_type: Option<String>,
+    #[serde(default)]
+    pub value: Value,
+}
+
+impl From<JsonAttribute> for NftAttribute {
+    fn from(attribute: JsonAttribute) -> Self {
+        let value = match attribute.value {
+            Value::String(value) => value,
+            Value::Null => String::new(),
+            value => value.to_string(),
+        };
+        NftAttribute {
+            trait_type: attribute.trait_type.unwrap_or_default(),
+            value,
+        }
+    }
+}
+
+/// Off-chain JSON metadata of an NFT.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct JsonMetadata {
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub image: Option<String>,
+    #[serde(default)]
+    pub attributes: Vec<JsonAttribute>,
+}
+
+/// Fetch the JSON metadata at each of `uris`, in the same order, a few at a
+/// time.
+///
+/// Metadata that cannot be fetched in time is left empty rather than failing
+/// the listing.
+pub async fn fetch_json_metadata(uris: Vec<String>) -> Vec<JsonMetadata> {
+    stream::iter(uris)
+        .map(|uri| async move {
+            fetch_one(&JSON_METADATA_CLIENT, &uri)
+                .await
+                .unwrap_or_default()
+        })
+        .buffered(JSON_METADATA_CONCURRENCY)
+        .collect()
+        .await
+}
+
+async fn fetch_one(client: &Client, uri: &str) -> Option<JsonMetadata> {
+    if !uri.starts_with("https://") && !uri.starts_with("http://") {
+        return None;
+    }
+    let response = client
+        .get(uri)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status());
+    match response {
+        Ok(response) => response
+            .json()
+            .await
+            .inspect_err(|e| debug!("Invalid NFT metadata at {}: {}", uri, e))
+            .ok(),
+        Err(e) => {
+            debug!("Failed to fetch NFT metadata at {}: {}", uri, e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        serde_json::json,
+        smbcloud_wallet_core_rpc::token_metadata::{
+            fixtures::borsh_string, METAPLEX_KEY_METADATA_V1,
+        },
+    };
+
+    fn metadata_fixture(mint: &Pubkey) -> Vec<u8> {
+        let mut data = vec![METAPLEX_KEY_METADATA_V1];
+        data.extend_from_slice(&[0; 32]);
+        data.extend_from_slice(mint.as_ref());
+        data.extend(borsh_string("Mad Lad #1", 32));
+        data.extend(borsh_string("MAD", 10));
+        data.extend(borsh_string("https://example.com/1.json", 200));
+        // Royalties and one creator.
+        data.extend_from_slice(&500u16.to_le_bytes());
+        data.extend_from_slice(&[1, 1, 0, 0, 0]);
+        data.extend_from_slice(&[0; 32]);
+        data.extend_from_slice(&[1, 100]);
+        // Primary sale happened, mutable, no edition nonce.
+        data.extend_from_slice(&[1, 1, 0]);
+        data
+    }
+
+    #[test]
+    fn test_parse_legacy_nft_metadata() {
+        let mint = Pubkey::new_unique();
+        let mut data = metadata_fixture(&mint);
+        // Legacy accounts are zero padded to their fixed size.
+        data.resize(679, 0);
+
+        let metadata = parse_nft_metadata(&data).unwrap();
+        assert_eq!(metadata.mint, mint);
+        assert_eq!(metadata.name, "Mad Lad #1");
+        assert_eq!(metadata.symbol, "MAD");
+        assert_eq!(metadata.uri, "https://example.com/1.json");
+        assert_eq!(metadata.token_standard, None);
+        assert_eq!(metadata.verified_collection(), None);
+        assert!(!metadata.is_programmable());
+        // An account that ends before the edition nonce.
+        let fixture = metadata_fixture(&mint);
+        assert!(parse_nft_metadata(&fixture[..fixture.len() - 1]).is_some());
+        assert_eq!(parse_nft_metadata(&data[..100]), None);
+    }
+
+    #[test]
+    fn test_parse_programmable_nft_metadata() {
+        let mint = Pubkey::new_unique();
+        let collection = Pubkey::new_unique();
+        let rule_set = Pubkey::new_unique();
+        let mut data = metadata_fixture(&mint);
+        // Programmable non fungible, in a verified collection.
+        data.extend_from_slice(&[1, 4, 1, 1]);
+        data.extend_from_slice(collection.as_ref());
+        // No uses, sized collection details would only be set on the collection NFT.
+        data.extend_from_slice(&[0, 0]);
+        data.extend_from_slice(&[1, 0, 1]);
+        data.extend_from_slice(rule_set.as_ref());
+
+        let metadata = parse_nft_metadata(&data).unwrap();
+        assert_eq!(
+            metadata.token_standard,
+            Some(TokenStandard::ProgrammableNonFungible)
+        );
+        assert!(metadata.is_programmable());
+        assert_eq!(metadata.verified_collection(), Some(collection));
+        assert_eq!(metadata.rule_set, Some(rule_set));
+    }
+
+    #[test]
+    fn test_pdas() {
+        let mint = Pubkey::from_str("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v").unwrap();
+        assert_eq!(
+            metadata_key(&mint).to_string(),
+            "5x38Kp4hvdomTCnCrAny4UtMUt5rQBdB6px2K1Ui45Wq"
+        );
+        let token_account = Pubkey::new_unique();
+        assert_ne!(edition_key(&mint), metadata_key(&mint));
+        assert_ne!(
+            token_record_key(&mint, &token_account),
+            token_record_key(&mint, &Pubkey::new_unique())
+        );
+    }
+
+    #[test]
+    fn test_canopy_depth() {
+        fn tree(max_depth: usize, max_buffer_size: usize, canopy_depth: usize) -> Vec<u8> {
+            let mut data = vec![0; MERKLE_TREE_HEADER_LEN];
+            data[2..6].copy_from_slice(&(max_buffer_size as u32).to_le_bytes());
+            data[6..10].copy_from_slice(&(max_depth as u32).to_le_bytes());
+            let tree_len = 24 + (max_buffer_size + 1) * (40 + 32 * max_depth);
+            let canopy_len = ((1 << (canopy_depth + 1)) - 2) * 32;
+            data.resize(data.len() + tree_len + canopy_len, 0);
+            data
+        }
+
+        assert_eq!(canopy_depth(&tree(14, 64, 0)), Some(0));
+        assert_eq!(canopy_depth(&tree(14, 64, 11)), Some(11));
+        assert_eq!(canopy_depth(&tree(20, 1024, 17)), Some(17));
+        assert_eq!(canopy_depth(&tree(14, 64, 0)[..100]), None);
+    }
+
+    #[test]
+    fn test_json_attributes() {
+        let metadata: JsonMetadata = serde_json::from_value(json!({
+            "name": "Mad Lad #1",
+            "image": "https://example.com/1.png",
+            "attributes": [
+                { "trait_type": "Background", "value": "Blue" },
+                { "trait_type": "Level", "value": 3 },
+                { "value": "Untyped" }
+            ]
+        }))
+        .unwrap();
+        let attributes = metadata
+            .attributes
+            .into_iter()
+            .map(NftAttribute::from)
+            .collect::<Vec<_>>();
+        assert_eq!(attributes[1].value, "3");
+        assert_eq!(attributes[2].trait_type, "");
+        assert_eq!(metadata.image.as_deref(), Some("https://example.com/1.png"));
+    }
+}
@@ -0,0 +1,137 @@
+use {
+    crate::{models::nft::NftCollection, rpc::RpcError},
+    std::collections::HashMap,
+    thiserror::Error,
+};
+
+pub mod das;
+pub mod holdings;
+pub mod metadata;
+pub mod transfer;
+
+#[derive(Error, Debug)]
+pub enum NftError {
+    #[error("Failed to connect to RPC: {0}")]
+    ConnectionError(String),
+
+    #[error("DAS request failed: {0}")]
+    DasError(String),
+
+    #[error("Invalid address: {0}")]
+    InvalidAddress(String),
+
+    #[error("NFT not found: {0}")]
+    NftNotFound(String),
+
+    #[error("NFT is not owned by the wallet: {0}")]
+    NotOwner(String),
+
+    #[error("Invalid merkle proof: {0}")]
+    InvalidProof(String),
+
+    #[error("Transaction error: {0}")]
+    TransactionError(String),
+}
+
+impl RpcError for NftError {
+    fn connection_error(message: String) -> Self {
+        NftError::ConnectionError(message)
+    }
+
+    fn transaction_error(message: String) -> Self {
+        NftError::TransactionError(message)
+    }
+
+    fn invalid_address(address: String) -> Self {
+        NftError::InvalidAddress(address)
+    }
+}
+
+/// Merge collections that share an address.
+///
+/// Collections are ordered by name with NFTs outside a verified collection
+/// last, and NFTs by name within their collection.
+pub fn group_by_collection(
+    collections: impl IntoIterator<Item = NftCollection>,
+) -> Vec<NftCollection> {
+    let mut grouped: Vec<NftCollection> = Vec::new();
+    let mut index: HashMap<Option<String>, usize> = HashMap::new();
+    for collection in collections {
+        match index.get(&collection.address) {
+            Some(&position) => {
+                let group = &mut grouped[position];
+                group.name = group.name.take().or(collection.name);
+                group.image = group.image.take().or(collection.image);
+                group.nfts.extend(collection.nfts);
+            }
+            None => {
+                index.insert(collection.address.clone(), grouped.len());
+                grouped.push(collection);
+            }
+        }
+    }
+
+    for collection in &mut grouped {
+        collection.nfts.sort_by_key(|nft| nft.name.to_lowercase());
+    }
+    grouped.sort_by_key(|collection| {
+        (
+            collection.address.is_none(),
+            collection.name.clone().unwrap_or_default().to_lowercase(),
+        )
+    });
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::models::nft::{Nft, NftKind},
+    };
+
+    fn collection(address: Option<&str>, name: Option<&str>, nfts: &[&str]) -> NftCollection {
+        NftCollection {
+            address: address.map(str::to_string),
+            name: name.map(str::to_string),
+            image: None,
+            nfts: nfts
+                .iter()
+                .map(|name| Nft {
+                    id: name.to_string(),
+                    kind: NftKind::Standard,
+                    name: name.to_string(),
+                    symbol: String::new(),
+                    description: None,
+                    image: None,
+                    attributes: Vec::new(),
+                    uri: String::new(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_group_by_collection() {
+        let grouped = group_by_collection(vec![
+            collection(None, None, &["Loose"]),
+            collection(Some("mad"), None, &["Mad Lad #9"]),
+            collection(Some("claynosaurz"), Some("Claynosaurz"), &["Clay #2"]),
+            collection(Some("mad"), Some("Mad Lads"), &["Mad Lad #1"]),
+            collection(Some("claynosaurz"), Some("Other name"), &["Clay #1"]),
+        ]);
+
+        let names = grouped
+            .iter()
+            .map(|collection| collection.name.as_deref())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec![Some("Claynosaurz"), Some("Mad Lads"), None]);
+        let nfts = grouped[1]
+            .nfts
+            .iter()
+            .map(|nft| nft.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(nfts, vec!["Mad Lad #1", "Mad Lad #9"]);
+        assert_eq!(grouped[0].nfts.len(), 2);
+    }
+}
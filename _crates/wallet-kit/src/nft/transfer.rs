@@ -0,0 +1,360 @@
+use {
+    crate::{
+        nft::{
+            das::{DasAsset, DasAssetProof, DasProvider},
+            metadata::{
+                bubblegum_program_id, canopy_depth, edition_key, metadata_key, optional_account,
+                parse_nft_metadata, token_metadata_program_id, token_record_key, tree_config_key,
+                NftMetadata,
+            },
+            NftError,
+        },
+        rpc::{parse_address, send},
+    },
+    log::info,
+    smbcloud_wallet_constants::constants::{
+        METAPLEX_TOKEN_AUTH_RULES_PROGRAM_ID, SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+        SPL_NOOP_PROGRAM_ID,
+    },
+    solana_account_decoder::UiAccountData,
+    solana_client::{nonblocking::rpc_client::RpcClient, rpc_request::TokenAccountsFilter},
+    solana_sdk::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        sysvar,
+    },
+    solana_system_interface_v1::program as system_program,
+    spl_associated_token_account::{
+        get_associated_token_address, instruction::create_associated_token_account_idempotent,
+    },
+    spl_token::instruction as token_instruction,
+    std::str::FromStr,
+};
+
+/// Token Metadata instruction index of `Transfer`.
+const TOKEN_METADATA_TRANSFER: u8 = 49;
+
+/// Anchor discriminator of the Bubblegum `transfer` instruction.
+const BUBBLEGUM_TRANSFER_DISCRIMINATOR: [u8; 8] = [163, 52, 200, 231, 140, 3, 69, 186];
+
+/// Transfer the NFT of `mint` held by the wallet to `recipient`.
+///
+/// Programmable NFTs are transferred through Token Metadata, which enforces
+/// their rule set and creates the token account of the recipient.
+pub async fn transfer_nft(
+    rpc_client: &RpcClient,
+    keypair: Keypair,
+    mint: String,
+    recipient: String,
+) -> Result<String, NftError> {
+    let mint_pubkey = parse_address(&mint)?;
+    let recipient = parse_address(&recipient)?;
+    let owner = keypair.pubkey();
+
+    let metadata_account = rpc_client
+        .get_account(&metadata_key(&mint_pubkey))
+        .await
+        .map_err(|_| NftError::NftNotFound(mint.clone()))?;
+    let metadata = parse_nft_metadata(&metadata_account.data)
+        .ok_or_else(|| NftError::NftNotFound(mint.clone()))?;
+    let source = held_token_account(rpc_client, &owner, &mint_pubkey)
+        .await?
+        .ok_or_else(|| NftError::NotOwner(mint.clone()))?;
+
+    let instructions = if metadata.is_programmable() {
+        vec![programmable_transfer(
+            &metadata, &owner, &source, &recipient,
+        )]
+    } else {
+        let destination = get_associated_token_address(&recipient, &mint_pubkey);
+        vec![
+            create_associated_token_account_idempotent(
+                &owner,
+                &recipient,
+                &mint_pubkey,
+                &spl_token::id(),
+            ),
+            token_instruction::transfer_checked(
+                &spl_token::id(),
+                &source,
+                &mint_pubkey,
+                &destination,
+                &owner,
+                &[],
+                1,
+                0,
+            )
+            .map_err(|e| NftError::TransactionError(e.to_string()))?,
+        ]
+    };
+    let signature = send(rpc_client, &keypair, &instructions, &[]).await?;
+    info!("Transferred NFT {} to {}", mint, recipient);
+    Ok(signature)
+}
+
+/// Transfer the compressed NFT `asset_id` to `recipient`, proving the leaf
+/// through the `das` indexer.
+pub async fn transfer_compressed_nft(
+    rpc_client: &RpcClient,
+    das: &impl DasProvider,
+    keypair: Keypair,
+    asset_id: String,
+    recipient: String,
+) -> Result<String, NftError> {
+    let recipient = parse_address(&recipient)?;
+    let owner = keypair.pubkey();
+
+    let asset = das.get_asset(&asset_id).await?;
+    if asset.ownership.owner != owner.to_string() {
+        return Err(NftError::NotOwner(asset_id));
+    }
+    let proof = das.get_asset_proof(&asset_id).await?;
+    let tree = parse_address(&proof.tree_id)?;
+    let tree_account = rpc_client
+        .get_account(&tree)
+        .await
+        .map_err(|e| NftError::ConnectionError(e.to_string()))?;
+    let canopy_depth = canopy_depth(&tree_account.data)
+        .ok_or_else(|| NftError::InvalidProof(format!("Invalid merkle tree {}", tree)))?;
+
+    let instruction = compressed_transfer(&asset, &proof, canopy_depth, &owner, &recipient)?;
+    let signature = send(rpc_client, &keypair, &[instruction], &[]).await?;
+    info!("Transferred compressed NFT {} to {}", asset_id, recipient);
+    Ok(signature)
+}
+
+/// The token account of `owner` that holds the token of `mint`.
+async fn held_token_account(
+    rpc_client: &RpcClient,
+    owner: &Pubkey,
+    mint: &Pubkey,
+) -> Result<Option<Pubkey>, NftError> {
+    let token_accounts = rpc_client
+        .get_token_accounts_by_owner(owner, TokenAccountsFilter::Mint(*mint))
+        .await
+        .map_err(|e| NftError::ConnectionError(e.to_string()))?;
+
+    // Empty token accounts of the mint may be left from earlier transfers.
+    let held = token_accounts
+        .into_iter()
+        .find(|keyed_account| match &keyed_account.account.data {
+            UiAccountData::Json(account) => account.parsed["info"]["tokenAmount"]["amount"] == "1",
+            _ => false,
+        });
+    held.map(|keyed_account| parse_address(&keyed_account.pubkey))
+        .transpose()
+}
+
+/// Token Metadata `TransferV1` of a programmable NFT, without authorization data.
+fn programmable_transfer(
+    metadata: &NftMetadata,
+    owner: &Pubkey,
+    source: &Pubkey,
+    recipient: &Pubkey,
+) -> Instruction {
+    let program_id = token_metadata_program_id();
+    let mint = metadata.mint;
+    let destination = get_associated_token_address(recipient, &mint);
+    let auth_rules_program = optional_account(metadata.rule_set.map(|_| {
+        Pubkey::from_str(METAPLEX_TOKEN_AUTH_RULES_PROGRAM_ID)
+            .expect("Invalid token auth rules program id")
+    }));
+
+    let accounts = vec![
+        AccountMeta::new(*source, false),
+        AccountMeta::new_readonly(*owner, false),
+        AccountMeta::new(destination, false),
+        AccountMeta::new_readonly(*recipient, false),
+        AccountMeta::new_readonly(mint, false),
+        AccountMeta::new(metadata_key(&mint), false),
+        AccountMeta::new_readonly(edition_key(&mint), false),
+        AccountMeta::new(token_record_key(&mint, source), false),
+        AccountMeta::new(token_record_key(&mint, &destination), false),
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new(*owner, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        AccountMeta::new_readonly(auth_rules_program, false),
+        AccountMeta::new_readonly(optional_account(metadata.rule_set), false),
+    ];
+    // `TransferArgs::V1` of one token and no authorization data.
+    let mut data = vec![TOKEN_METADATA_TRANSFER, 0];
+    data.extend_from_slice(&1u64.to_le_bytes());
+    data.push(0);
+
+    Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+/// Bubblegum `transfer` of the leaf of `asset`.
+///
+/// Proof nodes cached in the canopy of the tree are left out, which keeps the
+/// transaction within its size limit.
+fn compressed_transfer(
+    asset: &DasAsset,
+    proof: &DasAssetProof,
+    canopy_depth: usize,
+    owner: &Pubkey,
+    recipient: &Pubkey,
+) -> Result<Instruction, NftError> {
+    let compression = asset
+        .compression
+        .as_ref()
+        .filter(|compression| compression.compressed)
+        .ok_or_else(|| NftError::InvalidProof(format!("{} is not compressed", asset.id)))?;
+    let tree = parse_address(&proof.tree_id)?;
+    let delegate = match &asset.ownership.delegate {
+        Some(delegate) => parse_address(delegate)?,
+        None => *owner,
+    };
+    let proof_len = proof.proof.len().saturating_sub(canopy_depth);
+    let proof_nodes = proof.proof[..proof_len]
+        .iter()
+        .map(|node| parse_address(node).map(|node| AccountMeta::new_readonly(node, false)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(tree_config_key(&tree), false),
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new_readonly(delegate, false),
+        AccountMeta::new_readonly(*recipient, false),
+        AccountMeta::new(tree, false),
+        AccountMeta::new_readonly(program_id(SPL_NOOP_PROGRAM_ID), false),
+        AccountMeta::new_readonly(program_id(SPL_ACCOUNT_COMPRESSION_PROGRAM_ID), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    accounts.extend(proof_nodes);
+
+    let mut data = BUBBLEGUM_TRANSFER_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&hash(&proof.root)?);
+    data.extend_from_slice(&hash(&compression.data_hash)?);
+    data.extend_from_slice(&hash(&compression.creator_hash)?);
+    data.extend_from_slice(&compression.leaf_id.to_le_bytes());
+    data.extend_from_slice(&leaf_index(proof)?.to_le_bytes());
+
+    Ok(Instruction {
+        program_id: bubblegum_program_id(),
+        accounts,
+        data,
+    })
+}
+
+/// Index of the leaf among the leaves of the tree, from its node index.
+fn leaf_index(proof: &DasAssetProof) -> Result<u32, NftError> {
+    1u64.checked_shl(proof.proof.len() as u32)
+        .and_then(|leaves| proof.node_index.checked_sub(leaves))
+        .and_then(|index| u32::try_from(index).ok())
+        .ok_or_else(|| NftError::InvalidProof(format!("Invalid node index {}", proof.node_index)))
+}
+
+fn hash(value: &str) -> Result<[u8; 32], NftError> {
+    bs58::decode(value)
+        .into_vec()
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| NftError::InvalidProof(format!("Invalid hash {}", value)))
+}
+
+fn program_id(address: &str) -> Pubkey {
+    Pubkey::from_str(address).expect("Invalid program id")
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::nft::{
+            das::{DasCompression, DasOwnership},
+            metadata::TokenStandard,
+        },
+    };
+
+    fn proof(depth: usize, node_index: u64) -> DasAssetProof {
+        DasAssetProof {
+            root: bs58::encode([1; 32]).into_string(),
+            proof: (0..depth)
+                .map(|_| Pubkey::new_unique().to_string())
+                .collect(),
+            node_index,
+            tree_id: Pubkey::new_unique().to_string(),
+        }
+    }
+
+    #[test]
+    fn test_leaf_index() {
+        assert_eq!(leaf_index(&proof(14, (1 << 14) + 5)).unwrap(), 5);
+        assert!(leaf_index(&proof(14, 5)).is_err());
+    }
+
+    #[test]
+    fn test_compressed_transfer() {
+        let owner = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let asset = DasAsset {
+            id: "asset".to_string(),
+            content: None,
+            grouping: Vec::new(),
+            compression: Some(DasCompression {
+                compressed: true,
+                tree: String::new(),
+                leaf_id: 5,
+                data_hash: bs58::encode([2; 32]).into_string(),
+                creator_hash: bs58::encode([3; 32]).into_string(),
+            }),
+            ownership: DasOwnership {
+                owner: owner.to_string(),
+                delegate: None,
+            },
+            burnt: false,
+        };
+        let proof = proof(14, (1 << 14) + 5);
+
+        let instruction = compressed_transfer(&asset, &proof, 11, &owner, &recipient).unwrap();
+        // Eight accounts and the three proof nodes below the canopy.
+        assert_eq!(instruction.accounts.len(), 8 + 3);
+        assert_eq!(instruction.accounts[2].pubkey, owner);
+        assert!(instruction.accounts[1].is_signer);
+        assert_eq!(instruction.data.len(), 8 + 32 * 3 + 8 + 4);
+        assert_eq!(instruction.data[8..40], [1; 32]);
+        assert_eq!(instruction.data[instruction.data.len() - 4..], [5, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_programmable_transfer() {
+        let owner = Pubkey::new_unique();
+        let source = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let mut metadata = NftMetadata {
+            mint: Pubkey::new_unique(),
+            name: "Mad Lad #1".to_string(),
+            symbol: "MAD".to_string(),
+            uri: String::new(),
+            token_standard: Some(TokenStandard::ProgrammableNonFungible),
+            collection: None,
+            rule_set: None,
+        };
+
+        let instruction = programmable_transfer(&metadata, &owner, &source, &recipient);
+        assert_eq!(instruction.accounts.len(), 17);
+        assert_eq!(instruction.data, [49, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(instruction.accounts[16].pubkey, token_metadata_program_id());
+
+        let rule_set = Pubkey::new_unique();
+        metadata.rule_set = Some(rule_set);
+        let instruction = programmable_transfer(&metadata, &owner, &source, &recipient);
+        assert_eq!(instruction.accounts[16].pubkey, rule_set);
+        assert_eq!(
+            instruction.accounts[8].pubkey,
+            token_record_key(
+                &metadata.mint,
+                &get_associated_token_address(&recipient, &metadata.mint)
+            )
+        );
+    }
+}
@@ -0,0 +1,606 @@
+use {
+    crate::{
+        cost_basis::{Lot, Lots, DUST, OPENING_LOT},
+        history::{fetch_transactions, transaction_balance_changes},
+        models::portfolio_history::{
+            AssetPnl, BalanceChange, ChartPoint, CostBasisMethod, HistoryRange, PortfolioChart,
+            PortfolioHistory, PortfolioPnl, PortfolioSnapshot, SnapshotAsset,
+        },
+    },
+    log::{debug, warn},
+    smbcloud_wallet_core_http::price_data::price_history::PriceHistoryProvider,
+    smbcloud_wallet_core_model::models::{
+        environment::Environment, portfolio::PortfolioValuation, price::PricePoint,
+    },
+    solana_client::nonblocking::rpc_client::RpcClient,
+    solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey},
+    std::{collections::HashMap, str::FromStr},
+    thiserror::Error,
+};
+
+/// Minimum time between two snapshots of the same wallet, in seconds.
+pub const SNAPSHOT_INTERVAL: i64 = 15 * 60;
+
+/// Snapshots older than this are dropped, in seconds.
+pub const SNAPSHOT_RETENTION: i64 = 366 * 24 * 60 * 60;
+
+/// Most transactions read to reconstruct past balances.
+pub const MAX_HISTORY_TRANSACTIONS: usize = 500;
+
+#[derive(Error, Debug)]
+pub enum PortfolioHistoryError {
+    #[error("Failed to connect to RPC: {0}")]
+    ConnectionError(String),
+
+    #[error("Invalid address: {0}")]
+    InvalidAddress(String),
+}
+
+/// Chart and profit and loss of a wallet over `range`.
+///
+/// Past balances are reconstructed from the wallet's transactions and valued with
+/// historical prices; stored `snapshots` of the wallet take precedence where they
+/// exist. Cost basis starts at the beginning of the range: holdings older than that
+/// enter at the price of that moment. Amounts are converted from USD at the
+/// current `usd_rate`. Tokens in `hidden_mints` are left out.
+///
+/// Only the last `MAX_HISTORY_TRANSACTIONS` transactions are read, the result is
+/// marked truncated when the range holds more.
+#[allow(clippy::too_many_arguments)]
+pub async fn portfolio_history(
+    rpc_url: String,
+    pubkey: &str,
+    network: Environment,
+    valuation: &PortfolioValuation,
+    usd_rate: f64,
+    snapshots: &[PortfolioSnapshot],
+    price_history: &dyn PriceHistoryProvider,
+    range: HistoryRange,
+    method: CostBasisMethod,
+    hidden_mints: &[String],
+) -> Result<PortfolioHistory, PortfolioHistoryError> {
+    let now = valuation.updated_at;
+    let start = now - range.duration();
+    let BalanceChanges {
+        mut changes,
+        truncated,
+    } = fetch_balance_changes(rpc_url, pubkey, start, MAX_HISTORY_TRANSACTIONS).await?;
+    changes.retain(|change| !hidden_mints.contains(&change.mint));
+    debug!("{} balance changes since {}", changes.len(), start);
+
+    // Tokens nobody prices today are not worth a history request.
+    let mut mints: Vec<&str> = valuation
+        .assets
+        .iter()
+        .filter(|asset| asset.price.is_some())
+        .map(|asset| asset.mint.as_str())
+        .chain(changes.iter().map(|change| change.mint.as_str()))
+        .collect();
+    mints.sort();
+    mints.dedup();
+
+    let mut prices = HashMap::new();
+    for mint in mints {
+        match price_history
+            .price_history(mint, start - range.step(), now, range.step())
+            .await
+        {
+            Ok(points) => {
+                prices.insert(mint.to_string(), points);
+            }
+            Err(e) => warn!("No price history for {}: {}", mint, e),
+        }
+    }
+
+    let mut wallet_snapshots: Vec<PortfolioSnapshot> = snapshots
+        .iter()
+        .filter(|snapshot| snapshot.pubkey == pubkey && snapshot.network == network)
+        .cloned()
+        .collect();
+    wallet_snapshots.push(snapshot_from_valuation(
+        pubkey, network, valuation, usd_rate,
+    ));
+
+    Ok(PortfolioHistory {
+        chart: portfolio_chart(
+            range,
+            now,
+            &changes,
+            &prices,
+            &wallet_snapshots,
+            valuation,
+            usd_rate,
+        ),
+        pnl: portfolio_pnl(method, start, valuation, usd_rate, &changes, &prices),
+        truncated,
+    })
+}
+
+/// A USD snapshot of `valuation`, which is in a currency buying `usd_rate` per dollar.
+pub fn snapshot_from_valuation(
+    pubkey: &str,
+    network: Environment,
+    valuation: &PortfolioValuation,
+    usd_rate: f64,
+) -> PortfolioSnapshot {
+    PortfolioSnapshot {
+        pubkey: pubkey.to_string(),
+        network,
+        timestamp: valuation.updated_at,
+        total_usd: valuation.total_value / usd_rate,
+        assets: valuation
+            .assets
+            .iter()
+            .map(|asset| SnapshotAsset {
+                mint: asset.mint.clone(),
+                amount: asset.amount,
+                usd_price: asset.price.map(|price| price / usd_rate),
+            })
+            .collect(),
+    }
+}
+
+/// Add `snapshot` unless the wallet already has one from the last [`SNAPSHOT_INTERVAL`].
+///
+/// Snapshots older than [`SNAPSHOT_RETENTION`] are dropped. Returns whether the
+/// snapshot was added.
+pub fn record_snapshot(
+    snapshots: &mut Vec<PortfolioSnapshot>,
+    snapshot: PortfolioSnapshot,
+) -> bool {
+    let recent = snapshots.iter().any(|s| {
+        s.pubkey == snapshot.pubkey
+            && s.network == snapshot.network
+            && (snapshot.timestamp - s.timestamp).abs() < SNAPSHOT_INTERVAL
+    });
+    if recent {
+        return false;
+    }
+    let oldest = snapshot.timestamp - SNAPSHOT_RETENTION;
+    snapshots.push(snapshot);
+    snapshots.retain(|s| s.timestamp >= oldest);
+    true
+}
+
+/// Balances at `timestamp`, undoing the changes that happened after it.
+pub fn balances_at(
+    current: &HashMap<String, f64>,
+    changes: &[BalanceChange],
+    timestamp: i64,
+) -> HashMap<String, f64> {
+    let mut balances = current.clone();
+    for change in changes.iter().filter(|change| change.timestamp > timestamp) {
+        *balances.entry(change.mint.clone()).or_default() -= change.delta;
+    }
+    // A negative balance means the history is incomplete.
+    balances.retain(|_, amount| *amount > DUST);
+    balances
+}
+
+/// The price closest in time to `timestamp`. `history` is sorted oldest first.
+pub fn price_at(history: &[PricePoint], timestamp: i64) -> Option<f64> {
+    let index = history.partition_point(|point| point.timestamp < timestamp);
+    let after = history.get(index);
+    let before = index.checked_sub(1).and_then(|i| history.get(i));
+    match (before, after) {
+        (Some(b), Some(a)) if timestamp - b.timestamp <= a.timestamp - timestamp => {
+            Some(b.usd_price)
+        }
+        (_, Some(a)) => Some(a.usd_price),
+        (Some(b), None) => Some(b.usd_price),
+        (None, None) => None,
+    }
+}
+
+/// Portfolio values every `range.step()` from the start of the range until `now`.
+///
+/// A point uses the snapshot within half a step of it when there is one, and
+/// otherwise the reconstructed balances valued at the historical prices.
+pub fn portfolio_chart(
+    range: HistoryRange,
+    now: i64,
+    changes: &[BalanceChange],
+    prices: &HashMap<String, Vec<PricePoint>>,
+    snapshots: &[PortfolioSnapshot],
+    valuation: &PortfolioValuation,
+    usd_rate: f64,
+) -> PortfolioChart {
+    let current = current_amounts(valuation);
+    let step = range.step();
+    let start = now - range.duration();
+    let mut timestamps: Vec<i64> = (0..)
+        .map(|i| start + i * step)
+        .take_while(|timestamp| *timestamp < now)
+        .collect();
+    timestamps.push(now);
+
+    let points: Vec<ChartPoint> = timestamps
+        .into_iter()
+        .map(|timestamp| {
+            let snapshot = snapshots
+                .iter()
+                .filter(|s| (s.timestamp - timestamp).abs() <= step / 2)
+                .min_by_key(|s| (s.timestamp - timestamp).abs());
+            let usd = match snapshot {
+                Some(snapshot) => snapshot.total_usd,
+                None => balances_at(&current, changes, timestamp)
+                    .iter()
+                    .filter_map(|(mint, amount)| {
+                        Some(amount * price_at(prices.get(mint)?, timestamp)?)
+                    })
+                    .sum(),
+            };
+            ChartPoint {
+                timestamp,
+                value: usd * usd_rate,
+            }
+        })
+        .collect();
+
+    let first = points.first().map(|point| point.value).unwrap_or_default();
+    let last = points.last().map(|point| point.value).unwrap_or_default();
+    let change = last - first;
+    PortfolioChart {
+        range,
+        currency: valuation.currency,
+        points,
+        change,
+        change_percent: (first.abs() > DUST).then(|| change / first * 100.0),
+    }
+}
+
+/// Cost basis and profit and loss of every asset held now or traded since `start`.
+///
+/// Tokens received are bought and tokens sent, fees included, are sold at the
+/// price of the moment.
+pub fn portfolio_pnl(
+    method: CostBasisMethod,
+    start: i64,
+    valuation: &PortfolioValuation,
+    usd_rate: f64,
+    changes: &[BalanceChange],
+    prices: &HashMap<String, Vec<PricePoint>>,
+) -> PortfolioPnl {
+    let mut mints: Vec<&str> = valuation
+        .assets
+        .iter()
+        .map(|asset| asset.mint.as_str())
+        .collect();
+    for change in changes {
+        if !mints.contains(&change.mint.as_str()) {
+            mints.push(&change.mint);
+        }
+    }
+
+    let no_history = Vec::new();
+    let mut assets = Vec::new();
+    for mint in mints {
+        let asset = valuation.assets.iter().find(|asset| asset.mint == mint);
+        let amount = asset.map(|asset| asset.amount).unwrap_or_default();
+        let history = prices.get(mint).unwrap_or(&no_history);
+        let mut mint_changes: Vec<&BalanceChange> = changes
+            .iter()
+            .filter(|change| change.mint == mint)
+            .collect();
+        mint_changes.sort_by_key(|change| change.timestamp);
+
+        let mut lots = Lots::new(method);
+        let received: f64 = mint_changes.iter().map(|change| change.delta).sum();
+        let opening = amount - received;
+        if opening > DUST {
+            let unit_cost = price_at(history, start).unwrap_or_default();
+            lots.buy(Lot::new(OPENING_LOT, start, opening, unit_cost));
+        }
+        let mut realized = 0.0;
+        for change in mint_changes {
+            let price = price_at(history, change.timestamp).unwrap_or_default();
+            if change.delta > 0.0 {
+                lots.buy(Lot::new(
+                    &change.signature,
+                    change.timestamp,
+                    change.delta,
+                    price,
+                ));
+            } else {
+                let sold = -change.delta;
+                let cost: f64 = lots.sell(sold).iter().map(Lot::cost).sum();
+                realized += sold * price - cost;
+            }
+        }
+
+        if amount <= DUST && realized.abs() <= DUST {
+            continue;
+        }
+        let cost_basis = lots.cost() * usd_rate;
+        let current_value = asset.and_then(|asset| asset.value);
+        assets.push(AssetPnl {
+            mint: mint.to_string(),
+            symbol: asset.and_then(|asset| asset.symbol.clone()),
+            amount,
+            cost_basis,
+            average_cost: (amount > DUST).then(|| cost_basis / amount),
+            current_value,
+            realized_pnl: realized * usd_rate,
+            unrealized_pnl: current_value.map(|value| value - cost_basis),
+        });
+    }
+
+    PortfolioPnl {
+        currency: valuation.currency,
+        method,
+        total_cost_basis: assets.iter().map(|asset| asset.cost_basis).sum(),
+        total_realized_pnl: assets.iter().map(|asset| asset.realized_pnl).sum(),
+        total_unrealized_pnl: assets.iter().filter_map(|asset| asset.unrealized_pnl).sum(),
+        assets,
+    }
+}
+
+/// Balance changes of a wallet, oldest first.
+pub struct BalanceChanges {
+    pub changes: Vec<BalanceChange>,
+    /// Whether older transactions in the period were left out.
+    pub truncated: bool,
+}
+
+/// Read the wallet's transactions since `since`, newest first, up to `limit` of them,
+/// and return its balance changes oldest first.
+pub async fn fetch_balance_changes(
+    rpc_url: String,
+    owner: &str,
+    since: i64,
+    limit: usize,
+) -> Result<BalanceChanges, PortfolioHistoryError> {
+    let owner_key = Pubkey::from_str(owner)
+        .map_err(|_| PortfolioHistoryError::InvalidAddress(owner.to_string()))?;
+    let rpc_client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+
+    let history = fetch_transactions(&rpc_client, &owner_key, since, limit)
+        .await
+        .map_err(|e| PortfolioHistoryError::ConnectionError(e.to_string()))?;
+    let mut changes: Vec<BalanceChange> = history
+        .transactions
+        .iter()
+        .flat_map(|transaction| transaction_balance_changes(transaction, &owner_key))
+        .collect();
+    changes.sort_by_key(|change| change.timestamp);
+    Ok(BalanceChanges {
+        changes,
+        truncated: history.truncated,
+    })
+}
+
+fn current_amounts(valuation: &PortfolioValuation) -> HashMap<String, f64> {
+    valuation
+        .assets
+        .iter()
+        .map(|asset| (asset.mint.clone(), asset.amount))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        smbcloud_wallet_constants::assets_solana::ADDRESS_SOL,
+        smbcloud_wallet_core_model::models::{
+            currency::FiatCurrency,
+            portfolio::Holding,
+            price::{AssetPrice, PriceSource},
+        },
+    };
+
+    const WALLET: &str = "mvines9iiHiQTysrwkJjGf2gb9Ex9jXJX8ns3qwf2kN";
+    const HOUR: i64 = 60 * 60;
+    const NOW: i64 = 1_760_000_000;
+
+    fn change(timestamp: i64, mint: &str, delta: f64) -> BalanceChange {
+        BalanceChange {
+            signature: format!("sig-{}", timestamp),
+            timestamp,
+            mint: mint.to_string(),
+            delta,
+        }
+    }
+
+    fn history(points: &[(i64, f64)]) -> Vec<PricePoint> {
+        points
+            .iter()
+            .map(|(timestamp, usd_price)| PricePoint {
+                timestamp: *timestamp,
+                usd_price: *usd_price,
+            })
+            .collect()
+    }
+
+    /// A wallet holding `sol` SOL priced at `usd_price`, valued in `currency`.
+    fn valuation(
+        sol: f64,
+        usd_price: f64,
+        currency: FiatCurrency,
+        usd_rate: f64,
+    ) -> PortfolioValuation {
+        let holdings = vec![Holding {
+            mint: ADDRESS_SOL.to_string(),
+            symbol: Some("SOL".to_string()),
+            amount: sol,
+        }];
+        let price = AssetPrice {
+            mint: ADDRESS_SOL.to_string(),
+            usd_price,
+            price_change_24h: None,
+            source: PriceSource::Jupiter,
+            updated_at: NOW,
+        };
+        let prices = HashMap::from([(ADDRESS_SOL.to_string(), price)]);
+        PortfolioValuation::new(holdings, &prices, currency, usd_rate, NOW)
+    }
+
+    #[test]
+    fn test_record_snapshot() {
+        let current = valuation(1.0, 100.0, FiatCurrency::USD, 1.0);
+        let snapshot = |timestamp| PortfolioSnapshot {
+            timestamp,
+            ..snapshot_from_valuation(WALLET, Environment::Mainnet, &current, 1.0)
+        };
+        let mut snapshots = vec![snapshot(NOW - SNAPSHOT_RETENTION - HOUR)];
+
+        assert!(record_snapshot(&mut snapshots, snapshot(NOW)));
+        // The expired snapshot is gone.
+        assert_eq!(snapshots.len(), 1);
+        assert!(!record_snapshot(&mut snapshots, snapshot(NOW + 60)));
+        // Another network is another wallet.
+        let devnet = PortfolioSnapshot {
+            network: Environment::Devnet,
+            ..snapshot(NOW + 60)
+        };
+        assert!(record_snapshot(&mut snapshots, devnet));
+        assert!(record_snapshot(
+            &mut snapshots,
+            snapshot(NOW + SNAPSHOT_INTERVAL)
+        ));
+        assert_eq!(snapshots.len(), 3);
+    }
+
+    #[test]
+    fn test_balances_at() {
+        let current = HashMap::from([(ADDRESS_SOL.to_string(), 3.0)]);
+        let changes = vec![
+            change(NOW - 3 * HOUR, ADDRESS_SOL, 5.0),
+            change(NOW - 2 * HOUR, "bach", 10.0),
+            change(NOW - HOUR, ADDRESS_SOL, -2.0),
+            change(NOW - HOUR, "bach", -10.0),
+        ];
+        let before_sale = balances_at(&current, &changes, NOW - 90 * 60);
+        assert_eq!(before_sale[ADDRESS_SOL], 5.0);
+        assert_eq!(before_sale["bach"], 10.0);
+        let at_start = balances_at(&current, &changes, NOW - 4 * HOUR);
+        assert!(at_start.is_empty());
+    }
+
+    #[test]
+    fn test_price_at() {
+        let points = history(&[(0, 1.0), (100, 2.0), (200, 3.0)]);
+        assert_eq!(price_at(&points, -50), Some(1.0));
+        assert_eq!(price_at(&points, 40), Some(1.0));
+        assert_eq!(price_at(&points, 60), Some(2.0));
+        assert_eq!(price_at(&points, 500), Some(3.0));
+        assert_eq!(price_at(&[], 500), None);
+    }
+
+    #[test]
+    fn test_portfolio_chart() {
+        let current = valuation(2.0, 200.0, FiatCurrency::SEK, 10.0);
+        // One SOL was bought six hours ago.
+        let changes = vec![change(NOW - 6 * HOUR, ADDRESS_SOL, 1.0)];
+        let prices = HashMap::from([(
+            ADDRESS_SOL.to_string(),
+            history(&[(NOW - 25 * HOUR, 100.0), (NOW - 5 * HOUR, 150.0)]),
+        )]);
+        let snapshots = vec![snapshot_from_valuation(
+            WALLET,
+            Environment::Mainnet,
+            &current,
+            10.0,
+        )];
+
+        let chart = portfolio_chart(
+            HistoryRange::Day,
+            NOW,
+            &changes,
+            &prices,
+            &snapshots,
+            &current,
+            10.0,
+        );
+        assert_eq!(chart.points.len(), 25);
+        assert_eq!(chart.points[0].timestamp, NOW - 24 * HOUR);
+        // One SOL at 100 USD, in SEK.
+        assert_eq!(chart.points[0].value, 1000.0);
+        // Two SOL at 150 USD after the purchase.
+        assert_eq!(chart.points[19].value, 3000.0);
+        // The last point is the current snapshot.
+        assert_eq!(chart.points[24].timestamp, NOW);
+        assert_eq!(chart.points[24].value, 4000.0);
+        assert_eq!(chart.change, 3000.0);
+        assert_eq!(chart.change_percent, Some(300.0));
+    }
+
+    #[test]
+    fn test_portfolio_pnl_fifo_and_average() {
+        // Hold one SOL from before the range, buy one at 200, sell one at 300.
+        let current = valuation(1.0, 400.0, FiatCurrency::USD, 1.0);
+        let start = NOW - 10 * HOUR;
+        let changes = vec![
+            change(NOW - 8 * HOUR, ADDRESS_SOL, 1.0),
+            change(NOW - 4 * HOUR, ADDRESS_SOL, -1.0),
+        ];
+        let prices = HashMap::from([(
+            ADDRESS_SOL.to_string(),
+            history(&[
+                (start, 100.0),
+                (NOW - 8 * HOUR, 200.0),
+                (NOW - 4 * HOUR, 300.0),
+            ]),
+        )]);
+
+        let fifo = portfolio_pnl(
+            CostBasisMethod::Fifo,
+            start,
+            &current,
+            1.0,
+            &changes,
+            &prices,
+        );
+        let sol = &fifo.assets[0];
+        // The SOL bought at 100 was sold first.
+        assert_eq!(sol.realized_pnl, 200.0);
+        assert_eq!(sol.cost_basis, 200.0);
+        assert_eq!(sol.unrealized_pnl, Some(200.0));
+
+        let average = portfolio_pnl(
+            CostBasisMethod::Average,
+            start,
+            &current,
+            1.0,
+            &changes,
+            &prices,
+        );
+        let sol = &average.assets[0];
+        assert_eq!(sol.realized_pnl, 150.0);
+        assert_eq!(sol.cost_basis, 150.0);
+        assert_eq!(sol.average_cost, Some(150.0));
+        assert_eq!(sol.unrealized_pnl, Some(250.0));
+        assert_eq!(
+            average.total_realized_pnl + average.total_unrealized_pnl,
+            400.0
+        );
+    }
+
+    #[test]
+    fn test_portfolio_pnl_keeps_sold_out_assets() {
+        let current = valuation(0.0, 400.0, FiatCurrency::USD, 1.0);
+        let changes = vec![
+            change(NOW - 8 * HOUR, "bach", 100.0),
+            change(NOW - 4 * HOUR, "bach", -100.0),
+        ];
+        let prices = HashMap::from([(
+            "bach".to_string(),
+            history(&[(NOW - 8 * HOUR, 0.5), (NOW - 4 * HOUR, 0.25)]),
+        )]);
+
+        let pnl = portfolio_pnl(
+            CostBasisMethod::Fifo,
+            NOW - HistoryRange::Day.duration(),
+            &current,
+            1.0,
+            &changes,
+            &prices,
+        );
+        assert_eq!(pnl.assets.len(), 1);
+        let bach = &pnl.assets[0];
+        assert_eq!(bach.mint, "bach");
+        assert_eq!(bach.amount, 0.0);
+        assert_eq!(bach.realized_pnl, -25.0);
+        assert_eq!(bach.average_cost, None);
+    }
+}
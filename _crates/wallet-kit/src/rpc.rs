@@ -0,0 +1,50 @@
+use {
+    solana_client::nonblocking::rpc_client::RpcClient,
+    solana_sdk::{
+        instruction::Instruction,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    },
+    std::str::FromStr,
+};
+
+/// Error of a module that builds and sends its own transactions.
+pub(crate) trait RpcError {
+    fn connection_error(message: String) -> Self;
+    fn transaction_error(message: String) -> Self;
+    fn invalid_address(address: String) -> Self;
+}
+
+/// Sign `instructions` with `keypair`, which pays the fee, and `extra_signers`,
+/// send them and wait for the confirmation.
+pub(crate) async fn send<E: RpcError>(
+    rpc_client: &RpcClient,
+    keypair: &Keypair,
+    instructions: &[Instruction],
+    extra_signers: &[&Keypair],
+) -> Result<String, E> {
+    let blockhash = rpc_client
+        .get_latest_blockhash()
+        .await
+        .map_err(|e| E::connection_error(e.to_string()))?;
+
+    let mut signers = vec![keypair];
+    signers.extend_from_slice(extra_signers);
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&keypair.pubkey()),
+        &signers,
+        blockhash,
+    );
+
+    let signature = rpc_client
+        .send_and_confirm_transaction(&transaction)
+        .await
+        .map_err(|e| E::transaction_error(e.to_string()))?;
+    Ok(signature.to_string())
+}
+
+pub(crate) fn parse_address<E: RpcError>(address: &str) -> Result<Pubkey, E> {
+    Pubkey::from_str(address).map_err(|_| E::invalid_address(address.to_string()))
+}
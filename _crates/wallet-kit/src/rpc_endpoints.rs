@@ -0,0 +1,524 @@
+//! Ranked RPC endpoints of a network with failover.
+//!
+//! Calls go to the best ranked endpoint that is healthy and keeps up with the
+//! others. When none is, the first one is used. Endpoints of the user's custom
+//! networks rank before the built-in ones, once [`verify_network`] found
+//! they serve the cluster they were added for.
+
+use {
+    log::warn,
+    smbcloud_wallet_core_model::models::{
+        custom_network::{websocket_url, CustomNetwork},
+        environment::Environment,
+        rpc_endpoint::{host, RpcEndpointStatus},
+    },
+    smbcloud_wallet_core_network::model::{ErrorCode, ErrorResponse},
+    smbcloud_wallet_core_rpc::health::{
+        endpoint_genesis_hash, endpoint_health, websocket_slot, EndpointHealth,
+        HEALTH_CHECK_TIMEOUT,
+    },
+    std::{
+        collections::HashMap,
+        future::Future,
+        sync::{OnceLock, RwLock, RwLockReadGuard, RwLockWriteGuard},
+        time::{Duration, SystemTime, UNIX_EPOCH},
+    },
+};
+
+/// Most slots an endpoint may trail the most advanced one and still be used.
+pub const MAX_SLOT_LAG: u64 = 50;
+
+/// How often the endpoints of the network in use should be checked.
+pub const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Most slots the WebSocket endpoint may be apart from the RPC endpoint and
+/// still be taken as the same cluster. It notifies processed slots, ahead of
+/// the finalized slot `getSlot` returns.
+const MAX_WEBSOCKET_SLOT_DISTANCE: u64 = 150;
+
+const ENVIRONMENTS: [Environment; 4] = [
+    Environment::Local,
+    Environment::Devnet,
+    Environment::Testnet,
+    Environment::Mainnet,
+];
+
+static SHARED_RPC_ENDPOINTS: OnceLock<HashMap<Environment, RpcEndpoints>> = OnceLock::new();
+
+/// The RPC endpoints of one network, by preference.
+pub struct RpcEndpoints {
+    endpoints: RwLock<Endpoints>,
+}
+
+/// Endpoint URLs and their status, in the same order.
+struct Endpoints {
+    urls: Vec<String>,
+    statuses: Vec<RpcEndpointStatus>,
+    /// WebSocket endpoints set by the user, by RPC endpoint. Other endpoints
+    /// serve WebSocket next to RPC.
+    ws_urls: HashMap<String, String>,
+}
+
+impl RpcEndpoints {
+    pub fn new(urls: Vec<String>) -> Self {
+        let statuses = urls
+            .iter()
+            .enumerate()
+            .map(|(rank, url)| unchecked(rank, url))
+            .collect();
+        RpcEndpoints {
+            endpoints: RwLock::new(Endpoints {
+                urls,
+                statuses,
+                ws_urls: HashMap::new(),
+            }),
+        }
+    }
+
+    /// The process wide endpoints of `environment`.
+    ///
+    /// Every wallet kit without an RPC URL of its own reads and reports
+    /// endpoint health here, so a failure one kit sees moves the others too.
+    pub fn shared(environment: Environment) -> &'static RpcEndpoints {
+        &SHARED_RPC_ENDPOINTS.get_or_init(|| {
+            ENVIRONMENTS
+                .into_iter()
+                .map(|environment| (environment, RpcEndpoints::new(environment.rpc_urls())))
+                .collect()
+        })[&environment]
+    }
+
+    /// Rank the endpoints of `networks` before the built-in ones of their
+    /// cluster. A custom local cluster replaces `localhost`, which is another
+    /// cluster.
+    ///
+    /// Calls only fail over between endpoints of one cluster. Local networks
+    /// may each be a cluster of their own, the first one is used along with
+    /// the networks that share its genesis hash.
+    pub fn apply_custom_networks(networks: &[CustomNetwork]) {
+        for environment in ENVIRONMENTS {
+            let cluster = cluster_networks(networks, environment);
+            let mut urls: Vec<String> = cluster
+                .iter()
+                .map(|network| network.rpc_url.clone())
+                .collect();
+            if environment != Environment::Local || urls.is_empty() {
+                urls.extend(environment.rpc_urls());
+            }
+            let mut unique = Vec::with_capacity(urls.len());
+            for url in urls {
+                if !unique.contains(&url) {
+                    unique.push(url);
+                }
+            }
+            let endpoints = RpcEndpoints::shared(environment);
+            endpoints.set_urls(unique);
+            endpoints.write().ws_urls = cluster
+                .iter()
+                .filter_map(|network| Some((network.rpc_url.clone(), network.ws_url.clone()?)))
+                .collect();
+        }
+    }
+
+    /// Replace the endpoints, keeping the status of the ones still listed.
+    /// A network always has an endpoint, so an empty list is ignored.
+    pub fn set_urls(&self, urls: Vec<String>) {
+        if urls.is_empty() {
+            return;
+        }
+        let mut endpoints = self.write();
+        let mut statuses = Vec::with_capacity(urls.len());
+        for (rank, url) in urls.iter().enumerate() {
+            let known = endpoints.urls.iter().position(|known| known == url);
+            statuses.push(match known {
+                Some(known) => RpcEndpointStatus {
+                    rank: rank as u32,
+                    ..endpoints.statuses[known].clone()
+                },
+                None => unchecked(rank, url),
+            });
+        }
+        endpoints.urls = urls;
+        endpoints.statuses = statuses;
+    }
+
+    /// The endpoint calls should go to now.
+    ///
+    /// Take it once per transaction: the client built from it sends and
+    /// confirms on the same endpoint, even when another one becomes active
+    /// meanwhile.
+    pub fn active_url(&self) -> String {
+        let endpoints = self.read();
+        endpoints.urls[active_index(&endpoints.statuses)].clone()
+    }
+
+    /// WebSocket endpoint of the node calls go to now.
+    pub fn active_ws_url(&self) -> String {
+        let endpoints = self.read();
+        let url = &endpoints.urls[active_index(&endpoints.statuses)];
+        match endpoints.ws_urls.get(url) {
+            Some(ws_url) => ws_url.clone(),
+            None => websocket_url(url),
+        }
+    }
+
+    /// Status of every endpoint, by preference.
+    pub fn statuses(&self) -> Vec<RpcEndpointStatus> {
+        let mut statuses = self.read().statuses.clone();
+        let active = active_index(&statuses);
+        statuses[active].active = true;
+        statuses
+    }
+
+    /// Check every endpoint with `getHealth` and `getSlot` and route calls to
+    /// the best one.
+    pub async fn check_health(&self) -> Vec<RpcEndpointStatus> {
+        let urls = self.read().urls.clone();
+        let mut results = Vec::with_capacity(urls.len());
+        for url in &urls {
+            results.push(endpoint_health(url.clone(), HEALTH_CHECK_TIMEOUT).await);
+        }
+        self.record_health(&urls, results, now());
+        self.statuses()
+    }
+
+    /// Mark `url` unhealthy until its next health check, so calls fail over
+    /// to the next endpoint.
+    pub fn report_failure(&self, url: &str, error: &str) {
+        let mut endpoints = self.write();
+        let Some(index) = endpoints.urls.iter().position(|known| known == url) else {
+            return;
+        };
+        let active = active_index(&endpoints.statuses);
+        endpoints.statuses[index].healthy = false;
+        endpoints.statuses[index].error = Some(error.to_string());
+        endpoints.log_failover(active);
+    }
+
+    /// Run an idempotent read, retrying it on the other endpoints when the
+    /// active one fails with a network error.
+    pub async fn read_with_failover<T, F, Fut>(&self, read: F) -> Result<T, ErrorResponse>
+    where
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = Result<T, ErrorResponse>>,
+    {
+        let (urls, active) = {
+            let endpoints = self.read();
+            (endpoints.urls.clone(), active_index(&endpoints.statuses))
+        };
+        let order = std::iter::once(active).chain((0..urls.len()).filter(|&i| i != active));
+        let mut last_error = None;
+        for index in order {
+            let url = &urls[index];
+            match read(url.clone()).await {
+                Err(ErrorResponse::Error { code, message }) if is_retryable(&code) => {
+                    self.report_failure(url, &message);
+                    last_error = Some(ErrorResponse::Error { code, message });
+                }
+                result => return result,
+            }
+        }
+        Err(last_error.unwrap_or(ErrorResponse::Error {
+            code: ErrorCode::NetworkError,
+            message: "No RPC endpoint".to_string(),
+        }))
+    }
+
+    /// Record the health checks of `urls`, unless the endpoints changed while
+    /// they ran.
+    fn record_health(
+        &self,
+        urls: &[String],
+        results: Vec<Result<EndpointHealth, ErrorResponse>>,
+        checked_at: i64,
+    ) {
+        let highest_slot = results
+            .iter()
+            .filter_map(|result| result.as_ref().ok().map(|health| health.slot))
+            .max();
+        let mut endpoints = self.write();
+        if endpoints.urls != urls {
+            return;
+        }
+        let active = active_index(&endpoints.statuses);
+        for (status, result) in endpoints.statuses.iter_mut().zip(results) {
+            status.checked_at = Some(checked_at);
+            match result {
+                Ok(health) => {
+                    status.healthy = true;
+                    status.slot = Some(health.slot);
+                    status.slot_lag =
+                        highest_slot.map(|highest| highest.saturating_sub(health.slot));
+                    status.latency_ms = Some(health.latency.as_millis() as u64);
+                    status.error = None;
+                }
+                Err(ErrorResponse::Error { message, .. }) => {
+                    status.healthy = false;
+                    status.slot = None;
+                    status.slot_lag = None;
+                    status.latency_ms = None;
+                    status.error = Some(message);
+                }
+            }
+        }
+        endpoints.log_failover(active);
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, Endpoints> {
+        self.endpoints.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, Endpoints> {
+        self.endpoints.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Endpoints {
+    /// Log when the active endpoint is no longer the one at `from`.
+    fn log_failover(&self, from: usize) {
+        let to = active_index(&self.statuses);
+        if from != to {
+            warn!(
+                "RPC failover from {} to {}",
+                host(&self.urls[from]),
+                host(&self.urls[to])
+            );
+        }
+    }
+}
+
+/// Check a custom network before it is saved or used.
+///
+/// The expected genesis hash must be the one of the cluster, and the
+/// endpoint must serve it, so a wallet is never pointed at another cluster
+/// than it thinks. A WebSocket endpoint set apart from it must notify
+/// slots of the same cluster.
+pub async fn verify_network(network: &CustomNetwork) -> Result<(), ErrorResponse> {
+    network.validate()?;
+    let genesis_hash = endpoint_genesis_hash(network.rpc_url.clone(), HEALTH_CHECK_TIMEOUT).await?;
+    if genesis_hash != network.genesis_hash {
+        return Err(ErrorResponse::Error {
+            code: ErrorCode::GenesisMismatch,
+            message: format!(
+                "{} serves the cluster with genesis hash {}, not {}",
+                host(&network.rpc_url),
+                genesis_hash,
+                network.genesis_hash
+            ),
+        });
+    }
+
+    if let Some(ws_url) = &network.ws_url {
+        let ws_slot = websocket_slot(ws_url.clone(), HEALTH_CHECK_TIMEOUT).await?;
+        let rpc_slot = endpoint_health(network.rpc_url.clone(), HEALTH_CHECK_TIMEOUT)
+            .await?
+            .slot;
+        if ws_slot.abs_diff(rpc_slot) > MAX_WEBSOCKET_SLOT_DISTANCE {
+            return Err(ErrorResponse::Error {
+                code: ErrorCode::GenesisMismatch,
+                message: format!(
+                    "{} is at slot {}, {} at slot {}",
+                    host(ws_url),
+                    ws_slot,
+                    host(&network.rpc_url),
+                    rpc_slot
+                ),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// The networks of `environment` serving the cluster it uses: the public
+/// cluster, or the one of the first local network.
+fn cluster_networks(networks: &[CustomNetwork], environment: Environment) -> Vec<&CustomNetwork> {
+    let mut networks = networks
+        .iter()
+        .filter(|network| network.environment == environment)
+        .peekable();
+    let Some(genesis_hash) = environment
+        .genesis_hash()
+        .or_else(|| Some(networks.peek()?.genesis_hash.as_str()))
+    else {
+        return Vec::new();
+    };
+    networks
+        .filter(|network| {
+            let same_cluster = network.genesis_hash == genesis_hash;
+            if !same_cluster {
+                warn!(
+                    "{} is another {} cluster than {}, calls do not fail over to it",
+                    network.name, environment, genesis_hash
+                );
+            }
+            same_cluster
+        })
+        .collect()
+}
+
+/// Status of an endpoint not checked yet.
+fn unchecked(rank: usize, url: &str) -> RpcEndpointStatus {
+    RpcEndpointStatus {
+        host: host(url),
+        rank: rank as u32,
+        healthy: true,
+        active: false,
+        slot: None,
+        slot_lag: None,
+        latency_ms: None,
+        error: None,
+        checked_at: None,
+    }
+}
+
+/// The first endpoint that is healthy and keeps up, or the first one.
+fn active_index(statuses: &[RpcEndpointStatus]) -> usize {
+    statuses
+        .iter()
+        .position(|status| status.healthy && status.slot_lag.is_none_or(|lag| lag <= MAX_SLOT_LAG))
+        .unwrap_or_default()
+}
+
+/// Errors another endpoint may not return.
+fn is_retryable(code: &ErrorCode) -> bool {
+    matches!(code, ErrorCode::NetworkError | ErrorCode::BalanceError)
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn urls() -> Vec<String> {
+        vec![
+            "https://primary.example.com/?api-key=secret".to_string(),
+            "https://fallback.example.com".to_string(),
+        ]
+    }
+
+    fn endpoints() -> RpcEndpoints {
+        RpcEndpoints::new(urls())
+    }
+
+    fn healthy(slot: u64) -> Result<EndpointHealth, ErrorResponse> {
+        Ok(EndpointHealth {
+            slot,
+            latency: Duration::from_millis(20),
+        })
+    }
+
+    fn down() -> Result<EndpointHealth, ErrorResponse> {
+        Err(ErrorResponse::Error {
+            code: ErrorCode::NetworkError,
+            message: "connection refused".to_string(),
+        })
+    }
+
+    #[test]
+    fn test_host_hides_api_key() {
+        let statuses = endpoints().statuses();
+        assert_eq!(statuses[0].host, "primary.example.com");
+        assert!(statuses[0].active);
+        assert!(!statuses[1].active);
+    }
+
+    #[test]
+    fn test_failover_on_health_check() {
+        let endpoints = endpoints();
+        endpoints.record_health(&urls(), vec![down(), healthy(100)], 0);
+        assert_eq!(endpoints.active_url(), "https://fallback.example.com");
+
+        // The primary is preferred again once it recovers.
+        endpoints.record_health(&urls(), vec![healthy(100), healthy(100)], 1);
+        assert!(endpoints.active_url().starts_with("https://primary"));
+    }
+
+    #[test]
+    fn test_failover_on_slot_lag() {
+        let endpoints = endpoints();
+        endpoints.record_health(
+            &urls(),
+            vec![healthy(1_000), healthy(1_000 + MAX_SLOT_LAG + 1)],
+            0,
+        );
+        let statuses = endpoints.statuses();
+        assert_eq!(statuses[0].slot_lag, Some(MAX_SLOT_LAG + 1));
+        assert!(statuses[1].active);
+    }
+
+    #[test]
+    fn test_health_check_of_replaced_endpoints() {
+        let endpoints = endpoints();
+        endpoints.set_urls(vec!["https://custom.example.com".to_string()]);
+        endpoints.record_health(&urls(), vec![down(), healthy(100)], 0);
+        assert!(endpoints.statuses()[0].healthy);
+    }
+
+    #[test]
+    fn test_set_urls() {
+        let endpoints = endpoints();
+        endpoints.record_health(&urls(), vec![healthy(100), down()], 0);
+        let mut custom_first = urls();
+        custom_first.insert(0, "https://custom.example.com".to_string());
+        endpoints.set_urls(custom_first);
+
+        let statuses = endpoints.statuses();
+        assert_eq!(statuses[0].host, "custom.example.com");
+        assert_eq!(statuses[0].checked_at, None);
+        assert!(statuses[0].active);
+        // What is known of the built-in endpoints is kept.
+        assert_eq!((statuses[1].rank, statuses[1].slot), (1, Some(100)));
+        assert!(!statuses[2].healthy);
+    }
+
+    #[test]
+    fn test_report_failure() {
+        let endpoints = endpoints();
+        let primary = endpoints.active_url();
+        endpoints.report_failure(&primary, "timed out");
+        assert_eq!(endpoints.active_url(), "https://fallback.example.com");
+
+        // With every endpoint down the first one is still used.
+        endpoints.report_failure("https://fallback.example.com", "timed out");
+        assert_eq!(endpoints.active_url(), primary);
+    }
+
+    #[test]
+    fn test_cluster_networks() {
+        let network = |name: &str, environment: Environment, genesis_hash: &str| CustomNetwork {
+            name: name.to_string(),
+            environment,
+            rpc_url: format!("http://{}.example.com", name),
+            ws_url: None,
+            genesis_hash: genesis_hash.to_string(),
+        };
+        let networks = [
+            network("validator", Environment::Local, "first"),
+            network("other", Environment::Local, "second"),
+            network("replica", Environment::Local, "first"),
+            network("helius", Environment::Mainnet, "mainnet"),
+        ];
+
+        let names = |environment| {
+            cluster_networks(&networks, environment)
+                .iter()
+                .map(|network| network.name.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(Environment::Local), vec!["validator", "replica"]);
+        assert!(names(Environment::Devnet).is_empty());
+    }
+
+    #[test]
+    fn test_shared_endpoints() {
+        let local = RpcEndpoints::shared(Environment::Local);
+        assert_eq!(local.active_url(), "http://localhost:8899");
+        assert_eq!(local.active_ws_url(), "ws://localhost:8900");
+    }
+}
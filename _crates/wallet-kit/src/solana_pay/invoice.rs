@@ -0,0 +1,369 @@
+use {
+    crate::{
+        models::solana_pay::{Invoice, InvoiceStatus, TransferRequest},
+        solana_pay::{transfer_request::encode_transfer_request, SolanaPayError},
+        transactions::TransactionError,
+        units::to_base_units,
+    },
+    log::{debug, info, warn},
+    smbcloud_wallet_constants::constants::SOL_DECIMALS,
+    smbcloud_wallet_core_model::models::environment::Environment,
+    solana_client::{
+        nonblocking::rpc_client::RpcClient, rpc_client::GetConfirmedSignaturesForAddress2Config,
+        rpc_config::RpcTransactionConfig,
+    },
+    solana_sdk::{
+        commitment_config::CommitmentConfig,
+        pubkey::Pubkey,
+        signature::{Keypair, Signature, Signer},
+    },
+    solana_transaction_status_client_types::{
+        option_serializer::OptionSerializer, EncodedTransactionWithStatusMeta,
+        UiTransactionEncoding, UiTransactionTokenBalance,
+    },
+    std::{
+        str::FromStr,
+        time::{Duration, SystemTime, UNIX_EPOCH},
+    },
+};
+
+/// Create an invoice for `request` on `network` that expires after `expires_in` seconds.
+///
+/// A fresh reference key is generated for every invoice; any reference in
+/// `request` is replaced. The amount is required.
+pub fn create_invoice(
+    request: TransferRequest,
+    network: Environment,
+    expires_in: i64,
+) -> Result<Invoice, SolanaPayError> {
+    Pubkey::from_str(&request.recipient)
+        .map_err(|_| SolanaPayError::InvalidRecipient(request.recipient.clone()))?;
+    if let Some(spl_token) = &request.spl_token {
+        Pubkey::from_str(spl_token)
+            .map_err(|_| SolanaPayError::InvalidSplToken(spl_token.clone()))?;
+    }
+    let amount = request.amount.ok_or(SolanaPayError::MissingAmount)?;
+    if !amount.is_finite() || amount <= 0.0 {
+        return Err(SolanaPayError::InvalidAmount(amount.to_string()));
+    }
+
+    let reference = Keypair::new().pubkey().to_string();
+    let created_at = unix_timestamp();
+    let request = TransferRequest {
+        reference: vec![reference.clone()],
+        ..request
+    };
+
+    Ok(Invoice {
+        uri: encode_transfer_request(&request),
+        reference,
+        network,
+        recipient: request.recipient,
+        amount,
+        spl_token: request.spl_token,
+        label: request.label,
+        message: request.message,
+        memo: request.memo,
+        created_at,
+        expires_at: created_at + expires_in,
+        status: InvoiceStatus::Pending,
+        received_amount: 0.0,
+        signatures: Vec::new(),
+    })
+}
+
+/// The payments found for an invoice so far.
+///
+/// A watcher keeps one across its checks, so each check only fetches the
+/// transactions that referenced the invoice since the previous one.
+#[derive(Default)]
+struct PaymentScan {
+    /// Amount received, in base units.
+    received: u64,
+    /// Decimals of the invoiced mint, once a payment revealed them.
+    decimals: Option<u8>,
+    signatures: Vec<String>,
+    /// The newest referencing transaction already looked at.
+    until: Option<Signature>,
+}
+
+/// Look up the payments referencing the invoice and update its status.
+///
+/// Every successful transaction carrying the reference counts towards the
+/// amount, so a customer can top up an underpaid invoice. Only transfers of
+/// the invoiced mint to the invoiced recipient are counted.
+pub async fn check_invoice(rpc_url: String, invoice: Invoice) -> Result<Invoice, SolanaPayError> {
+    if invoice.status == InvoiceStatus::Paid {
+        return Ok(invoice);
+    }
+    let rpc_client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+    let mut scan = PaymentScan::default();
+    scan_payments(&rpc_client, &invoice, &mut scan).await?;
+    Ok(apply_scan(invoice, &scan))
+}
+
+/// Poll the chain every `interval` until the invoice is paid or expired.
+///
+/// `on_update` is called whenever the status or the received amount changes.
+/// A failing RPC call is logged and retried on the next poll.
+pub async fn watch_invoice<F>(
+    rpc_url: String,
+    mut invoice: Invoice,
+    interval: Duration,
+    mut on_update: F,
+) -> Result<Invoice, SolanaPayError>
+where
+    F: FnMut(&Invoice),
+{
+    if invoice.status == InvoiceStatus::Paid {
+        return Ok(invoice);
+    }
+    let rpc_client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+    let mut scan = PaymentScan::default();
+    loop {
+        let settled = invoice.is_settled(unix_timestamp());
+        match scan_payments(&rpc_client, &invoice, &mut scan).await {
+            Ok(()) => {
+                let updated = apply_scan(invoice.clone(), &scan);
+                if updated != invoice {
+                    on_update(&updated);
+                }
+                invoice = updated;
+                // The last check after expiry decides between expired and underpaid.
+                if settled {
+                    return Ok(invoice);
+                }
+            }
+            Err(SolanaPayError::Transaction(TransactionError::ConnectionError(e))) => {
+                warn!(
+                    "Failed to check invoice {}, retrying: {}",
+                    invoice.reference, e
+                );
+            }
+            Err(e) => return Err(e),
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
+
+/// Add the payments referencing the invoice since the last scan to `scan`.
+async fn scan_payments(
+    rpc_client: &RpcClient,
+    invoice: &Invoice,
+    scan: &mut PaymentScan,
+) -> Result<(), SolanaPayError> {
+    let reference = Pubkey::from_str(&invoice.reference)
+        .map_err(|_| SolanaPayError::InvalidReference(invoice.reference.clone()))?;
+    let recipient = Pubkey::from_str(&invoice.recipient)
+        .map_err(|_| SolanaPayError::InvalidRecipient(invoice.recipient.clone()))?;
+
+    let config = GetConfirmedSignaturesForAddress2Config {
+        until: scan.until,
+        commitment: Some(CommitmentConfig::confirmed()),
+        ..GetConfirmedSignaturesForAddress2Config::default()
+    };
+    let statuses = rpc_client
+        .get_signatures_for_address_with_config(&reference, config)
+        .await
+        .map_err(|e| TransactionError::ConnectionError(e.to_string()))?;
+
+    // Signatures are returned newest first. The scan only moves past a
+    // transaction once it is counted, so a failed fetch is retried.
+    for status in statuses.iter().rev() {
+        let signature = Signature::from_str(&status.signature)
+            .map_err(|e| TransactionError::TransactionError(e.to_string()))?;
+        if status.err.is_none() {
+            let config = RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Base64),
+                commitment: Some(CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            };
+            let transaction = rpc_client
+                .get_transaction_with_config(&signature, config)
+                .await
+                .map_err(|e| TransactionError::ConnectionError(e.to_string()))?;
+
+            match received_amount(
+                &transaction.transaction,
+                &recipient,
+                invoice.spl_token.as_deref(),
+            ) {
+                Some((amount, mint_decimals)) => {
+                    debug!("{} paid {} base units", status.signature, amount);
+                    scan.received += amount;
+                    scan.decimals = Some(mint_decimals);
+                    scan.signatures.push(status.signature.clone());
+                }
+                None => debug!("{} does not pay the invoice", status.signature),
+            }
+        }
+        scan.until = Some(signature);
+    }
+    Ok(())
+}
+
+/// The invoice with the status and amount of the payments in `scan`.
+fn apply_scan(mut invoice: Invoice, scan: &PaymentScan) -> Invoice {
+    let expired = unix_timestamp() >= invoice.expires_at;
+    let decimals = scan.decimals.unwrap_or(SOL_DECIMALS);
+    let expected = to_base_units(invoice.amount, decimals);
+    let status = invoice_status(expected, scan.received, expired);
+    if status != invoice.status {
+        info!("Invoice {} is now {:?}", invoice.reference, status);
+    }
+    invoice.status = status;
+    invoice.received_amount = scan.received as f64 / 10f64.powi(decimals as i32);
+    invoice.signatures = scan.signatures.clone();
+    invoice
+}
+
+/// Amount received by `recipient` in a transaction, in base units, and the mint decimals.
+fn received_amount(
+    transaction: &EncodedTransactionWithStatusMeta,
+    recipient: &Pubkey,
+    spl_token: Option<&str>,
+) -> Option<(u64, u8)> {
+    let meta = transaction.meta.as_ref()?;
+    if meta.err.is_some() {
+        return None;
+    }
+
+    match spl_token {
+        None => {
+            let decoded = transaction.transaction.decode()?;
+            let index = decoded
+                .message
+                .static_account_keys()
+                .iter()
+                .position(|key| key == recipient)?;
+            let pre = *meta.pre_balances.get(index)?;
+            let post = *meta.post_balances.get(index)?;
+            (post > pre).then(|| (post - pre, SOL_DECIMALS))
+        }
+        Some(mint) => {
+            let owner = recipient.to_string();
+            let (pre, _) = token_balance(&meta.pre_token_balances, &owner, mint);
+            let (post, decimals) = token_balance(&meta.post_token_balances, &owner, mint);
+            let decimals = decimals?;
+            (post > pre).then(|| (post - pre, decimals))
+        }
+    }
+}
+
+/// Total balance of `owner` token accounts for `mint`, in base units.
+fn token_balance(
+    balances: &OptionSerializer<Vec<UiTransactionTokenBalance>>,
+    owner: &str,
+    mint: &str,
+) -> (u64, Option<u8>) {
+    let OptionSerializer::Some(balances) = balances else {
+        return (0, None);
+    };
+    balances
+        .iter()
+        .filter(|balance| balance.mint == mint)
+        .filter(|balance| matches!(&balance.owner, OptionSerializer::Some(o) if o == owner))
+        .fold((0, None), |(total, _), balance| {
+            let amount = balance.ui_token_amount.amount.parse::<u64>().unwrap_or(0);
+            (total + amount, Some(balance.ui_token_amount.decimals))
+        })
+}
+
+fn invoice_status(expected: u64, received: u64, expired: bool) -> InvoiceStatus {
+    if received >= expected {
+        InvoiceStatus::Paid
+    } else if received > 0 {
+        InvoiceStatus::Underpaid
+    } else if expired {
+        InvoiceStatus::Expired
+    } else {
+        InvoiceStatus::Pending
+    }
+}
+
+fn unix_timestamp() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::solana_pay::transfer_request::parse_transfer_request};
+
+    const RECIPIENT: &str = "mvines9iiHiQTysrwkJjGf2gb9Ex9jXJX8ns3qwf2kN";
+    const USDC: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+    fn request() -> TransferRequest {
+        let mut request = TransferRequest::new(RECIPIENT);
+        request.amount = Some(12.5);
+        request.spl_token = Some(USDC.to_string());
+        request.label = Some("Bach Coffee".to_string());
+        request
+    }
+
+    #[test]
+    fn test_create_invoice() {
+        let invoice = create_invoice(request(), Environment::Mainnet, 600).unwrap();
+        assert_eq!(invoice.status, InvoiceStatus::Pending);
+        assert_eq!(invoice.expires_at - invoice.created_at, 600);
+        assert!(Pubkey::from_str(&invoice.reference).is_ok());
+
+        let parsed = parse_transfer_request(&invoice.uri).unwrap();
+        assert_eq!(parsed, invoice.transfer_request());
+        assert_eq!(parsed.reference, vec![invoice.reference.clone()]);
+
+        let other = create_invoice(request(), Environment::Mainnet, 600).unwrap();
+        assert_ne!(invoice.reference, other.reference);
+    }
+
+    #[test]
+    fn test_create_invoice_requires_amount() {
+        let mut without_amount = request();
+        without_amount.amount = None;
+        assert!(matches!(
+            create_invoice(without_amount, Environment::Mainnet, 600),
+            Err(SolanaPayError::MissingAmount)
+        ));
+        let mut zero = request();
+        zero.amount = Some(0.0);
+        assert!(matches!(
+            create_invoice(zero, Environment::Mainnet, 600),
+            Err(SolanaPayError::InvalidAmount(_))
+        ));
+    }
+
+    #[test]
+    fn test_invoice_status() {
+        let expected = to_base_units(12.5, 6);
+        assert_eq!(expected, 12_500_000);
+        assert_eq!(invoice_status(expected, 0, false), InvoiceStatus::Pending);
+        assert_eq!(invoice_status(expected, 0, true), InvoiceStatus::Expired);
+        assert_eq!(
+            invoice_status(expected, 12_000_000, false),
+            InvoiceStatus::Underpaid
+        );
+        assert_eq!(
+            invoice_status(expected, 12_000_000, true),
+            InvoiceStatus::Underpaid
+        );
+        assert_eq!(
+            invoice_status(expected, expected, true),
+            InvoiceStatus::Paid
+        );
+        assert_eq!(
+            invoice_status(expected, expected + 1, false),
+            InvoiceStatus::Paid
+        );
+    }
+
+    #[test]
+    fn test_to_base_units_rounding() {
+        // 0.1 + 0.2 style float noise must not cause an underpaid invoice.
+        assert_eq!(to_base_units(0.3, 9), 300_000_000);
+        assert_eq!(to_base_units(1.005, 6), 1_005_000);
+        // Paying 2.01 must match what the invoice expects.
+        assert_eq!(to_base_units(2.01, 6), 2_010_000);
+    }
+}
@@ -0,0 +1,95 @@
+use {
+    crate::{
+        models::solana_pay::SolanaPayRequest,
+        solana_pay::{
+            transaction_request::parse_transaction_request,
+            transfer_request::parse_transfer_request,
+        },
+        transactions::TransactionError,
+    },
+    smbcloud_wallet_constants::constants::{NOTWALLET_DEEP_LINK_SCHEME, SOLANA_PAY_SCHEME},
+    thiserror::Error,
+};
+
+pub mod invoice;
+pub mod pay;
+pub mod transaction_request;
+pub mod transfer_request;
+
+#[derive(Error, Debug)]
+pub enum SolanaPayError {
+    #[error("Invalid Solana Pay URI: {0}")]
+    InvalidUri(String),
+
+    #[error("Invalid recipient: {0}")]
+    InvalidRecipient(String),
+
+    #[error("Invalid amount: {0}")]
+    InvalidAmount(String),
+
+    #[error("Invalid spl-token: {0}")]
+    InvalidSplToken(String),
+
+    #[error("Invalid reference: {0}")]
+    InvalidReference(String),
+
+    #[error("Amount is required to pay this request")]
+    MissingAmount,
+
+    #[error("Expected a transfer request, got a transaction request")]
+    TransactionRequestNotSupported,
+
+    #[error("Merchant request failed: {0}")]
+    NetworkError(String),
+
+    #[error("Invalid transaction: {0}")]
+    InvalidTransaction(String),
+
+    #[error("Transaction requires an unexpected signature: {0}")]
+    UnexpectedSigner(String),
+
+    #[error("Transaction calls an unexpected program: {0}")]
+    UnexpectedProgram(String),
+
+    #[error("Transaction carries an unexpected instruction: {0}")]
+    UnexpectedInstruction(String),
+
+    #[error(transparent)]
+    Transaction(#[from] TransactionError),
+}
+
+/// Parse any Solana Pay URI, dispatching on the kind of request it carries.
+pub fn parse_solana_pay_uri(uri: &str) -> Result<SolanaPayRequest, SolanaPayError> {
+    if is_transaction_request(uri) {
+        Ok(SolanaPayRequest::Transaction {
+            request: parse_transaction_request(uri)?,
+        })
+    } else {
+        Ok(SolanaPayRequest::Transfer {
+            request: parse_transfer_request(uri)?,
+        })
+    }
+}
+
+/// Rewrite `notwallet://…` and `notwallet:…` deep links into `solana:…` URIs.
+pub(crate) fn normalize_uri(uri: &str) -> String {
+    let uri = uri.trim();
+    let deep_link = format!("{}:", NOTWALLET_DEEP_LINK_SCHEME);
+    match uri.strip_prefix(&deep_link) {
+        Some(rest) => format!(
+            "{}:{}",
+            SOLANA_PAY_SCHEME,
+            rest.strip_prefix("//").unwrap_or(rest)
+        ),
+        None => uri.to_string(),
+    }
+}
+
+/// Transaction requests carry an `https` link instead of a recipient.
+pub(crate) fn is_transaction_request(uri: &str) -> bool {
+    let uri = normalize_uri(uri);
+    let prefix = format!("{}:", SOLANA_PAY_SCHEME);
+    uri.strip_prefix(&prefix)
+        .map(|rest| rest.to_lowercase().starts_with("https"))
+        .unwrap_or(false)
+}
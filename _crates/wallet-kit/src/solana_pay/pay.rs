@@ -0,0 +1,156 @@
+use {
+    crate::{
+        models::solana_pay::TransferRequest,
+        solana_pay::{transfer_request::parse_transfer_request, SolanaPayError},
+        transactions::{
+            create_token_transfer_ix_with_options, create_transfer_ix_with_options, TransferOptions,
+        },
+        units::parse_base_units,
+    },
+    log::info,
+    smbcloud_wallet_constants::constants::{
+        SOL_DECIMALS, SPL_TOKEN_2022_PROGRAM_ID, SPL_TOKEN_PROGRAM_ID,
+    },
+    smbcloud_wallet_core_rpc::token_metadata::parse_mint_decimals,
+    solana_client::nonblocking::rpc_client::RpcClient,
+    solana_sdk::{pubkey::Pubkey, signature::Keypair},
+    std::str::FromStr,
+};
+
+/// Parse a Solana Pay transfer request URI and pay it.
+///
+/// `amount` is only used when the request does not specify one, e.g. a
+/// donation QR code where the user types the amount.
+pub async fn pay_solana_pay_uri(
+    rpc_url: String,
+    sender_keypair: Keypair,
+    from_pubkey: String,
+    uri: String,
+    amount: Option<f64>,
+) -> Result<String, SolanaPayError> {
+    let request = parse_transfer_request(&uri)?;
+    pay_transfer_request(rpc_url, sender_keypair, from_pubkey, request, amount).await
+}
+
+/// Pay an already parsed transfer request.
+///
+/// The recipient receives exactly the requested amount; the treasury fee is
+/// charged on top so merchants can validate the payment. Tokens are sent with
+/// a checked transfer to the associated token account of the recipient, in
+/// the program that owns the mint, after checking the amount against the
+/// decimals of the mint read from chain. Reference keys are attached to the
+/// transfer instruction and the memo, if any, is sent as an SPL Memo instruction.
+pub async fn pay_transfer_request(
+    rpc_url: String,
+    sender_keypair: Keypair,
+    from_pubkey: String,
+    request: TransferRequest,
+    amount: Option<f64>,
+) -> Result<String, SolanaPayError> {
+    let amount = request
+        .amount
+        .or(amount)
+        .ok_or(SolanaPayError::MissingAmount)?;
+
+    let references = request
+        .reference
+        .iter()
+        .map(|reference| {
+            Pubkey::from_str(reference)
+                .map_err(|_| SolanaPayError::InvalidReference(reference.clone()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let options = TransferOptions {
+        references,
+        memo: request.memo.clone(),
+        fee_on_top: true,
+        skip_confirmation: false,
+    };
+
+    info!(
+        "Paying Solana Pay request: {} {} to {}",
+        amount,
+        request.spl_token.as_deref().unwrap_or("SOL"),
+        request.recipient
+    );
+
+    let signature = match request.spl_token {
+        None => {
+            check_amount(amount, SOL_DECIMALS)?;
+            create_transfer_ix_with_options(
+                rpc_url,
+                sender_keypair,
+                from_pubkey,
+                request.recipient,
+                amount,
+                options,
+            )
+            .await?
+        }
+        Some(spl_token) => {
+            let (token_program, decimals) = token_mint(&rpc_url, &spl_token).await?;
+            check_amount(amount, decimals)?;
+            create_token_transfer_ix_with_options(
+                rpc_url,
+                sender_keypair,
+                from_pubkey,
+                request.recipient,
+                spl_token,
+                token_program,
+                amount,
+                options,
+            )
+            .await?
+        }
+    };
+
+    Ok(signature)
+}
+
+/// The token program that owns `mint`, either Token or Token-2022, and the mint decimals.
+async fn token_mint(rpc_url: &str, mint: &str) -> Result<(String, u8), SolanaPayError> {
+    let mint_pubkey =
+        Pubkey::from_str(mint).map_err(|_| SolanaPayError::InvalidSplToken(mint.to_string()))?;
+    let account = RpcClient::new(rpc_url.to_string())
+        .get_account(&mint_pubkey)
+        .await
+        .map_err(|e| SolanaPayError::NetworkError(e.to_string()))?;
+    let owner = account.owner.to_string();
+    match owner.as_str() {
+        SPL_TOKEN_PROGRAM_ID | SPL_TOKEN_2022_PROGRAM_ID => {
+            let decimals = parse_mint_decimals(&account.data)
+                .ok_or_else(|| SolanaPayError::InvalidSplToken(mint.to_string()))?;
+            Ok((owner, decimals))
+        }
+        _ => Err(SolanaPayError::InvalidSplToken(mint.to_string())),
+    }
+}
+
+/// Refuse amounts with more fraction digits than the mint has decimals,
+/// which could not be paid exactly.
+fn check_amount(amount: f64, decimals: u8) -> Result<(), SolanaPayError> {
+    let value = amount.to_string();
+    if amount <= 0.0 || parse_base_units(&value, decimals).is_none() {
+        return Err(SolanaPayError::InvalidAmount(value));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_amount() {
+        assert!(check_amount(2.01, 6).is_ok());
+        assert!(check_amount(1.0, 0).is_ok());
+        assert!(check_amount(0.000000001, SOL_DECIMALS).is_ok());
+        assert!(matches!(
+            check_amount(1.1234567, 6),
+            Err(SolanaPayError::InvalidAmount(_))
+        ));
+        assert!(check_amount(0.0000000001, SOL_DECIMALS).is_err());
+        assert!(check_amount(0.0, 6).is_err());
+    }
+}
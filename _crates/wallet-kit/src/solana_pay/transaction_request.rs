@@ -0,0 +1,559 @@
+use {
+    crate::{
+        models::solana_pay::{
+            InstructionSummary, TransactionRequest, TransactionRequestMetadata,
+            TransactionRequestPayload, TransactionRequestPreview, TransactionRequestResponse,
+        },
+        solana_pay::{is_transaction_request, normalize_uri, SolanaPayError},
+        transactions::TransactionError,
+    },
+    base64::{engine::general_purpose, Engine as _},
+    log::{info, warn},
+    percent_encoding::percent_decode_str,
+    reqwest::{
+        header::{ACCEPT, CONTENT_TYPE},
+        Client,
+    },
+    smbcloud_wallet_constants::constants::{
+        ASSOCIATED_TOKEN_PROGRAM_ID, COMPUTE_BUDGET_PROGRAM_ID, LAMPORTS_PER_SOL,
+        SOLANA_PAY_SCHEME, SPL_MEMO_PROGRAM_ID, SPL_TOKEN_2022_PROGRAM_ID, SPL_TOKEN_PROGRAM_ID,
+        SYSTEM_PROGRAM_ID,
+    },
+    smbcloud_wallet_core_network::request,
+    solana_client::nonblocking::rpc_client::RpcClient,
+    solana_sdk::{
+        instruction::CompiledInstruction,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        transaction::VersionedTransaction,
+    },
+    std::{future::Future, str::FromStr},
+    url::{form_urlencoded, Url},
+};
+
+/// Programs a merchant transaction may call. Anything else is rejected before
+/// the user is asked to sign, as are the instructions of these programs that
+/// a payment does not need, see [`decode_instruction`].
+const ALLOWED_PROGRAMS: [(&str, &str); 6] = [
+    (SYSTEM_PROGRAM_ID, "System Program"),
+    (SPL_TOKEN_PROGRAM_ID, "Token Program"),
+    (SPL_TOKEN_2022_PROGRAM_ID, "Token-2022 Program"),
+    (
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+        "Associated Token Account Program",
+    ),
+    (SPL_MEMO_PROGRAM_ID, "Memo Program"),
+    (COMPUTE_BUDGET_PROGRAM_ID, "Compute Budget Program"),
+];
+
+/// Parse a Solana Pay transaction request URI.
+///
+/// The link is percent-decoded and must use `https`. Its own query string is
+/// kept as part of the link; `label` and `message` are read from the outer URI.
+pub fn parse_transaction_request(uri: &str) -> Result<TransactionRequest, SolanaPayError> {
+    if !is_transaction_request(uri) {
+        return Err(SolanaPayError::InvalidUri(
+            "Expected a transaction request link".to_string(),
+        ));
+    }
+    let uri = normalize_uri(uri);
+    let rest = &uri[SOLANA_PAY_SCHEME.len() + 1..];
+    let (encoded_link, query) = match rest.split_once('?') {
+        Some((link, query)) => (link, Some(query)),
+        None => (rest, None),
+    };
+
+    let link = percent_decode_str(encoded_link)
+        .decode_utf8()
+        .map_err(|e| SolanaPayError::InvalidUri(e.to_string()))?
+        .to_string();
+    check_link(&link)?;
+
+    let mut request = TransactionRequest {
+        link,
+        label: None,
+        message: None,
+    };
+    for (key, value) in form_urlencoded::parse(query.unwrap_or_default().as_bytes()) {
+        match key.as_ref() {
+            "label" => request.label = Some(value.to_string()),
+            "message" => request.message = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    Ok(request)
+}
+
+/// The merchant server behind a transaction request link.
+pub trait MerchantClient {
+    /// The merchant label and icon. Only used for display.
+    fn get_metadata(
+        &self,
+        link: &str,
+    ) -> impl Future<Output = Result<TransactionRequestMetadata, SolanaPayError>> + Send;
+
+    /// Send the wallet account to the merchant and receive the transaction to sign.
+    fn post_account(
+        &self,
+        link: &str,
+        account: &str,
+    ) -> impl Future<Output = Result<TransactionRequestResponse, SolanaPayError>> + Send;
+}
+
+/// Talks to merchants over HTTP.
+pub struct HttpMerchantClient {
+    client: Client,
+}
+
+impl HttpMerchantClient {
+    pub fn new(client: Client) -> Self {
+        HttpMerchantClient { client }
+    }
+}
+
+impl Default for HttpMerchantClient {
+    fn default() -> Self {
+        HttpMerchantClient::new(Client::new())
+    }
+}
+
+impl MerchantClient for HttpMerchantClient {
+    async fn get_metadata(&self, link: &str) -> Result<TransactionRequestMetadata, SolanaPayError> {
+        let builder = self.client.get(link).header(ACCEPT, "application/json");
+        request(builder)
+            .await
+            .map_err(|e| SolanaPayError::NetworkError(e.to_string()))
+    }
+
+    async fn post_account(
+        &self,
+        link: &str,
+        account: &str,
+    ) -> Result<TransactionRequestResponse, SolanaPayError> {
+        let payload = TransactionRequestPayload {
+            account: account.to_string(),
+        };
+        let builder = self
+            .client
+            .post(link)
+            .header(CONTENT_TYPE, "application/json")
+            .header(ACCEPT, "application/json")
+            .json(&payload);
+        request(builder)
+            .await
+            .map_err(|e| SolanaPayError::NetworkError(e.to_string()))
+    }
+}
+
+/// Decode a merchant transaction and check that it is safe to sign with `wallet`.
+///
+/// The wallet must be one of the required signers, every other required
+/// signature must already be present and valid, address lookup tables are
+/// not accepted, and every instruction must be a SOL or token transfer, the
+/// creation of an associated token account, a memo or a compute budget setting.
+/// Anything that could approve a delegate, change an authority, close an
+/// account or reassign the wallet is rejected.
+pub fn verify_transaction(
+    transaction: &str,
+    wallet: &Pubkey,
+) -> Result<VersionedTransaction, SolanaPayError> {
+    let bytes = general_purpose::STANDARD
+        .decode(transaction)
+        .map_err(|e| SolanaPayError::InvalidTransaction(e.to_string()))?;
+    let transaction: VersionedTransaction = bincode::deserialize(&bytes)
+        .map_err(|e| SolanaPayError::InvalidTransaction(e.to_string()))?;
+
+    let message = &transaction.message;
+    if message
+        .address_table_lookups()
+        .is_some_and(|lookups| !lookups.is_empty())
+    {
+        return Err(SolanaPayError::InvalidTransaction(
+            "Address lookup tables are not supported".to_string(),
+        ));
+    }
+
+    let keys = message.static_account_keys();
+    let num_signers = message.header().num_required_signatures as usize;
+    if num_signers > keys.len() || transaction.signatures.len() != num_signers {
+        return Err(SolanaPayError::InvalidTransaction(
+            "Signature count does not match the message header".to_string(),
+        ));
+    }
+
+    let wallet_index = keys[..num_signers]
+        .iter()
+        .position(|key| key == wallet)
+        .ok_or_else(|| SolanaPayError::InvalidTransaction(format!("{} is not a signer", wallet)))?;
+
+    let verified = transaction.verify_with_results();
+    for (index, key) in keys[..num_signers].iter().enumerate() {
+        if index != wallet_index && !verified[index] {
+            return Err(SolanaPayError::UnexpectedSigner(key.to_string()));
+        }
+    }
+
+    for instruction in message.instructions() {
+        let program_id = keys
+            .get(instruction.program_id_index as usize)
+            .ok_or_else(|| {
+                SolanaPayError::InvalidTransaction("Program index out of bounds".to_string())
+            })?;
+        if program_name(program_id).is_none() {
+            return Err(SolanaPayError::UnexpectedProgram(program_id.to_string()));
+        }
+        decode_instruction(&program_id.to_string(), &instruction.data)
+            .map_err(SolanaPayError::UnexpectedInstruction)?;
+    }
+
+    Ok(transaction)
+}
+
+/// Fetch a transaction request from the merchant and build a preview for the user.
+///
+/// The link must use `https`, whoever built the request. A failing `GET` does
+/// not block the request, the label from the URI is used instead.
+pub async fn fetch_transaction_request(
+    merchant: &impl MerchantClient,
+    request: TransactionRequest,
+    account: String,
+) -> Result<TransactionRequestPreview, SolanaPayError> {
+    check_link(&request.link)?;
+    let wallet = Pubkey::from_str(&account)
+        .map_err(|_| TransactionError::InvalidAddress(account.clone()))?;
+
+    let metadata = match merchant.get_metadata(&request.link).await {
+        Ok(metadata) => Some(metadata),
+        Err(e) => {
+            warn!("Failed to get transaction request metadata: {}", e);
+            None
+        }
+    };
+
+    let response = merchant.post_account(&request.link, &account).await?;
+    let transaction = verify_transaction(&response.transaction, &wallet)?;
+
+    let message = &transaction.message;
+    let keys = message.static_account_keys();
+    let writable_accounts = keys
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| is_writable(&transaction, *index))
+        .map(|(_, key)| key.to_string())
+        .collect();
+    let instructions = message
+        .instructions()
+        .iter()
+        .map(|instruction| summarize_instruction(instruction, keys))
+        .collect();
+
+    let (label, icon) = match metadata {
+        Some(metadata) => (Some(metadata.label), Some(metadata.icon)),
+        None => (request.label, None),
+    };
+
+    Ok(TransactionRequestPreview {
+        link: request.link,
+        label,
+        icon,
+        message: response.message.or(request.message),
+        transaction: response.transaction,
+        fee_payer: keys[0].to_string(),
+        writable_accounts,
+        instructions,
+    })
+}
+
+/// Sign a previously previewed merchant transaction and send it.
+///
+/// The transaction is verified again, and only the wallet signature is added;
+/// the message is never modified.
+pub async fn approve_transaction_request(
+    rpc_url: String,
+    keypair: Keypair,
+    transaction: String,
+) -> Result<String, SolanaPayError> {
+    let mut transaction = verify_transaction(&transaction, &keypair.pubkey())?;
+
+    let wallet_index = transaction
+        .message
+        .static_account_keys()
+        .iter()
+        .position(|key| *key == keypair.pubkey())
+        .ok_or_else(|| SolanaPayError::InvalidTransaction("Wallet is not a signer".to_string()))?;
+    transaction.signatures[wallet_index] = keypair.sign_message(&transaction.message.serialize());
+
+    info!("Sending Solana Pay transaction for {}", keypair.pubkey());
+    let rpc_client = RpcClient::new(rpc_url);
+    let signature = rpc_client
+        .send_and_confirm_transaction(&transaction)
+        .await
+        .map_err(|e| TransactionError::TransactionError(e.to_string()))?;
+
+    Ok(signature.to_string())
+}
+
+/// Parse a transaction request link, which must use `https`.
+fn check_link(link: &str) -> Result<Url, SolanaPayError> {
+    let url = Url::parse(link).map_err(|e| SolanaPayError::InvalidUri(e.to_string()))?;
+    if url.scheme() != "https" {
+        return Err(SolanaPayError::InvalidUri(format!(
+            "Transaction request link must use https: {}",
+            link
+        )));
+    }
+    Ok(url)
+}
+
+fn program_name(program_id: &Pubkey) -> Option<&'static str> {
+    let program_id = program_id.to_string();
+    ALLOWED_PROGRAMS
+        .iter()
+        .find(|(id, _)| *id == program_id)
+        .map(|(_, name)| *name)
+}
+
+fn is_writable(transaction: &VersionedTransaction, index: usize) -> bool {
+    let header = transaction.message.header();
+    let num_keys = transaction.message.static_account_keys().len();
+    let num_signers = header.num_required_signatures as usize;
+    if index < num_signers {
+        index < num_signers - header.num_readonly_signed_accounts as usize
+    } else {
+        index < num_keys.saturating_sub(header.num_readonly_unsigned_accounts as usize)
+    }
+}
+
+fn summarize_instruction(instruction: &CompiledInstruction, keys: &[Pubkey]) -> InstructionSummary {
+    let program_id = keys[instruction.program_id_index as usize].to_string();
+    let accounts = instruction
+        .accounts
+        .iter()
+        .filter_map(|index| keys.get(*index as usize))
+        .map(|key| key.to_string())
+        .collect();
+    // Verified transactions only carry instructions that decode.
+    let description = decode_instruction(&program_id, &instruction.data)
+        .unwrap_or_else(|kind| format!("Unexpected instruction: {}", kind));
+
+    InstructionSummary {
+        program: program_name(&keys[instruction.program_id_index as usize])
+            .unwrap_or("Unknown Program")
+            .to_string(),
+        program_id,
+        description,
+        accounts,
+    }
+}
+
+/// Describe an instruction a payment may carry, or return the kind of any
+/// other instruction so it can be rejected.
+fn decode_instruction(program_id: &str, data: &[u8]) -> Result<String, String> {
+    match program_id {
+        SYSTEM_PROGRAM_ID => match (read_u32(data, 0), read_u64(data, 4)) {
+            (Some(SYSTEM_TRANSFER), Some(lamports)) => Ok(format!(
+                "Transfer {} SOL",
+                lamports as f64 / LAMPORTS_PER_SOL
+            )),
+            (Some(tag), _) => Err(format!(
+                "System {}",
+                instruction_name(&SYSTEM_INSTRUCTIONS, tag)
+            )),
+            (None, _) => Err("System instruction without data".to_string()),
+        },
+        SPL_TOKEN_PROGRAM_ID | SPL_TOKEN_2022_PROGRAM_ID => match data.first() {
+            Some(&TOKEN_TRANSFER) => match read_u64(data, 1) {
+                Some(amount) => Ok(format!("Transfer {} token base units", amount)),
+                None => Err("Token Transfer without an amount".to_string()),
+            },
+            Some(&TOKEN_TRANSFER_CHECKED) => match (read_u64(data, 1), data.get(9)) {
+                (Some(amount), Some(decimals)) => Ok(format!(
+                    "Transfer {} tokens",
+                    amount as f64 / 10f64.powi(*decimals as i32)
+                )),
+                _ => Err("Token TransferChecked without an amount".to_string()),
+            },
+            Some(tag) => Err(format!(
+                "Token {}",
+                instruction_name(&TOKEN_INSTRUCTIONS, *tag as u32)
+            )),
+            None => Err("Token instruction without data".to_string()),
+        },
+        ASSOCIATED_TOKEN_PROGRAM_ID => match data.first() {
+            None | Some(0) => Ok("Create associated token account".to_string()),
+            Some(1) => Ok("Create associated token account if missing".to_string()),
+            Some(2) => Err("Associated Token Account RecoverNested".to_string()),
+            Some(tag) => Err(format!("Associated Token Account instruction {}", tag)),
+        },
+        SPL_MEMO_PROGRAM_ID => Ok(format!("Memo: {}", String::from_utf8_lossy(data))),
+        COMPUTE_BUDGET_PROGRAM_ID => match (data.first(), read_u32(data, 1), read_u64(data, 1)) {
+            (Some(1), Some(bytes), _) => Ok(format!("Request a heap of {} bytes", bytes)),
+            (Some(2), Some(units), _) => Ok(format!("Set compute unit limit to {}", units)),
+            (Some(3), _, Some(price)) => Ok(format!(
+                "Set compute unit price to {} micro-lamports",
+                price
+            )),
+            (Some(4), Some(bytes), _) => Ok(format!(
+                "Set loaded accounts data size limit to {} bytes",
+                bytes
+            )),
+            _ => Err("Compute Budget instruction".to_string()),
+        },
+        _ => Err(format!("Instruction of program {}", program_id)),
+    }
+}
+
+/// `SystemInstruction::Transfer`.
+const SYSTEM_TRANSFER: u32 = 2;
+
+/// `TokenInstruction::Transfer`.
+const TOKEN_TRANSFER: u8 = 3;
+
+/// `TokenInstruction::TransferChecked`.
+const TOKEN_TRANSFER_CHECKED: u8 = 12;
+
+/// `SystemInstruction` variants by tag.
+const SYSTEM_INSTRUCTIONS: [&str; 13] = [
+    "CreateAccount",
+    "Assign",
+    "Transfer",
+    "CreateAccountWithSeed",
+    "AdvanceNonceAccount",
+    "WithdrawNonceAccount",
+    "InitializeNonceAccount",
+    "AuthorizeNonceAccount",
+    "Allocate",
+    "AllocateWithSeed",
+    "AssignWithSeed",
+    "TransferWithSeed",
+    "UpgradeNonceAccount",
+];
+
+/// `TokenInstruction` variants by tag, shared by Token and Token-2022.
+const TOKEN_INSTRUCTIONS: [&str; 25] = [
+    "InitializeMint",
+    "InitializeAccount",
+    "InitializeMultisig",
+    "Transfer",
+    "Approve",
+    "Revoke",
+    "SetAuthority",
+    "MintTo",
+    "Burn",
+    "CloseAccount",
+    "FreezeAccount",
+    "ThawAccount",
+    "TransferChecked",
+    "ApproveChecked",
+    "MintToChecked",
+    "BurnChecked",
+    "InitializeAccount2",
+    "SyncNative",
+    "InitializeAccount3",
+    "InitializeMultisig2",
+    "InitializeMint2",
+    "GetAccountDataSize",
+    "InitializeImmutableOwner",
+    "AmountToUiAmount",
+    "UiAmountToAmount",
+];
+
+fn instruction_name(names: &[&str], tag: u32) -> String {
+    match names.get(tag as usize) {
+        Some(name) => name.to_string(),
+        None => format!("instruction {}", tag),
+    }
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset + 4)?;
+    Some(u32::from_le_bytes(bytes.try_into().ok()?))
+}
+
+fn read_u64(data: &[u8], offset: usize) -> Option<u64> {
+    let bytes = data.get(offset..offset + 8)?;
+    Some(u64::from_le_bytes(bytes.try_into().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*, crate::models::solana_pay::SolanaPayRequest,
+        crate::solana_pay::parse_solana_pay_uri,
+    };
+
+    #[test]
+    fn test_parse_transaction_request() {
+        let uri = "solana:https%3A%2F%2Fexample.com%2Fsolana-pay%3Forder%3D42?label=Bach%20Coffee&message=Thanks";
+        let request = parse_transaction_request(uri).unwrap();
+        assert_eq!(request.link, "https://example.com/solana-pay?order=42");
+        assert_eq!(request.label.as_deref(), Some("Bach Coffee"));
+        assert_eq!(request.message.as_deref(), Some("Thanks"));
+    }
+
+    #[test]
+    fn test_parse_transaction_request_deep_link() {
+        let uri = "notwallet://https%3A%2F%2Fexample.com%2Fpay";
+        let request = parse_transaction_request(uri).unwrap();
+        assert_eq!(request.link, "https://example.com/pay");
+        assert_eq!(request.label, None);
+    }
+
+    #[test]
+    fn test_parse_transaction_request_requires_https() {
+        assert!(parse_transaction_request("solana:http%3A%2F%2Fexample.com%2Fpay").is_err());
+        assert!(parse_transaction_request("solana:https%3A%2F%2F").is_err());
+    }
+
+    #[test]
+    fn test_parse_solana_pay_uri_dispatch() {
+        let transaction = parse_solana_pay_uri("solana:https%3A%2F%2Fexample.com%2Fpay").unwrap();
+        assert!(matches!(transaction, SolanaPayRequest::Transaction { .. }));
+        let transfer =
+            parse_solana_pay_uri("solana:mvines9iiHiQTysrwkJjGf2gb9Ex9jXJX8ns3qwf2kN").unwrap();
+        assert!(matches!(transfer, SolanaPayRequest::Transfer { .. }));
+    }
+
+    #[test]
+    fn test_summarize_system_transfer() {
+        let keys = vec![
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::default(),
+        ];
+        let mut data = 2u32.to_le_bytes().to_vec();
+        data.extend_from_slice(&500_000_000u64.to_le_bytes());
+        let instruction = CompiledInstruction::new_from_raw_parts(2, data, vec![0, 1]);
+        let summary = summarize_instruction(&instruction, &keys);
+        assert_eq!(summary.program, "System Program");
+        assert_eq!(summary.description, "Transfer 0.5 SOL");
+        assert_eq!(
+            summary.accounts,
+            vec![keys[0].to_string(), keys[1].to_string()]
+        );
+    }
+
+    #[test]
+    fn test_decode_instruction_rejects_authority_changes() {
+        // SetAuthority, Approve and CloseAccount of the Token Program.
+        for (tag, kind) in [(6u8, "SetAuthority"), (4, "Approve"), (9, "CloseAccount")] {
+            let result = decode_instruction(SPL_TOKEN_PROGRAM_ID, &[tag, 0, 0]);
+            assert_eq!(result, Err(format!("Token {}", kind)));
+        }
+        let assign = decode_instruction(SYSTEM_PROGRAM_ID, &1u32.to_le_bytes());
+        assert_eq!(assign, Err("System Assign".to_string()));
+
+        let mut transfer_checked = vec![12u8];
+        transfer_checked.extend_from_slice(&1_500_000u64.to_le_bytes());
+        transfer_checked.push(6);
+        assert_eq!(
+            decode_instruction(SPL_TOKEN_2022_PROGRAM_ID, &transfer_checked),
+            Ok("Transfer 1.5 tokens".to_string())
+        );
+        assert_eq!(
+            decode_instruction(ASSOCIATED_TOKEN_PROGRAM_ID, &[1]),
+            Ok("Create associated token account if missing".to_string())
+        );
+    }
+}
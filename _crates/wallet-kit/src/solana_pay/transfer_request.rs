@@ -0,0 +1,241 @@
+use {
+    crate::{
+        models::solana_pay::TransferRequest,
+        solana_pay::{is_transaction_request, normalize_uri, SolanaPayError},
+        units::parse_base_units,
+    },
+    smbcloud_wallet_constants::constants::{SOLANA_PAY_SCHEME, SOL_DECIMALS},
+    smbcloud_wallet_core_model::token_registry::TokenRegistry,
+    solana_sdk::pubkey::Pubkey,
+    std::str::FromStr,
+    url::{form_urlencoded, Url},
+};
+
+/// Parse a Solana Pay transfer request URI.
+///
+/// Accepts `solana:` URIs as produced by merchants and QR codes, and
+/// `notwallet:` deep links carrying the same payload, e.g.
+/// `notwallet://<recipient>?amount=1`.
+pub fn parse_transfer_request(uri: &str) -> Result<TransferRequest, SolanaPayError> {
+    if is_transaction_request(uri) {
+        return Err(SolanaPayError::TransactionRequestNotSupported);
+    }
+    let uri = normalize_uri(uri);
+    let url = Url::parse(&uri).map_err(|e| SolanaPayError::InvalidUri(e.to_string()))?;
+    if url.scheme() != SOLANA_PAY_SCHEME {
+        return Err(SolanaPayError::InvalidUri(format!(
+            "Unsupported scheme: {}",
+            url.scheme()
+        )));
+    }
+
+    let path = url.path();
+    Pubkey::from_str(path).map_err(|_| SolanaPayError::InvalidRecipient(path.to_string()))?;
+
+    let mut request = TransferRequest::new(path);
+    let mut amount: Option<String> = None;
+    for (key, value) in url.query_pairs() {
+        match key.as_ref() {
+            "amount" => {
+                if amount.is_some() {
+                    return Err(SolanaPayError::InvalidAmount(
+                        "Multiple amount fields".to_string(),
+                    ));
+                }
+                amount = Some(value.to_string());
+            }
+            "spl-token" => {
+                if request.spl_token.is_some() {
+                    return Err(SolanaPayError::InvalidSplToken(
+                        "Multiple spl-token fields".to_string(),
+                    ));
+                }
+                Pubkey::from_str(&value)
+                    .map_err(|_| SolanaPayError::InvalidSplToken(value.to_string()))?;
+                request.spl_token = Some(value.to_string());
+            }
+            "reference" => {
+                Pubkey::from_str(&value)
+                    .map_err(|_| SolanaPayError::InvalidReference(value.to_string()))?;
+                request.reference.push(value.to_string());
+            }
+            "label" => request.label = Some(value.to_string()),
+            "message" => request.message = Some(value.to_string()),
+            "memo" => request.memo = Some(value.to_string()),
+            // Unknown fields are ignored, as required by the spec.
+            _ => {}
+        }
+    }
+
+    if let Some(amount) = amount {
+        let decimals = match &request.spl_token {
+            Some(mint) => TokenRegistry::shared().get(mint).map(|token| token.decimal),
+            None => Some(SOL_DECIMALS),
+        };
+        request.amount = Some(parse_amount(&amount, decimals)?);
+    }
+
+    Ok(request)
+}
+
+/// Encode a transfer request as a `solana:` URI, ready to be rendered as a QR code.
+pub fn encode_transfer_request(request: &TransferRequest) -> String {
+    let mut params = form_urlencoded::Serializer::new(String::new());
+    if let Some(amount) = request.amount {
+        params.append_pair("amount", &amount.to_string());
+    }
+    if let Some(spl_token) = &request.spl_token {
+        params.append_pair("spl-token", spl_token);
+    }
+    for reference in &request.reference {
+        params.append_pair("reference", reference);
+    }
+    if let Some(label) = &request.label {
+        params.append_pair("label", label);
+    }
+    if let Some(message) = &request.message {
+        params.append_pair("message", message);
+    }
+    if let Some(memo) = &request.memo {
+        params.append_pair("memo", memo);
+    }
+    let query = params.finish();
+
+    if query.is_empty() {
+        format!("{}:{}", SOLANA_PAY_SCHEME, request.recipient)
+    } else {
+        format!("{}:{}?{}", SOLANA_PAY_SCHEME, request.recipient, query)
+    }
+}
+
+/// Validate an amount as described by the spec: a non-negative decimal with a
+/// leading digit, no exponent, and no more fraction digits than the mint allows.
+///
+/// The decimals of a mint missing from the registry are unknown here; they
+/// are read from chain and checked again when the request is paid.
+fn parse_amount(value: &str, decimals: Option<u8>) -> Result<f64, SolanaPayError> {
+    let invalid = || SolanaPayError::InvalidAmount(value.to_string());
+    let (whole, fraction) = match value.split_once('.') {
+        Some((whole, fraction)) => (whole, Some(fraction)),
+        None => (value, None),
+    };
+    if whole.is_empty() || !whole.chars().all(|c| c.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    if let Some(fraction) = fraction {
+        if fraction.is_empty() || !fraction.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+    }
+    if let Some(decimals) = decimals {
+        parse_base_units(value, decimals).ok_or_else(invalid)?;
+    }
+    value.parse::<f64>().map_err(|_| invalid())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RECIPIENT: &str = "mvines9iiHiQTysrwkJjGf2gb9Ex9jXJX8ns3qwf2kN";
+    const USDC: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+    const REFERENCE: &str = "82ZJ7nbGpixjeDCmEhUcmwXYfvurzAgGdtSMuHnUgyny";
+
+    #[test]
+    fn test_parse_sol_transfer_request() {
+        let uri = format!("solana:{}?amount=1&label=Michael&message=Thanks%20for%20all%20the%20fish&memo=OrderId12345", RECIPIENT);
+        let request = parse_transfer_request(&uri).unwrap();
+        assert_eq!(request.recipient, RECIPIENT);
+        assert_eq!(request.amount, Some(1.0));
+        assert_eq!(request.spl_token, None);
+        assert_eq!(request.label.as_deref(), Some("Michael"));
+        assert_eq!(request.message.as_deref(), Some("Thanks for all the fish"));
+        assert_eq!(request.memo.as_deref(), Some("OrderId12345"));
+    }
+
+    #[test]
+    fn test_parse_spl_transfer_request_with_references() {
+        let uri = format!(
+            "solana:{}?amount=0.01&spl-token={}&reference={}&reference={}",
+            RECIPIENT, USDC, REFERENCE, RECIPIENT
+        );
+        let request = parse_transfer_request(&uri).unwrap();
+        assert_eq!(request.amount, Some(0.01));
+        assert_eq!(request.spl_token.as_deref(), Some(USDC));
+        assert_eq!(request.reference, vec![REFERENCE, RECIPIENT]);
+    }
+
+    #[test]
+    fn test_parse_without_amount() {
+        let request = parse_transfer_request(&format!("solana:{}", RECIPIENT)).unwrap();
+        assert_eq!(request.amount, None);
+        assert!(request.reference.is_empty());
+    }
+
+    #[test]
+    fn test_parse_invalid_amounts() {
+        for amount in [".5", "1.", "-1", "1e3", "abc", ""] {
+            let uri = format!("solana:{}?amount={}", RECIPIENT, amount);
+            assert!(
+                matches!(
+                    parse_transfer_request(&uri),
+                    Err(SolanaPayError::InvalidAmount(_))
+                ),
+                "amount {} should be rejected",
+                amount
+            );
+        }
+        // USDC only has 6 decimals.
+        let uri = format!("solana:{}?amount=1.1234567&spl-token={}", RECIPIENT, USDC);
+        assert!(parse_transfer_request(&uri).is_err());
+    }
+
+    #[test]
+    fn test_parse_invalid_keys() {
+        assert!(matches!(
+            parse_transfer_request("solana:not-a-pubkey"),
+            Err(SolanaPayError::InvalidRecipient(_))
+        ));
+        let uri = format!("solana:{}?reference=nope", RECIPIENT);
+        assert!(matches!(
+            parse_transfer_request(&uri),
+            Err(SolanaPayError::InvalidReference(_))
+        ));
+        assert!(parse_transfer_request(&format!("bitcoin:{}", RECIPIENT)).is_err());
+    }
+
+    #[test]
+    fn test_parse_transaction_request_link() {
+        let uri = "solana:https%3A%2F%2Fexample.com%2Fsolana-pay";
+        assert!(matches!(
+            parse_transfer_request(uri),
+            Err(SolanaPayError::TransactionRequestNotSupported)
+        ));
+    }
+
+    #[test]
+    fn test_parse_deep_link() {
+        let uri = format!("notwallet://{}?amount=2.5", RECIPIENT);
+        let request = parse_transfer_request(&uri).unwrap();
+        assert_eq!(request.recipient, RECIPIENT);
+        assert_eq!(request.amount, Some(2.5));
+    }
+
+    #[test]
+    fn test_encode_round_trip() {
+        let mut request = TransferRequest::new(RECIPIENT);
+        request.amount = Some(12.5);
+        request.spl_token = Some(USDC.to_string());
+        request.reference = vec![REFERENCE.to_string()];
+        request.label = Some("Bach & Sons".to_string());
+        request.message = Some("Coffee #1".to_string());
+
+        let uri = encode_transfer_request(&request);
+        assert!(uri.starts_with(&format!("solana:{}?amount=12.5", RECIPIENT)));
+        assert_eq!(parse_transfer_request(&uri).unwrap(), request);
+        assert_eq!(
+            encode_transfer_request(&TransferRequest::new(RECIPIENT)),
+            format!("solana:{}", RECIPIENT)
+        );
+    }
+}
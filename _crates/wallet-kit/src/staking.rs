@@ -0,0 +1,478 @@
+use {
+    crate::{
+        rpc::{parse_address, send, RpcError},
+        units::to_base_units,
+    },
+    log::{debug, info},
+    smbcloud_wallet_constants::constants::{LAMPORTS_PER_SOL, SOL_DECIMALS},
+    smbcloud_wallet_core_model::models::stake::{
+        StakeAccount, StakeActivationState, StakeOperation, StakeReward, Validator,
+    },
+    solana_account_decoder::UiAccountEncoding,
+    solana_client::{
+        client_error::ClientError,
+        nonblocking::rpc_client::RpcClient,
+        rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+        rpc_filter::{Memcmp, RpcFilterType},
+    },
+    solana_sdk::{
+        message::Message,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+    },
+    solana_stake_interface::{
+        instruction as stake_instruction, program as stake_program,
+        state::{Authorized, Lockup, StakeStateV2},
+    },
+    solana_system_interface_v1::instruction as system_instruction,
+    thiserror::Error,
+};
+
+/// Offset of the staker authority in a serialized stake account.
+const STAKE_AUTHORITY_OFFSET: usize = 12;
+
+#[derive(Error, Debug)]
+pub enum StakingError {
+    #[error("Failed to connect to RPC: {0}")]
+    ConnectionError(String),
+
+    #[error("Invalid address: {0}")]
+    InvalidAddress(String),
+
+    #[error("Invalid private key")]
+    InvalidKeypair,
+
+    #[error("Invalid amount: {0}")]
+    InvalidAmount(String),
+
+    #[error("Insufficient funds")]
+    InsufficientFunds,
+
+    #[error("Transaction error: {0}")]
+    TransactionError(String),
+}
+
+impl RpcError for StakingError {
+    fn connection_error(message: String) -> Self {
+        StakingError::ConnectionError(message)
+    }
+
+    fn transaction_error(message: String) -> Self {
+        StakingError::TransactionError(message)
+    }
+
+    fn invalid_address(address: String) -> Self {
+        StakingError::InvalidAddress(address)
+    }
+}
+
+/// Decode a base58 encoded private key, as stored by the apps.
+pub fn keypair_from_base58(privkey: &str) -> Result<Keypair, StakingError> {
+    let bytes = bs58::decode(privkey)
+        .into_vec()
+        .map_err(|_| StakingError::InvalidKeypair)?;
+    Keypair::try_from(bytes.as_slice()).map_err(|_| StakingError::InvalidKeypair)
+}
+
+/// List current and delinquent validators, ordered by active stake.
+///
+/// The skip rate is computed from the block production of the current epoch.
+pub async fn get_validators(rpc_client: &RpcClient) -> Result<Vec<Validator>, StakingError> {
+    let vote_accounts = rpc_client
+        .get_vote_accounts()
+        .await
+        .map_err(|e| StakingError::ConnectionError(e.to_string()))?;
+    let block_production = rpc_client
+        .get_block_production()
+        .await
+        .map_err(|e| StakingError::ConnectionError(e.to_string()))?
+        .value
+        .by_identity;
+
+    let current = vote_accounts.current.into_iter().map(|info| (info, false));
+    let delinquent = vote_accounts
+        .delinquent
+        .into_iter()
+        .map(|info| (info, true));
+    let mut validators: Vec<Validator> = current
+        .chain(delinquent)
+        .map(|(info, delinquent)| {
+            let skip_rate = block_production.get(&info.node_pubkey).and_then(
+                |(leader_slots, blocks_produced)| {
+                    skip_rate(*leader_slots as u64, *blocks_produced as u64)
+                },
+            );
+            Validator {
+                vote_account: info.vote_pubkey,
+                identity: info.node_pubkey,
+                commission: info.commission,
+                activated_stake: info.activated_stake as f64 / LAMPORTS_PER_SOL,
+                last_vote: info.last_vote,
+                skip_rate,
+                delinquent,
+            }
+        })
+        .collect();
+    validators.sort_by(|a, b| b.activated_stake.total_cmp(&a.activated_stake));
+
+    Ok(validators)
+}
+
+/// List the stake accounts whose stake authority is `owner`.
+pub async fn get_stake_accounts(
+    rpc_client: &RpcClient,
+    owner: String,
+) -> Result<Vec<StakeAccount>, StakingError> {
+    let owner_pubkey = parse_address(&owner)?;
+
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![
+            RpcFilterType::DataSize(StakeStateV2::size_of() as u64),
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                STAKE_AUTHORITY_OFFSET,
+                &owner_pubkey.to_bytes(),
+            )),
+        ]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+    let accounts = rpc_client
+        .get_program_accounts_with_config(&stake_program::id(), config)
+        .await
+        .map_err(|e| StakingError::ConnectionError(e.to_string()))?;
+    let epoch = rpc_client
+        .get_epoch_info()
+        .await
+        .map_err(|e| StakingError::ConnectionError(e.to_string()))?
+        .epoch;
+
+    let mut stake_accounts = Vec::new();
+    for (address, account) in accounts {
+        match bincode::deserialize::<StakeStateV2>(&account.data) {
+            Ok(state) => {
+                stake_accounts.push(stake_account(address, account.lamports, &state, epoch))
+            }
+            Err(e) => debug!("Skipping stake account {}: {}", address, e),
+        }
+    }
+
+    Ok(stake_accounts)
+}
+
+/// Create a new stake account funded with `amount` SOL and delegate it to `vote_account`.
+///
+/// The wallet is both the stake and the withdraw authority. It also pays the
+/// rent exempt reserve of the stake account and the transaction fee.
+pub async fn create_stake_account(
+    rpc_client: &RpcClient,
+    keypair: Keypair,
+    amount: f64,
+    vote_account: String,
+) -> Result<StakeOperation, StakingError> {
+    let vote = parse_address(&vote_account)?;
+    let lamports = to_lamports(amount)?;
+    let connection_error = |e: ClientError| StakingError::ConnectionError(e.to_string());
+
+    // The stake account also holds its rent exempt reserve, which is not staked.
+    let rent = rpc_client
+        .get_minimum_balance_for_rent_exemption(StakeStateV2::size_of())
+        .await
+        .map_err(connection_error)?;
+    let stake = Keypair::new();
+    let instructions = stake_instruction::create_account_and_delegate_stake(
+        &keypair.pubkey(),
+        &stake.pubkey(),
+        &vote,
+        &Authorized::auto(&keypair.pubkey()),
+        &Lockup::default(),
+        lamports.saturating_add(rent),
+    );
+
+    let blockhash = rpc_client
+        .get_latest_blockhash()
+        .await
+        .map_err(connection_error)?;
+    let fee = rpc_client
+        .get_fee_for_message(&Message::new_with_blockhash(
+            &instructions,
+            Some(&keypair.pubkey()),
+            &blockhash,
+        ))
+        .await
+        .map_err(connection_error)?;
+    let balance = rpc_client
+        .get_balance(&keypair.pubkey())
+        .await
+        .map_err(connection_error)?;
+    if balance < lamports.saturating_add(rent).saturating_add(fee) {
+        return Err(StakingError::InsufficientFunds);
+    }
+
+    let signature = send(rpc_client, &keypair, &instructions, &[&stake]).await?;
+
+    info!(
+        "Delegated {} SOL to {} with stake account {}",
+        amount,
+        vote_account,
+        stake.pubkey()
+    );
+    Ok(StakeOperation {
+        stake_account: stake.pubkey().to_string(),
+        signature,
+    })
+}
+
+/// Delegate an existing, inactive stake account to `vote_account`.
+pub async fn delegate_stake(
+    rpc_client: &RpcClient,
+    keypair: Keypair,
+    stake_account: String,
+    vote_account: String,
+) -> Result<String, StakingError> {
+    let stake = parse_address(&stake_account)?;
+    let vote = parse_address(&vote_account)?;
+
+    let instruction = stake_instruction::delegate_stake(&stake, &keypair.pubkey(), &vote);
+    send(rpc_client, &keypair, &[instruction], &[]).await
+}
+
+/// Start the cooldown of a stake account. The SOL can be withdrawn once inactive.
+pub async fn deactivate_stake(
+    rpc_client: &RpcClient,
+    keypair: Keypair,
+    stake_account: String,
+) -> Result<String, StakingError> {
+    let stake = parse_address(&stake_account)?;
+
+    let instruction = stake_instruction::deactivate_stake(&stake, &keypair.pubkey());
+    send(rpc_client, &keypair, &[instruction], &[]).await
+}
+
+/// Withdraw `amount` SOL from an inactive stake account back to the wallet.
+///
+/// `None` withdraws the whole balance, which closes the account.
+pub async fn withdraw_stake(
+    rpc_client: &RpcClient,
+    keypair: Keypair,
+    stake_account: String,
+    amount: Option<f64>,
+) -> Result<String, StakingError> {
+    let stake = parse_address(&stake_account)?;
+    let lamports = match amount {
+        Some(amount) => to_lamports(amount)?,
+        None => rpc_client
+            .get_balance(&stake)
+            .await
+            .map_err(|e| StakingError::ConnectionError(e.to_string()))?,
+    };
+
+    let instruction =
+        stake_instruction::withdraw(&stake, &keypair.pubkey(), &keypair.pubkey(), lamports, None);
+    send(rpc_client, &keypair, &[instruction], &[]).await
+}
+
+/// Move `amount` SOL of stake into a new stake account with the same delegation.
+pub async fn split_stake(
+    rpc_client: &RpcClient,
+    keypair: Keypair,
+    stake_account: String,
+    amount: f64,
+) -> Result<StakeOperation, StakingError> {
+    let stake = parse_address(&stake_account)?;
+    let lamports = to_lamports(amount)?;
+
+    // The split destination must be rent exempt on its own.
+    let rent = rpc_client
+        .get_minimum_balance_for_rent_exemption(StakeStateV2::size_of())
+        .await
+        .map_err(|e| StakingError::ConnectionError(e.to_string()))?;
+
+    let split = Keypair::new();
+    let mut instructions = vec![system_instruction::transfer(
+        &keypair.pubkey(),
+        &split.pubkey(),
+        rent,
+    )];
+    instructions.extend(stake_instruction::split(
+        &stake,
+        &keypair.pubkey(),
+        lamports,
+        &split.pubkey(),
+    ));
+    let signature = send(rpc_client, &keypair, &instructions, &[&split]).await?;
+
+    Ok(StakeOperation {
+        stake_account: split.pubkey().to_string(),
+        signature,
+    })
+}
+
+/// Merge `source` into `destination`. Both accounts must have the same
+/// authorities and a compatible activation state.
+pub async fn merge_stake(
+    rpc_client: &RpcClient,
+    keypair: Keypair,
+    destination: String,
+    source: String,
+) -> Result<String, StakingError> {
+    let destination = parse_address(&destination)?;
+    let source = parse_address(&source)?;
+
+    let instructions = stake_instruction::merge(&destination, &source, &keypair.pubkey());
+    send(rpc_client, &keypair, &instructions, &[]).await
+}
+
+/// Inflation rewards of the given stake accounts for `epoch`, or the last
+/// completed epoch when `None`. Accounts without a reward are left out.
+pub async fn get_stake_rewards(
+    rpc_client: &RpcClient,
+    stake_accounts: Vec<String>,
+    epoch: Option<u64>,
+) -> Result<Vec<StakeReward>, StakingError> {
+    let addresses = stake_accounts
+        .iter()
+        .map(|address| parse_address(address))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let rewards = rpc_client
+        .get_inflation_reward(&addresses, epoch)
+        .await
+        .map_err(|e| StakingError::ConnectionError(e.to_string()))?;
+
+    Ok(stake_accounts
+        .into_iter()
+        .zip(rewards)
+        .filter_map(|(stake_account, reward)| {
+            reward.map(|reward| StakeReward {
+                stake_account,
+                epoch: reward.epoch,
+                amount: reward.amount as f64 / LAMPORTS_PER_SOL,
+                post_balance: reward.post_balance as f64 / LAMPORTS_PER_SOL,
+                commission: reward.commission,
+            })
+        })
+        .collect())
+}
+
+fn stake_account(address: Pubkey, lamports: u64, state: &StakeStateV2, epoch: u64) -> StakeAccount {
+    let mut account = StakeAccount {
+        address: address.to_string(),
+        balance: lamports as f64 / LAMPORTS_PER_SOL,
+        delegated_stake: 0.0,
+        rent_exempt_reserve: 0.0,
+        validator: None,
+        state: StakeActivationState::Inactive,
+        activation_epoch: None,
+        deactivation_epoch: None,
+    };
+    if let Some(meta) = state.meta() {
+        account.rent_exempt_reserve = meta.rent_exempt_reserve as f64 / LAMPORTS_PER_SOL;
+    }
+    if let Some(delegation) = state.delegation() {
+        account.delegated_stake = delegation.stake as f64 / LAMPORTS_PER_SOL;
+        account.validator = Some(delegation.voter_pubkey.to_string());
+        account.state = activation_state(
+            delegation.activation_epoch,
+            delegation.deactivation_epoch,
+            epoch,
+        );
+        account.activation_epoch = Some(delegation.activation_epoch);
+        account.deactivation_epoch =
+            (delegation.deactivation_epoch != u64::MAX).then_some(delegation.deactivation_epoch);
+    }
+    account
+}
+
+/// Activation state of a delegation at `epoch`.
+///
+/// Warmup and cooldown are treated as taking a single epoch, which holds
+/// unless a large share of the cluster stake changes at once.
+fn activation_state(
+    activation_epoch: u64,
+    deactivation_epoch: u64,
+    epoch: u64,
+) -> StakeActivationState {
+    if deactivation_epoch != u64::MAX {
+        // Deactivated in the epoch it was delegated: the stake never became active.
+        if deactivation_epoch == activation_epoch || epoch > deactivation_epoch {
+            StakeActivationState::Inactive
+        } else {
+            StakeActivationState::Deactivating
+        }
+    } else if activation_epoch == u64::MAX || epoch > activation_epoch {
+        // Bootstrap stakes are created active with an activation epoch of `u64::MAX`.
+        StakeActivationState::Active
+    } else {
+        StakeActivationState::Activating
+    }
+}
+
+fn skip_rate(leader_slots: u64, blocks_produced: u64) -> Option<f64> {
+    (leader_slots > 0).then(|| 1.0 - blocks_produced as f64 / leader_slots as f64)
+}
+
+fn to_lamports(amount: f64) -> Result<u64, StakingError> {
+    if !amount.is_finite() || amount <= 0.0 {
+        return Err(StakingError::InvalidAmount(amount.to_string()));
+    }
+    Ok(to_base_units(amount, SOL_DECIMALS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_activation_state() {
+        assert_eq!(
+            activation_state(10, u64::MAX, 10),
+            StakeActivationState::Activating
+        );
+        assert_eq!(
+            activation_state(10, u64::MAX, 11),
+            StakeActivationState::Active
+        );
+        assert_eq!(
+            activation_state(u64::MAX, u64::MAX, 0),
+            StakeActivationState::Active
+        );
+        assert_eq!(
+            activation_state(10, 12, 12),
+            StakeActivationState::Deactivating
+        );
+        assert_eq!(activation_state(10, 12, 13), StakeActivationState::Inactive);
+        assert_eq!(activation_state(10, 10, 10), StakeActivationState::Inactive);
+    }
+
+    #[test]
+    fn test_skip_rate() {
+        assert_eq!(skip_rate(0, 0), None);
+        assert_eq!(skip_rate(100, 100), Some(0.0));
+        assert_eq!(skip_rate(100, 75), Some(0.25));
+    }
+
+    #[test]
+    fn test_to_lamports() {
+        assert_eq!(to_lamports(1.5).unwrap(), 1_500_000_000);
+        assert!(to_lamports(0.0).is_err());
+        assert!(to_lamports(-1.0).is_err());
+        assert!(to_lamports(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_keypair_from_base58() {
+        let keypair = Keypair::new();
+        let privkey = bs58::encode(keypair.to_bytes()).into_string();
+        assert_eq!(
+            keypair_from_base58(&privkey).unwrap().pubkey(),
+            keypair.pubkey()
+        );
+        assert!(matches!(
+            keypair_from_base58("not a key"),
+            Err(StakingError::InvalidKeypair)
+        ));
+    }
+}
@@ -0,0 +1,709 @@
+//! Live updates of a wallet over the WebSocket API of its RPC node.
+//!
+//! A [`SubscriptionWorker`] follows the SOL and token accounts of one wallet
+//! with `accountSubscribe`, the transactions mentioning it with
+//! `logsSubscribe`, and the signatures it is asked to watch on the network of
+//! that wallet with `signatureSubscribe`. When the connection drops it
+//! reconnects with backoff. [`check_signature_statuses`] settles signatures
+//! without any subscription, such as those sent on another network.
+
+use {
+    crate::{
+        kit::WalletKitError,
+        models::subscriptions::{
+            AccountUpdate, SignatureUpdate, TransactionUpdate, WalletNotification,
+        },
+        rpc_endpoints::RpcEndpoints,
+    },
+    futures::stream::{BoxStream, SelectAll, StreamExt},
+    log::{debug, error, info, warn},
+    smbcloud_wallet_constants::constants::{SPL_TOKEN_2022_PROGRAM_ID, SPL_TOKEN_PROGRAM_ID},
+    smbcloud_wallet_core_model::models::environment::Environment,
+    solana_account_decoder::{UiAccount, UiAccountData, UiAccountEncoding},
+    solana_client::{
+        client_error::ClientError,
+        nonblocking::{
+            pubsub_client::{PubsubClient, PubsubClientError},
+            rpc_client::RpcClient,
+        },
+        rpc_config::{
+            RpcAccountInfoConfig, RpcSignatureSubscribeConfig, RpcTransactionLogsConfig,
+            RpcTransactionLogsFilter,
+        },
+        rpc_request::{TokenAccountsFilter, MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS},
+        rpc_response::{RpcLogsResponse, RpcSignatureResult},
+    },
+    solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature},
+    solana_transaction_status_client_types::TransactionStatus,
+    std::{
+        collections::HashMap,
+        str::FromStr,
+        time::{Duration, Instant},
+    },
+    tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+};
+
+/// First wait before reconnecting, doubled after every failed attempt.
+const RECONNECT_BACKOFF_MIN: Duration = Duration::from_secs(1);
+
+/// Longest wait before reconnecting.
+const RECONNECT_BACKOFF_MAX: Duration = Duration::from_secs(60);
+
+/// How often watched signatures are also looked up with `getSignatureStatuses`,
+/// for those that landed while the connection was down.
+pub const SIGNATURE_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How long a watched signature may go unconfirmed before it is given up.
+/// Longer than the blockhash of a transaction is valid.
+pub const SIGNATURE_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Why a signature the network never saw is given up.
+const EXPIRED_ERROR: &str = "Transaction expired before it was confirmed";
+
+enum Command {
+    Watch {
+        environment: Environment,
+        wallet: Pubkey,
+    },
+    WatchSignature {
+        environment: Environment,
+        signature: Signature,
+    },
+}
+
+/// Handle to tell a [`SubscriptionWorker`] what to follow.
+#[derive(Clone)]
+pub struct WalletSubscriptions {
+    commands: UnboundedSender<Command>,
+}
+
+impl WalletSubscriptions {
+    /// Create the handle and its worker, which reports to `notifications`.
+    ///
+    /// The worker is to be spawned on a Tokio runtime. It stops once every
+    /// handle is dropped.
+    pub fn new(
+        notifications: UnboundedSender<WalletNotification>,
+    ) -> (WalletSubscriptions, SubscriptionWorker) {
+        let (commands, receiver) = unbounded_channel();
+        let worker = SubscriptionWorker {
+            commands: receiver,
+            notifications,
+            target: None,
+            signatures: HashMap::new(),
+        };
+        (WalletSubscriptions { commands }, worker)
+    }
+
+    /// Follow `wallet` on `environment` instead of the wallet followed so far.
+    pub fn watch(&self, environment: Environment, wallet: &str) -> Result<(), WalletKitError> {
+        let wallet = Pubkey::from_str(wallet)
+            .map_err(|_| WalletKitError::InvalidAddress(wallet.to_string()))?;
+        self.send(Command::Watch {
+            environment,
+            wallet,
+        })
+    }
+
+    /// Report when `signature`, sent on `environment`, is confirmed, fails or
+    /// expires. It is only followed while `environment` is the network of the
+    /// followed wallet.
+    pub fn watch_signature(
+        &self,
+        environment: Environment,
+        signature: &str,
+    ) -> Result<(), WalletKitError> {
+        let signature = Signature::from_str(signature)
+            .map_err(|_| WalletKitError::InvalidSignature(signature.to_string()))?;
+        self.send(Command::WatchSignature {
+            environment,
+            signature,
+        })
+    }
+
+    fn send(&self, command: Command) -> Result<(), WalletKitError> {
+        self.commands
+            .send(command)
+            .map_err(|_| WalletKitError::ConnectionError("Subscriptions stopped".to_string()))
+    }
+}
+
+/// Keeps the subscriptions of the followed wallet open.
+pub struct SubscriptionWorker {
+    commands: UnboundedReceiver<Command>,
+    notifications: UnboundedSender<WalletNotification>,
+    target: Option<(Environment, Pubkey)>,
+    /// Watched signatures by network, and when they were sent.
+    signatures: HashMap<Environment, HashMap<Signature, Instant>>,
+}
+
+/// A notification of one of the subscriptions.
+enum Event {
+    Account(Pubkey, UiAccount),
+    Logs(RpcLogsResponse),
+    Signature(Signature, RpcSignatureResult),
+}
+
+/// Why a session ended.
+enum SessionEnd {
+    /// Another wallet or network is to be followed.
+    Retarget,
+    /// The connection failed or dropped.
+    Disconnected(String),
+    /// Every handle was dropped.
+    Stopped,
+}
+
+impl SubscriptionWorker {
+    pub async fn run(mut self) {
+        let mut backoff = RECONNECT_BACKOFF_MIN;
+        loop {
+            let Some((environment, wallet)) = self.target else {
+                match self.commands.recv().await {
+                    Some(command) => {
+                        self.apply(command);
+                        continue;
+                    }
+                    None => return,
+                }
+            };
+            let error = match self.session(environment, wallet, &mut backoff).await {
+                SessionEnd::Retarget => {
+                    backoff = RECONNECT_BACKOFF_MIN;
+                    continue;
+                }
+                SessionEnd::Stopped => return,
+                SessionEnd::Disconnected(error) => error,
+            };
+
+            warn!(
+                "Subscriptions of {} on {} dropped: {}. Reconnecting in {:?}",
+                wallet, environment, error, backoff
+            );
+            // Signatures may still land while the connection is down.
+            self.check_signatures(environment, &rpc_client(environment))
+                .await;
+            let reconnect = tokio::time::sleep(backoff);
+            tokio::pin!(reconnect);
+            backoff = next_backoff(backoff);
+            loop {
+                tokio::select! {
+                    _ = &mut reconnect => break,
+                    command = self.commands.recv() => match command {
+                        None => return,
+                        Some(command) => {
+                            if self.apply(command) {
+                                backoff = RECONNECT_BACKOFF_MIN;
+                                break;
+                            }
+                        }
+                    },
+                }
+            }
+        }
+    }
+
+    /// Follow the target until the connection drops or the target changes.
+    async fn session(
+        &mut self,
+        environment: Environment,
+        wallet: Pubkey,
+        backoff: &mut Duration,
+    ) -> SessionEnd {
+        let client =
+            match PubsubClient::new(&RpcEndpoints::shared(environment).active_ws_url()).await {
+                Ok(client) => client,
+                Err(e) => return SessionEnd::Disconnected(e.to_string()),
+            };
+        let rpc_client = rpc_client(environment);
+
+        let mut accounts = vec![wallet];
+        accounts.extend(token_accounts(&rpc_client, &wallet).await);
+        let mut streams = SelectAll::new();
+        let subscribed = self
+            .subscribe_all(&client, &mut streams, &accounts, environment, wallet)
+            .await;
+        if let Err(e) = subscribed {
+            return SessionEnd::Disconnected(e.to_string());
+        }
+        info!(
+            "Following {} and {} token accounts on {}",
+            wallet,
+            accounts.len() - 1,
+            environment
+        );
+        *backoff = RECONNECT_BACKOFF_MIN;
+
+        let mut signature_check = tokio::time::interval(SIGNATURE_CHECK_INTERVAL);
+        loop {
+            tokio::select! {
+                command = self.commands.recv() => match command {
+                    None => return SessionEnd::Stopped,
+                    Some(Command::WatchSignature { environment: network, signature }) => {
+                        // Signatures of other networks wait for their network to be followed.
+                        if !self.watch_signature(network, signature) || network != environment {
+                            continue;
+                        }
+                        match subscribe_signature(&client, signature).await {
+                            Ok(stream) => streams.push(stream),
+                            Err(e) => return SessionEnd::Disconnected(e.to_string()),
+                        }
+                    }
+                    Some(command) => {
+                        if self.apply(command) {
+                            return SessionEnd::Retarget;
+                        }
+                    }
+                },
+                event = streams.next() => match event {
+                    None => return SessionEnd::Disconnected("Connection closed".to_string()),
+                    Some(Event::Account(account, ui_account)) => {
+                        let update = account_update(&wallet, &account, &ui_account);
+                        self.notify(WalletNotification::Account(update));
+                    }
+                    Some(Event::Logs(logs)) => {
+                        self.notify(WalletNotification::Transaction(TransactionUpdate {
+                            wallet: wallet.to_string(),
+                            signature: logs.signature,
+                            error: logs.err.map(|e| e.to_string()),
+                        }));
+                        // The transaction may have opened a token account.
+                        for account in token_accounts(&rpc_client, &wallet).await {
+                            if accounts.contains(&account) {
+                                continue;
+                            }
+                            match subscribe_account(&client, account).await {
+                                Ok(stream) => streams.push(stream),
+                                Err(e) => return SessionEnd::Disconnected(e.to_string()),
+                            }
+                            accounts.push(account);
+                        }
+                    }
+                    Some(Event::Signature(signature, result)) => {
+                        if let RpcSignatureResult::ProcessedSignature(processed) = result {
+                            self.resolve(environment, signature, processed.err.map(|e| e.to_string()));
+                        }
+                    }
+                },
+                _ = signature_check.tick() => self.check_signatures(environment, &rpc_client).await,
+            }
+        }
+    }
+
+    async fn subscribe_all<'a>(
+        &self,
+        client: &'a PubsubClient,
+        streams: &mut SelectAll<BoxStream<'a, Event>>,
+        accounts: &[Pubkey],
+        environment: Environment,
+        wallet: Pubkey,
+    ) -> Result<(), PubsubClientError> {
+        for account in accounts {
+            streams.push(subscribe_account(client, *account).await?);
+        }
+        streams.push(subscribe_logs(client, wallet).await?);
+        for signature in self
+            .signatures
+            .get(&environment)
+            .into_iter()
+            .flat_map(HashMap::keys)
+        {
+            streams.push(subscribe_signature(client, *signature).await?);
+        }
+        Ok(())
+    }
+
+    /// Apply `command` and tell whether the target changed.
+    fn apply(&mut self, command: Command) -> bool {
+        match command {
+            Command::Watch {
+                environment,
+                wallet,
+            } => {
+                let target = Some((environment, wallet));
+                let changed = self.target != target;
+                self.target = target;
+                changed
+            }
+            Command::WatchSignature {
+                environment,
+                signature,
+            } => {
+                self.watch_signature(environment, signature);
+                false
+            }
+        }
+    }
+
+    /// Watch `signature` on `environment` and tell whether it was not yet.
+    fn watch_signature(&mut self, environment: Environment, signature: Signature) -> bool {
+        let signatures = self.signatures.entry(environment).or_default();
+        if signatures.contains_key(&signature) {
+            return false;
+        }
+        signatures.insert(signature, Instant::now());
+        true
+    }
+
+    /// Look up the signatures watched on `environment`, for those whose
+    /// notification was missed, and give up on those that expired.
+    async fn check_signatures(&mut self, environment: Environment, rpc_client: &RpcClient) {
+        let Some(watched) = self.signatures.get(&environment).filter(|s| !s.is_empty()) else {
+            return;
+        };
+        let sent: Vec<(Signature, Instant)> = watched
+            .iter()
+            .map(|(signature, sent)| (*signature, *sent))
+            .collect();
+        let signatures: Vec<Signature> = sent.iter().map(|(signature, _)| *signature).collect();
+        let statuses = match signature_statuses(rpc_client, &signatures).await {
+            Ok(statuses) => statuses,
+            Err(e) => {
+                error!("Failed to check signature statuses: {}", e);
+                return;
+            }
+        };
+        for ((signature, sent), status) in sent.into_iter().zip(statuses) {
+            let expired = is_expired(sent, Instant::now());
+            if let Some(error) = settlement(status.as_ref(), expired) {
+                self.resolve(environment, signature, error);
+            }
+        }
+    }
+
+    /// Report the outcome of a watched signature and stop watching it.
+    fn resolve(&mut self, environment: Environment, signature: Signature, error: Option<String>) {
+        let removed = self
+            .signatures
+            .get_mut(&environment)
+            .and_then(|signatures| signatures.remove(&signature));
+        if removed.is_none() {
+            return;
+        }
+        debug!("Signature {} resolved: {:?}", signature, error);
+        self.notify(WalletNotification::Signature(SignatureUpdate {
+            signature: signature.to_string(),
+            error,
+        }));
+    }
+
+    fn notify(&self, notification: WalletNotification) {
+        if self.notifications.send(notification).is_err() {
+            warn!("Wallet notification dropped, nobody is listening");
+        }
+    }
+}
+
+fn rpc_client(environment: Environment) -> RpcClient {
+    RpcClient::new_with_commitment(
+        RpcEndpoints::shared(environment).active_url(),
+        CommitmentConfig::confirmed(),
+    )
+}
+
+async fn subscribe_account(
+    client: &PubsubClient,
+    account: Pubkey,
+) -> Result<BoxStream<'_, Event>, PubsubClientError> {
+    let config = RpcAccountInfoConfig {
+        encoding: Some(UiAccountEncoding::JsonParsed),
+        commitment: Some(CommitmentConfig::confirmed()),
+        ..RpcAccountInfoConfig::default()
+    };
+    let (stream, _unsubscribe) = client.account_subscribe(&account, Some(config)).await?;
+    Ok(stream
+        .map(move |response| Event::Account(account, response.value))
+        .boxed())
+}
+
+async fn subscribe_logs(
+    client: &PubsubClient,
+    wallet: Pubkey,
+) -> Result<BoxStream<'_, Event>, PubsubClientError> {
+    let (stream, _unsubscribe) = client
+        .logs_subscribe(
+            RpcTransactionLogsFilter::Mentions(vec![wallet.to_string()]),
+            RpcTransactionLogsConfig {
+                commitment: Some(CommitmentConfig::confirmed()),
+            },
+        )
+        .await?;
+    Ok(stream.map(|response| Event::Logs(response.value)).boxed())
+}
+
+async fn subscribe_signature(
+    client: &PubsubClient,
+    signature: Signature,
+) -> Result<BoxStream<'_, Event>, PubsubClientError> {
+    let config = RpcSignatureSubscribeConfig {
+        commitment: Some(CommitmentConfig::confirmed()),
+        enable_received_notification: Some(false),
+    };
+    let (stream, _unsubscribe) = client.signature_subscribe(&signature, Some(config)).await?;
+    Ok(stream
+        .map(move |response| Event::Signature(signature, response.value))
+        .boxed())
+}
+
+/// SPL Token and Token-2022 accounts of `owner`.
+async fn token_accounts(rpc_client: &RpcClient, owner: &Pubkey) -> Vec<Pubkey> {
+    let mut accounts = Vec::new();
+    for program_id in [SPL_TOKEN_PROGRAM_ID, SPL_TOKEN_2022_PROGRAM_ID] {
+        let Ok(program_id) = Pubkey::from_str(program_id) else {
+            continue;
+        };
+        match rpc_client
+            .get_token_accounts_by_owner(owner, TokenAccountsFilter::ProgramId(program_id))
+            .await
+        {
+            Ok(keyed_accounts) => accounts.extend(
+                keyed_accounts
+                    .iter()
+                    .filter_map(|keyed_account| Pubkey::from_str(&keyed_account.pubkey).ok()),
+            ),
+            Err(e) => error!("Failed to get token accounts of {}: {}", program_id, e),
+        }
+    }
+    accounts
+}
+
+fn account_update(wallet: &Pubkey, account: &Pubkey, ui_account: &UiAccount) -> AccountUpdate {
+    let info = match &ui_account.data {
+        UiAccountData::Json(parsed) => Some(&parsed.parsed["info"]),
+        _ => None,
+    };
+    AccountUpdate {
+        wallet: wallet.to_string(),
+        account: account.to_string(),
+        lamports: ui_account.lamports,
+        mint: info.and_then(|info| info["mint"].as_str().map(str::to_string)),
+        ui_amount: info.and_then(|info| info["tokenAmount"]["uiAmount"].as_f64()),
+    }
+}
+
+/// Look up `signatures` with `getSignatureStatuses`, for transfers no
+/// subscription may follow, such as those sent on another network than the one
+/// of the followed wallet. Each signature comes with how long ago it was sent.
+///
+/// Returns an update for every signature that settled, the ones still unknown
+/// after [`SIGNATURE_TIMEOUT`] as expired.
+pub async fn check_signature_statuses(
+    rpc_url: String,
+    signatures: &[(String, Duration)],
+) -> Result<Vec<SignatureUpdate>, WalletKitError> {
+    let signatures: Vec<(Signature, bool)> = signatures
+        .iter()
+        .filter_map(|(signature, age)| match Signature::from_str(signature) {
+            Ok(parsed) => Some((parsed, *age > SIGNATURE_TIMEOUT)),
+            Err(_) => {
+                warn!("Skipping invalid signature {}", signature);
+                None
+            }
+        })
+        .collect();
+    let keys: Vec<Signature> = signatures.iter().map(|(signature, _)| *signature).collect();
+    let rpc_client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+    let statuses = signature_statuses(&rpc_client, &keys)
+        .await
+        .map_err(|e| WalletKitError::ConnectionError(e.to_string()))?;
+    Ok(signatures
+        .into_iter()
+        .zip(statuses)
+        .filter_map(|((signature, expired), status)| {
+            Some(SignatureUpdate {
+                signature: signature.to_string(),
+                error: settlement(status.as_ref(), expired)?,
+            })
+        })
+        .collect())
+}
+
+/// Statuses of `signatures`, in as many `getSignatureStatuses` requests as the
+/// RPC limit on signatures per request takes.
+async fn signature_statuses(
+    rpc_client: &RpcClient,
+    signatures: &[Signature],
+) -> Result<Vec<Option<TransactionStatus>>, ClientError> {
+    let mut statuses = Vec::with_capacity(signatures.len());
+    for chunk in signatures.chunks(MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS) {
+        statuses.extend(
+            rpc_client
+                .get_signature_statuses_with_history(chunk)
+                .await?
+                .value,
+        );
+    }
+    Ok(statuses)
+}
+
+/// How a signature with `status` settled, `None` while it may still land.
+/// The inner value is why it did not succeed.
+fn settlement(status: Option<&TransactionStatus>, expired: bool) -> Option<Option<String>> {
+    match status {
+        Some(status) if status.satisfies_commitment(CommitmentConfig::confirmed()) => {
+            Some(status.err.as_ref().map(|e| e.to_string()))
+        }
+        Some(_) => None,
+        None if expired => Some(Some(EXPIRED_ERROR.to_string())),
+        None => None,
+    }
+}
+
+fn next_backoff(backoff: Duration) -> Duration {
+    (backoff * 2).min(RECONNECT_BACKOFF_MAX)
+}
+
+fn is_expired(sent: Instant, now: Instant) -> bool {
+    now.duration_since(sent) > SIGNATURE_TIMEOUT
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, serde_json::json, solana_account_decoder::parse_account_data::ParsedAccount};
+
+    fn ui_account(data: UiAccountData) -> UiAccount {
+        UiAccount {
+            lamports: 2_039_280,
+            data,
+            owner: SPL_TOKEN_PROGRAM_ID.to_string(),
+            executable: false,
+            rent_epoch: 0,
+            space: Some(165),
+        }
+    }
+
+    #[test]
+    fn test_token_account_update() {
+        let wallet = Pubkey::new_unique();
+        let account = Pubkey::new_unique();
+        let mint = Pubkey::new_unique().to_string();
+        let data = UiAccountData::Json(ParsedAccount {
+            program: "spl-token".to_string(),
+            parsed: json!({
+                "type": "account",
+                "info": {
+                    "mint": mint,
+                    "owner": wallet.to_string(),
+                    "tokenAmount": { "amount": "1500000", "decimals": 6, "uiAmount": 1.5 },
+                },
+            }),
+            space: 165,
+        });
+
+        let update = account_update(&wallet, &account, &ui_account(data));
+        assert_eq!(update.account, account.to_string());
+        assert_eq!(update.mint, Some(mint));
+        assert_eq!(update.ui_amount, Some(1.5));
+    }
+
+    #[test]
+    fn test_sol_account_update() {
+        let wallet = Pubkey::new_unique();
+        let data = UiAccountData::Binary(String::new(), UiAccountEncoding::Base64);
+        let update = account_update(&wallet, &wallet, &ui_account(data));
+        assert_eq!(update.lamports, 2_039_280);
+        assert_eq!(update.mint, None);
+        assert_eq!(update.ui_amount, None);
+    }
+
+    #[test]
+    fn test_backoff() {
+        assert_eq!(next_backoff(RECONNECT_BACKOFF_MIN), Duration::from_secs(2));
+        assert_eq!(next_backoff(RECONNECT_BACKOFF_MAX), RECONNECT_BACKOFF_MAX);
+    }
+
+    #[test]
+    fn test_signature_expiry() {
+        let sent = Instant::now();
+        assert!(!is_expired(sent, sent + SIGNATURE_TIMEOUT));
+        assert!(is_expired(
+            sent,
+            sent + SIGNATURE_TIMEOUT + Duration::from_secs(1)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_signatures_skipped() {
+        // Nothing is left to look up, so no request is sent.
+        let updates = check_signature_statuses(
+            "http://localhost:1".to_string(),
+            &[("not-a-signature".to_string(), SIGNATURE_TIMEOUT)],
+        )
+        .await
+        .unwrap();
+        assert!(updates.is_empty());
+    }
+
+    #[test]
+    fn test_settlement() {
+        let status = |confirmations: Option<usize>| TransactionStatus {
+            slot: 1,
+            confirmations,
+            status: Ok(()),
+            err: None,
+            confirmation_status: None,
+        };
+        // Finalized, without confirmations left to count.
+        assert_eq!(settlement(Some(&status(None)), false), Some(None));
+        assert_eq!(settlement(Some(&status(Some(0))), true), None);
+        assert_eq!(
+            settlement(None, true),
+            Some(Some(EXPIRED_ERROR.to_string()))
+        );
+        assert_eq!(settlement(None, false), None);
+    }
+
+    #[test]
+    fn test_watch_commands() {
+        let (notifications, _) = unbounded_channel();
+        let (subscriptions, mut worker) = WalletSubscriptions::new(notifications);
+        assert!(subscriptions
+            .watch(Environment::Devnet, "not-a-wallet")
+            .is_err());
+
+        let wallet = Pubkey::new_unique();
+        subscriptions
+            .watch(Environment::Devnet, &wallet.to_string())
+            .unwrap();
+        let command = worker.commands.try_recv().unwrap();
+        assert!(worker.apply(command));
+        assert_eq!(worker.target, Some((Environment::Devnet, wallet)));
+
+        // Following the same wallet again changes nothing.
+        subscriptions
+            .watch(Environment::Devnet, &wallet.to_string())
+            .unwrap();
+        let command = worker.commands.try_recv().unwrap();
+        assert!(!worker.apply(command));
+    }
+
+    #[test]
+    fn test_watch_signature_by_network() {
+        let (notifications, mut received) = unbounded_channel();
+        let (subscriptions, mut worker) = WalletSubscriptions::new(notifications);
+        let signature = Signature::new_unique();
+        for environment in [
+            Environment::Devnet,
+            Environment::Devnet,
+            Environment::Mainnet,
+        ] {
+            subscriptions
+                .watch_signature(environment, &signature.to_string())
+                .unwrap();
+        }
+        while let Ok(command) = worker.commands.try_recv() {
+            assert!(!worker.apply(command));
+        }
+        // Watching the same signature twice on a network does not restart it.
+        assert!(!worker.watch_signature(Environment::Devnet, signature));
+        assert!(worker.watch_signature(Environment::Testnet, signature));
+
+        // Settling a signature on one network leaves the other one watched.
+        worker.resolve(Environment::Devnet, signature, None);
+        worker.resolve(Environment::Devnet, signature, None);
+        assert!(received.try_recv().is_ok());
+        assert!(received.try_recv().is_err());
+        assert!(worker.signatures[&Environment::Mainnet].contains_key(&signature));
+        assert!(worker.signatures[&Environment::Devnet].is_empty());
+    }
+}
@@ -7,9 +7,12 @@ use {
     smbcloud_wallet_constants::constants::{
         JUPITER_BASE_URL, JUPITER_SWAP_PATH, JUPITER_SWAP_QUOTE_PATH,
     },
-    smbcloud_wallet_core_model::models::asset_solana::SolanaAsset,
-    smbcloud_wallet_core_network::{model::ErrorResponse, request},
-    solana_client::rpc_client::RpcClient,
+    smbcloud_wallet_core_model::token_registry::TokenRegistry,
+    smbcloud_wallet_core_network::{
+        model::{ErrorCode, ErrorResponse},
+        request,
+    },
+    solana_client::nonblocking::rpc_client::RpcClient,
     solana_sdk::{
         signature::{Keypair, Signature},
         transaction::VersionedTransaction,
@@ -39,17 +42,20 @@ use {
 ///
 /// # Examples
 ///
-/// ```rust
+/// ```no_run
 /// use smbcloud_wallet_kit::swap::get_jupiter_swap_quote;
-/// use smbcloud_wallet_kit::assets::{SOLANA, BACH_TOKEN};
+/// use smbcloud_wallet_constants::assets_solana::{ADDRESS_BACH_TOKEN, ADDRESS_SOL};
 ///
+/// # async fn example() -> Result<(), smbcloud_wallet_core_network::model::ErrorResponse> {
 /// // Get quote for swapping 1 SOL to BACH with 1% slippage
 /// let quote = get_jupiter_swap_quote(
-///     SOLANA,
-///     BACH_TOKEN,
-///     1, // 1 SOL
+///     ADDRESS_SOL,
+///     ADDRESS_BACH_TOKEN,
+///     1.0, // 1 SOL
 ///     100 // 1% slippage
 /// ).await?;
+/// # Ok(())
+/// # }
 /// ```
 pub async fn get_jupiter_swap_quote(
     from_token: &str,
@@ -57,12 +63,27 @@ pub async fn get_jupiter_swap_quote(
     amount: f64,
     slippage_bps: u64,
 ) -> Result<SwapQuoteResponse, ErrorResponse> {
-    let amount_denomination =
-        if let Some(from_token) = SolanaAsset::from_address(from_token.to_string()) {
-            (amount * 10f64.powi(from_token.metadata().decimal as i32)) as i64
-        } else {
-            panic!("Check supported assets in the wallet-core-model crate.")
-        };
+    jupiter_swap_quote(&Client::new(), from_token, to_token, amount, slippage_bps).await
+}
+
+/// Get a swap quote through `client`, see [`get_jupiter_swap_quote`].
+pub(crate) async fn jupiter_swap_quote(
+    client: &Client,
+    from_token: &str,
+    to_token: &str,
+    amount: f64,
+    slippage_bps: u64,
+) -> Result<SwapQuoteResponse, ErrorResponse> {
+    let from_metadata = match TokenRegistry::shared().get(from_token) {
+        Some(metadata) => metadata,
+        None => {
+            return Err(ErrorResponse::Error {
+                code: ErrorCode::InvalidPubkey,
+                message: format!("Unknown token {}", from_token),
+            })
+        }
+    };
+    let amount_denomination = (amount * from_metadata.smallest_denomination()) as i64;
     let url = format!(
         "{}{}?inputMint={}&outputMint={}&amount={}&slippageBps={}",
         JUPITER_BASE_URL,
@@ -72,8 +93,7 @@ pub async fn get_jupiter_swap_quote(
         amount_denomination,
         slippage_bps,
     );
-    let client = Client::new().get(url);
-    request(client).await
+    request(client.get(url)).await
 }
 
 /// Build a swap transaction using Jupiter's swap API.
@@ -97,16 +117,35 @@ pub async fn get_jupiter_swap_quote(
 ///
 /// # Examples
 ///
-/// ```rust
+/// ```no_run
 /// use smbcloud_wallet_kit::swap::build_swap_transaction;
-/// use smbcloud_wallet_kit::models::swap::SwapTransactionPayload;
+/// use smbcloud_wallet_kit::models::swap::{
+///     PrioritizationFeeLamports, PriorityLevelWithMaxLamports, SwapQuoteResponse,
+///     SwapTransactionPayload,
+/// };
 ///
+/// # async fn example(
+/// #     quote: SwapQuoteResponse,
+/// #     user_public_key: String,
+/// # ) -> Result<(), smbcloud_wallet_core_network::model::ErrorResponse> {
 /// let payload = SwapTransactionPayload {
-///     // ... payload fields from quote and user preferences
+///     quoteResponse: quote,
+///     userPublicKey: user_public_key,
+///     dynamicComputeUnitLimit: true,
+///     dynamicSlippage: true,
+///     wrapAndUnwrapSol: true,
+///     prioritizationFeeLamports: PrioritizationFeeLamports {
+///         priorityLevelWithMaxLamports: PriorityLevelWithMaxLamports {
+///             maxLamports: 1_000_000,
+///             priorityLevel: "veryHigh".to_string(),
+///         },
+///     },
 /// };
 ///
 /// let transaction = build_swap_transaction(payload).await?;
 /// // transaction.swapTransaction contains the base64 encoded transaction
+/// # Ok(())
+/// # }
 /// ```
 ///
 /// # Notes
@@ -118,9 +157,15 @@ pub async fn get_jupiter_swap_quote(
 pub async fn build_swap_transaction(
     payload: SwapTransactionPayload,
 ) -> Result<SwapTransactionResponse, ErrorResponse> {
-    let url = format!("{}{}", JUPITER_BASE_URL, JUPITER_SWAP_PATH);
-    let client = Client::new();
+    jupiter_swap_transaction(&Client::new(), payload).await
+}
 
+/// Build a swap transaction through `client`, see [`build_swap_transaction`].
+pub(crate) async fn jupiter_swap_transaction(
+    client: &Client,
+    payload: SwapTransactionPayload,
+) -> Result<SwapTransactionResponse, ErrorResponse> {
+    let url = format!("{}{}", JUPITER_BASE_URL, JUPITER_SWAP_PATH);
     let builder = client
         .post(url)
         .header(CONTENT_TYPE, "application/json")
@@ -146,15 +191,21 @@ pub async fn build_swap_transaction(
 ///
 /// # Examples
 ///
-/// ```rust
+/// ```no_run
 /// use smbcloud_wallet_kit::swap::send_jupiter_swap_transaction;
 /// use solana_sdk::signature::Keypair;
 ///
+/// # async fn example(
+/// #     base64_transaction: String,
+/// #     keypair: Keypair,
+/// # ) -> Result<(), smbcloud_wallet_core_network::model::ErrorResponse> {
 /// let signature = send_jupiter_swap_transaction(
 ///     "https://api.mainnet-beta.solana.com".to_string(),
 ///     base64_transaction,
 ///     keypair
 /// ).await?;
+/// # Ok(())
+/// # }
 /// ```
 ///
 /// # Notes
@@ -165,6 +216,17 @@ pub async fn send_jupiter_swap_transaction(
     rpc_url: String,
     swap_transaction: String,
     keypair: Keypair,
+) -> Result<Signature, ErrorResponse> {
+    let rpc_client = RpcClient::new(rpc_url);
+    send_swap_transaction(&rpc_client, swap_transaction, &keypair).await
+}
+
+/// Sign and send a Jupiter swap transaction through `rpc_client`, see
+/// [`send_jupiter_swap_transaction`].
+pub(crate) async fn send_swap_transaction(
+    rpc_client: &RpcClient,
+    swap_transaction: String,
+    keypair: &Keypair,
 ) -> Result<Signature, ErrorResponse> {
     // Decode the base64 transaction
     let transaction_bytes = general_purpose::STANDARD
@@ -188,17 +250,17 @@ pub async fn send_jupiter_swap_transaction(
 
     // Sign the transaction with the provided keypair
     let signed_versioned_transaction =
-        VersionedTransaction::try_new(versioned_transaction.message, &[&keypair]).map_err(|e| {
+        VersionedTransaction::try_new(versioned_transaction.message, &[keypair]).map_err(|e| {
             ErrorResponse::Error {
                 code: smbcloud_wallet_core_network::model::ErrorCode::ParseError,
                 message: format!("Failed to sign transaction: {}", e),
             }
         })?;
 
-    // Create RPC client and send the signed transaction
-    let rpc_client = RpcClient::new(rpc_url);
+    // Send the signed transaction
     let signature = rpc_client
         .send_and_confirm_transaction(&signed_versioned_transaction)
+        .await
         .map_err(|e| ErrorResponse::Error {
             code: smbcloud_wallet_core_network::model::ErrorCode::NetworkError,
             message: format!("Failed to send transaction: {}", e),
@@ -0,0 +1,922 @@
+use {
+    crate::{
+        cost_basis::{Lot, Lots, DUST, OPENING_LOT},
+        fee::TreasuryFeeManager,
+        history::{fetch_transactions, sol_delta, token_deltas, HistoryTransaction},
+        models::{
+            portfolio_history::{BalanceChange, CostBasisMethod},
+            tax_export::{
+                LedgerAmount, LedgerEntry, LedgerKind, LotAssignment, TaxExportFormat, TaxLedger,
+            },
+        },
+        portfolio_history::{balances_at, price_at},
+    },
+    chrono::DateTime,
+    log::{debug, warn},
+    smbcloud_wallet_constants::{assets_solana::ADDRESS_SOL, constants::LAMPORTS_PER_SOL},
+    smbcloud_wallet_core_http::{
+        fx_data::fx_history::FxHistoryProvider, price_data::price_history::PriceHistoryProvider,
+    },
+    smbcloud_wallet_core_model::models::{
+        currency::FiatCurrency,
+        portfolio::PortfolioValuation,
+        price::{FxRatePoint, PricePoint},
+        stake::StakeAccount,
+    },
+    solana_client::{client_error::ClientError, nonblocking::rpc_client::RpcClient},
+    solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey},
+    std::{collections::HashMap, str::FromStr},
+    thiserror::Error,
+};
+
+/// Most transactions read for an export.
+pub const MAX_EXPORT_TRANSACTIONS: usize = 5000;
+
+/// Most epochs searched for staking rewards, about two years.
+const MAX_REWARD_EPOCHS: u64 = 400;
+
+/// Most price points requested per token.
+const PRICE_POINTS: i64 = 1000;
+
+/// Finest spacing of the requested price points, in seconds.
+const MIN_PRICE_INTERVAL: i64 = 60 * 60;
+
+/// How far before the period exchange rates are read, to span the days without one.
+const FX_LOOKBACK: i64 = 7 * 24 * 60 * 60;
+
+#[derive(Error, Debug)]
+pub enum TaxExportError {
+    #[error("Failed to connect to RPC: {0}")]
+    ConnectionError(String),
+
+    #[error("Invalid address: {0}")]
+    InvalidAddress(String),
+
+    #[error("Invalid period: {0}")]
+    InvalidPeriod(String),
+
+    #[error("More than {0} transactions since the start of the period, choose a later start")]
+    TooManyTransactions(usize),
+
+    #[error("No exchange rates: {0}")]
+    ExchangeRates(String),
+
+    #[error("Failed to serialize the ledger: {0}")]
+    SerializationError(String),
+}
+
+/// What a transaction moved in and out of a wallet, fees apart.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct TransactionMovements {
+    /// Network fee paid by the wallet, in SOL.
+    network_fee: f64,
+    /// Fees paid to the treasury, by mint.
+    platform_fees: Vec<(String, f64)>,
+    /// Everything else by mint, negative for amounts sent.
+    deltas: Vec<(String, f64)>,
+}
+
+/// The taxable events of a wallet between `from` and `to`, valued at their time.
+///
+/// Transfers, swaps and fees are read from the wallet's transactions, staking
+/// rewards from the inflation rewards of `stake_accounts`. SOL moved between the
+/// wallet and these stake accounts is not an event. Holdings from before `from`
+/// are a single opening lot at the price of that moment. Prices are historical
+/// USD prices, converted to the valuation currency at the exchange rate of the
+/// same day from `fx_history`. Tokens in `hidden_mints` are left out.
+///
+/// Fails when the wallet has more than `MAX_EXPORT_TRANSACTIONS` transactions
+/// since `from`, rather than leaving the older ones out.
+#[allow(clippy::too_many_arguments)]
+pub async fn tax_ledger(
+    rpc_url: String,
+    pubkey: &str,
+    stake_accounts: &[StakeAccount],
+    valuation: &PortfolioValuation,
+    price_history: &dyn PriceHistoryProvider,
+    fx_history: &dyn FxHistoryProvider,
+    from: i64,
+    to: i64,
+    method: CostBasisMethod,
+    hidden_mints: &[String],
+) -> Result<TaxLedger, TaxExportError> {
+    if from >= to {
+        return Err(TaxExportError::InvalidPeriod(format!(
+            "{} is not before {}",
+            from, to
+        )));
+    }
+    let owner =
+        Pubkey::from_str(pubkey).map_err(|_| TaxExportError::InvalidAddress(pubkey.to_string()))?;
+    let stake_keys = stake_accounts
+        .iter()
+        .map(|account| {
+            Pubkey::from_str(&account.address)
+                .map_err(|_| TaxExportError::InvalidAddress(account.address.clone()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let rpc_client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
+
+    // Everything since `from` is read, later entries are needed for the opening balances.
+    let history = fetch_transactions(&rpc_client, &owner, from, MAX_EXPORT_TRANSACTIONS)
+        .await
+        .map_err(|e| TaxExportError::ConnectionError(e.to_string()))?;
+    if history.truncated {
+        return Err(TaxExportError::TooManyTransactions(MAX_EXPORT_TRANSACTIONS));
+    }
+    let transactions = history.transactions;
+    let treasury = TreasuryFeeManager::treasury_pubkey().ok();
+    let mut entries: Vec<LedgerEntry> = transactions
+        .iter()
+        .flat_map(|transaction| {
+            let mut movements =
+                transaction_movements(transaction, &owner, &stake_keys, treasury.as_ref());
+            movements
+                .deltas
+                .retain(|(mint, _)| !hidden_mints.contains(mint));
+            classify(&transaction.signature, transaction.block_time, movements)
+        })
+        .collect();
+    entries.extend(
+        fetch_staking_rewards(&rpc_client, stake_accounts, &stake_keys, from)
+            .await
+            .map_err(|e| TaxExportError::ConnectionError(e.to_string()))?,
+    );
+    debug!("{} ledger entries since {}", entries.len(), from);
+
+    // Staked SOL is held by the wallet as well.
+    let mut current: HashMap<String, f64> = HashMap::new();
+    for asset in &valuation.assets {
+        *current.entry(asset.mint.clone()).or_default() += asset.amount;
+    }
+    let staked: f64 = stake_accounts.iter().map(|account| account.balance).sum();
+    *current.entry(ADDRESS_SOL.to_string()).or_default() += staked;
+    let opening = opening_balances(&current, &entries, from);
+
+    let mut mints: Vec<&str> = opening.keys().map(String::as_str).collect();
+    for entry in &entries {
+        mints.extend(entry.sent.iter().map(|amount| amount.mint.as_str()));
+        mints.extend(entry.received.iter().map(|amount| amount.mint.as_str()));
+    }
+    mints.sort();
+    mints.dedup();
+    let now = valuation.updated_at;
+    let interval = ((now - from) / PRICE_POINTS).max(MIN_PRICE_INTERVAL);
+    let mut prices = HashMap::new();
+    for mint in mints {
+        match price_history
+            .price_history(mint, from - interval, to.min(now), interval)
+            .await
+        {
+            Ok(points) => {
+                prices.insert(mint.to_string(), points);
+            }
+            Err(e) => warn!("No price history for {}: {}", mint, e),
+        }
+    }
+
+    let fx_rates = fx_history
+        .usd_rate_history(valuation.currency, from - FX_LOOKBACK, to.min(now))
+        .await
+        .map_err(|e| TaxExportError::ExchangeRates(e.to_string()))?;
+
+    entries.retain(|entry| entry.timestamp <= to);
+    let mut entries = build_ledger(entries, &opening, from, method, &prices, &fx_rates);
+    let symbols: HashMap<&str, &str> = valuation
+        .assets
+        .iter()
+        .filter_map(|asset| Some((asset.mint.as_str(), asset.symbol.as_deref()?)))
+        .chain([(ADDRESS_SOL, "SOL")])
+        .collect();
+    for amount in entries
+        .iter_mut()
+        .flat_map(|entry| entry.sent.iter_mut().chain(entry.received.iter_mut()))
+    {
+        amount.symbol = symbols.get(amount.mint.as_str()).map(|s| s.to_string());
+    }
+
+    Ok(TaxLedger {
+        pubkey: pubkey.to_string(),
+        currency: valuation.currency,
+        method,
+        from,
+        to,
+        entries,
+    })
+}
+
+/// Value `entries` and match what they send against the lots acquired before.
+///
+/// `opening` are the balances at `from`, valued at the price of that moment.
+/// Entries are sorted oldest first. USD values are converted at the rate of
+/// `fx_rates` closest before them. Amounts without a price open lots without a
+/// cost, and the entries they take part in are marked as unpriced.
+pub fn build_ledger(
+    mut entries: Vec<LedgerEntry>,
+    opening: &HashMap<String, f64>,
+    from: i64,
+    method: CostBasisMethod,
+    prices: &HashMap<String, Vec<PricePoint>>,
+    fx_rates: &[FxRatePoint],
+) -> Vec<LedgerEntry> {
+    entries.sort_by_key(|entry| entry.timestamp);
+    let value_at = |mint: &str, amount: f64, timestamp: i64| {
+        let usd_price = price_at(prices.get(mint)?, timestamp)?;
+        Some(amount * usd_price * rate_at(fx_rates, timestamp)?)
+    };
+    let open_lot = |id: &str, timestamp: i64, amount: f64, value: Option<f64>| match value {
+        Some(value) => Lot::new(id, timestamp, amount, value / amount),
+        None => Lot::unpriced(id, timestamp, amount),
+    };
+
+    let mut lots: HashMap<String, Lots> = HashMap::new();
+    for (mint, amount) in opening.iter().filter(|(_, amount)| **amount > DUST) {
+        lots.entry(mint.clone())
+            .or_insert_with(|| Lots::new(method))
+            .buy(open_lot(
+                OPENING_LOT,
+                from,
+                *amount,
+                value_at(mint, *amount, from),
+            ));
+    }
+
+    for entry in &mut entries {
+        let timestamp = entry.timestamp;
+        // A swap is worth what was received, or what was sent when that has no price.
+        entry.value = [&entry.received, &entry.sent]
+            .into_iter()
+            .flatten()
+            .find_map(|amount| value_at(&amount.mint, amount.amount, timestamp));
+
+        if let Some(received) = &entry.received {
+            lots.entry(received.mint.clone())
+                .or_insert_with(|| Lots::new(method))
+                .buy(open_lot(&entry.id, timestamp, received.amount, entry.value));
+        }
+        if let Some(sent) = &entry.sent {
+            let taken = lots
+                .entry(sent.mint.clone())
+                .or_insert_with(|| Lots::new(method))
+                .sell(sent.amount);
+            let cost_basis = taken
+                .iter()
+                .all(|lot| lot.priced)
+                .then(|| taken.iter().map(Lot::cost).sum::<f64>());
+            entry.cost_basis = cost_basis;
+            entry.gain = entry
+                .value
+                .zip(cost_basis)
+                .map(|(value, cost)| value - cost);
+            entry.lots = taken
+                .into_iter()
+                .map(|lot| LotAssignment {
+                    cost_basis: lot.priced.then(|| lot.cost()),
+                    lot: lot.id,
+                    acquired_at: lot.acquired_at,
+                    amount: lot.amount,
+                })
+                .collect();
+        }
+        entry.unpriced =
+            entry.value.is_none() || (entry.sent.is_some() && entry.cost_basis.is_none());
+    }
+    entries
+}
+
+/// The exchange rate in effect at `timestamp`, the first one for earlier times.
+fn rate_at(fx_rates: &[FxRatePoint], timestamp: i64) -> Option<f64> {
+    let index = fx_rates.partition_point(|point| point.timestamp <= timestamp);
+    index
+        .checked_sub(1)
+        .or((!fx_rates.is_empty()).then_some(0))
+        .map(|i| fx_rates[i].usd_rate)
+}
+
+/// Render a ledger in `format`.
+pub fn export_ledger(
+    ledger: &TaxLedger,
+    format: TaxExportFormat,
+) -> Result<String, TaxExportError> {
+    match format {
+        TaxExportFormat::Koinly => Ok(koinly_csv(ledger)),
+        TaxExportFormat::CoinTracker => Ok(cointracker_csv(ledger)),
+        TaxExportFormat::Json => serde_json::to_string_pretty(ledger)
+            .map_err(|e| TaxExportError::SerializationError(e.to_string())),
+    }
+}
+
+/// Balances at `from`, undoing the entries that happened after it.
+fn opening_balances(
+    current: &HashMap<String, f64>,
+    entries: &[LedgerEntry],
+    from: i64,
+) -> HashMap<String, f64> {
+    let change = |entry: &LedgerEntry, amount: &LedgerAmount, sign: f64| BalanceChange {
+        signature: entry.id.clone(),
+        timestamp: entry.timestamp,
+        mint: amount.mint.clone(),
+        delta: sign * amount.amount,
+    };
+    let changes: Vec<BalanceChange> = entries
+        .iter()
+        .flat_map(|entry| {
+            let sent = entry.sent.iter().map(|amount| change(entry, amount, -1.0));
+            let received = entry
+                .received
+                .iter()
+                .map(|amount| change(entry, amount, 1.0));
+            sent.chain(received).collect::<Vec<_>>()
+        })
+        .collect();
+    // An entry at `from` happened after the opening.
+    balances_at(current, &changes, from - 1)
+}
+
+/// Split what a transaction moved for `owner` into fees and the rest.
+///
+/// SOL moved to or from `own_accounts` stays with the wallet. What `treasury`
+/// received of a token the wallet sent is a platform fee.
+fn transaction_movements(
+    transaction: &HistoryTransaction,
+    owner: &Pubkey,
+    own_accounts: &[Pubkey],
+    treasury: Option<&Pubkey>,
+) -> TransactionMovements {
+    let mut movements = TransactionMovements::default();
+    let encoded = &transaction.transaction;
+    let Some(meta) = encoded.meta.as_ref() else {
+        return movements;
+    };
+    let fee_payer = encoded
+        .transaction
+        .decode()
+        .and_then(|decoded| decoded.message.static_account_keys().first().copied());
+    if fee_payer.as_ref() == Some(owner) {
+        movements.network_fee = meta.fee as f64 / LAMPORTS_PER_SOL;
+    }
+
+    let sol = own_accounts
+        .iter()
+        .chain([owner])
+        .filter_map(|account| sol_delta(encoded, account))
+        .sum::<f64>()
+        + movements.network_fee;
+    let mut deltas = vec![(ADDRESS_SOL.to_string(), sol)];
+    deltas.extend(token_deltas(encoded, &owner.to_string()));
+
+    if let Some(treasury) = treasury.filter(|treasury| *treasury != owner) {
+        let mut received = token_deltas(encoded, &treasury.to_string());
+        received.extend(sol_delta(encoded, treasury).map(|delta| (ADDRESS_SOL.to_string(), delta)));
+        for (mint, fee) in received.into_iter().filter(|(_, fee)| *fee > DUST) {
+            let sent = deltas
+                .iter_mut()
+                .find(|(m, delta)| *m == mint && *delta < -DUST);
+            if let Some((_, delta)) = sent {
+                let fee = fee.min(-*delta);
+                *delta += fee;
+                movements.platform_fees.push((mint, fee));
+            }
+        }
+    }
+
+    deltas.retain(|(_, delta)| delta.abs() > DUST);
+    movements.deltas = deltas;
+    movements
+}
+
+/// Turn the movements of one transaction into ledger entries.
+///
+/// A token sent together with another one received is a swap, SOL only pairs
+/// when no other token does, as it is also spent on account rent.
+fn classify(signature: &str, timestamp: i64, movements: TransactionMovements) -> Vec<LedgerEntry> {
+    let entry = |id: String, kind: LedgerKind| LedgerEntry {
+        id,
+        signature: Some(signature.to_string()),
+        timestamp,
+        kind,
+        sent: None,
+        received: None,
+        description: None,
+        value: None,
+        cost_basis: None,
+        gain: None,
+        lots: Vec::new(),
+        unpriced: false,
+    };
+    let amount = |mint: &str, amount: f64| LedgerAmount {
+        mint: mint.to_string(),
+        symbol: None,
+        amount,
+    };
+
+    let (mut sent, mut received): (Vec<_>, Vec<_>) = movements
+        .deltas
+        .into_iter()
+        .partition(|(_, delta)| *delta < 0.0);
+    let mut transfers = Vec::new();
+    let swap_leg = |legs: &[(String, f64)]| {
+        legs.iter()
+            .position(|(mint, _)| mint != ADDRESS_SOL)
+            .or((!legs.is_empty()).then_some(0))
+    };
+    if let (Some(s), Some(r)) = (swap_leg(&sent), swap_leg(&received)) {
+        let (sent_mint, sent_delta) = sent.remove(s);
+        let (received_mint, received_delta) = received.remove(r);
+        transfers.push((
+            LedgerKind::Swap,
+            Some(amount(&sent_mint, -sent_delta)),
+            Some(amount(&received_mint, received_delta)),
+        ));
+    }
+    for (mint, delta) in sent {
+        transfers.push((LedgerKind::TransferOut, Some(amount(&mint, -delta)), None));
+    }
+    for (mint, delta) in received {
+        transfers.push((LedgerKind::TransferIn, None, Some(amount(&mint, delta))));
+    }
+
+    let mut entries: Vec<LedgerEntry> = transfers
+        .into_iter()
+        .enumerate()
+        .map(|(i, (kind, sent, received))| {
+            let id = match i {
+                0 => signature.to_string(),
+                i => format!("{}-{}", signature, i),
+            };
+            LedgerEntry {
+                sent,
+                received,
+                ..entry(id, kind)
+            }
+        })
+        .collect();
+    if movements.network_fee > DUST {
+        entries.push(LedgerEntry {
+            sent: Some(amount(ADDRESS_SOL, movements.network_fee)),
+            description: Some("Network fee".to_string()),
+            ..entry(format!("{}-network-fee", signature), LedgerKind::NetworkFee)
+        });
+    }
+    for (mint, fee) in movements.platform_fees {
+        entries.push(LedgerEntry {
+            sent: Some(amount(&mint, fee)),
+            description: Some("Platform fee".to_string()),
+            ..entry(
+                format!("{}-platform-fee-{}", signature, mint),
+                LedgerKind::PlatformFee,
+            )
+        });
+    }
+    entries
+}
+
+/// Inflation rewards of `stake_accounts` credited since `from`, newest first.
+///
+/// `stake_keys` are the parsed addresses of `stake_accounts`.
+async fn fetch_staking_rewards(
+    rpc_client: &RpcClient,
+    stake_accounts: &[StakeAccount],
+    stake_keys: &[Pubkey],
+    from: i64,
+) -> Result<Vec<LedgerEntry>, ClientError> {
+    let mut entries = Vec::new();
+    let Some(first_epoch) = stake_accounts
+        .iter()
+        .filter_map(|account| account.activation_epoch)
+        .min()
+    else {
+        return Ok(entries);
+    };
+    let epoch = rpc_client.get_epoch_info().await?.epoch;
+    let first_epoch = first_epoch.max(epoch.saturating_sub(MAX_REWARD_EPOCHS));
+
+    for epoch in (first_epoch..epoch).rev() {
+        let rewards = match rpc_client
+            .get_inflation_reward(stake_keys, Some(epoch))
+            .await
+        {
+            Ok(rewards) => rewards,
+            Err(e) => {
+                // Nodes only keep the rewards of recent epochs.
+                warn!("No staking rewards for epoch {}: {}", epoch, e);
+                break;
+            }
+        };
+        let Some(slot) = rewards.iter().flatten().map(|r| r.effective_slot).next() else {
+            continue;
+        };
+        let timestamp = rpc_client.get_block_time(slot).await?;
+        if timestamp < from {
+            break;
+        }
+        for (address, reward) in stake_keys.iter().zip(rewards) {
+            let Some(reward) = reward else {
+                continue;
+            };
+            entries.push(LedgerEntry {
+                id: format!("reward-{}-{}", epoch, address),
+                signature: None,
+                timestamp,
+                kind: LedgerKind::StakingReward,
+                sent: None,
+                received: Some(LedgerAmount {
+                    mint: ADDRESS_SOL.to_string(),
+                    symbol: Some("SOL".to_string()),
+                    amount: reward.amount as f64 / LAMPORTS_PER_SOL,
+                }),
+                description: Some(format!("Epoch {} reward of {}", epoch, address)),
+                value: None,
+                cost_basis: None,
+                gain: None,
+                lots: Vec::new(),
+                unpriced: false,
+            });
+        }
+    }
+    Ok(entries)
+}
+
+/// Koinly universal CSV, fees as outgoing `cost` rows.
+///
+/// Both CSV formats end with the cost basis, the lots and whether a price is missing.
+fn koinly_csv(ledger: &TaxLedger) -> String {
+    let mut csv = String::from(
+        "Date,Sent Amount,Sent Currency,Received Amount,Received Currency,Fee Amount,\
+         Fee Currency,Net Worth Amount,Net Worth Currency,Label,Description,TxHash,\
+         Cost Basis,Lots,Unpriced\n",
+    );
+    for entry in &ledger.entries {
+        let label = match entry.kind {
+            LedgerKind::StakingReward => "staking",
+            LedgerKind::NetworkFee | LedgerKind::PlatformFee => "cost",
+            _ => "",
+        };
+        let (sent_amount, sent_currency) = amount_columns(entry.sent.as_ref());
+        let (received_amount, received_currency) = amount_columns(entry.received.as_ref());
+        let row = [
+            format_date(entry.timestamp, "%Y-%m-%d %H:%M:%S UTC"),
+            sent_amount,
+            sent_currency,
+            received_amount,
+            received_currency,
+            String::new(),
+            String::new(),
+            fiat_column(entry.value, ledger.currency),
+            ledger.currency.code().to_string(),
+            label.to_string(),
+            entry.description.clone().unwrap_or_default(),
+            entry.signature.clone().unwrap_or_default(),
+            fiat_column(entry.cost_basis, ledger.currency),
+            lots_column(&entry.lots, ledger.currency),
+            unpriced_column(entry.unpriced),
+        ];
+        push_row(&mut csv, &row);
+    }
+    csv
+}
+
+/// CoinTracker CSV, fees in the fee columns of their own rows.
+fn cointracker_csv(ledger: &TaxLedger) -> String {
+    let mut csv = String::from(
+        "Date,Received Quantity,Received Currency,Sent Quantity,Sent Currency,Fee Amount,\
+         Fee Currency,Tag,Cost Basis,Lots,Unpriced\n",
+    );
+    for entry in &ledger.entries {
+        let is_fee = matches!(entry.kind, LedgerKind::NetworkFee | LedgerKind::PlatformFee);
+        let (received_amount, received_currency) = amount_columns(entry.received.as_ref());
+        let (sent_amount, sent_currency) = amount_columns(entry.sent.as_ref().filter(|_| !is_fee));
+        let (fee_amount, fee_currency) = amount_columns(entry.sent.as_ref().filter(|_| is_fee));
+        let tag = match entry.kind {
+            LedgerKind::StakingReward => "stake",
+            _ => "",
+        };
+        let row = [
+            format_date(entry.timestamp, "%m/%d/%Y %H:%M:%S"),
+            received_amount,
+            received_currency,
+            sent_amount,
+            sent_currency,
+            fee_amount,
+            fee_currency,
+            tag.to_string(),
+            fiat_column(entry.cost_basis, ledger.currency),
+            lots_column(&entry.lots, ledger.currency),
+            unpriced_column(entry.unpriced),
+        ];
+        push_row(&mut csv, &row);
+    }
+    csv
+}
+
+/// Amount and currency columns, the currency being the symbol or else the mint.
+fn amount_columns(amount: Option<&LedgerAmount>) -> (String, String) {
+    match amount {
+        Some(amount) => (
+            format_token_amount(amount.amount),
+            amount.symbol.clone().unwrap_or_else(|| amount.mint.clone()),
+        ),
+        None => (String::new(), String::new()),
+    }
+}
+
+fn fiat_column(amount: Option<f64>, currency: FiatCurrency) -> String {
+    amount
+        .map(|amount| format!("{:.*}", currency.decimals(), amount))
+        .unwrap_or_default()
+}
+
+/// Lots as `lot:amount:cost basis`, separated by semicolons. The cost basis
+/// of a lot without a price is left empty.
+fn lots_column(lots: &[LotAssignment], currency: FiatCurrency) -> String {
+    lots.iter()
+        .map(|lot| {
+            format!(
+                "{}:{}:{}",
+                lot.lot,
+                format_token_amount(lot.amount),
+                fiat_column(lot.cost_basis, currency)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+fn unpriced_column(unpriced: bool) -> String {
+    if unpriced { "yes" } else { "" }.to_string()
+}
+
+/// Up to nine decimals, the precision of SOL, without trailing zeros.
+fn format_token_amount(amount: f64) -> String {
+    let formatted = format!("{:.9}", amount);
+    formatted
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+fn format_date(timestamp: i64, format: &str) -> String {
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|date| date.format(format).to_string())
+        .unwrap_or_default()
+}
+
+fn push_row(csv: &mut String, columns: &[String]) {
+    let row: Vec<String> = columns
+        .iter()
+        .map(|column| {
+            if column.contains([',', '"', '\n']) {
+                format!("\"{}\"", column.replace('"', "\"\""))
+            } else {
+                column.clone()
+            }
+        })
+        .collect();
+    csv.push_str(&row.join(","));
+    csv.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_760_000_000;
+    const HOUR: i64 = 60 * 60;
+    const BACH: &str = "CKfatsPMUf8SkiURsDXs7eK6GWb4Jsd6UDbs7twMCWxo";
+
+    fn movements(network_fee: f64, deltas: &[(&str, f64)]) -> TransactionMovements {
+        TransactionMovements {
+            network_fee,
+            platform_fees: Vec::new(),
+            deltas: deltas
+                .iter()
+                .map(|(mint, delta)| (mint.to_string(), *delta))
+                .collect(),
+        }
+    }
+
+    fn history(points: &[(i64, f64)]) -> Vec<PricePoint> {
+        points
+            .iter()
+            .map(|(timestamp, usd_price)| PricePoint {
+                timestamp: *timestamp,
+                usd_price: *usd_price,
+            })
+            .collect()
+    }
+
+    fn ledger(entries: Vec<LedgerEntry>) -> TaxLedger {
+        TaxLedger {
+            pubkey: "wallet".to_string(),
+            currency: FiatCurrency::USD,
+            method: CostBasisMethod::Fifo,
+            from: NOW - 24 * HOUR,
+            to: NOW,
+            entries,
+        }
+    }
+
+    #[test]
+    fn test_classify_swap_with_fees() {
+        // USDC for BACH, paying rent for the BACH account in SOL.
+        let mut swap = movements(
+            0.000005,
+            &[(ADDRESS_SOL, -0.002), ("usdc", -10.0), (BACH, 500.0)],
+        );
+        swap.platform_fees.push(("usdc".to_string(), 0.025));
+
+        let entries = classify("sig", NOW, swap);
+        let kinds: Vec<LedgerKind> = entries.iter().map(|entry| entry.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                LedgerKind::Swap,
+                LedgerKind::TransferOut,
+                LedgerKind::NetworkFee,
+                LedgerKind::PlatformFee
+            ]
+        );
+        assert_eq!(entries[0].id, "sig");
+        assert_eq!(entries[0].sent.as_ref().unwrap().mint, "usdc");
+        assert_eq!(entries[0].received.as_ref().unwrap().amount, 500.0);
+        assert_eq!(entries[1].id, "sig-1");
+        assert_eq!(entries[1].sent.as_ref().unwrap().mint, ADDRESS_SOL);
+        assert_eq!(entries[2].sent.as_ref().unwrap().amount, 0.000005);
+        assert_eq!(entries[3].sent.as_ref().unwrap().amount, 0.025);
+    }
+
+    #[test]
+    fn test_classify_transfers() {
+        let entries = classify("in", NOW, movements(0.0, &[(BACH, 5.0)]));
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].kind, LedgerKind::TransferIn);
+
+        // A failed transaction only pays its fee.
+        let entries = classify("failed", NOW, movements(0.000005, &[]));
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].kind, LedgerKind::NetworkFee);
+    }
+
+    #[test]
+    fn test_build_ledger_assigns_lots() {
+        let start = NOW - 24 * HOUR;
+        let entries = [
+            classify(
+                "sell",
+                NOW - 2 * HOUR,
+                movements(0.0, &[(ADDRESS_SOL, -1.5)]),
+            ),
+            classify(
+                "buy",
+                NOW - 10 * HOUR,
+                movements(0.0, &[(ADDRESS_SOL, 1.0)]),
+            ),
+        ]
+        .concat();
+        let opening = HashMap::from([(ADDRESS_SOL.to_string(), 1.0)]);
+        let prices = HashMap::from([(
+            ADDRESS_SOL.to_string(),
+            history(&[
+                (start, 100.0),
+                (NOW - 10 * HOUR, 150.0),
+                (NOW - 2 * HOUR, 200.0),
+            ]),
+        )]);
+        // The currency weakens before the sale.
+        let fx_rates = [
+            FxRatePoint {
+                timestamp: start - 12 * HOUR,
+                usd_rate: 2.0,
+            },
+            FxRatePoint {
+                timestamp: NOW - 5 * HOUR,
+                usd_rate: 3.0,
+            },
+        ];
+
+        let entries = build_ledger(
+            entries,
+            &opening,
+            start,
+            CostBasisMethod::Fifo,
+            &prices,
+            &fx_rates,
+        );
+        assert_eq!(entries[0].id, "buy");
+        assert_eq!(entries[0].value, Some(300.0));
+        assert!(entries[0].lots.is_empty());
+
+        let sell = &entries[1];
+        assert_eq!(sell.value, Some(900.0));
+        assert_eq!(sell.lots.len(), 2);
+        assert_eq!(sell.lots[0].lot, OPENING_LOT);
+        assert_eq!(sell.lots[0].cost_basis, Some(200.0));
+        assert_eq!(sell.lots[1].lot, "buy");
+        assert_eq!(sell.lots[1].amount, 0.5);
+        assert_eq!(sell.lots[1].acquired_at, Some(NOW - 10 * HOUR));
+        assert_eq!(sell.cost_basis, Some(350.0));
+        assert_eq!(sell.gain, Some(550.0));
+        assert!(!sell.unpriced);
+    }
+
+    #[test]
+    fn test_build_ledger_marks_unpriced_entries() {
+        let entries = [
+            classify("sell", NOW - HOUR, movements(0.0, &[(BACH, -5.0)])),
+            classify("buy", NOW - 2 * HOUR, movements(0.0, &[(BACH, 5.0)])),
+        ]
+        .concat();
+        let fx_rates = [FxRatePoint {
+            timestamp: NOW - 24 * HOUR,
+            usd_rate: 1.0,
+        }];
+
+        let entries = build_ledger(
+            entries,
+            &HashMap::new(),
+            NOW - 24 * HOUR,
+            CostBasisMethod::Fifo,
+            &HashMap::new(),
+            &fx_rates,
+        );
+        assert!(entries.iter().all(|entry| entry.unpriced));
+        let sell = &entries[1];
+        assert_eq!(sell.cost_basis, None);
+        assert_eq!(sell.gain, None);
+        assert_eq!(sell.lots[0].cost_basis, None);
+    }
+
+    #[test]
+    fn test_rate_at() {
+        let fx_rates = [
+            FxRatePoint {
+                timestamp: NOW,
+                usd_rate: 10.0,
+            },
+            FxRatePoint {
+                timestamp: NOW + 24 * HOUR,
+                usd_rate: 11.0,
+            },
+        ];
+        assert_eq!(rate_at(&fx_rates, NOW - HOUR), Some(10.0));
+        assert_eq!(rate_at(&fx_rates, NOW + 23 * HOUR), Some(10.0));
+        assert_eq!(rate_at(&fx_rates, NOW + 72 * HOUR), Some(11.0));
+        assert_eq!(rate_at(&[], NOW), None);
+    }
+
+    #[test]
+    fn test_opening_balances() {
+        let entries = classify("buy", NOW - HOUR, movements(0.25, &[(BACH, 5.0)]));
+        let current = HashMap::from([(BACH.to_string(), 7.0), (ADDRESS_SOL.to_string(), 1.0)]);
+        let opening = opening_balances(&current, &entries, NOW - 2 * HOUR);
+        assert_eq!(opening[BACH], 2.0);
+        assert_eq!(opening[ADDRESS_SOL], 1.25);
+        // Nothing changed before the first entry.
+        let opening = opening_balances(&current, &entries, NOW - HOUR + 1);
+        assert_eq!(opening[BACH], 7.0);
+    }
+
+    #[test]
+    fn test_csv_exports() {
+        let mut swap = classify(
+            "sig",
+            NOW,
+            movements(0.000005, &[("usdc", -10.0), (BACH, 500.0)]),
+        );
+        swap[0].sent.as_mut().unwrap().symbol = Some("USDC".to_string());
+        swap[0].description = Some("Swap, on Jupiter".to_string());
+        swap[0].value = Some(10.0);
+        swap[1].unpriced = true;
+        let ledger = ledger(swap);
+
+        let koinly = export_ledger(&ledger, TaxExportFormat::Koinly).unwrap();
+        let lines: Vec<&str> = koinly.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[1],
+            format!(
+                "2025-10-09 08:53:20 UTC,10,USDC,500,{},,,10.00,USD,,\"Swap, on Jupiter\",sig,,,",
+                BACH
+            )
+        );
+        assert!(lines[2].ends_with(",cost,Network fee,sig,,,yes"));
+
+        let cointracker = export_ledger(&ledger, TaxExportFormat::CoinTracker).unwrap();
+        let lines: Vec<&str> = cointracker.lines().collect();
+        assert_eq!(
+            lines[2],
+            format!("10/09/2025 08:53:20,,,,,0.000005,{},,,,yes", ADDRESS_SOL)
+        );
+
+        let json = export_ledger(&ledger, TaxExportFormat::Json).unwrap();
+        let parsed: TaxLedger = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, ledger);
+    }
+
+    #[test]
+    fn test_format_token_amount() {
+        assert_eq!(format_token_amount(1.5), "1.5");
+        assert_eq!(format_token_amount(0.1 + 0.2), "0.3");
+        assert_eq!(format_token_amount(12.0), "12");
+    }
+}
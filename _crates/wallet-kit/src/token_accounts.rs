@@ -0,0 +1,365 @@
+use {
+    crate::{
+        history::signed_recently,
+        models::token_accounts::{ClosedTokenAccounts, EmptyTokenAccount, RentReclaim},
+        rpc::{parse_address, send, RpcError},
+    },
+    log::{info, warn},
+    smbcloud_wallet_constants::constants::{
+        LAMPORTS_PER_SOL, SPL_TOKEN_2022_PROGRAM_ID, SPL_TOKEN_PROGRAM_ID,
+    },
+    solana_account_decoder::{
+        parse_token::{UiAccountState, UiTokenAccount},
+        parse_token_extension::UiExtension,
+        UiAccountData,
+    },
+    solana_client::{nonblocking::rpc_client::RpcClient, rpc_request::TokenAccountsFilter},
+    solana_sdk::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+    },
+    spl_associated_token_account::get_associated_token_address,
+    std::collections::HashSet,
+    thiserror::Error,
+};
+
+/// Accounts closed per transaction, which keeps it within the size limit.
+const CLOSE_BATCH_SIZE: usize = 20;
+
+/// Transactions of a token account searched for one its owner signed.
+const OWNER_ACTIVITY_LIMIT: usize = 25;
+
+/// Instruction index of `CloseAccount`, the same in both token programs.
+const CLOSE_ACCOUNT_INSTRUCTION: u8 = 9;
+
+#[derive(Error, Debug)]
+pub enum TokenAccountsError {
+    #[error("Failed to connect to RPC: {0}")]
+    ConnectionError(String),
+
+    #[error("Invalid address: {0}")]
+    InvalidAddress(String),
+
+    #[error("Transaction error: {0}")]
+    TransactionError(String),
+}
+
+impl RpcError for TokenAccountsError {
+    fn connection_error(message: String) -> Self {
+        TokenAccountsError::ConnectionError(message)
+    }
+
+    fn transaction_error(message: String) -> Self {
+        TokenAccountsError::TransactionError(message)
+    }
+
+    fn invalid_address(address: String) -> Self {
+        TokenAccountsError::InvalidAddress(address)
+    }
+}
+
+/// Empty token accounts of `owner` in both token programs, with the SOL
+/// closing them returns.
+pub async fn get_empty_token_accounts(
+    rpc_client: &RpcClient,
+    owner: String,
+) -> Result<RentReclaim, TokenAccountsError> {
+    let owner = parse_address(&owner)?;
+    let accounts = empty_token_accounts(rpc_client, &owner).await?;
+    let total_rent = accounts.iter().map(|account| account.rent).sum();
+    Ok(RentReclaim {
+        accounts,
+        total_rent,
+    })
+}
+
+/// Of `mints`, those `owner` moved itself, by sending, swapping or closing them
+/// in a transaction it signed. Tokens only ever received from others are left out.
+///
+/// Only the latest transactions of each token account are read.
+pub async fn owner_initiated_mints(
+    rpc_client: &RpcClient,
+    owner: String,
+    mints: &[String],
+) -> Result<HashSet<String>, TokenAccountsError> {
+    let owner = parse_address(&owner)?;
+    let mut initiated = HashSet::new();
+    for mint in mints {
+        let token_accounts = rpc_client
+            .get_token_accounts_by_owner(&owner, TokenAccountsFilter::Mint(parse_address(mint)?))
+            .await
+            .map_err(|e| TokenAccountsError::ConnectionError(e.to_string()))?;
+        for keyed_account in token_accounts {
+            let account = parse_address(&keyed_account.pubkey)?;
+            let signed = signed_recently(rpc_client, &account, &owner, OWNER_ACTIVITY_LIMIT)
+                .await
+                .map_err(|e| TokenAccountsError::ConnectionError(e.to_string()))?;
+            if signed {
+                initiated.insert(mint.clone());
+                break;
+            }
+        }
+    }
+    Ok(initiated)
+}
+
+/// Close the given empty token accounts and return their rent to the wallet.
+///
+/// Accounts are checked again before closing, as a transfer may have arrived
+/// since they were listed. Those no longer closable are skipped, as are the
+/// accounts of a batch whose transaction fails.
+pub async fn close_empty_token_accounts(
+    rpc_client: &RpcClient,
+    keypair: Keypair,
+    accounts: Vec<String>,
+) -> Result<ClosedTokenAccounts, TokenAccountsError> {
+    let owner = keypair.pubkey();
+    let empty = empty_token_accounts(rpc_client, &owner).await?;
+
+    let (closable, mut skipped): (Vec<_>, Vec<_>) = accounts
+        .into_iter()
+        .partition(|address| empty.iter().any(|account| &account.address == address));
+    let closable = empty
+        .into_iter()
+        .filter(|account| closable.contains(&account.address))
+        .collect::<Vec<_>>();
+
+    let mut signatures = Vec::new();
+    let mut closed = Vec::new();
+    let mut reclaimed = 0.0;
+    let mut last_error = None;
+    for batch in closable.chunks(CLOSE_BATCH_SIZE) {
+        let instructions = batch
+            .iter()
+            .map(|account| {
+                Ok(close_account_instruction(
+                    &parse_address(&account.program_id)?,
+                    &parse_address(&account.address)?,
+                    &owner,
+                ))
+            })
+            .collect::<Result<Vec<_>, TokenAccountsError>>()?;
+        let addresses = batch.iter().map(|account| account.address.clone());
+        match send(rpc_client, &keypair, &instructions, &[]).await {
+            Ok(signature) => {
+                signatures.push(signature);
+                closed.extend(addresses);
+                reclaimed += batch.iter().map(|account| account.rent).sum::<f64>();
+            }
+            Err(e) => {
+                warn!("Failed to close {} token accounts: {}", batch.len(), e);
+                skipped.extend(addresses);
+                last_error = Some(e);
+            }
+        }
+    }
+
+    // Report a failure when nothing could be closed at all.
+    if let (true, Some(e)) = (closed.is_empty(), last_error) {
+        return Err(e);
+    }
+    info!(
+        "Closed {} token accounts, reclaimed {} SOL",
+        closed.len(),
+        reclaimed
+    );
+    Ok(ClosedTokenAccounts {
+        signatures,
+        closed,
+        skipped,
+        reclaimed,
+    })
+}
+
+async fn empty_token_accounts(
+    rpc_client: &RpcClient,
+    owner: &Pubkey,
+) -> Result<Vec<EmptyTokenAccount>, TokenAccountsError> {
+    let wsol_account = get_associated_token_address(owner, &spl_token::native_mint::id());
+    let mut empty = Vec::new();
+    for program_id in [SPL_TOKEN_PROGRAM_ID, SPL_TOKEN_2022_PROGRAM_ID] {
+        let token_accounts = rpc_client
+            .get_token_accounts_by_owner(
+                owner,
+                TokenAccountsFilter::ProgramId(parse_address(program_id)?),
+            )
+            .await
+            .map_err(|e| TokenAccountsError::ConnectionError(e.to_string()))?;
+
+        for keyed_account in token_accounts {
+            let UiAccountData::Json(parsed) = &keyed_account.account.data else {
+                continue;
+            };
+            let Ok(account) =
+                serde_json::from_value::<UiTokenAccount>(parsed.parsed["info"].clone())
+            else {
+                continue;
+            };
+            if is_closable(&keyed_account.pubkey, &account, owner, &wsol_account) {
+                empty.push(EmptyTokenAccount {
+                    address: keyed_account.pubkey,
+                    mint: account.mint,
+                    program_id: program_id.to_string(),
+                    rent: keyed_account.account.lamports as f64 / LAMPORTS_PER_SOL,
+                });
+            }
+        }
+    }
+    Ok(empty)
+}
+
+/// Whether the wallet can close `account` without losing anything but its rent.
+///
+/// Accounts holding tokens, frozen accounts and the associated wSOL account
+/// swaps wrap SOL through are never closed.
+fn is_closable(
+    address: &str,
+    account: &UiTokenAccount,
+    owner: &Pubkey,
+    wsol_account: &Pubkey,
+) -> bool {
+    let empty = account.token_amount.amount == "0";
+    let frozen = account.state == UiAccountState::Frozen;
+    let wsol_in_use = account.is_native && address == wsol_account.to_string();
+    empty && !frozen && !wsol_in_use && closable_once_empty(account, owner)
+}
+
+/// Whether `owner` can close `account` once it holds no tokens.
+///
+/// Not when another authority closes it, nor for Token-2022 accounts with
+/// withheld fees or confidential balances, which the program refuses to close.
+pub(crate) fn closable_once_empty(account: &UiTokenAccount, owner: &Pubkey) -> bool {
+    let closable_by_owner = account
+        .close_authority
+        .as_ref()
+        .is_none_or(|authority| *authority == owner.to_string());
+    let locked_by_extension = account.extensions.iter().any(|extension| match extension {
+        UiExtension::TransferFeeAmount(fee) => fee.withheld_amount > 0,
+        UiExtension::ConfidentialTransferAccount(_) => true,
+        _ => false,
+    });
+    closable_by_owner && !locked_by_extension
+}
+
+/// `CloseAccount` of either token program, sending the rent of `account` to its owner.
+pub(crate) fn close_account_instruction(
+    program_id: &Pubkey,
+    account: &Pubkey,
+    owner: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*account, false),
+            AccountMeta::new(*owner, false),
+            AccountMeta::new_readonly(*owner, true),
+        ],
+        data: vec![CLOSE_ACCOUNT_INSTRUCTION],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, serde_json::json, std::str::FromStr};
+
+    fn token_account(amount: &str, state: &str, extra: serde_json::Value) -> UiTokenAccount {
+        let mut info = json!({
+            "mint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+            "owner": "Owner11111111111111111111111111111111111111",
+            "tokenAmount": {
+                "amount": amount,
+                "decimals": 6,
+                "uiAmount": 0.0,
+                "uiAmountString": "0"
+            },
+            "state": state,
+            "isNative": false
+        });
+        if let (Some(info), Some(extra)) = (info.as_object_mut(), extra.as_object()) {
+            info.extend(extra.clone());
+        }
+        serde_json::from_value(info).unwrap()
+    }
+
+    #[test]
+    fn test_is_closable() {
+        let owner = Pubkey::new_unique();
+        let wsol_account = get_associated_token_address(&owner, &spl_token::native_mint::id());
+        let address = Pubkey::new_unique().to_string();
+        let closable =
+            |account: &UiTokenAccount| is_closable(&address, account, &owner, &wsol_account);
+
+        assert!(closable(&token_account("0", "initialized", json!({}))));
+        assert!(!closable(&token_account("1", "initialized", json!({}))));
+        assert!(!closable(&token_account("0", "frozen", json!({}))));
+        assert!(!closable(&token_account(
+            "0",
+            "initialized",
+            json!({ "closeAuthority": Pubkey::new_unique().to_string() })
+        )));
+        assert!(closable(&token_account(
+            "0",
+            "initialized",
+            json!({ "closeAuthority": owner.to_string() })
+        )));
+    }
+
+    #[test]
+    fn test_wsol_account_in_use() {
+        let owner = Pubkey::new_unique();
+        let wsol_account = get_associated_token_address(&owner, &spl_token::native_mint::id());
+        let native = token_account("0", "initialized", json!({ "isNative": true }));
+        assert!(!is_closable(
+            &wsol_account.to_string(),
+            &native,
+            &owner,
+            &wsol_account
+        ));
+        // Other wSOL accounts only hold their rent once empty.
+        assert!(is_closable(
+            &Pubkey::new_unique().to_string(),
+            &native,
+            &owner,
+            &wsol_account
+        ));
+    }
+
+    #[test]
+    fn test_token_2022_extensions() {
+        let owner = Pubkey::new_unique();
+        let address = Pubkey::new_unique().to_string();
+        let wsol_account = Pubkey::new_unique();
+        let withheld = |amount: u64| {
+            token_account(
+                "0",
+                "initialized",
+                json!({ "extensions": [{
+                    "extension": "transferFeeAmount",
+                    "state": { "withheldAmount": amount }
+                }] }),
+            )
+        };
+        assert!(is_closable(&address, &withheld(0), &owner, &wsol_account));
+        assert!(!is_closable(&address, &withheld(5), &owner, &wsol_account));
+    }
+
+    #[test]
+    fn test_close_account_instruction() {
+        let owner = Pubkey::new_unique();
+        let account = Pubkey::new_unique();
+        let token_2022 = Pubkey::from_str(SPL_TOKEN_2022_PROGRAM_ID).unwrap();
+        let instruction = close_account_instruction(&token_2022, &account, &owner);
+        assert_eq!(instruction.program_id, token_2022);
+        assert_eq!(instruction.data, vec![CLOSE_ACCOUNT_INSTRUCTION]);
+        assert_eq!(instruction.accounts[1].pubkey, owner);
+        assert!(instruction.accounts[2].is_signer);
+
+        // Same layout as the SPL Token instruction builder.
+        let spl_instruction =
+            spl_token::instruction::close_account(&spl_token::id(), &account, &owner, &owner, &[])
+                .unwrap();
+        assert_eq!(spl_instruction.data, instruction.data);
+        assert_eq!(spl_instruction.accounts, instruction.accounts);
+    }
+}
@@ -1,18 +1,27 @@
 use {
-    crate::fee::TreasuryFeeManager,
+    crate::fee::{FeeBreakdown, TreasuryFeeManager},
     log::{debug, info, warn},
-    smbcloud_wallet_constants::constants::{
-        SEMITONE_PER_BACH, THE_STABLE_FOUNDATION_TREASURY_WALLET_FEE,
-    },
-    smbcloud_wallet_core_model::models::asset_solana::SolanaAsset,
+    smbcloud_wallet_constants::constants::{SEMITONE_PER_BACH, SPL_MEMO_PROGRAM_ID},
+    smbcloud_wallet_core::token_registry::resolve_tokens,
+    smbcloud_wallet_core_model::token_registry::TokenRegistry,
     solana_client::{nonblocking::rpc_client::RpcClient, rpc_request::TokenAccountsFilter},
     solana_sdk::{
-        program_pack::Pack, pubkey::Pubkey, signature::Keypair, signer::Signer, system_instruction,
+        instruction::{AccountMeta, Instruction},
+        program_pack::Pack,
+        pubkey::Pubkey,
+        signature::{Keypair, Signature},
+        signer::Signer,
         transaction::Transaction,
     },
-    spl_token::{
-        instruction as token_instruction,
-        state::{Account as TokenAccount, Mint},
+    solana_system_interface_v1::instruction as system_instruction,
+    spl_associated_token_account::{
+        get_associated_token_address_with_program_id,
+        instruction::create_associated_token_account_idempotent,
+    },
+    spl_token::state::Account as TokenAccount,
+    spl_token_2022::{
+        extension::StateWithExtensions, instruction as token_2022_instruction,
+        state::Account as Token2022Account,
     },
     std::str::FromStr,
     thiserror::Error,
@@ -42,6 +51,75 @@ pub enum TransactionError {
     TreasuryError(String),
 }
 
+/// Optional extras attached to a transfer, e.g. Solana Pay reference keys and memo.
+#[derive(Debug, Clone, Default)]
+pub struct TransferOptions {
+    /// Read-only, non-signer accounts appended to the transfer instruction so the
+    /// transaction can be found later with `getSignaturesForAddress`.
+    pub references: Vec<Pubkey>,
+    /// UTF-8 memo, sent as an SPL Memo instruction right before the transfer.
+    pub memo: Option<String>,
+    /// Charge the treasury fee on top of the amount so the recipient receives it in full.
+    pub fee_on_top: bool,
+    /// Return once the transaction is sent, when its confirmation is followed
+    /// elsewhere, e.g. with a signature subscription.
+    pub skip_confirmation: bool,
+}
+
+impl TransferOptions {
+    fn fee_breakdown(
+        &self,
+        amount: f64,
+        currency: String,
+    ) -> Result<FeeBreakdown, TransactionError> {
+        let breakdown = if self.fee_on_top {
+            TreasuryFeeManager::calculate_fees_on_top(amount, currency)
+        } else {
+            TreasuryFeeManager::calculate_fees(amount, currency)
+        };
+        breakdown.map_err(|e| TransactionError::FeeCalculationError(e.to_string()))
+    }
+
+    /// Send `transaction`, waiting for its confirmation unless skipped.
+    async fn send(
+        &self,
+        rpc_client: &RpcClient,
+        transaction: &Transaction,
+    ) -> Result<Signature, TransactionError> {
+        let sent = if self.skip_confirmation {
+            rpc_client.send_transaction(transaction).await
+        } else {
+            rpc_client.send_and_confirm_transaction(transaction).await
+        };
+        sent.map_err(|e| TransactionError::TransactionError(e.to_string()))
+    }
+
+    /// Returns the memo instruction (if any) followed by the transfer instruction
+    /// with the reference keys attached.
+    fn wrap_transfer(
+        &self,
+        mut transfer: Instruction,
+    ) -> Result<Vec<Instruction>, TransactionError> {
+        let mut instructions = Vec::new();
+        if let Some(memo) = &self.memo {
+            let memo_program = Pubkey::from_str(SPL_MEMO_PROGRAM_ID)
+                .map_err(|_| TransactionError::InvalidAddress(SPL_MEMO_PROGRAM_ID.to_string()))?;
+            instructions.push(Instruction::new_with_bytes(
+                memo_program,
+                memo.as_bytes(),
+                vec![],
+            ));
+        }
+        transfer.accounts.extend(
+            self.references
+                .iter()
+                .map(|reference| AccountMeta::new_readonly(*reference, false)),
+        );
+        instructions.push(transfer);
+        Ok(instructions)
+    }
+}
+
 /// Creates and sends a SOL transfer transaction with 0.25% fee to treasury
 pub async fn create_transfer_ix(
     rpc_url: String,
@@ -49,10 +127,49 @@ pub async fn create_transfer_ix(
     from_pubkey: String,
     to_pubkey: String,
     amount: f64,
+) -> Result<String, TransactionError> {
+    create_transfer_ix_with_options(
+        rpc_url,
+        sender_keypair,
+        from_pubkey,
+        to_pubkey,
+        amount,
+        TransferOptions::default(),
+    )
+    .await
+}
+
+/// Same as [`create_transfer_ix`], with reference keys, memo and fee mode taken from `options`.
+pub async fn create_transfer_ix_with_options(
+    rpc_url: String,
+    sender_keypair: Keypair,
+    from_pubkey: String,
+    to_pubkey: String,
+    amount: f64,
+    options: TransferOptions,
 ) -> Result<String, TransactionError> {
     // Connect to the Solana cluster
     let rpc_client = RpcClient::new(rpc_url);
+    send_sol_transfer(
+        &rpc_client,
+        &sender_keypair,
+        from_pubkey,
+        to_pubkey,
+        amount,
+        options,
+    )
+    .await
+}
 
+/// Send a SOL transfer through `rpc_client`, see [`create_transfer_ix_with_options`].
+pub(crate) async fn send_sol_transfer(
+    rpc_client: &RpcClient,
+    sender_keypair: &Keypair,
+    from_pubkey: String,
+    to_pubkey: String,
+    amount: f64,
+    options: TransferOptions,
+) -> Result<String, TransactionError> {
     // Parse public keys
     let from = Pubkey::from_str(&from_pubkey)
         .map_err(|_| TransactionError::InvalidAddress(from_pubkey.clone()))?;
@@ -61,8 +178,7 @@ pub async fn create_transfer_ix(
         .map_err(|_| TransactionError::InvalidAddress(to_pubkey.clone()))?;
 
     // Calculate fee breakdown
-    let fee_breakdown = TreasuryFeeManager::calculate_fees(amount, "SOL".to_string())
-        .map_err(|e| TransactionError::FeeCalculationError(e.to_string()))?;
+    let fee_breakdown = options.fee_breakdown(amount, "SOL".to_string())?;
 
     // Convert to lamports
     let fee_lamports = fee_breakdown.fee_lamports();
@@ -91,6 +207,8 @@ pub async fn create_transfer_ix(
     let fee_instruction = TreasuryFeeManager::create_sol_fee_instruction(&from, fee_lamports)
         .map_err(|e| TransactionError::FeeCalculationError(e.to_string()))?;
     let main_instruction = system_instruction::transfer(&from, &to, net_amount_lamports);
+    let mut instructions = vec![fee_instruction];
+    instructions.extend(options.wrap_transfer(main_instruction)?);
 
     // Get recent blockhash
     let blockhash = rpc_client
@@ -98,19 +216,16 @@ pub async fn create_transfer_ix(
         .await
         .map_err(|e| TransactionError::ConnectionError(e.to_string()))?;
 
-    // Create and sign transaction with both instructions
+    // Create and sign transaction with the fee and transfer instructions
     let transaction = Transaction::new_signed_with_payer(
-        &[fee_instruction, main_instruction],
+        &instructions,
         Some(&from),
-        &[&sender_keypair],
+        &[sender_keypair],
         blockhash,
     );
 
     // Send transaction
-    let signature = rpc_client
-        .send_and_confirm_transaction(&transaction)
-        .await
-        .map_err(|e| TransactionError::TransactionError(e.to_string()))?;
+    let signature = options.send(rpc_client, &transaction).await?;
 
     // Log fee collection for audit
     TreasuryFeeManager::log_fee_collection(&signature.to_string(), &fee_breakdown, "SOL");
@@ -133,13 +248,68 @@ pub async fn create_token_transfer_ix(
     token_program_id: String,
     amount: f64,
 ) -> Result<String, TransactionError> {
-    // Get the SolanaAsset
-    let asset = match SolanaAsset::from_address(token_mint_address.clone()) {
-        Some(asset) => asset,
-        None => return Err(TransactionError::InvalidAddress(from_pubkey.clone())),
-    };
+    create_token_transfer_ix_with_options(
+        rpc_url,
+        sender_keypair,
+        from_pubkey,
+        to_pubkey,
+        token_mint_address,
+        token_program_id,
+        amount,
+        TransferOptions::default(),
+    )
+    .await
+}
+
+/// Same as [`create_token_transfer_ix`], with reference keys, memo and fee mode taken from `options`.
+#[allow(clippy::too_many_arguments)]
+pub async fn create_token_transfer_ix_with_options(
+    rpc_url: String,
+    sender_keypair: Keypair,
+    from_pubkey: String,
+    to_pubkey: String,
+    token_mint_address: String,
+    token_program_id: String,
+    amount: f64,
+    options: TransferOptions,
+) -> Result<String, TransactionError> {
     // Connect to the Solana cluster
     let rpc_client = RpcClient::new(rpc_url);
+    send_token_transfer(
+        &rpc_client,
+        &sender_keypair,
+        from_pubkey,
+        to_pubkey,
+        token_mint_address,
+        token_program_id,
+        amount,
+        options,
+    )
+    .await
+}
+
+/// Send a token transfer through `rpc_client`, see [`create_token_transfer_ix_with_options`].
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn send_token_transfer(
+    rpc_client: &RpcClient,
+    sender_keypair: &Keypair,
+    from_pubkey: String,
+    to_pubkey: String,
+    token_mint_address: String,
+    token_program_id: String,
+    amount: f64,
+    options: TransferOptions,
+) -> Result<String, TransactionError> {
+    // Get the token metadata, reading it from chain for unknown tokens
+    let asset = resolve_tokens(
+        TokenRegistry::shared(),
+        rpc_client.url(),
+        std::slice::from_ref(&token_mint_address),
+    )
+    .await
+    .map_err(|e| TransactionError::ConnectionError(e.to_string()))?
+    .pop()
+    .ok_or_else(|| TransactionError::InvalidAddress(token_mint_address.clone()))?;
 
     // Parse public keys
     let from_wallet = Pubkey::from_str(&from_pubkey)
@@ -155,75 +325,43 @@ pub async fn create_token_transfer_ix(
         .map_err(|_| TransactionError::InvalidAddress(token_program_id.clone()))?;
 
     // Calculate fee breakdown for token transaction
-    let fee_breakdown = TreasuryFeeManager::calculate_fees(amount, asset.metadata().symbol)
-        .map_err(|e| TransactionError::FeeCalculationError(e.to_string()))?;
+    let fee_breakdown = options.fee_breakdown(amount, asset.symbol.clone())?;
 
     // Find the token accounts for the sender, recipient, and treasury
-    let sender_token_account = find_token_account(&rpc_client, &from_wallet, &token_mint).await?;
+    let sender_token_account = find_token_account(rpc_client, &from_wallet, &token_mint).await?;
 
-    // Ensure recipient has a token account for this mint
+    // Pay the associated token account of the recipient, as Solana Pay
+    // requires, creating it when the recipient has none yet
     let recipient_token_account =
-        match find_token_account(&rpc_client, &to_wallet, &token_mint).await {
-            Ok(account) => {
-                debug!("Recipient token account exists: {}", account);
-                account
-            }
-            Err(_) => {
-                info!("Creating recipient token account for mint: {}", token_mint);
-                create_token_account(
-                    &rpc_client,
-                    &sender_keypair,
-                    &to_wallet,
-                    &token_mint,
-                    &token_program,
-                )
-                .await?
-            }
-        };
-
-    // Check if treasury has a token account for this mint, if not we'll need to create one
-    let treasury_wallet =
-        Pubkey::from_str(THE_STABLE_FOUNDATION_TREASURY_WALLET_FEE).map_err(|_| {
-            TransactionError::InvalidAddress(THE_STABLE_FOUNDATION_TREASURY_WALLET_FEE.to_string())
-        })?;
-
-    let treasury_token_account_result =
-        find_token_account(&rpc_client, &treasury_wallet, &token_mint).await;
-
-    let _treasury_token_account = match treasury_token_account_result {
-        Ok(account) => {
-            debug!("Treasury token account exists: {}", account);
-            account
-        }
-        Err(_) => {
-            info!("Creating treasury token account for mint: {}", token_mint);
-            // Create a token account for the treasury (sender pays for creation)
-            let new_account = create_token_account(
-                &rpc_client,
-                &sender_keypair,
-                &treasury_wallet,
-                &token_mint,
-                &token_program,
-            )
-            .await
-            .map_err(|e| {
-                TransactionError::TreasuryError(format!(
-                    "Failed to create treasury token account: {}",
-                    e
-                ))
-            })?;
-
-            info!("Created treasury token account: {}", new_account);
-            new_account
-        }
-    };
+        get_associated_token_address_with_program_id(&to_wallet, &token_mint, &token_program);
+    let recipient_account = rpc_client
+        .get_account_with_commitment(&recipient_token_account, rpc_client.commitment())
+        .await
+        .map_err(|e| TransactionError::ConnectionError(e.to_string()))?
+        .value;
+    if recipient_account.is_some() {
+        debug!(
+            "Recipient token account exists: {}",
+            recipient_token_account
+        );
+    } else {
+        info!("Creating recipient token account for mint: {}", token_mint);
+        create_token_account(
+            rpc_client,
+            sender_keypair,
+            &to_wallet,
+            &token_mint,
+            &token_program,
+        )
+        .await?;
+    }
 
-    // Get treasury token account
+    // Get the treasury token account, the sender pays for its creation
     let treasury_wallet = TreasuryFeeManager::treasury_pubkey()
         .map_err(|e| TransactionError::TreasuryError(e.to_string()))?;
 
     let treasury_token_account =
-        match find_token_account(&rpc_client, &treasury_wallet, &token_mint).await {
+        match find_token_account(rpc_client, &treasury_wallet, &token_mint).await {
             Ok(account) => {
                 debug!("Treasury token account exists: {}", account);
                 account
@@ -231,8 +369,8 @@ pub async fn create_token_transfer_ix(
             Err(_) => {
                 info!("Creating treasury token account for mint: {}", token_mint);
                 create_token_account(
-                    &rpc_client,
-                    &sender_keypair,
+                    rpc_client,
+                    sender_keypair,
                     &treasury_wallet,
                     &token_mint,
                     &token_program,
@@ -249,15 +387,14 @@ pub async fn create_token_transfer_ix(
 
     // Convert to the smallest unit
 
-    let denomination = asset.smallest_denomination();
-    let fee_denomination = fee_breakdown.fee_token_units(denomination);
-    let net_amount = fee_breakdown.net_token_units(denomination);
-    let total_amount = fee_breakdown.total_token_units(denomination);
+    let fee_denomination = fee_breakdown.fee_base_units(asset.decimal);
+    let net_amount = fee_breakdown.net_base_units(asset.decimal);
+    let total_amount = fee_breakdown.total_base_units(asset.decimal);
 
     debug!("Token fee breakdown: {}", fee_breakdown.format_summary());
 
     // Check token balance
-    let token_balance = get_token_balance(&rpc_client, &sender_token_account).await?;
+    let token_balance = get_token_balance(rpc_client, &sender_token_account).await?;
 
     if token_balance < total_amount {
         warn!(
@@ -276,21 +413,28 @@ pub async fn create_token_transfer_ix(
     let fee_instruction = TreasuryFeeManager::create_token_fee_instruction(
         &token_program,
         &sender_token_account,
+        &token_mint,
         &treasury_token_account,
         &from_wallet,
         fee_denomination,
+        asset.decimal,
     )
     .map_err(|e| TransactionError::TransactionError(e.to_string()))?;
 
-    let main_instruction = token_instruction::transfer(
+    // Checked transfers carry the mint and decimals, which Token-2022 needs
+    let main_instruction = token_2022_instruction::transfer_checked(
         &token_program,
         &sender_token_account,
+        &token_mint,
         &recipient_token_account,
         &from_wallet,
-        &[&from_wallet],
+        &[],
         net_amount,
+        asset.decimal,
     )
     .map_err(|e| TransactionError::TransactionError(e.to_string()))?;
+    let mut instructions = vec![fee_instruction];
+    instructions.extend(options.wrap_transfer(main_instruction)?);
 
     // Get recent blockhash
     let blockhash = rpc_client
@@ -298,19 +442,16 @@ pub async fn create_token_transfer_ix(
         .await
         .map_err(|e| TransactionError::ConnectionError(e.to_string()))?;
 
-    // Create and sign transaction with both instructions
+    // Create and sign transaction with the fee and transfer instructions
     let transaction = Transaction::new_signed_with_payer(
-        &[fee_instruction, main_instruction],
+        &instructions,
         Some(&from_wallet),
-        &[&sender_keypair],
+        &[sender_keypair],
         blockhash,
     );
 
     // Send transaction
-    let signature = rpc_client
-        .send_and_confirm_transaction(&transaction)
-        .await
-        .map_err(|e| TransactionError::TransactionError(e.to_string()))?;
+    let signature = options.send(rpc_client, &transaction).await?;
 
     // Log fee collection for audit
     TreasuryFeeManager::log_fee_collection(&signature.to_string(), &fee_breakdown, "Token");
@@ -333,7 +474,7 @@ async fn find_token_account(
         .await
         .map_err(|e| TransactionError::ConnectionError(e.to_string()))?;
 
-    if let Some(account_info) = token_accounts.iter().next() {
+    if let Some(account_info) = token_accounts.first() {
         let pubkey = Pubkey::from_str(&account_info.pubkey)
             .map_err(|_| TransactionError::InvalidAddress(account_info.pubkey.clone()))?;
         Ok(pubkey)
@@ -342,7 +483,9 @@ async fn find_token_account(
     }
 }
 
-// Helper function to create a token account
+// Helper function to create the associated token account of `owner`.
+// Reusing the associated account avoids leaving a new token account, and its
+// rent, behind on every transfer.
 async fn create_token_account(
     rpc_client: &RpcClient,
     payer: &Keypair,
@@ -350,46 +493,15 @@ async fn create_token_account(
     token_mint: &Pubkey,
     token_program: &Pubkey,
 ) -> Result<Pubkey, TransactionError> {
-    // Create a new keypair for the token account
-    let token_account_keypair = Keypair::new();
-    let token_account_pubkey = token_account_keypair.pubkey();
-
-    // Get token mint data to determine account size
-    let mint_info = rpc_client
-        .get_account(token_mint)
-        .await
-        .map_err(|e| TransactionError::ConnectionError(e.to_string()))?;
-
-    let _mint = Mint::unpack(&mint_info.data)
-        .map_err(|_| TransactionError::TransactionError("Failed to unpack mint".to_string()))?;
-
-    // Calculate space required for token account
-    let space = TokenAccount::LEN;
-
-    // Get minimum rent for token account
-    let rent = rpc_client
-        .get_minimum_balance_for_rent_exemption(space)
-        .await
-        .map_err(|e| TransactionError::ConnectionError(e.to_string()))?;
-
-    // Create account instruction
-    let create_account_ix = system_instruction::create_account(
+    let token_account_pubkey =
+        get_associated_token_address_with_program_id(owner, token_mint, token_program);
+    let create_account_ix = create_associated_token_account_idempotent(
         &payer.pubkey(),
-        &token_account_pubkey,
-        rent,
-        space as u64,
+        owner,
+        token_mint,
         token_program,
     );
 
-    // Initialize token account instruction
-    let init_account_ix = token_instruction::initialize_account(
-        token_program,
-        &token_account_pubkey,
-        token_mint,
-        owner,
-    )
-    .map_err(|e| TransactionError::TransactionError(e.to_string()))?;
-
     // Get recent blockhash
     let blockhash = rpc_client
         .get_latest_blockhash()
@@ -398,9 +510,9 @@ async fn create_token_account(
 
     // Create and sign transaction
     let transaction = Transaction::new_signed_with_payer(
-        &[create_account_ix, init_account_ix],
+        &[create_account_ix],
         Some(&payer.pubkey()),
-        &[payer, &token_account_keypair],
+        &[payer],
         blockhash,
     );
 
@@ -423,11 +535,13 @@ async fn get_token_balance(
         .await
         .map_err(|e| TransactionError::ConnectionError(e.to_string()))?;
 
-    let token_account = TokenAccount::unpack(&account.data).map_err(|_| {
-        TransactionError::TransactionError("Failed to unpack token account".to_string())
-    })?;
+    // Token-2022 accounts may carry extensions after the base account
+    let token_account =
+        StateWithExtensions::<Token2022Account>::unpack(&account.data).map_err(|_| {
+            TransactionError::TransactionError("Failed to unpack token account".to_string())
+        })?;
 
-    Ok(token_account.amount)
+    Ok(token_account.base.amount)
 }
 
 /// Represents the cost breakdown of a transaction
@@ -0,0 +1,88 @@
+//! Conversion of decimal token amounts to integer base units.
+//!
+//! Amounts are converted digit by digit, never by multiplying floats, so
+//! 2.01 with 6 decimals is 2_010_000 units where `2.01 * 1e6` truncates to
+//! 2_009_999.
+
+/// Base units of `value`, a plain decimal such as `2.01`, for a mint with `decimals`.
+///
+/// Returns `None` when `value` is not a non-negative decimal without exponent,
+/// has more fraction digits than `decimals`, or does not fit in a `u64`.
+pub fn parse_base_units(value: &str, decimals: u8) -> Option<u64> {
+    let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+    let is_digits = |digits: &str| digits.bytes().all(|b| b.is_ascii_digit());
+    if whole.is_empty() || !is_digits(whole) || !is_digits(fraction) {
+        return None;
+    }
+    if fraction.len() > decimals as usize {
+        return None;
+    }
+
+    let scale = 10u64.checked_pow(decimals as u32)?;
+    let whole = whole.parse::<u64>().ok()?.checked_mul(scale)?;
+    let fraction = if fraction.is_empty() {
+        0
+    } else {
+        format!("{:0<width$}", fraction, width = decimals as usize)
+            .parse::<u64>()
+            .ok()?
+    };
+    whole.checked_add(fraction)
+}
+
+/// Base units of `amount` for a mint with `decimals`, rounded down.
+///
+/// `amount` is read through its shortest decimal representation, the digits
+/// it was parsed from, so amounts typed by the user or taken from a payment
+/// request convert exactly. Negative and non-finite amounts are 0, amounts
+/// too large for a `u64` saturate.
+pub fn to_base_units(amount: f64, decimals: u8) -> u64 {
+    if !amount.is_finite() || amount <= 0.0 {
+        return 0;
+    }
+    // `Display` never uses an exponent for floats.
+    let value = amount.to_string();
+    let (whole, fraction) = value.split_once('.').unwrap_or((&value, ""));
+    let fraction = &fraction[..fraction.len().min(decimals as usize)];
+    parse_base_units(&format!("{}.{}", whole, fraction), decimals).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_base_units() {
+        assert_eq!(parse_base_units("2.01", 6), Some(2_010_000));
+        assert_eq!(parse_base_units("1", 9), Some(1_000_000_000));
+        assert_eq!(parse_base_units("0.000001", 6), Some(1));
+        assert_eq!(parse_base_units("007.5", 2), Some(750));
+        assert_eq!(parse_base_units("12", 0), Some(12));
+        // Too many fraction digits for the mint.
+        assert_eq!(parse_base_units("1.1234567", 6), None);
+        assert_eq!(parse_base_units("1.5", 0), None);
+        // Not a plain decimal.
+        for value in ["", ".5", "-1", "1e3", "1,5", "abc", " 1"] {
+            assert_eq!(parse_base_units(value, 6), None, "{:?}", value);
+        }
+        // Does not fit in a u64.
+        assert_eq!(parse_base_units("18446744073709551616", 0), None);
+        assert_eq!(parse_base_units("18446744073710", 6), None);
+    }
+
+    #[test]
+    fn test_to_base_units() {
+        assert_eq!(to_base_units(2.01, 6), 2_010_000);
+        assert_eq!(to_base_units(0.3, 9), 300_000_000);
+        assert_eq!(to_base_units(1.005, 6), 1_005_000);
+        assert_eq!(to_base_units(0.1 + 0.2, 9), 300_000_000);
+        assert_eq!(to_base_units(12.5, 6), 12_500_000);
+        assert_eq!(to_base_units(0.0000001, 9), 100);
+        // Digits the mint cannot hold are dropped.
+        assert_eq!(to_base_units(1.23456789, 6), 1_234_567);
+        assert_eq!(to_base_units(0.0, 6), 0);
+        assert_eq!(to_base_units(-1.0, 6), 0);
+        assert_eq!(to_base_units(f64::NAN, 6), 0);
+        assert_eq!(to_base_units(1e30, 9), u64::MAX);
+    }
+}
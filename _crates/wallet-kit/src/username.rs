@@ -0,0 +1,477 @@
+use {
+    crate::models::username::{UsernameAttestation, UsernameChallenge, UsernameProof},
+    solana_sdk::{
+        pubkey::Pubkey,
+        signature::{Keypair, Signature, Signer},
+    },
+    std::str::FromStr,
+    thiserror::Error,
+};
+
+/// First line of every username ownership message.
+pub const USERNAME_PROOF_HEADER: &str = "NotWallet username ownership";
+
+/// First line of every directory attestation.
+pub const USERNAME_ATTESTATION_HEADER: &str = "NotWallet username directory";
+
+/// Longest an attestation may be valid for, in seconds.
+pub const USERNAME_ATTESTATION_MAX_VALIDITY: i64 = 24 * 60 * 60;
+
+/// Clock difference tolerated with the directory, in seconds.
+const CLOCK_SKEW: i64 = 5 * 60;
+
+const USERNAME_MIN_LENGTH: usize = 3;
+const USERNAME_MAX_LENGTH: usize = 32;
+
+#[derive(Error, Debug)]
+pub enum UsernameError {
+    #[error("Invalid username: {0}")]
+    InvalidUsername(String),
+
+    #[error("Invalid address: {0}")]
+    InvalidAddress(String),
+
+    #[error("Invalid ownership message: {0}")]
+    InvalidMessage(String),
+
+    #[error("Ownership proof is for {0}")]
+    ProofMismatch(String),
+
+    #[error("Invalid ownership signature")]
+    InvalidSignature,
+
+    #[error("Ownership proof is not attested by the directory")]
+    MissingAttestation,
+
+    #[error("Invalid directory signature")]
+    InvalidAttestation,
+
+    #[error("Directory attestation expired")]
+    ExpiredAttestation,
+}
+
+/// Whether `to` is a `@username` recipient rather than an address.
+pub fn is_username(to: &str) -> bool {
+    to.trim().starts_with('@')
+}
+
+/// Strip the leading `@` and lowercase the name.
+///
+/// Usernames are 3 to 32 characters of `a-z`, `0-9` and `_`.
+pub fn normalize_username(input: &str) -> Result<String, UsernameError> {
+    let username = input.trim().trim_start_matches('@').to_lowercase();
+    let valid_length = (USERNAME_MIN_LENGTH..=USERNAME_MAX_LENGTH).contains(&username.len());
+    let valid_chars = username
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if !valid_length || !valid_chars {
+        return Err(UsernameError::InvalidUsername(input.to_string()));
+    }
+    Ok(username)
+}
+
+/// Read the username and pubkey out of an ownership message.
+///
+/// The message looks like:
+///
+/// ```text
+/// NotWallet username ownership
+/// Username: alice
+/// Pubkey: mvines9iiHiQTysrwkJjGf2gb9Ex9jXJX8ns3qwf2kN
+/// Nonce: 6f1c2a
+/// ```
+pub fn parse_proof_message(message: &str) -> Result<(String, Pubkey), UsernameError> {
+    let [username, pubkey, _nonce] = message_fields(
+        message,
+        USERNAME_PROOF_HEADER,
+        ["Username", "Pubkey", "Nonce"],
+    )?;
+    Ok((normalize_username(username)?, parse_pubkey(pubkey)?))
+}
+
+/// What a directory attestation states: the username, the pubkey it is
+/// assigned to, and the unix times it was issued at and expires at.
+///
+/// The message looks like:
+///
+/// ```text
+/// NotWallet username directory
+/// Username: alice
+/// Pubkey: mvines9iiHiQTysrwkJjGf2gb9Ex9jXJX8ns3qwf2kN
+/// Issued: 1760000000
+/// Expires: 1760003600
+/// ```
+pub fn parse_attestation_message(
+    message: &str,
+) -> Result<(String, Pubkey, i64, i64), UsernameError> {
+    let [username, pubkey, issued, expires] = message_fields(
+        message,
+        USERNAME_ATTESTATION_HEADER,
+        ["Username", "Pubkey", "Issued", "Expires"],
+    )?;
+    let timestamp = |value: &str| {
+        value
+            .parse::<i64>()
+            .map_err(|_| UsernameError::InvalidMessage(value.to_string()))
+    };
+    Ok((
+        normalize_username(username)?,
+        parse_pubkey(pubkey)?,
+        timestamp(issued)?,
+        timestamp(expires)?,
+    ))
+}
+
+/// The values of `fields` in a message made of `header` and one `Field: value`
+/// line per field, each exactly once.
+fn message_fields<'a, const N: usize>(
+    message: &'a str,
+    header: &str,
+    fields: [&str; N],
+) -> Result<[&'a str; N], UsernameError> {
+    let mut lines = message.lines();
+    if lines.next() != Some(header) {
+        return Err(UsernameError::InvalidMessage("missing header".to_string()));
+    }
+
+    let mut values = [None; N];
+    for line in lines {
+        let (field, value) = line
+            .split_once(": ")
+            .ok_or_else(|| UsernameError::InvalidMessage(line.to_string()))?;
+        let index = fields
+            .iter()
+            .position(|known| *known == field)
+            .ok_or_else(|| UsernameError::InvalidMessage(line.to_string()))?;
+        if values[index].replace(value).is_some() {
+            return Err(UsernameError::InvalidMessage(format!(
+                "duplicate {}",
+                field
+            )));
+        }
+    }
+
+    let mut found = [""; N];
+    for (index, value) in values.into_iter().enumerate() {
+        found[index] = value.ok_or_else(|| {
+            UsernameError::InvalidMessage(format!("missing {}", fields[index].to_lowercase()))
+        })?;
+    }
+    Ok(found)
+}
+
+fn parse_pubkey(pubkey: &str) -> Result<Pubkey, UsernameError> {
+    Pubkey::from_str(pubkey).map_err(|_| UsernameError::InvalidAddress(pubkey.to_string()))
+}
+
+/// Sign the directory challenge to claim `username` for `keypair`.
+///
+/// The challenge is checked first so the wallet never signs a message that
+/// claims another name or key.
+pub fn sign_username_claim(
+    keypair: &Keypair,
+    username: &str,
+    challenge: &UsernameChallenge,
+) -> Result<UsernameProof, UsernameError> {
+    let username = normalize_username(username)?;
+    let (claimed, pubkey) = parse_proof_message(&challenge.message)?;
+    if claimed != username || pubkey != keypair.pubkey() {
+        return Err(UsernameError::ProofMismatch(format!(
+            "@{} {}",
+            claimed, pubkey
+        )));
+    }
+
+    let signature = keypair.sign_message(challenge.message.as_bytes());
+    Ok(UsernameProof {
+        username,
+        pubkey: pubkey.to_string(),
+        message: challenge.message.clone(),
+        signature: signature.to_string(),
+        attestation: None,
+    })
+}
+
+/// Check that `proof` was signed by its pubkey for `username`, and that
+/// `directory` attests the username is assigned to that pubkey at `now`, a
+/// unix time. Returns the pubkey.
+///
+/// The directory is not trusted on its own: a proof for another name or a
+/// pubkey that differs from the signed message is rejected. The owner's
+/// signature is not enough either, as it outlives a username given up and
+/// reassigned, so the directory attestation must be current.
+pub fn verify_username_proof(
+    proof: &UsernameProof,
+    username: &str,
+    directory: &Pubkey,
+    now: i64,
+) -> Result<Pubkey, UsernameError> {
+    let username = normalize_username(username)?;
+    let (claimed, pubkey) = parse_proof_message(&proof.message)?;
+    if claimed != username || proof.pubkey != pubkey.to_string() {
+        return Err(UsernameError::ProofMismatch(format!(
+            "@{} {}",
+            claimed, pubkey
+        )));
+    }
+    if !verify_signature(&proof.signature, &pubkey, &proof.message) {
+        return Err(UsernameError::InvalidSignature);
+    }
+
+    let attestation = proof
+        .attestation
+        .as_ref()
+        .ok_or(UsernameError::MissingAttestation)?;
+    verify_attestation(attestation, &username, &pubkey, directory, now)?;
+    Ok(pubkey)
+}
+
+/// Check that `attestation` was signed by `directory`, assigns `username` to
+/// `pubkey`, and is valid at `now`.
+fn verify_attestation(
+    attestation: &UsernameAttestation,
+    username: &str,
+    pubkey: &Pubkey,
+    directory: &Pubkey,
+    now: i64,
+) -> Result<(), UsernameError> {
+    if !verify_signature(&attestation.signature, directory, &attestation.message) {
+        return Err(UsernameError::InvalidAttestation);
+    }
+    let (attested, assigned, issued, expires) = parse_attestation_message(&attestation.message)?;
+    if attested != username || assigned != *pubkey {
+        return Err(UsernameError::ProofMismatch(format!(
+            "@{} {}",
+            attested, assigned
+        )));
+    }
+    let valid = issued - CLOCK_SKEW <= now
+        && now < expires
+        && expires - issued <= USERNAME_ATTESTATION_MAX_VALIDITY;
+    if !valid {
+        return Err(UsernameError::ExpiredAttestation);
+    }
+    Ok(())
+}
+
+fn verify_signature(signature: &str, pubkey: &Pubkey, message: &str) -> bool {
+    Signature::from_str(signature)
+        .is_ok_and(|signature| signature.verify(pubkey.as_ref(), message.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn challenge(username: &str, pubkey: &Pubkey) -> UsernameChallenge {
+        UsernameChallenge {
+            message: format!(
+                "{}\nUsername: {}\nPubkey: {}\nNonce: 6f1c2a",
+                USERNAME_PROOF_HEADER, username, pubkey
+            ),
+        }
+    }
+
+    const NOW: i64 = 1_760_000_000;
+
+    fn attestation(
+        directory: &Keypair,
+        username: &str,
+        pubkey: &Pubkey,
+        issued: i64,
+        expires: i64,
+    ) -> UsernameAttestation {
+        let message = format!(
+            "{}\nUsername: {}\nPubkey: {}\nIssued: {}\nExpires: {}",
+            USERNAME_ATTESTATION_HEADER, username, pubkey, issued, expires
+        );
+        UsernameAttestation {
+            signature: directory.sign_message(message.as_bytes()).to_string(),
+            message,
+        }
+    }
+
+    /// A claim of `username` by `keypair`, as returned by `directory`.
+    fn attested_proof(keypair: &Keypair, username: &str, directory: &Keypair) -> UsernameProof {
+        let mut proof =
+            sign_username_claim(keypair, username, &challenge(username, &keypair.pubkey()))
+                .unwrap();
+        proof.attestation = Some(attestation(
+            directory,
+            username,
+            &keypair.pubkey(),
+            NOW - 60,
+            NOW + 3600,
+        ));
+        proof
+    }
+
+    #[test]
+    fn test_normalize_username() {
+        assert_eq!(normalize_username("@Alice_01").unwrap(), "alice_01");
+        assert_eq!(normalize_username(" bob ").unwrap(), "bob");
+        assert!(normalize_username("@al").is_err());
+        assert!(normalize_username("alice.sol").is_err());
+        assert!(normalize_username(&"a".repeat(33)).is_err());
+        assert!(is_username("@alice"));
+        assert!(!is_username("alice.sol"));
+    }
+
+    #[test]
+    fn test_parse_proof_message() {
+        let pubkey = Pubkey::new_unique();
+        let (username, parsed) = parse_proof_message(&challenge("Alice", &pubkey).message).unwrap();
+        assert_eq!(username, "alice");
+        assert_eq!(parsed, pubkey);
+
+        let without_nonce = format!(
+            "{}\nUsername: alice\nPubkey: {}",
+            USERNAME_PROOF_HEADER, pubkey
+        );
+        assert!(parse_proof_message(&without_nonce).is_err());
+        let duplicate = format!("{}\nUsername: bob", challenge("alice", &pubkey).message);
+        assert!(parse_proof_message(&duplicate).is_err());
+        assert!(parse_proof_message("Sign in to example.com").is_err());
+    }
+
+    #[test]
+    fn test_sign_and_verify_username_claim() {
+        let keypair = Keypair::new();
+        let directory = Keypair::new();
+        let proof = attested_proof(&keypair, "alice", &directory);
+        let verify = |proof: &UsernameProof, username: &str| {
+            verify_username_proof(proof, username, &directory.pubkey(), NOW)
+        };
+        assert_eq!(verify(&proof, "@ALICE").unwrap(), keypair.pubkey());
+
+        // A proof for one name does not resolve another.
+        assert!(matches!(
+            verify(&proof, "bob"),
+            Err(UsernameError::ProofMismatch(_))
+        ));
+
+        // The directory cannot swap the pubkey without the owner's signature.
+        let mut swapped = proof.clone();
+        swapped.pubkey = Pubkey::new_unique().to_string();
+        assert!(verify(&swapped, "alice").is_err());
+        let other = Keypair::new();
+        let mut forged = proof.clone();
+        forged.signature = other.sign_message(proof.message.as_bytes()).to_string();
+        assert!(matches!(
+            verify(&forged, "alice"),
+            Err(UsernameError::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn test_verify_username_attestation() {
+        let keypair = Keypair::new();
+        let directory = Keypair::new();
+        let proof = attested_proof(&keypair, "alice", &directory);
+        let verify = |proof: &UsernameProof, now: i64| {
+            verify_username_proof(proof, "alice", &directory.pubkey(), now)
+        };
+
+        // The owner's signature alone is not enough.
+        let mut unattested = proof.clone();
+        unattested.attestation = None;
+        assert!(matches!(
+            verify(&unattested, NOW),
+            Err(UsernameError::MissingAttestation)
+        ));
+
+        // Only the directory key attests.
+        let mut forged = proof.clone();
+        forged.attestation = Some(attestation(
+            &Keypair::new(),
+            "alice",
+            &keypair.pubkey(),
+            NOW - 60,
+            NOW + 3600,
+        ));
+        assert!(matches!(
+            verify(&forged, NOW),
+            Err(UsernameError::InvalidAttestation)
+        ));
+
+        // Attestations are time bounded.
+        assert!(matches!(
+            verify(&proof, NOW + 3600),
+            Err(UsernameError::ExpiredAttestation)
+        ));
+        assert!(matches!(
+            verify(&proof, NOW - 60 - CLOCK_SKEW - 1),
+            Err(UsernameError::ExpiredAttestation)
+        ));
+        let mut long_lived = proof.clone();
+        long_lived.attestation = Some(attestation(
+            &directory,
+            "alice",
+            &keypair.pubkey(),
+            NOW - 60,
+            NOW + USERNAME_ATTESTATION_MAX_VALIDITY,
+        ));
+        assert!(matches!(
+            verify(&long_lived, NOW),
+            Err(UsernameError::ExpiredAttestation)
+        ));
+    }
+
+    #[test]
+    fn test_verify_reassigned_username() {
+        let previous_owner = Keypair::new();
+        let owner = Keypair::new();
+        let directory = Keypair::new();
+        let previous = attested_proof(&previous_owner, "alice", &directory);
+        let current = attested_proof(&owner, "alice", &directory);
+
+        // The old proof signed by the previous owner with the current record.
+        let mut replayed = previous.clone();
+        replayed.attestation = current.attestation.clone();
+        assert!(matches!(
+            verify_username_proof(&replayed, "alice", &directory.pubkey(), NOW),
+            Err(UsernameError::ProofMismatch(_))
+        ));
+        // The old proof with its own attestation, once that expired.
+        assert!(matches!(
+            verify_username_proof(&previous, "alice", &directory.pubkey(), NOW + 3600),
+            Err(UsernameError::ExpiredAttestation)
+        ));
+        assert_eq!(
+            verify_username_proof(&current, "alice", &directory.pubkey(), NOW).unwrap(),
+            owner.pubkey()
+        );
+    }
+
+    #[test]
+    fn test_parse_attestation_message() {
+        let pubkey = Pubkey::new_unique();
+        let directory = Keypair::new();
+        let attestation = attestation(&directory, "Alice", &pubkey, NOW, NOW + 60);
+        assert_eq!(
+            parse_attestation_message(&attestation.message).unwrap(),
+            ("alice".to_string(), pubkey, NOW, NOW + 60)
+        );
+        let without_expiry = format!(
+            "{}\nUsername: alice\nPubkey: {}\nIssued: {}",
+            USERNAME_ATTESTATION_HEADER, pubkey, NOW
+        );
+        assert!(parse_attestation_message(&without_expiry).is_err());
+        // An ownership message is not an attestation.
+        assert!(parse_attestation_message(&challenge("alice", &pubkey).message).is_err());
+    }
+
+    #[test]
+    fn test_sign_username_claim_rejects_foreign_challenge() {
+        let keypair = Keypair::new();
+        let other = Pubkey::new_unique();
+        assert!(matches!(
+            sign_username_claim(&keypair, "alice", &challenge("alice", &other)),
+            Err(UsernameError::ProofMismatch(_))
+        ));
+        assert!(matches!(
+            sign_username_claim(&keypair, "alice", &challenge("bob", &keypair.pubkey())),
+            Err(UsernameError::ProofMismatch(_))
+        ));
+    }
+}
@@ -0,0 +1,210 @@
+//! Wrapped SOL, the SPL Token form of SOL that swaps and dApps trade with.
+
+use {
+    crate::{
+        rpc::{parse_address, send, RpcError},
+        token_accounts::closable_once_empty,
+    },
+    log::info,
+    smbcloud_wallet_constants::constants::LAMPORTS_PER_SOL,
+    solana_account_decoder::{
+        parse_token::{UiAccountState, UiTokenAccount},
+        UiAccountData,
+    },
+    solana_client::{
+        client_error::ClientError, nonblocking::rpc_client::RpcClient,
+        rpc_request::TokenAccountsFilter,
+    },
+    solana_sdk::{
+        instruction::Instruction,
+        message::Message,
+        program_pack::Pack,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+    },
+    solana_system_interface_v1::instruction as system_instruction,
+    spl_associated_token_account::{
+        get_associated_token_address, instruction::create_associated_token_account_idempotent,
+    },
+    spl_token::{instruction as token_instruction, native_mint, state::Account as TokenAccount},
+    thiserror::Error,
+};
+
+#[derive(Error, Debug)]
+pub enum WsolError {
+    #[error("Failed to connect to RPC: {0}")]
+    ConnectionError(String),
+
+    #[error("Invalid address: {0}")]
+    InvalidAddress(String),
+
+    #[error("Invalid amount: {0}")]
+    InvalidAmount(f64),
+
+    #[error("Insufficient funds")]
+    InsufficientFunds,
+
+    #[error("The wallet holds no wrapped SOL")]
+    NothingToUnwrap,
+
+    #[error("Transaction error: {0}")]
+    TransactionError(String),
+}
+
+impl RpcError for WsolError {
+    fn connection_error(message: String) -> Self {
+        WsolError::ConnectionError(message)
+    }
+
+    fn transaction_error(message: String) -> Self {
+        WsolError::TransactionError(message)
+    }
+
+    fn invalid_address(address: String) -> Self {
+        WsolError::InvalidAddress(address)
+    }
+}
+
+/// Wrap `amount` SOL into the associated wSOL account of the wallet, creating
+/// it if needed.
+///
+/// The wallet must also afford the transaction fee, and the rent of the wSOL
+/// account when it is created.
+pub async fn wrap_sol(
+    rpc_client: &RpcClient,
+    keypair: Keypair,
+    amount: f64,
+) -> Result<String, WsolError> {
+    if !amount.is_finite() || amount <= 0.0 {
+        return Err(WsolError::InvalidAmount(amount));
+    }
+    let owner = keypair.pubkey();
+    let lamports = (amount * LAMPORTS_PER_SOL).round() as u64;
+    let instructions = wrap_instructions(&owner, lamports);
+    let connection_error = |e: ClientError| WsolError::ConnectionError(e.to_string());
+
+    let wsol_account = get_associated_token_address(&owner, &native_mint::id());
+    let account_rent = if rpc_client
+        .get_balance(&wsol_account)
+        .await
+        .map_err(connection_error)?
+        > 0
+    {
+        0
+    } else {
+        rpc_client
+            .get_minimum_balance_for_rent_exemption(TokenAccount::LEN)
+            .await
+            .map_err(connection_error)?
+    };
+    let blockhash = rpc_client
+        .get_latest_blockhash()
+        .await
+        .map_err(connection_error)?;
+    let fee = rpc_client
+        .get_fee_for_message(&Message::new_with_blockhash(
+            &instructions,
+            Some(&owner),
+            &blockhash,
+        ))
+        .await
+        .map_err(connection_error)?;
+    let balance = rpc_client
+        .get_balance(&owner)
+        .await
+        .map_err(connection_error)?;
+    if balance < lamports.saturating_add(account_rent).saturating_add(fee) {
+        return Err(WsolError::InsufficientFunds);
+    }
+
+    let signature = send(rpc_client, &keypair, &instructions, &[]).await?;
+    info!("Wrapped {} SOL", amount);
+    Ok(signature)
+}
+
+/// Unwrap all wrapped SOL of the wallet by closing its native token accounts,
+/// which returns their balance and rent as SOL.
+pub async fn unwrap_sol(rpc_client: &RpcClient, keypair: Keypair) -> Result<String, WsolError> {
+    let owner = keypair.pubkey();
+    let token_accounts = rpc_client
+        .get_token_accounts_by_owner(&owner, TokenAccountsFilter::Mint(native_mint::id()))
+        .await
+        .map_err(|e| WsolError::ConnectionError(e.to_string()))?;
+
+    let mut instructions = Vec::new();
+    let mut lamports = 0;
+    for keyed_account in token_accounts {
+        let UiAccountData::Json(parsed) = &keyed_account.account.data else {
+            continue;
+        };
+        let Ok(account) = serde_json::from_value::<UiTokenAccount>(parsed.parsed["info"].clone())
+        else {
+            continue;
+        };
+        if account.state == UiAccountState::Frozen || !closable_once_empty(&account, &owner) {
+            continue;
+        }
+        let address = parse_address(&keyed_account.pubkey)?;
+        instructions.push(
+            token_instruction::close_account(&spl_token::id(), &address, &owner, &owner, &[])
+                .map_err(|e| WsolError::TransactionError(e.to_string()))?,
+        );
+        lamports += keyed_account.account.lamports;
+    }
+    if instructions.is_empty() {
+        return Err(WsolError::NothingToUnwrap);
+    }
+
+    let signature = send(rpc_client, &keypair, &instructions, &[]).await?;
+    info!("Unwrapped {} SOL", lamports as f64 / LAMPORTS_PER_SOL);
+    Ok(signature)
+}
+
+/// Fund the associated wSOL account of `owner` with `lamports` and sync its
+/// token balance.
+fn wrap_instructions(owner: &Pubkey, lamports: u64) -> Vec<Instruction> {
+    let wsol_account = get_associated_token_address(owner, &native_mint::id());
+    vec![
+        create_associated_token_account_idempotent(
+            owner,
+            owner,
+            &native_mint::id(),
+            &spl_token::id(),
+        ),
+        system_instruction::transfer(owner, &wsol_account, lamports),
+        token_instruction::sync_native(&spl_token::id(), &wsol_account)
+            .expect("SPL Token is a valid token program"),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap_instructions() {
+        let owner = Pubkey::new_unique();
+        let wsol_account = get_associated_token_address(&owner, &native_mint::id());
+        let instructions = wrap_instructions(&owner, 1_000_000_000);
+        assert_eq!(instructions.len(), 3);
+        assert_eq!(
+            instructions[0].program_id,
+            spl_associated_token_account::id()
+        );
+        assert_eq!(instructions[1].accounts[1].pubkey, wsol_account);
+        // The token balance only follows the lamports once synced.
+        assert_eq!(instructions[2].program_id, spl_token::id());
+        assert_eq!(instructions[2].accounts[0].pubkey, wsol_account);
+    }
+
+    #[tokio::test]
+    async fn test_wrap_invalid_amount() {
+        let result = wrap_sol(
+            &RpcClient::new("http://localhost:8899".to_string()),
+            Keypair::new(),
+            -1.0,
+        )
+        .await;
+        assert!(matches!(result, Err(WsolError::InvalidAmount(_))));
+    }
+}
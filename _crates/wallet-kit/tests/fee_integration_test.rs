@@ -4,7 +4,8 @@
 
 use {
     smbcloud_wallet_constants::constants::{
-        LAMPORTS_PER_SOL, SEMITONE_PER_BACH, THE_STABLE_FOUNDATION_TREASURY_WALLET_FEE,
+        LAMPORTS_PER_SOL, SEMITONE_PER_BACH, SPL_TOKEN_2022_PROGRAM_ID,
+        THE_STABLE_FOUNDATION_TREASURY_WALLET_FEE,
     },
     smbcloud_wallet_kit::fee::{
         FeeBreakdown, FeeConfig, TreasuryFeeManager, DEFAULT_FEE_PERCENTAGE,
@@ -23,15 +24,15 @@ fn test_fee_breakdown_accuracy() {
 
     for (amount, expected_fee, expected_net) in test_cases {
         let breakdown = FeeBreakdown::new(amount, "SOL".to_string())
-            .expect(&format!("Failed to create breakdown for amount {}", amount));
+            .unwrap_or_else(|e| panic!("Failed to create breakdown for amount {}: {}", amount, e));
 
         assert_eq!(
             breakdown.fee_amount, expected_fee,
             "Fee amount mismatch for {} SOL",
             amount
         );
-        assert_eq!(
-            breakdown.net_amount, expected_net,
+        assert!(
+            (breakdown.net_amount - expected_net).abs() < 1e-12,
             "Net amount mismatch for {} SOL",
             amount
         );
@@ -68,14 +69,17 @@ fn test_token_units_conversion_accuracy() {
     let amount = 1.0; // 1 BACH
     let breakdown = FeeBreakdown::new(amount, "BACH".to_string()).unwrap();
 
-    // 1 BACH = 1,000,000,000 st
-    // Fee: 0.0025 BACH = 2,500,000 st
-    // Net: 0.9975 BACH = 997,500,000 st
-    assert_eq!(breakdown.fee_token_units(SEMITONE_PER_BACH), 2_500_000);
-    assert_eq!(breakdown.net_token_units(SEMITONE_PER_BACH), 997_500_000);
+    // 1 BACH = 1,000,000,000,000 st
+    // Fee: 0.0025 BACH = 2,500,000,000 st
+    // Net: 0.9975 BACH = 997,500,000,000 st
+    assert_eq!(breakdown.fee_token_units(SEMITONE_PER_BACH), 2_500_000_000);
+    assert_eq!(
+        breakdown.net_token_units(SEMITONE_PER_BACH),
+        997_500_000_000
+    );
     assert_eq!(
         breakdown.total_token_units(SEMITONE_PER_BACH),
-        1_000_000_000
+        1_000_000_000_000
     );
 }
 
@@ -129,8 +133,10 @@ fn test_fee_config_functionality() {
     assert_eq!(breakdown.net_amount, 99.0);
 
     // Test disabled fees
-    let mut disabled_config = FeeConfig::default();
-    disabled_config.fees_enabled = false;
+    let disabled_config = FeeConfig {
+        fees_enabled: false,
+        ..FeeConfig::default()
+    };
 
     let no_fee_breakdown = disabled_config
         .calculate_breakdown(100.0, "SOL".to_string())
@@ -283,10 +289,7 @@ fn test_concurrent_fee_calculations() {
 
             // Verify calculations are consistent
             assert_eq!(breakdown.fee_amount, amount * DEFAULT_FEE_PERCENTAGE);
-            assert_eq!(
-                breakdown.net_amount,
-                amount * (1.0 - DEFAULT_FEE_PERCENTAGE)
-            );
+            assert!((breakdown.net_amount - amount * (1.0 - DEFAULT_FEE_PERCENTAGE)).abs() < 1e-10);
 
             breakdown
         });
@@ -344,7 +347,7 @@ fn test_fee_system_constants() {
 
     // Verify conversion constants
     assert_eq!(LAMPORTS_PER_SOL, 1_000_000_000.0);
-    assert_eq!(SEMITONE_PER_BACH, 1_000_000_000.0);
+    assert_eq!(SEMITONE_PER_BACH, 1_000_000_000_000.0);
 }
 
 #[test]
@@ -389,8 +392,8 @@ fn test_treasury_account_operations() {
         THE_STABLE_FOUNDATION_TREASURY_WALLET_FEE
     );
 
-    // Test that treasury pubkey is valid Solana address
-    assert!(pubkey.is_on_curve()); // Ensure it's a valid point on the Ed25519 curve
+    // The treasury is a program derived address, off the Ed25519 curve
+    assert!(!pubkey.is_on_curve());
 }
 
 #[test]
@@ -482,6 +485,7 @@ fn test_instruction_creation_scenarios() {
     let token_program = Pubkey::from_str("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA").unwrap();
     let token_account1 = Pubkey::from_str("4fYNw3dojWmQ4dXtSGE9epjRGy9HFayjHVLLAGn8bW7Y").unwrap();
     let token_account2 = Pubkey::from_str("7rQ9CgpYe2c6nNPVTsKLXaAb2L8p4y7t3UvRe9jdFxGh").unwrap();
+    let token_mint = Pubkey::from_str("CKfatsPMUf8SkiURsDXs7eK6GWb4Jsd6UDbs7twMCWxo").unwrap();
 
     // Test SOL fee instruction
     let sol_instruction = TreasuryFeeManager::create_sol_fee_instruction(&from_pubkey, 1000000);
@@ -491,9 +495,24 @@ fn test_instruction_creation_scenarios() {
     let token_instruction = TreasuryFeeManager::create_token_fee_instruction(
         &token_program,
         &token_account1,
+        &token_mint,
         &token_account2,
         &from_pubkey,
         1000000,
+        9,
+    );
+    assert!(token_instruction.is_ok());
+
+    // Token-2022 is accepted as well
+    let token_2022_program = Pubkey::from_str(SPL_TOKEN_2022_PROGRAM_ID).unwrap();
+    let token_instruction = TreasuryFeeManager::create_token_fee_instruction(
+        &token_2022_program,
+        &token_account1,
+        &token_mint,
+        &token_account2,
+        &from_pubkey,
+        1000000,
+        9,
     );
     assert!(token_instruction.is_ok());
 }
@@ -533,10 +552,13 @@ fn test_fee_system_documentation_examples() {
 
     // Example 2: Token conversion
     let bach_breakdown = FeeBreakdown::new(1.0, "BACH".to_string()).unwrap();
-    assert_eq!(bach_breakdown.fee_token_units(SEMITONE_PER_BACH), 2_500_000);
+    assert_eq!(
+        bach_breakdown.fee_token_units(SEMITONE_PER_BACH),
+        2_500_000_000
+    );
     assert_eq!(
         bach_breakdown.net_token_units(SEMITONE_PER_BACH),
-        997_500_000
+        997_500_000_000
     );
 
     // Example 3: Configuration
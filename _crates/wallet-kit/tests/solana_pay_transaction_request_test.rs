@@ -0,0 +1,386 @@
+//! Integration tests for Solana Pay transaction requests
+//!
+//! A mock merchant answers the `GET` and `POST` requests so the whole fetch
+//! and verify flow runs without network access. The HTTP client is run
+//! against a merchant server bound to localhost.
+
+use {
+    base64::{engine::general_purpose, Engine as _},
+    smbcloud_wallet_constants::constants::{SPL_MEMO_PROGRAM_ID, SPL_TOKEN_PROGRAM_ID},
+    smbcloud_wallet_kit::{
+        models::solana_pay::{
+            TransactionRequest, TransactionRequestMetadata, TransactionRequestResponse,
+        },
+        solana_pay::{
+            transaction_request::{
+                fetch_transaction_request, verify_transaction, HttpMerchantClient, MerchantClient,
+            },
+            SolanaPayError,
+        },
+    },
+    solana_sdk::{
+        hash::Hash,
+        instruction::{AccountMeta, Instruction},
+        message::Message,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        transaction::{Transaction, VersionedTransaction},
+    },
+    solana_system_interface_v1::instruction as system_instruction,
+    std::str::FromStr,
+    tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+        task::JoinHandle,
+    },
+};
+
+const LINK: &str = "https://example.com/pay";
+
+/// Serves `transaction` to every `POST` and a label and icon to every `GET`.
+struct MockMerchant {
+    transaction: String,
+}
+
+impl MerchantClient for MockMerchant {
+    async fn get_metadata(
+        &self,
+        _link: &str,
+    ) -> Result<TransactionRequestMetadata, SolanaPayError> {
+        Ok(TransactionRequestMetadata {
+            label: "Bach Coffee".to_string(),
+            icon: "https://example.com/icon.svg".to_string(),
+        })
+    }
+
+    async fn post_account(
+        &self,
+        _link: &str,
+        _account: &str,
+    ) -> Result<TransactionRequestResponse, SolanaPayError> {
+        Ok(TransactionRequestResponse {
+            transaction: self.transaction.clone(),
+            message: Some("Thanks for your order".to_string()),
+        })
+    }
+}
+
+fn mock_merchant(transaction: Transaction) -> MockMerchant {
+    MockMerchant {
+        transaction: encode(transaction),
+    }
+}
+
+fn encode(transaction: Transaction) -> String {
+    let transaction = VersionedTransaction::from(transaction);
+    general_purpose::STANDARD.encode(bincode::serialize(&transaction).unwrap())
+}
+
+fn memo(text: &str) -> Instruction {
+    Instruction::new_with_bytes(
+        Pubkey::from_str(SPL_MEMO_PROGRAM_ID).unwrap(),
+        text.as_bytes(),
+        vec![],
+    )
+}
+
+/// A request received by [`serve`]: method, headers and body.
+struct Received {
+    method: String,
+    headers: String,
+    body: String,
+}
+
+/// Bind a merchant server on localhost that answers the next `responses.len()`
+/// requests, in order, with the given status line and JSON body.
+///
+/// Returns the link to the server and a handle to the requests it received.
+async fn serve(responses: Vec<(&'static str, String)>) -> (String, JoinHandle<Vec<Received>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let link = format!("http://{}/pay?order=42", listener.local_addr().unwrap());
+    let handle = tokio::spawn(async move {
+        let mut received = Vec::new();
+        for (status, body) in responses {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buffer = Vec::new();
+            let mut chunk = [0u8; 1024];
+            // Read the headers, then as much body as announced.
+            let header_end = loop {
+                let read = stream.read(&mut chunk).await.unwrap();
+                buffer.extend_from_slice(&chunk[..read]);
+                if let Some(end) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+                    break end + 4;
+                }
+            };
+            let headers = String::from_utf8_lossy(&buffer[..header_end]).to_string();
+            let content_length = headers
+                .lines()
+                .find_map(|line| {
+                    let (name, value) = line.split_once(':')?;
+                    if name.eq_ignore_ascii_case("content-length") {
+                        value.trim().parse::<usize>().ok()
+                    } else {
+                        None
+                    }
+                })
+                .unwrap_or(0);
+            while buffer.len() < header_end + content_length {
+                let read = stream.read(&mut chunk).await.unwrap();
+                buffer.extend_from_slice(&chunk[..read]);
+            }
+
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+            stream.shutdown().await.unwrap();
+
+            received.push(Received {
+                method: headers.split(' ').next().unwrap_or_default().to_string(),
+                headers: headers.to_lowercase(),
+                body: String::from_utf8_lossy(&buffer[header_end..]).to_string(),
+            });
+        }
+        received
+    });
+    (link, handle)
+}
+
+fn request(link: &str) -> TransactionRequest {
+    TransactionRequest {
+        link: link.to_string(),
+        label: Some("Fallback label".to_string()),
+        message: None,
+    }
+}
+
+#[tokio::test]
+async fn test_fetch_transaction_request_preview() {
+    let wallet = Keypair::new();
+    let merchant = Pubkey::new_unique();
+    let instructions = vec![
+        system_instruction::transfer(&wallet.pubkey(), &merchant, 10_000_000),
+        memo("order-42"),
+    ];
+    let mut message = Message::new(&instructions, Some(&wallet.pubkey()));
+    message.recent_blockhash = Hash::new_unique();
+    let merchant_client = mock_merchant(Transaction::new_unsigned(message));
+
+    let preview =
+        fetch_transaction_request(&merchant_client, request(LINK), wallet.pubkey().to_string())
+            .await
+            .expect("Transaction request should be accepted");
+
+    assert_eq!(preview.label.as_deref(), Some("Bach Coffee"));
+    assert_eq!(
+        preview.icon.as_deref(),
+        Some("https://example.com/icon.svg")
+    );
+    assert_eq!(preview.message.as_deref(), Some("Thanks for your order"));
+    assert_eq!(preview.transaction, merchant_client.transaction);
+    assert_eq!(preview.fee_payer, wallet.pubkey().to_string());
+    assert!(preview.writable_accounts.contains(&merchant.to_string()));
+    assert_eq!(preview.instructions.len(), 2);
+    assert_eq!(preview.instructions[0].description, "Transfer 0.01 SOL");
+    assert_eq!(preview.instructions[1].description, "Memo: order-42");
+}
+
+#[tokio::test]
+async fn test_fetch_transaction_request_with_merchant_signature() {
+    let wallet = Keypair::new();
+    let merchant = Keypair::new();
+    let mut transfer = system_instruction::transfer(&wallet.pubkey(), &merchant.pubkey(), 1_000);
+    transfer
+        .accounts
+        .push(AccountMeta::new_readonly(merchant.pubkey(), true));
+    let blockhash = Hash::new_unique();
+    let mut message = Message::new(&[transfer], Some(&wallet.pubkey()));
+    message.recent_blockhash = blockhash;
+
+    // The merchant signature is missing, so the wallet must refuse the transaction.
+    let unsigned = mock_merchant(Transaction::new_unsigned(message.clone()));
+    let result =
+        fetch_transaction_request(&unsigned, request(LINK), wallet.pubkey().to_string()).await;
+    assert!(matches!(result, Err(SolanaPayError::UnexpectedSigner(_))));
+
+    // Once the merchant has signed, only the wallet signature is left.
+    let mut signed = Transaction::new_unsigned(message);
+    signed.partial_sign(&[&merchant], blockhash);
+    let preview = fetch_transaction_request(
+        &mock_merchant(signed),
+        request(LINK),
+        wallet.pubkey().to_string(),
+    )
+    .await
+    .expect("Co-signed transaction should be accepted");
+    assert_eq!(preview.fee_payer, wallet.pubkey().to_string());
+}
+
+#[tokio::test]
+async fn test_fetch_transaction_request_rejects_unknown_program() {
+    let wallet = Keypair::new();
+    let program = Pubkey::new_unique();
+    let instruction = Instruction::new_with_bytes(
+        program,
+        &[1, 2, 3],
+        vec![AccountMeta::new(wallet.pubkey(), true)],
+    );
+    let mut message = Message::new(&[instruction], Some(&wallet.pubkey()));
+    message.recent_blockhash = Hash::new_unique();
+
+    let merchant_client = mock_merchant(Transaction::new_unsigned(message));
+    let result =
+        fetch_transaction_request(&merchant_client, request(LINK), wallet.pubkey().to_string())
+            .await;
+    assert!(
+        matches!(result, Err(SolanaPayError::UnexpectedProgram(id)) if id == program.to_string())
+    );
+}
+
+#[tokio::test]
+async fn test_fetch_transaction_request_wallet_not_signer() {
+    let wallet = Keypair::new();
+    let payer = Keypair::new();
+    let mut message = Message::new(&[memo("hello")], Some(&payer.pubkey()));
+    message.recent_blockhash = Hash::new_unique();
+
+    let merchant_client = mock_merchant(Transaction::new_unsigned(message));
+    let result =
+        fetch_transaction_request(&merchant_client, request(LINK), wallet.pubkey().to_string())
+            .await;
+    assert!(matches!(result, Err(SolanaPayError::InvalidTransaction(_))));
+}
+
+#[tokio::test]
+async fn test_fetch_transaction_request_rejects_set_authority() {
+    let wallet = Keypair::new();
+    let token_account = Pubkey::new_unique();
+    // SetAuthority of the account owner to a new owner.
+    let new_owner = Pubkey::new_unique();
+    let mut data = vec![6, 2, 1];
+    data.extend_from_slice(new_owner.as_ref());
+    let instruction = Instruction::new_with_bytes(
+        Pubkey::from_str(SPL_TOKEN_PROGRAM_ID).unwrap(),
+        &data,
+        vec![
+            AccountMeta::new(token_account, false),
+            AccountMeta::new_readonly(wallet.pubkey(), true),
+        ],
+    );
+    let mut message = Message::new(&[instruction], Some(&wallet.pubkey()));
+    message.recent_blockhash = Hash::new_unique();
+
+    let merchant_client = mock_merchant(Transaction::new_unsigned(message));
+    let result =
+        fetch_transaction_request(&merchant_client, request(LINK), wallet.pubkey().to_string())
+            .await;
+    assert!(
+        matches!(result, Err(SolanaPayError::UnexpectedInstruction(kind)) if kind == "Token SetAuthority")
+    );
+}
+
+#[tokio::test]
+async fn test_fetch_transaction_request_requires_https() {
+    let wallet = Keypair::new();
+    let mut message = Message::new(&[memo("hello")], Some(&wallet.pubkey()));
+    message.recent_blockhash = Hash::new_unique();
+
+    // The request may come from the frontend, so the link is checked again.
+    let merchant_client = mock_merchant(Transaction::new_unsigned(message));
+    let result = fetch_transaction_request(
+        &merchant_client,
+        request("http://example.com/pay"),
+        wallet.pubkey().to_string(),
+    )
+    .await;
+    assert!(matches!(result, Err(SolanaPayError::InvalidUri(_))));
+}
+
+#[tokio::test]
+async fn test_http_merchant_client() {
+    let wallet = Keypair::new();
+    let mut message = Message::new(&[memo("order-42")], Some(&wallet.pubkey()));
+    message.recent_blockhash = Hash::new_unique();
+    let transaction = encode(Transaction::new_unsigned(message));
+
+    let (link, server) = serve(vec![
+        (
+            "200 OK",
+            r#"{"label":"Bach Coffee","icon":"https://example.com/icon.svg"}"#.to_string(),
+        ),
+        (
+            "200 OK",
+            format!(
+                r#"{{"transaction":"{}","message":"Thanks for your order"}}"#,
+                transaction
+            ),
+        ),
+    ])
+    .await;
+
+    let client = HttpMerchantClient::default();
+    let metadata = client.get_metadata(&link).await.unwrap();
+    assert_eq!(metadata.label, "Bach Coffee");
+    assert_eq!(metadata.icon, "https://example.com/icon.svg");
+
+    let response = client
+        .post_account(&link, &wallet.pubkey().to_string())
+        .await
+        .unwrap();
+    assert_eq!(response.transaction, transaction);
+    assert_eq!(response.message.as_deref(), Some("Thanks for your order"));
+    assert!(verify_transaction(&response.transaction, &wallet.pubkey()).is_ok());
+
+    let received = server.await.unwrap();
+    assert_eq!(received[0].method, "GET");
+    assert!(received[0].headers.contains("get /pay?order=42 "));
+    assert!(received[0].headers.contains("accept: application/json"));
+    assert_eq!(received[1].method, "POST");
+    assert!(received[1].headers.contains("post /pay?order=42 "));
+    assert!(received[1].headers.contains("content-type: application/json"));
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&received[1].body).unwrap(),
+        serde_json::json!({ "account": wallet.pubkey().to_string() })
+    );
+}
+
+#[tokio::test]
+async fn test_http_merchant_client_error_status() {
+    let (link, server) = serve(vec![
+        ("404 Not Found", r#"{"error":"Unknown order"}"#.to_string()),
+        ("500 Internal Server Error", "oops".to_string()),
+        ("200 OK", r#"{"message":"No transaction"}"#.to_string()),
+    ])
+    .await;
+
+    let client = HttpMerchantClient::default();
+    let account = Keypair::new().pubkey().to_string();
+    assert!(matches!(
+        client.get_metadata(&link).await,
+        Err(SolanaPayError::NetworkError(_))
+    ));
+    assert!(matches!(
+        client.post_account(&link, &account).await,
+        Err(SolanaPayError::NetworkError(_))
+    ));
+    // A success status with a body that is not a transaction is an error too.
+    assert!(matches!(
+        client.post_account(&link, &account).await,
+        Err(SolanaPayError::NetworkError(_))
+    ));
+    server.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_http_merchant_client_unreachable() {
+    // Bind and drop a listener so nothing answers on the port.
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let link = format!("http://{}/pay", listener.local_addr().unwrap());
+    drop(listener);
+
+    let result = HttpMerchantClient::default().get_metadata(&link).await;
+    assert!(matches!(result, Err(SolanaPayError::NetworkError(_))));
+}
@@ -0,0 +1,161 @@
+//! Integration tests for native staking
+//!
+//! These tests need a local cluster:
+//!
+//! ```sh
+//! solana-test-validator --reset
+//! cargo test -p smbcloud-wallet-kit --test staking_test -- --ignored --test-threads=1
+//! ```
+
+use {
+    smbcloud_wallet_constants::{constants::LAMPORTS_PER_SOL, rpc::local_rpc_url},
+    smbcloud_wallet_core_model::models::stake::{StakeAccount, StakeActivationState},
+    smbcloud_wallet_kit::staking::{
+        create_stake_account, deactivate_stake, get_stake_accounts, get_stake_rewards,
+        get_validators, merge_stake, split_stake, withdraw_stake,
+    },
+    solana_client::nonblocking::rpc_client::RpcClient,
+    solana_sdk::signature::{Keypair, Signer},
+    std::time::Duration,
+};
+
+/// Create a wallet funded by an airdrop on the local cluster.
+async fn funded_wallet(sol: f64) -> Keypair {
+    let wallet = Keypair::new();
+    let rpc_client = RpcClient::new(local_rpc_url());
+    let signature = rpc_client
+        .request_airdrop(&wallet.pubkey(), (sol * LAMPORTS_PER_SOL) as u64)
+        .await
+        .expect("Airdrop failed, is solana-test-validator running?");
+    while !rpc_client
+        .confirm_transaction(&signature)
+        .await
+        .unwrap_or(false)
+    {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+    wallet
+}
+
+fn local_rpc_client() -> RpcClient {
+    RpcClient::new(local_rpc_url())
+}
+
+fn copy(keypair: &Keypair) -> Keypair {
+    Keypair::try_from(keypair.to_bytes().as_slice()).unwrap()
+}
+
+async fn stake_account(owner: &Keypair, address: &str) -> StakeAccount {
+    get_stake_accounts(&local_rpc_client(), owner.pubkey().to_string())
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|account| account.address == address)
+        .expect("Stake account not found")
+}
+
+#[tokio::test]
+#[ignore = "requires solana-test-validator"]
+async fn test_get_validators() {
+    let validators = get_validators(&local_rpc_client()).await.unwrap();
+    assert!(!validators.is_empty());
+    let validator = &validators[0];
+    assert!(validator.activated_stake > 0.0);
+    assert!(!validator.delinquent);
+}
+
+#[tokio::test]
+#[ignore = "requires solana-test-validator"]
+async fn test_stake_lifecycle() {
+    let wallet = funded_wallet(10.0).await;
+    let vote_account = get_validators(&local_rpc_client()).await.unwrap()[0]
+        .vote_account
+        .clone();
+
+    // Create and delegate.
+    let created = create_stake_account(
+        &local_rpc_client(),
+        copy(&wallet),
+        2.0,
+        vote_account.clone(),
+    )
+    .await
+    .unwrap();
+    let account = stake_account(&wallet, &created.stake_account).await;
+    assert_eq!(account.validator.as_deref(), Some(vote_account.as_str()));
+    assert_eq!(account.state, StakeActivationState::Activating);
+    assert!((account.balance - 2.0).abs() < f64::EPSILON);
+
+    // Split half of the stake into a new account with the same delegation.
+    let split = split_stake(
+        &local_rpc_client(),
+        copy(&wallet),
+        created.stake_account.clone(),
+        1.0,
+    )
+    .await
+    .unwrap();
+    let split_account = stake_account(&wallet, &split.stake_account).await;
+    assert_eq!(
+        split_account.validator.as_deref(),
+        Some(vote_account.as_str())
+    );
+
+    // Both accounts are activating in the same epoch, so they can be merged back.
+    merge_stake(
+        &local_rpc_client(),
+        copy(&wallet),
+        created.stake_account.clone(),
+        split.stake_account.clone(),
+    )
+    .await
+    .unwrap();
+    let accounts = get_stake_accounts(&local_rpc_client(), wallet.pubkey().to_string())
+        .await
+        .unwrap();
+    assert_eq!(accounts.len(), 1);
+
+    // Deactivating in the activation epoch makes the stake withdrawable right away.
+    deactivate_stake(
+        &local_rpc_client(),
+        copy(&wallet),
+        created.stake_account.clone(),
+    )
+    .await
+    .unwrap();
+    let account = stake_account(&wallet, &created.stake_account).await;
+    assert_eq!(account.state, StakeActivationState::Inactive);
+
+    withdraw_stake(
+        &local_rpc_client(),
+        copy(&wallet),
+        created.stake_account,
+        None,
+    )
+    .await
+    .unwrap();
+    let accounts = get_stake_accounts(&local_rpc_client(), wallet.pubkey().to_string())
+        .await
+        .unwrap();
+    assert!(accounts.is_empty());
+}
+
+#[tokio::test]
+#[ignore = "requires solana-test-validator"]
+async fn test_get_stake_rewards_for_new_account() {
+    let wallet = funded_wallet(5.0).await;
+    let vote_account = get_validators(&local_rpc_client()).await.unwrap()[0]
+        .vote_account
+        .clone();
+    let created = create_stake_account(&local_rpc_client(), copy(&wallet), 1.0, vote_account)
+        .await
+        .unwrap();
+
+    // A stake account created in this epoch has not earned anything yet. In the
+    // first epoch of a fresh cluster there is no completed epoch to query at all.
+    if let Ok(rewards) =
+        get_stake_rewards(&local_rpc_client(), vec![created.stake_account], None).await
+    {
+        assert!(rewards.is_empty());
+    }
+}
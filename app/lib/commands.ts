@@ -11,6 +11,7 @@ export const GET_BACH_BALANCE = "get_bach_balance";
 export const GET_SOL_BALANCE = "get_sol_balance";
 export const GET_VERIFIED_ASSETS = "get_verified_assets";
 export const SEND_TOKEN = "send_token";
+export const RESOLVE_RECIPIENT = "resolve_recipient";
 export const GET_TREASURY_BACH_BALANCE = "get_treasury_bach_balance";
 export const GET_TREASURY_SOL_BALANCE = "get_treasury_sol_balance";
 export const GET_SWAP_QUOTE = "get_swap_quote";
@@ -18,3 +19,7 @@ export const BUILD_SWAP_TRANSACTION = "build_swap_transaction";
 export const CHECK_PUBKEY = "check_pubkey";
 export const GET_WALLET_BALANCE = "get_wallet_balance";
 export const GET_WALLET_ASSETS_BALANCE = "get_wallet_assets_balance";
+export const CREATE_SOLANA_PAY_URI = "create_solana_pay_uri";
+export const PAY_SOLANA_PAY_URI = "pay_solana_pay_uri";
+export const GET_TRANSACTION_REQUEST = "get_transaction_request";
+export const APPROVE_TRANSACTION_REQUEST = "approve_transaction_request";
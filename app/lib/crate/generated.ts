@@ -229,6 +229,11 @@ export interface SwapTransactionPayload {
   userPublicKey: string;
   dynamicComputeUnitLimit: boolean;
   dynamicSlippage: boolean;
+  /**
+   * Whether Jupiter wraps SOL paid and unwraps SOL received. Without it the
+   * swap reads from and leaves wrapped SOL in the wSOL token account.
+   */
+  wrapAndUnwrapSol: boolean;
   prioritizationFeeLamports: PrioritizationFeeLamports;
 }
 
@@ -303,105 +308,805 @@ export const THE_STABLE_FOUNDATION_TREASURY_WALLET_FEE = "GHwjki2QkzkY9ZsDWEpvxk
 
 export const SPL_TOKEN_PROGRAM_ID = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
 
+export const SPL_TOKEN_2022_PROGRAM_ID = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+
+export const SPL_MEMO_PROGRAM_ID = "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr";
+
 export interface Metadata {
   address: string;
   name: string;
   symbol: string;
   decimal: number;
   logo_uri: string;
+  /** Whether the token is on the bundled verified list. */
+  verified: boolean;
 }
 
-export type SolanaAsset =
-  | {
-      "Sol": {
-        meta: Metadata;
-      }
-    }
-  | {
-      "BachToken": {
-        meta: Metadata;
-      }
-    }
-  | {
-      "Jupiter": {
-        meta: Metadata;
-      }
-    }
-  /** Begin Bitcoin */
-  | {
-      "ZBtc": {
-        meta: Metadata;
-      }
-    }
-  | {
-      "CbBtc": {
-        meta: Metadata;
-      }
-    }
-  | {
-      "XBtc": {
-        meta: Metadata;
-      }
-    }
+export interface BalanceV1 {
+  meta: Metadata;
   /**
-   * End Bitcoin
-   * Begin USD Stablecoins
+   * Balance in its smallest nomimal. For example, a 0.01 SOL balance will return 10000000,
+   * 0.010000000 * 1_000_000_000.
    */
-  | {
-      "Usdc": {
-        meta: Metadata;
-      }
-    }
-  | {
-      "Usdt": {
-        meta: Metadata;
-      }
-    }
-  | {
-      "Usdg": {
-        meta: Metadata;
-      }
-    }
-  | {
-      "Usds": {
-        meta: Metadata;
-      }
-    }
-  | {
-      "Usd1": {
-        meta: Metadata;
-      }
-    }
+  balance: number;
+  /** Balance in its easy-to-read form. For example, a 0.01 SOL. */
+  ui_amount: number;
+  /** Whether the token is listed, see the token preferences. */
+  visibility: TokenVisibility;
+}
+
+/** Whether a token is listed in balances and history. */
+export type TokenVisibility =
+  | "Visible" | "Hidden" | "Spam";
+
+/** What the user chose for a token. */
+export interface TokenPreference {
+  mint: string;
+  /** Added by the user, listed even without a balance. */
+  custom: boolean;
   /**
-   * End USD Stablecoins
-   * Begin Euro stablecoins
+   * `Some(true)` when hidden by the user, `Some(false)` when shown even if it
+   * looks like spam, `None` to go by the spam classification.
    */
+  hidden?: boolean;
+}
+
+export type Environment =
+  | "Local" | "Devnet" | "Testnet" | "Mainnet";
+
+/** The fiat currencies balances and prices can be shown in, by ISO 4217 code. */
+export type FiatCurrency =
+  | "USD" | "EUR" | "GBP" | "JPY" | "CNY" | "IDR" | "SEK" | "NOK" | "DKK" | "CHF" | "PLN" | "CZK" | "HUF" | "TRY" | "CAD" | "MXN" | "BRL" | "ARS" | "CLP" | "COP" | "AUD" | "NZD" | "SGD" | "HKD" | "TWD" | "KRW" | "INR" | "MYR" | "THB" | "PHP" | "VND" | "AED" | "SAR" | "ILS" | "ZAR" | "NGN";
+
+/** The API a price was fetched from. */
+export type PriceSource =
+  | "Birdeye" | "Jupiter";
+
+/** One asset of a wallet with its price in the valuation currency. */
+export interface AssetValuation {
+  mint: string;
+  symbol?: string;
+  amount: number;
+  /** Unit price, `None` when no provider knows the token. */
+  price?: number;
+  /** `amount * price`. */
+  value?: number;
+  /** Price change over the last 24 hours, in percent. */
+  price_change_24h?: number;
+  price_source?: PriceSource;
+  /** Unix timestamp of the price, in seconds. */
+  price_updated_at?: number;
+}
+
+/** The value of a wallet, asset by asset, in one fiat currency. */
+export interface PortfolioValuation {
+  currency: FiatCurrency;
+  /** Highest value first, unpriced assets last. */
+  assets: Array<AssetValuation>;
+  /** Sum of the priced asset values. */
+  total_value: number;
+  /** Change of `total_value` over the last 24 hours, in the valuation currency. */
+  total_change_24h?: number;
+  /** Unix timestamp of the valuation, in seconds. */
+  updated_at: number;
+}
+
+/** The period a portfolio chart covers. */
+export type HistoryRange =
+  | "Day" | "Week" | "Month" | "Year";
+
+/** How the cost of sold tokens is matched against their purchases. */
+export type CostBasisMethod =
+  /** First in, first out: the oldest purchases are sold first. */
+  | "Fifo"
+  /** Every unit costs the average price paid for the current holding. */
+  | "Average";
+
+export interface ChartPoint {
+  /** Unix timestamp in seconds. */
+  timestamp: number;
+  value: number;
+}
+
+/** Chart-ready portfolio values over a range. */
+export interface PortfolioChart {
+  range: HistoryRange;
+  currency: FiatCurrency;
+  /** Oldest first, spaced by the range step, the last point is now. */
+  points: Array<ChartPoint>;
+  /** Value change from the first to the last point. */
+  change: number;
+  /** `change` relative to the first point, in percent. `None` when the first point is zero. */
+  change_percent?: number;
+}
+
+/** Cost basis and profit and loss of one asset. */
+export interface AssetPnl {
+  mint: string;
+  symbol?: string;
+  amount: number;
+  /** What the current amount cost. */
+  cost_basis: number;
+  /** Average cost of one unit of the current amount. */
+  average_cost?: number;
+  current_value?: number;
+  /** Gains of everything sold, minus what it cost. */
+  realized_pnl: number;
+  /** `current_value - cost_basis`, `None` without a current price. */
+  unrealized_pnl?: number;
+}
+
+export interface PortfolioPnl {
+  currency: FiatCurrency;
+  method: CostBasisMethod;
+  assets: Array<AssetPnl>;
+  total_cost_basis: number;
+  total_realized_pnl: number;
+  total_unrealized_pnl: number;
+}
+
+export interface PortfolioHistory {
+  chart: PortfolioChart;
+  pnl: PortfolioPnl;
+  /**
+   * Whether older transactions of the range were left out, the balances
+   * before the oldest one read are then taken as they were at that time.
+   */
+  truncated: boolean;
+}
+
+/** What a ledger entry records, from the wallet's point of view. */
+export type LedgerKind =
+  | "TransferIn" | "TransferOut"
+  /** One token sent in exchange for another in the same transaction. */
+  | "Swap"
+  /** Inflation reward credited to one of the wallet's stake accounts. */
+  | "StakingReward"
+  /** The transaction fee paid to the network. */
+  | "NetworkFee"
+  /** The fee paid to the treasury by `TreasuryFeeManager`. */
+  | "PlatformFee";
+
+/** The file formats a ledger can be exported to. */
+export type TaxExportFormat =
+  /** Koinly universal CSV. */
+  | "Koinly"
+  /** CoinTracker CSV. */
+  | "CoinTracker"
+  /** The ledger itself, as JSON. */
+  | "Json";
+
+export interface LedgerAmount {
+  mint: string;
+  symbol?: string;
+  amount: number;
+}
+
+/** The part of an acquisition a disposal is matched against. */
+export interface LotAssignment {
+  /**
+   * Id of the entry that acquired the lot, `opening` for holdings from before
+   * the export and `average` with the average cost method.
+   */
+  lot: string;
+  /** Unix timestamp of the acquisition, in seconds. */
+  acquired_at?: number;
+  amount: number;
+  /** `None` when the acquisition had no price. */
+  cost_basis?: number;
+}
+
+export interface LedgerEntry {
+  /** Unique within the ledger. Acquisitions open a lot with this id. */
+  id: string;
+  /** `None` for staking rewards, which are not paid by a transaction. */
+  signature?: string;
+  /** Unix timestamp in seconds. */
+  timestamp: number;
+  kind: LedgerKind;
+  sent?: LedgerAmount;
+  received?: LedgerAmount;
+  description?: string;
+  /** Market value of the entry at its time, `None` without a price. */
+  value?: number;
+  /** Cost of what was sent, `None` when a lot it was taken from has no price. */
+  cost_basis?: number;
+  /** `value - cost_basis` of what was sent. */
+  gain?: number;
+  /** Lots the sent amount was taken from. */
+  lots: Array<LotAssignment>;
+  /** Whether the value or the cost basis is missing for lack of a price. */
+  unpriced: boolean;
+}
+
+/** Every taxable event of a wallet over a period, oldest first. */
+export interface TaxLedger {
+  pubkey: string;
+  currency: FiatCurrency;
+  method: CostBasisMethod;
+  /** Unix timestamp in seconds. */
+  from: number;
+  /** Unix timestamp in seconds. */
+  to: number;
+  entries: Array<LedgerEntry>;
+}
+
+/** How an NFT is held on chain, which decides how it is transferred. */
+export type NftKind =
+  | "Standard" | "Programmable" | "Compressed";
+
+export interface NftAttribute {
+  trait_type: string;
+  value: string;
+}
+
+export interface Nft {
+  /** Mint of the NFT, or the asset id of a compressed NFT. */
+  id: string;
+  kind: NftKind;
+  name: string;
+  symbol: string;
+  description?: string;
+  /** Image from the off-chain JSON metadata. */
+  image?: string;
+  attributes: Array<NftAttribute>;
+  /** URI of the off-chain JSON metadata. */
+  uri: string;
+}
+
+/** NFTs of one verified collection. */
+export interface NftCollection {
+  /** Mint of the collection NFT, `None` for NFTs outside a verified collection. */
+  address?: string;
+  name?: string;
+  image?: string;
+  nfts: Array<Nft>;
+}
+
+/** A token account with no balance left, whose rent can be reclaimed by closing it. */
+export interface EmptyTokenAccount {
+  address: string;
+  mint: string;
+  /** SPL Token or Token-2022. */
+  program_id: string;
+  /** SOL returned to the wallet when the account is closed. */
+  rent: number;
+}
+
+export interface RentReclaim {
+  accounts: Array<EmptyTokenAccount>;
+  /** SOL returned by closing every account. */
+  total_rent: number;
+}
+
+/** Outcome of closing empty token accounts. */
+export interface ClosedTokenAccounts {
+  /** One signature per batch of closed accounts. */
+  signatures: Array<string>;
+  closed: Array<string>;
+  /** Requested accounts that were no longer empty or could not be closed. */
+  skipped: Array<string>;
+  /** SOL returned to the wallet. */
+  reclaimed: number;
+}
+
+export type BurnKind =
+  /** A fungible balance of SPL Token or Token-2022. */
+  | "Token"
+  /** A Metaplex NFT, burnt with its metadata and edition accounts. */
+  | "Nft";
+
+/** What burning one mint destroys and returns to the wallet. */
+export interface BurnItem {
+  mint: string;
+  name: string;
+  symbol: string;
+  kind: BurnKind;
+  /** Tokens destroyed, in UI units. */
+  amount: number;
+  /** SOL returned by the accounts closed with the burn. */
+  rent: number;
+  /** Whether the token is on the verified registry, burning it must be forced. */
+  verified: boolean;
+}
+
+/** Confirmation summary shown before burning. */
+export interface BurnSummary {
+  items: Array<BurnItem>;
+  /** SOL returned by burning every item. */
+  recovered: number;
+}
+
+/** Outcome of burning tokens and NFTs. */
+export interface BurnResult {
+  /** One signature per burnt mint. */
+  signatures: Array<string>;
+  burned: Array<string>;
+  /** Requested mints whose transaction failed. */
+  skipped: Array<string>;
+  /** SOL returned to the wallet. */
+  recovered: number;
+}
+
+/** What the last health check found out about an RPC endpoint. */
+export interface RpcEndpointStatus {
+  /**
+   * Host of the endpoint. The path and query, which may hold an API key,
+   * are left out.
+   */
+  host: string;
+  /** Preference of the endpoint, 0 first. */
+  rank: number;
+  /**
+   * Whether the endpoint answered its last health check, or a call since.
+   * Endpoints not checked yet count as healthy.
+   */
+  healthy: boolean;
+  /** Whether calls currently go to this endpoint. */
+  active: boolean;
+  slot?: number;
+  /** Slots behind the most advanced endpoint of the network. */
+  slot_lag?: number;
+  latency_ms?: number;
+  error?: string;
+  /** Unix timestamp of the last health check. */
+  checked_at?: number;
+}
+
+export const RPC_ENDPOINTS_UPDATED_EVENT = "rpc_endpoints_updated";
+
+/**
+ * Genesis hashes of the public clusters, returned by `getGenesisHash`. They
+ * tell which cluster an endpoint serves whatever its URL.
+ */
+export const MAINNET_GENESIS_HASH =
+  "5eykt4UsFv8P8NJdTREpY1vzqKqZKvdpKuc147dw2N9d";
+export const TESTNET_GENESIS_HASH =
+  "4uhcVJyU9pJkvQyS88uRDiswHXSCkY3zQawwpjk2NsNY";
+export const DEVNET_GENESIS_HASH =
+  "EtWTRABZaYq6iMfeYKouRu166VU2xqa1wcaWoxPkrZBG";
+
+/** An RPC provider or cluster added by the user. */
+export interface CustomNetwork {
+  name: string;
+  /**
+   * Cluster the network serves. Its endpoint is tried before the built-in
+   * ones of the cluster, or instead of them for `Local`.
+   */
+  environment: Environment;
+  rpc_url: string;
+  /** WebSocket endpoint, derived from `rpc_url` when not set. */
+  ws_url?: string;
+  /** Genesis hash the endpoint must return from `getGenesisHash`. */
+  genesis_hash: string;
+}
+
+/** The balance of an account of the followed wallet changed. */
+export interface AccountUpdate {
+  wallet: string;
+  /** The wallet itself for SOL, or one of its token accounts. */
+  account: string;
+  lamports: number;
+  /** Mint of a token account. */
+  mint?: string;
+  /** Balance of a token account, in UI units. */
+  ui_amount?: number;
+}
+
+/** A transaction mentioning the followed wallet was confirmed. */
+export interface TransactionUpdate {
+  wallet: string;
+  signature: string;
+  /** Why the transaction failed, if it did. */
+  error?: string;
+}
+
+/** A watched signature was confirmed, failed or expired. */
+export interface SignatureUpdate {
+  signature: string;
+  /** Why the transaction did not succeed, if it did not. */
+  error?: string;
+}
+
+export const BALANCE_CHANGED_EVENT = "balance_changed";
+
+export const INCOMING_TRANSFER_EVENT = "incoming_transfer";
+
+export const JOURNAL_UPDATED_EVENT = "journal_updated";
+
+/** Last-known network responses, kept apart from the wallet data. */
+export const STORE_CACHE = ".cache.dat";
+
+/** Data served from the local cache, readable when the app is offline. */
+export interface Cached<T> {
+  data: T;
+  /** When the data was fetched from the network. */
+  fetched_at: Date;
+  /**
+   * The data is out of date and a newer copy is fetched in the background.
+   * `CACHE_UPDATED_EVENT` is emitted once it is cached.
+   */
+  stale: boolean;
+}
+
+export const CACHE_UPDATED_EVENT = "cache_updated";
+
+/** The USD price of a token. */
+export interface AssetPrice {
+  mint: string;
+  usd_price: number;
+  /** Price change over the last 24 hours, in percent. */
+  price_change_24h?: number;
+  source: PriceSource;
+  /** Unix timestamp of the price, in seconds. */
+  updated_at: number;
+}
+
+/** The USD price of a token at a point in time. */
+export interface PricePoint {
+  /** Unix timestamp in seconds. */
+  timestamp: number;
+  usd_price: number;
+}
+
+/** A validator that can receive stake delegations. */
+export interface Validator {
+  /** Vote account, the address stake is delegated to. */
+  vote_account: string;
+  /** Identity of the validator node. */
+  identity: string;
+  /** Percentage of rewards kept by the validator. */
+  commission: number;
+  /** Active stake, in SOL. */
+  activated_stake: number;
+  last_vote: number;
+  /**
+   * Ratio of leader slots without a block in the current epoch, if the
+   * validator had any leader slot.
+   */
+  skip_rate?: number;
+  delinquent: boolean;
+}
+
+export type StakeActivationState =
+  | "Activating" | "Active" | "Deactivating" | "Inactive";
+
+/** A stake account owned by the wallet. */
+export interface StakeAccount {
+  address: string;
+  /** Total balance of the account, in SOL. */
+  balance: number;
+  /** Delegated stake, in SOL. */
+  delegated_stake: number;
+  /** Lamports kept in the account to keep it rent exempt, in SOL. */
+  rent_exempt_reserve: number;
+  /** Vote account the stake is delegated to, if any. */
+  validator?: string;
+  state: StakeActivationState;
+  activation_epoch?: number;
+  deactivation_epoch?: number;
+}
+
+/** Result of an operation that creates a new stake account. */
+export interface StakeOperation {
+  stake_account: string;
+  signature: string;
+}
+
+/** Inflation reward credited to a stake account for an epoch. */
+export interface StakeReward {
+  stake_account: string;
+  epoch: number;
+  /** Reward, in SOL. */
+  amount: number;
+  /** Account balance after the reward, in SOL. */
+  post_balance: number;
+  commission?: number;
+}
+
+/** A saved recipient in the address book. */
+export interface Contact {
+  id: string;
+  label: string;
+  address: string;
+  /** The network the address was saved for. */
+  network: Environment;
+  /** NotWallet username, without the leading `@`. */
+  username?: string;
+  /** Domain name resolving to the address, e.g. `bonfida.sol`. */
+  domain?: string;
+  notes?: string;
+  /** Set after the first successful small transfer to the address. */
+  verified: boolean;
+  /** Unix timestamp in seconds. */
+  created_at: number;
+  /** Unix timestamp of the last transfer to the contact. */
+  last_used_at?: number;
+}
+
+/** The editable fields of a contact. */
+export interface ContactInput {
+  label: string;
+  address: string;
+  network: Environment;
+  username?: string;
+  domain?: string;
+  notes?: string;
+}
+
+/** State of an SPL stake pool, e.g. the JitoSOL pool. */
+export interface StakePoolInfo {
+  name?: string;
+  address: string;
+  /** Mint of the liquid staking token. */
+  pool_mint: string;
+  /** SOL received for one pool token, before fees. */
+  exchange_rate: number;
+  /** Fee on SOL deposits, as a fraction, e.g. `0.001` for 0.1%. */
+  sol_deposit_fee: number;
+  /** Fee on SOL withdrawals, as a fraction. */
+  sol_withdrawal_fee: number;
+  /** Total SOL managed by the pool. */
+  total_lamports: number;
+}
+
+export type LiquidStakeRoute =
+  | "StakePool" | "Jupiter";
+
+/** Quote for depositing SOL into a stake pool. */
+export interface LiquidStakeQuote {
+  pool: StakePoolInfo;
+  /** SOL deposited. */
+  amount: number;
+  /** Pool tokens received from a direct deposit, after fees. */
+  pool_tokens_out: number;
+  /** Pool tokens kept by the pool as the deposit fee. */
+  deposit_fee: number;
+  /** Pool tokens received by swapping on Jupiter, if a route was found. */
+  jupiter_out_amount?: number;
+  /** The route that returns more pool tokens. */
+  best_route: LiquidStakeRoute;
+}
+
+/** Quote for withdrawing SOL from a stake pool. */
+export interface LiquidUnstakeQuote {
+  pool: StakePoolInfo;
+  /** Pool tokens burnt. */
+  pool_tokens: number;
+  /** SOL received, after fees. */
+  sol_out: number;
+  /** Pool tokens kept by the pool as the withdrawal fee. */
+  withdrawal_fee: number;
+}
+
+/** The holdings of a wallet at one point in time, valued in USD. */
+export interface PortfolioSnapshot {
+  pubkey: string;
+  network: Environment;
+  /** Unix timestamp in seconds. */
+  timestamp: number;
+  total_usd: number;
+  assets: Array<SnapshotAsset>;
+}
+
+export interface SnapshotAsset {
+  mint: string;
+  amount: number;
+  usd_price?: number;
+}
+
+/** The change of one token balance of a wallet in a transaction. */
+export interface BalanceChange {
+  signature: string;
+  /** Unix timestamp of the block, in seconds. */
+  timestamp: number;
+  mint: string;
+  /** Amount received, negative for amounts sent and fees paid. */
+  delta: number;
+}
+
+/**
+ * A Solana Pay transfer request, e.g.
+ * `solana:<recipient>?amount=1&spl-token=<mint>&reference=<pubkey>&label=Shop`.
+ */
+export interface TransferRequest {
+  /** Base58 address of the native SOL account that receives the payment. */
+  recipient: string;
+  /** Amount in user units (e.g. `1.5` SOL). `None` lets the wallet ask the user. */
+  amount?: number;
+  /** Mint address of the SPL token. `None` means native SOL. */
+  spl_token?: string;
+  /** Reference keys used by the merchant to locate the transaction. */
+  reference: Array<string>;
+  /** Describes the source of the request, e.g. the merchant name. */
+  label?: string;
+  /** Describes the nature of the request, e.g. the purchased item. */
+  message?: string;
+  /** Memo to include in the transaction, visible on-chain. */
+  memo?: string;
+}
+
+/**
+ * A Solana Pay transaction request, e.g. `solana:https%3A%2F%2Fshop.example%2Fpay`.
+ * The merchant server builds the transaction; the wallet only reviews and signs it.
+ */
+export interface TransactionRequest {
+  /** HTTPS endpoint of the merchant, percent-decoded. */
+  link: string;
+  label?: string;
+  message?: string;
+}
+
+/** Any request encoded in a `solana:` URI. */
+export type SolanaPayRequest =
   | {
-      "Eurc": {
-        meta: Metadata;
-      }
-    }
-  | {
-      "BachToken0": {
-        meta: Metadata;
+      "Transfer": {
+        request: TransferRequest;
       }
     }
   | {
-      "BachToken1": {
-        meta: Metadata;
+      "Transaction": {
+        request: TransactionRequest;
       }
     };
 
-export interface BalanceV1 {
-  meta: Metadata;
+/** Response of the merchant to the `GET` request. */
+export interface TransactionRequestMetadata {
+  label: string;
+  icon: string;
+}
+
+/** One decoded instruction of a merchant transaction. */
+export interface InstructionSummary {
+  program_id: string;
+  /** Human-readable program name, e.g. "System Program". */
+  program: string;
+  /** Short description of what the instruction does, e.g. "Transfer 10000 lamports". */
+  description: string;
+  accounts: Array<string>;
+}
+
+/** Everything the user needs to review before signing a merchant transaction. */
+export interface TransactionRequestPreview {
+  link: string;
+  label?: string;
+  icon?: string;
+  /** Message returned by the merchant together with the transaction. */
+  message?: string;
+  /** Base64 encoded transaction, passed back unchanged when approving. */
+  transaction: string;
+  fee_payer: string;
+  /** Accounts the transaction may modify. */
+  writable_accounts: Array<string>;
+  instructions: Array<InstructionSummary>;
+}
+
+/** Lifecycle of a merchant invoice. */
+export type InvoiceStatus =
+  /** No payment found yet. */
+  | "Pending"
+  /** The full amount was received by the recipient. */
+  | "Paid"
+  /** A payment was found but it is less than the invoiced amount. */
+  | "Underpaid"
+  /** The invoice expired without any payment. */
+  | "Expired";
+
+/**
+ * An invoice created by the wallet acting as a point-of-sale.
+ *
+ * The unique `reference` key is attached to the payment, which lets the
+ * wallet find the transaction on-chain without knowing the payer.
+ */
+export interface Invoice {
+  /** Base58 reference public key, also used as the invoice id. */
+  reference: string;
+  /** The network the payment is expected on. */
+  network: Environment;
+  recipient: string;
+  /** Amount in user units. */
+  amount: number;
+  /** Mint address of the SPL token. `None` means native SOL. */
+  spl_token?: string;
+  label?: string;
+  message?: string;
+  memo?: string;
+  /** Unix timestamp, in seconds. */
+  created_at: number;
+  /** Unix timestamp, in seconds. */
+  expires_at: number;
+  status: InvoiceStatus;
+  /** Amount received so far, in user units. */
+  received_amount: number;
+  /** Signatures of the transactions that paid this invoice. */
+  signatures: Array<string>;
+  /** Solana Pay URI to render as a QR code. */
+  uri: string;
+}
+
+/** A username claim signed by the wallet that owns it. */
+export interface UsernameProof {
+  username: string;
+  pubkey: string;
+  /** The challenge issued by the directory, see `username::parse_proof_message`. */
+  message: string;
+  /** Base58 signature of `message` by `pubkey`. */
+  signature: string;
   /**
-   * Balance in its smallest nomimal. For example, a 0.01 SOL balance will return 10000000,
-   * 0.010000000 * 1_000_000_000.
+   * The directory's statement that the username is currently assigned to
+   * `pubkey`. Set on the proofs the directory returns.
    */
-  balance: number;
-  /** Balance in its easy-to-read form. For example, a 0.01 SOL. */
-  ui_amount: number;
+  attestation?: UsernameAttestation;
 }
 
-export type Environment =
-  | "Local" | "Devnet" | "Testnet" | "Mainnet";
+/** A time bounded statement signed by the username directory. */
+export interface UsernameAttestation {
+  /** See `username::parse_attestation_message`. */
+  message: string;
+  /** Base58 signature of `message` by the directory key. */
+  signature: string;
+}
+
+/** The message the directory asks a wallet to sign to claim a username. */
+export interface UsernameChallenge {
+  message: string;
+}
+
+export type RecipientSource =
+  | "Address"
+  | "Username"
+  /** A Solana Name Service `.sol` domain. */
+  | "Domain";
+
+/** A send recipient resolved to an address, shown to the user before signing. */
+export interface ResolvedRecipient {
+  /** What the user typed, e.g. `@alice` or `alice.sol`. */
+  input: string;
+  address: string;
+  source: RecipientSource;
+}
+
+export const STORE_INVOICES = "invoices";
+
+export const STORE_CONTACTS = "contacts";
+
+export const STORE_JOURNAL = "journal";
+
+export const STORE_PORTFOLIO_SNAPSHOTS = "portfolioSnapshots";
+
+export const STORE_TOKEN_REGISTRY = "tokenRegistry";
+
+export const STORE_TOKEN_PREFERENCES = "tokenPreferences";
+
+export const STORE_CUSTOM_NETWORKS = "customNetworks";
+
+/** A transfer sent from the wallet. */
+export interface JournalEntry {
+  id: string;
+  /** The transaction signature, missing if the transfer could not be sent. */
+  signature?: string;
+  status: JournalStatus;
+  network: Environment;
+  from: string;
+  to: string;
+  amount: number;
+  token_address: string;
+  /** The address book contact the transfer was sent to, if any. */
+  contact_id?: string;
+  timestamp: Date;
+}
+
+export type JournalStatus =
+  | "Pending"
+  | "Confirmed"
+  | {
+      "Failed": {
+        error: string;
+      }
+    };
+
+export const KEY_FIAT_CURRENCY = "fiat_currency";
+
+export const SOLANA_PAY_REQUEST_EVENT = "solana_pay_request";
+
+export const INVOICE_UPDATED_EVENT = "invoice_updated";
@@ -10,8 +10,14 @@ import ListItemText from "@mui/material/ListItemText";
 import Divider from "@mui/material/Divider";
 import CircularProgress from "@mui/material/CircularProgress";
 import Avatar from "@mui/material/Avatar";
-import { BalanceV1, SolanaWallet } from "@app/lib/crate/generated";
+import {
+  BalanceV1,
+  CACHE_UPDATED_EVENT,
+  Cached,
+  SolanaWallet,
+} from "@app/lib/crate/generated";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import OpenInNewIcon from "@mui/icons-material/OpenInNew";
 import IconButton from "@mui/material/IconButton";
 import { openUrl } from "@tauri-apps/plugin-opener";
@@ -39,15 +45,20 @@ export default function AssetsView({
   const { environment } = useNetworkEnvironment();
   const [assets, setAssets] = React.useState<BalanceV1[]>([]);
   const [loading, setLoading] = React.useState(true);
-  const fetchWalletAssetsBalance = async () => {
+  const fetchWalletAssetsBalance = async (showLoading = true) => {
     try {
-      setLoading(true);
-      // Fetch BACH balance
-      const tokenList = await invoke<BalanceV1[]>("get_wallet_assets_balance", {
-        network: environment,
-        pubkey: wallet.pubkey,
-        environment: xlpEnvironment,
-      });
+      setLoading(showLoading);
+      // The last-known balances come from the cache, newer ones follow
+      // with a cache update.
+      const cached = await invoke<Cached<BalanceV1[]>>(
+        "get_wallet_assets_balance",
+        {
+          network: environment,
+          pubkey: wallet.pubkey,
+          environment: xlpEnvironment,
+        },
+      );
+      const tokenList = cached.data;
       setAssets(tokenList);
       onAvailableAssetsUpdated(tokenList);
     } catch (err) {
@@ -60,6 +71,12 @@ export default function AssetsView({
 
   React.useEffect(() => {
     fetchWalletAssetsBalance();
+    const unlisten = listen<string>(CACHE_UPDATED_EVENT, () =>
+      fetchWalletAssetsBalance(false),
+    );
+    return () => {
+      unlisten.then((f) => f());
+    };
   }, [wallet.pubkey]);
 
   const handleOpenTokenInformation = async (token: string) => {
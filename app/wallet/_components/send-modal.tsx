@@ -10,10 +10,14 @@ import FormControl from "@mui/material/FormControl";
 import Select, { SelectChangeEvent } from "@mui/material/Select";
 import InputLabel from "@mui/material/InputLabel";
 import InputAdornment from "@mui/material/InputAdornment";
-import { BalanceV1, SolanaWallet } from "@app/lib/crate/generated";
+import {
+  BalanceV1,
+  ResolvedRecipient,
+  SolanaWallet,
+} from "@app/lib/crate/generated";
 import { selectionFeedback } from "@tauri-apps/plugin-haptics";
 import { invoke } from "@tauri-apps/api/core";
-import { SEND_TOKEN } from "@app/lib/commands";
+import { RESOLVE_RECIPIENT, SEND_TOKEN } from "@app/lib/commands";
 import CircularProgress from "@mui/material/CircularProgress";
 import Alert from "@mui/material/Alert";
 import { useLang } from "../../../src/LanguageContext";
@@ -56,6 +60,10 @@ export default function SendModal({
   const [isLoading, setIsLoading] = React.useState<boolean>(false);
   const [error, setError] = React.useState<string | null>(null);
   const [success, setSuccess] = React.useState<boolean>(false);
+  // A `@username` or `.sol` recipient, shown resolved before it is sent to
+  const [resolved, setResolved] = React.useState<ResolvedRecipient | null>(
+    null,
+  );
 
   // Reset form when modal opens/closes
   React.useEffect(() => {
@@ -63,6 +71,7 @@ export default function SendModal({
       setAmount("");
       setError(null);
       setSuccess(false);
+      setResolved(null);
       if (availableAssets.length > 0) {
         updateSelectedToken(availableAssets[0].meta.address);
       }
@@ -141,14 +150,27 @@ export default function SendModal({
         return;
       }
 
-      // Here you would invoke the Tauri command to send tokens
-      // This is a placeholder - implement the actual invoke call
+      // Names are sent to only once the user has seen their address
+      let expectedAddress = resolved?.address;
+      if (resolved?.input !== finalRecipient) {
+        const recipient = await invoke<ResolvedRecipient>(RESOLVE_RECIPIENT, {
+          network: environment,
+          to: finalRecipient,
+        });
+        if (recipient.source !== "Address") {
+          setResolved(recipient);
+          return;
+        }
+        expectedAddress = recipient.address;
+      }
+
       await invoke(SEND_TOKEN, {
         network: environment,
         from: senderAddress,
         to: finalRecipient,
         amount: parseFloat(amount),
         tokenAddress: selectedTokenAddress,
+        expectedAddress,
       });
 
       setSuccess(true);
@@ -219,6 +241,15 @@ export default function SendModal({
           </Alert>
         )}
 
+        {resolved &&
+          !success &&
+          resolved.input ===
+            (recipient === "custom" ? customAddress : recipient) && (
+            <Alert severity="info" sx={{ mb: 3, wordBreak: "break-all" }}>
+              {t.confirmResolvedRecipient(resolved.input, resolved.address)}
+            </Alert>
+          )}
+
         <Stack spacing={3}>
           <FormControl fullWidth>
             <InputLabel id="token-type-label">{t.tokenType}</InputLabel>
@@ -0,0 +1,265 @@
+import * as React from "react";
+import Modal from "@mui/material/Modal";
+import Typography from "@mui/material/Typography";
+import Button from "@mui/material/Button";
+import Box from "@mui/material/Box";
+import Stack from "@mui/material/Stack";
+import TextField from "@mui/material/TextField";
+import Avatar from "@mui/material/Avatar";
+import CircularProgress from "@mui/material/CircularProgress";
+import Alert from "@mui/material/Alert";
+import {
+  SolanaPayRequest,
+  SolanaWallet,
+  TransactionRequestPreview,
+} from "@app/lib/crate/generated";
+import {
+  APPROVE_TRANSACTION_REQUEST,
+  CREATE_SOLANA_PAY_URI,
+  GET_TRANSACTION_REQUEST,
+  PAY_SOLANA_PAY_URI,
+} from "@app/lib/commands";
+import { selectionFeedback } from "@tauri-apps/plugin-haptics";
+import { invoke } from "@tauri-apps/api/core";
+import { error as logError } from "@tauri-apps/plugin-log";
+import { useLang } from "../../../src/LanguageContext";
+import { useNetworkEnvironment } from "@app/lib/context/network-environment-context";
+
+interface SolanaPayModalProps {
+  /** The request of an opened `solana:` deep link, `null` when closed. */
+  request: SolanaPayRequest | null;
+  wallet: SolanaWallet;
+  onClose: () => void;
+}
+
+export default function SolanaPayModal({
+  request,
+  wallet,
+  onClose,
+}: SolanaPayModalProps) {
+  const { t } = useLang();
+  const { environment } = useNetworkEnvironment();
+  const [amount, setAmount] = React.useState("");
+  const [preview, setPreview] =
+    React.useState<TransactionRequestPreview | null>(null);
+  const [isLoading, setIsLoading] = React.useState(false);
+  const [error, setError] = React.useState<string | null>(null);
+  const [signature, setSignature] = React.useState<string | null>(null);
+
+  const transfer = request && "Transfer" in request ? request.Transfer : null;
+  const transaction =
+    request && "Transaction" in request ? request.Transaction : null;
+
+  // Merchant transactions are fetched and verified before they are shown
+  React.useEffect(() => {
+    setAmount("");
+    setPreview(null);
+    setError(null);
+    setSignature(null);
+    if (!transaction) {
+      return;
+    }
+    setIsLoading(true);
+    invoke<TransactionRequestPreview>(GET_TRANSACTION_REQUEST, {
+      request: transaction.request,
+      account: wallet.pubkey,
+    })
+      .then(setPreview)
+      .catch((err) => {
+        logError(`Error fetching transaction request: ${JSON.stringify(err)}`);
+        setError(`${err}`);
+      })
+      .finally(() => setIsLoading(false));
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, [request, wallet]);
+
+  const handlePay = async () => {
+    try {
+      setError(null);
+      setIsLoading(true);
+      await selectionFeedback();
+      if (transfer) {
+        const uri = await invoke<string>(CREATE_SOLANA_PAY_URI, {
+          request: transfer.request,
+        });
+        setSignature(
+          await invoke<string>(PAY_SOLANA_PAY_URI, {
+            network: environment,
+            from: wallet.pubkey,
+            uri,
+            amount: transfer.request.amount ?? parseFloat(amount),
+          }),
+        );
+      } else if (preview) {
+        setSignature(
+          await invoke<string>(APPROVE_TRANSACTION_REQUEST, {
+            network: environment,
+            from: wallet.pubkey,
+            transaction: preview.transaction,
+          }),
+        );
+      }
+    } catch (err) {
+      logError(`Error paying Solana Pay request: ${JSON.stringify(err)}`);
+      setError(`${t.paymentFailed}: ${err}`);
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  const label =
+    preview?.label ?? transfer?.request.label ?? transaction?.request.label;
+  const message =
+    preview?.message ??
+    transfer?.request.message ??
+    transaction?.request.message;
+  const canPay =
+    !signature &&
+    (transfer
+      ? transfer.request.amount !== undefined || parseFloat(amount) > 0
+      : preview !== null);
+
+  return (
+    <Modal
+      open={request !== null}
+      onClose={() => !isLoading && onClose()}
+      aria-labelledby="solana-pay-title"
+      sx={{
+        display: "flex",
+        alignItems: "center",
+        justifyContent: "center",
+      }}
+    >
+      <Box
+        sx={{
+          width: "90%",
+          maxWidth: 480,
+          bgcolor: "background.paper",
+          borderRadius: 2,
+          boxShadow: 24,
+          p: 4,
+          outline: "none",
+          maxHeight: "90vh",
+          overflow: "auto",
+        }}
+      >
+        <Typography
+          id="solana-pay-title"
+          variant="h6"
+          sx={{
+            mb: 3,
+            textAlign: "center",
+            fontWeight: "bold",
+            color: "transparent",
+            background: "linear-gradient(90deg, #9932CC 0%, #A64DFF 100%)",
+            backgroundClip: "text",
+            WebkitBackgroundClip: "text",
+          }}
+        >
+          {t.paymentRequest}
+        </Typography>
+
+        {error && (
+          <Alert severity="error" sx={{ mb: 3 }}>
+            {error}
+          </Alert>
+        )}
+        {signature && (
+          <Alert severity="success" sx={{ mb: 3, wordBreak: "break-all" }}>
+            {t.paymentSent}: {signature}
+          </Alert>
+        )}
+
+        <Stack spacing={2}>
+          {(label || preview?.icon) && (
+            <Stack direction="row" spacing={2} alignItems="center">
+              {preview?.icon && <Avatar src={preview.icon} alt={label} />}
+              <Typography variant="subtitle1" fontWeight="bold">
+                {label}
+              </Typography>
+            </Stack>
+          )}
+          {message && <Typography variant="body2">{message}</Typography>}
+
+          {transfer && (
+            <>
+              <Typography variant="body2" sx={{ wordBreak: "break-all" }}>
+                {t.recipient}: {transfer.request.recipient}
+              </Typography>
+              {transfer.request.amount !== undefined ? (
+                <Typography variant="body2">
+                  {t.amount}: {transfer.request.amount}{" "}
+                  {transfer.request.spl_token ?? "SOL"}
+                </Typography>
+              ) : (
+                <TextField
+                  label={t.amount}
+                  type="number"
+                  fullWidth
+                  value={amount}
+                  onChange={(event) => setAmount(event.target.value)}
+                  disabled={isLoading}
+                />
+              )}
+            </>
+          )}
+
+          {transaction && !preview && isLoading && (
+            <Typography variant="body2">{t.loadingPaymentRequest}</Typography>
+          )}
+          {preview && (
+            <>
+              <Typography variant="body2" fontWeight="bold">
+                {t.paymentRequestInstructions}
+              </Typography>
+              {preview.instructions.map((instruction, index) => (
+                <Typography key={index} variant="body2">
+                  {instruction.program}: {instruction.description}
+                </Typography>
+              ))}
+            </>
+          )}
+        </Stack>
+
+        <Stack direction="row" spacing={2} sx={{ mt: 4 }}>
+          <Button
+            variant="outlined"
+            onClick={onClose}
+            disabled={isLoading}
+            sx={{
+              flex: 1,
+              borderRadius: 2,
+              color: "#9932CC",
+              borderColor: "#9932CC",
+              "&:hover": {
+                borderColor: "#7B2599",
+                backgroundColor: "rgba(153, 50, 204, 0.04)",
+              },
+            }}
+          >
+            {t.cancel}
+          </Button>
+          <Button
+            variant="contained"
+            onClick={handlePay}
+            disabled={isLoading || !canPay}
+            sx={{
+              flex: 1,
+              borderRadius: 2,
+              background: "linear-gradient(90deg, #9932CC 0%, #A64DFF 100%)",
+              color: "#fff",
+              "&:hover": {
+                background: "linear-gradient(90deg, #8A2BE2 0%, #9400D3 100%)",
+              },
+            }}
+            startIcon={
+              isLoading ? <CircularProgress size={20} color="inherit" /> : null
+            }
+          >
+            {isLoading ? t.processing : transfer ? t.pay : t.approve}
+          </Button>
+        </Stack>
+      </Box>
+    </Modal>
+  );
+}
@@ -20,7 +20,6 @@ import {
   SOL_DECIMALS,
   Environment,
   BalanceV1,
-  SolanaAsset,
   Metadata,
 } from "@app/lib/crate/generated";
 import { selectionFeedback } from "@tauri-apps/plugin-haptics";
@@ -43,12 +42,7 @@ interface SwapModalProps {
   senderAddress: string;
   availableKeypairs: SolanaWallet[];
   availableAssets: BalanceV1[];
-  verifiedAssets: SolanaAsset[];
-}
-
-function getAssetMeta(asset: SolanaAsset): Metadata {
-  const values = Object.values(asset);
-  return values[0].meta;
+  verifiedAssets: Metadata[];
 }
 
 export default function SwapModal({
@@ -226,6 +220,7 @@ export default function SwapModal({
       userPublicKey: userPublicKey,
       dynamicComputeUnitLimit: true, // Automatically optimize compute units
       dynamicSlippage: true, // Automatically adjust slippage if needed
+      wrapAndUnwrapSol: true, // Pay and receive native SOL, leaving no wSOL behind
       prioritizationFeeLamports: prioritizationFee,
     };
   };
@@ -457,23 +452,18 @@ export default function SwapModal({
                       {verifiedAssets
                         .filter(
                           (asset) =>
-                            getAssetMeta(asset).address !==
+                            asset.address !==
                             selectedFromTokenBalance?.meta.address,
                         )
                         .map((asset, index) => (
-                          <MenuItem
-                            key={index}
-                            value={getAssetMeta(asset).address}
-                          >
+                          <MenuItem key={index} value={asset.address}>
                             <ListItemIcon>
                               <AssetIcon
-                                id={getAssetMeta(asset).address}
-                                logoUrl={getAssetMeta(asset).logo_uri}
+                                id={asset.address}
+                                logoUrl={asset.logo_uri}
                               />
                             </ListItemIcon>
-                            <ListItemText>
-                              {getAssetMeta(asset).symbol}
-                            </ListItemText>
+                            <ListItemText>{asset.symbol}</ListItemText>
                           </MenuItem>
                         ))}
                     </Select>
@@ -9,10 +9,19 @@ import Typography from "@mui/material/Typography";
 import AddIcon from "@mui/icons-material/Add";
 import SettingsIcon from "@mui/icons-material/Settings";
 import {
+  AccountUpdate,
   ADDRESS_SOL,
+  BALANCE_CHANGED_EVENT,
   BalanceV1,
-  SolanaAsset,
+  CACHE_UPDATED_EVENT,
+  Cached,
+  INCOMING_TRANSFER_EVENT,
+  Metadata,
+  PortfolioValuation,
+  SOLANA_PAY_REQUEST_EVENT,
+  SolanaPayRequest,
   SolanaWallet,
+  TransactionUpdate,
 } from "@app/lib/crate/generated";
 import IconButton from "@mui/material/IconButton";
 import SendIcon from "@mui/icons-material/Send";
@@ -22,6 +31,7 @@ import Box from "@mui/material/Box";
 import { selectionFeedback } from "@tauri-apps/plugin-haptics";
 import { writeText } from "@tauri-apps/plugin-clipboard-manager";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { GET_ALL_KEYPAIRS, GET_WALLET_BALANCE } from "@app/lib/commands";
 import SendModal from "./send-modal";
 import SwapModal from "./swap-modal";
@@ -32,6 +42,7 @@ import { useXlpEnvironment } from "@app/lib/context/xlp-environment-context";
 import QrCodeIcon from "@mui/icons-material/QrCode";
 import { useNetworkEnvironment } from "@app/lib/context/network-environment-context";
 import NoSolModal from "./modal-no-sol";
+import SolanaPayModal from "./solana-pay-modal";
 
 interface WalletCardProps {
   wallet: SolanaWallet;
@@ -53,16 +64,20 @@ export default function WalletCard({
   const { xlpEnvironment } = useXlpEnvironment();
   const { environment } = useNetworkEnvironment();
   const [walletBalance, setWalletBalance] = React.useState<string>("-");
+  // When the balance shown was fetched, if it is out of date
+  const [staleSince, setStaleSince] = React.useState<string | null>(null);
   const [walletUsername, setWalletUsername] = React.useState<string>(
     wallet.username || t.defaultUsername,
   );
   const [sendModalOpen, setSendModalOpen] = React.useState<boolean>(false);
   const [swapModalOpen, setSwapModalOpen] = React.useState<boolean>(false);
   const [noSolModalOpen, setNoSolModalOpen] = React.useState<boolean>(false);
+  const [solanaPayRequest, setSolanaPayRequest] =
+    React.useState<SolanaPayRequest | null>(null);
   const [availableKeypairs, setAvailableKeypairs] = React.useState<
     SolanaWallet[]
   >([]);
-  const [verifiedAssets, setVerifiedAssets] = React.useState<SolanaAsset[]>([]);
+  const [verifiedAssets, setVerifiedAssets] = React.useState<Metadata[]>([]);
   const [preSelectedTokenAddress, setPreSelectedTokenAddress] =
     React.useState<string>();
   const [scannedAddress, setScannedAddress] = React.useState<string>();
@@ -149,7 +164,7 @@ export default function WalletCard({
 
   const init = async () => {
     try {
-      const verifiedAssets = await invoke<SolanaAsset[]>("get_verified_assets");
+      const verifiedAssets = await invoke<Metadata[]>("get_verified_assets");
       setVerifiedAssets(verifiedAssets);
 
       // Check if we're coming from the scan QR page
@@ -166,22 +181,66 @@ export default function WalletCard({
         if (scannedAddress) setScannedAddress(scannedAddress);
       }
       // We don't need to wait for the wallet balance to show the send modal when needed.
-      const walletBalance = await invoke<string>(GET_WALLET_BALANCE, {
-        network: environment,
-        pubkey: wallet.pubkey,
-        environment: xlpEnvironment,
-      });
-      setWalletBalance(`${walletBalance}`);
+      await refreshBalance();
     } catch (err) {
       error(`Error fetching balance: ${JSON.stringify(err)}`);
     }
   };
 
+  const refreshBalance = async () => {
+    const cached = await invoke<Cached<PortfolioValuation>>(
+      GET_WALLET_BALANCE,
+      {
+        network: environment,
+        pubkey: wallet.pubkey,
+        environment: xlpEnvironment,
+      },
+    );
+    const valuation = cached.data;
+    setWalletBalance(
+      new Intl.NumberFormat(undefined, {
+        style: "currency",
+        currency: valuation.currency,
+      }).format(valuation.total_value),
+    );
+    setStaleSince(
+      cached.stale ? new Date(cached.fetched_at).toLocaleString() : null,
+    );
+  };
+
   React.useEffect(() => {
     init();
     // eslint-disable-next-line react-hooks/exhaustive-deps
   }, []);
 
+  // The backend follows the active wallet and tells when its balance changes
+  React.useEffect(() => {
+    const onChange = () =>
+      refreshBalance().catch((err) =>
+        error(`Error refreshing balance: ${JSON.stringify(err)}`),
+      );
+    const unlisteners = [
+      listen<AccountUpdate>(BALANCE_CHANGED_EVENT, onChange),
+      listen<TransactionUpdate>(INCOMING_TRANSFER_EVENT, onChange),
+      listen<string>(CACHE_UPDATED_EVENT, onChange),
+    ];
+    return () => {
+      unlisteners.forEach((unlisten) => unlisten.then((f) => f()));
+    };
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, [wallet, environment]);
+
+  // `solana:` deep links open the payment confirmation
+  React.useEffect(() => {
+    const unlisten = listen<SolanaPayRequest>(
+      SOLANA_PAY_REQUEST_EVENT,
+      (event) => setSolanaPayRequest(event.payload),
+    );
+    return () => {
+      unlisten.then((f) => f());
+    };
+  }, []);
+
   return (
     <Card
       sx={{
@@ -373,6 +432,14 @@ export default function WalletCard({
           >
             {walletBalance}
           </Typography>
+          {staleSince && (
+            <Typography
+              variant="caption"
+              sx={{ display: "block", color: "rgba(255, 255, 255, 0.8)" }}
+            >
+              {t.lastUpdated(staleSince)}
+            </Typography>
+          )}
           <Box
             sx={{
               position: "absolute",
@@ -495,6 +562,11 @@ export default function WalletCard({
         verifiedAssets={verifiedAssets}
       />
       <NoSolModal open={noSolModalOpen} onClose={handleCloseNoSolModal} />
+      <SolanaPayModal
+        request={solanaPayRequest}
+        wallet={wallet}
+        onClose={() => setSolanaPayRequest(null)}
+      />
     </Card>
   );
 }
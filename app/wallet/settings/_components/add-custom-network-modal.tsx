@@ -0,0 +1,223 @@
+import * as React from "react";
+import Modal from "@mui/material/Modal";
+import Typography from "@mui/material/Typography";
+import Button from "@mui/material/Button";
+import Box from "@mui/material/Box";
+import Stack from "@mui/material/Stack";
+import TextField from "@mui/material/TextField";
+import MenuItem from "@mui/material/MenuItem";
+import CircularProgress from "@mui/material/CircularProgress";
+import Alert from "@mui/material/Alert";
+import {
+  CustomNetwork,
+  DEVNET_GENESIS_HASH,
+  Environment,
+  MAINNET_GENESIS_HASH,
+  TESTNET_GENESIS_HASH,
+} from "@app/lib/crate/generated";
+import { selectionFeedback } from "@tauri-apps/plugin-haptics";
+import { invoke } from "@tauri-apps/api/core";
+import { useLang } from "../../../../src/LanguageContext";
+
+interface AddCustomNetworkModalProps {
+  open: boolean;
+  onClose: (networks?: CustomNetwork[]) => void;
+}
+
+const GENESIS_HASHES: Record<Environment, string> = {
+  Local: "",
+  Devnet: DEVNET_GENESIS_HASH,
+  Testnet: TESTNET_GENESIS_HASH,
+  Mainnet: MAINNET_GENESIS_HASH,
+};
+
+export default function AddCustomNetworkModal({
+  open,
+  onClose,
+}: AddCustomNetworkModalProps) {
+  const { t } = useLang();
+  const [name, setName] = React.useState("");
+  const [environment, setEnvironment] = React.useState<Environment>("Mainnet");
+  const [rpcUrl, setRpcUrl] = React.useState("");
+  const [wsUrl, setWsUrl] = React.useState("");
+  const [genesisHash, setGenesisHash] = React.useState(MAINNET_GENESIS_HASH);
+  const [isLoading, setIsLoading] = React.useState(false);
+  const [error, setError] = React.useState<string | null>(null);
+
+  React.useEffect(() => {
+    if (open) {
+      setName("");
+      setEnvironment("Mainnet");
+      setRpcUrl("");
+      setWsUrl("");
+      setGenesisHash(MAINNET_GENESIS_HASH);
+      setError(null);
+    }
+  }, [open]);
+
+  const onEnvironmentChange = (value: Environment) => {
+    setEnvironment(value);
+    setGenesisHash(GENESIS_HASHES[value]);
+  };
+
+  const handleAdd = async () => {
+    try {
+      setError(null);
+      setIsLoading(true);
+      await selectionFeedback();
+      const network: CustomNetwork = {
+        name: name.trim(),
+        environment,
+        rpc_url: rpcUrl.trim(),
+        ws_url: wsUrl.trim() || undefined,
+        genesis_hash: genesisHash.trim(),
+      };
+      // The network is only saved once its endpoint returns the genesis hash.
+      const networks = await invoke<CustomNetwork[]>("add_custom_network", {
+        network,
+      });
+      onClose(networks);
+    } catch (err) {
+      setError(`${t.addNetworkFailed}: ${err}`);
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  return (
+    <Modal
+      open={open}
+      aria-labelledby="add-custom-network-title"
+      sx={{
+        display: "flex",
+        alignItems: "center",
+        justifyContent: "center",
+      }}
+    >
+      <Box
+        sx={{
+          width: "90%",
+          maxWidth: 480,
+          bgcolor: "background.paper",
+          borderRadius: 2,
+          boxShadow: 24,
+          p: 4,
+          outline: "none",
+          maxHeight: "90vh",
+          overflow: "auto",
+        }}
+      >
+        <Typography
+          id="add-custom-network-title"
+          variant="h6"
+          sx={{
+            mb: 3,
+            textAlign: "center",
+            fontWeight: "bold",
+            color: "transparent",
+            background: "linear-gradient(90deg, #9932CC 0%, #A64DFF 100%)",
+            backgroundClip: "text",
+            WebkitBackgroundClip: "text",
+          }}
+        >
+          {t.addNetwork}
+        </Typography>
+
+        {error && (
+          <Alert severity="error" sx={{ mb: 3 }}>
+            {error}
+          </Alert>
+        )}
+
+        <Stack spacing={3}>
+          <TextField
+            label={t.networkName}
+            fullWidth
+            value={name}
+            onChange={(event) => setName(event.target.value)}
+            disabled={isLoading}
+          />
+          <TextField
+            select
+            label={t.cluster}
+            fullWidth
+            value={environment}
+            onChange={(event) =>
+              onEnvironmentChange(event.target.value as Environment)
+            }
+            disabled={isLoading}
+          >
+            <MenuItem value="Mainnet">Mainnet</MenuItem>
+            <MenuItem value="Testnet">Testnet</MenuItem>
+            <MenuItem value="Devnet">Devnet</MenuItem>
+            <MenuItem value="Local">Local</MenuItem>
+          </TextField>
+          <TextField
+            label={t.rpcUrl}
+            fullWidth
+            value={rpcUrl}
+            onChange={(event) => setRpcUrl(event.target.value)}
+            disabled={isLoading}
+            placeholder="https://"
+          />
+          <TextField
+            label={t.wsUrl}
+            fullWidth
+            value={wsUrl}
+            onChange={(event) => setWsUrl(event.target.value)}
+            disabled={isLoading}
+            placeholder="wss://"
+            helperText={t.wsUrlHelper}
+          />
+          <TextField
+            label={t.genesisHash}
+            fullWidth
+            value={genesisHash}
+            onChange={(event) => setGenesisHash(event.target.value)}
+            disabled={isLoading}
+            helperText={t.genesisHashHelper}
+          />
+        </Stack>
+
+        <Stack direction="row" spacing={2} sx={{ mt: 4 }}>
+          <Button
+            variant="outlined"
+            onClick={() => onClose()}
+            disabled={isLoading}
+            sx={{
+              flex: 1,
+              borderRadius: 2,
+              color: "#9932CC",
+              borderColor: "#9932CC",
+              "&:hover": {
+                borderColor: "#7B2599",
+                backgroundColor: "rgba(153, 50, 204, 0.04)",
+              },
+            }}
+          >
+            {t.cancel}
+          </Button>
+          <Button
+            variant="contained"
+            onClick={handleAdd}
+            disabled={isLoading || !name || !rpcUrl || !genesisHash}
+            sx={{
+              flex: 1,
+              borderRadius: 2,
+              background: "linear-gradient(90deg, #9932CC 0%, #A64DFF 100%)",
+              color: "#fff",
+              "&:hover": {
+                background: "linear-gradient(90deg, #8A2BE2 0%, #9400D3 100%)",
+              },
+            }}
+            startIcon={
+              isLoading ? <CircularProgress size={20} color="inherit" /> : null
+            }
+          >
+            {isLoading ? t.verifying : t.add}
+          </Button>
+        </Stack>
+      </Box>
+    </Modal>
+  );
+}
@@ -0,0 +1,128 @@
+"use client";
+
+import Box from "@mui/material/Box";
+import Card from "@mui/material/Card";
+import Typography from "@mui/material/Typography";
+import List from "@mui/material/List";
+import ListItem from "@mui/material/ListItem";
+import Button from "@mui/material/Button";
+import IconButton from "@mui/material/IconButton";
+import DeleteOutlineIcon from "@mui/icons-material/DeleteOutline";
+import { useLang } from "../../../../src/LanguageContext";
+import { CustomNetwork } from "@app/lib/crate/generated";
+import React from "react";
+import { error } from "@tauri-apps/plugin-log";
+import { invoke } from "@tauri-apps/api/core";
+import AddCustomNetworkModal from "./add-custom-network-modal";
+
+interface CustomNetworksSettingProps {
+  networks: CustomNetwork[];
+  onChange: (networks: CustomNetwork[]) => void;
+}
+
+export default function CustomNetworksSetting({
+  networks,
+  onChange,
+}: CustomNetworksSettingProps) {
+  const { t } = useLang();
+  const [showAddModal, setShowAddModal] = React.useState(false);
+
+  const removeNetwork = async (name: string) => {
+    try {
+      const remaining = await invoke<CustomNetwork[]>(
+        "remove_custom_network",
+        { name },
+      );
+      onChange(remaining);
+    } catch (e) {
+      error(`Failed to remove custom network: ${e}`);
+    }
+  };
+
+  const onAddModalClose = (added?: CustomNetwork[]) => {
+    setShowAddModal(false);
+    if (added) {
+      onChange(added);
+    }
+  };
+
+  return (
+    <Card
+      sx={{
+        width: "100%",
+        borderRadius: "20px",
+        boxShadow: "0 4px 20px rgba(139, 92, 246, 0.08)",
+        border: "1px solid rgba(139, 92, 246, 0.06)",
+        mb: 3,
+        overflow: "hidden",
+        bgcolor: "#FFFFFF",
+      }}
+    >
+      <Box
+        sx={{
+          p: 3,
+          pb: 1,
+          display: "flex",
+          alignItems: "center",
+          justifyContent: "space-between",
+        }}
+      >
+        <Typography
+          variant="h6"
+          sx={{
+            fontSize: "18px",
+            fontWeight: 600,
+            color: "#1F2937",
+            mb: 1,
+            letterSpacing: "-0.02em",
+          }}
+        >
+          {t.customNetworks}
+        </Typography>
+        <Button
+          size="small"
+          onClick={() => setShowAddModal(true)}
+          sx={{ textTransform: "none", color: "#8B5CF6" }}
+        >
+          {t.addNetwork}
+        </Button>
+      </Box>
+      <List sx={{ p: 0, pb: 1 }}>
+        {networks.length === 0 && (
+          <ListItem sx={{ px: 3 }}>
+            <Typography sx={{ fontSize: "13px", color: "#6B7280" }}>
+              {t.noCustomNetworks}
+            </Typography>
+          </ListItem>
+        )}
+        {networks.map((network) => (
+          <ListItem
+            key={network.name}
+            sx={{ px: 3 }}
+            secondaryAction={
+              <IconButton
+                edge="end"
+                aria-label={t.removeNetwork}
+                onClick={() => removeNetwork(network.name)}
+              >
+                <DeleteOutlineIcon />
+              </IconButton>
+            }
+          >
+            <Box sx={{ minWidth: 0 }}>
+              <Typography
+                sx={{ fontSize: "15px", fontWeight: 500, color: "#1F2937" }}
+              >
+                {network.name}
+              </Typography>
+              <Typography sx={{ fontSize: "13px", color: "#6B7280" }}>
+                {network.environment}
+              </Typography>
+            </Box>
+          </ListItem>
+        ))}
+      </List>
+      <AddCustomNetworkModal open={showAddModal} onClose={onAddModalClose} />
+    </Card>
+  );
+}
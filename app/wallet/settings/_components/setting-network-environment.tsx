@@ -18,7 +18,14 @@ enum State {
   Error,
 }
 
-export default function NetworkEnvironmentSetting() {
+interface NetworkEnvironmentSettingProps {
+  /** Whether a custom local cluster was added, so it can be selected. */
+  showLocal: boolean;
+}
+
+export default function NetworkEnvironmentSetting({
+  showLocal,
+}: NetworkEnvironmentSettingProps) {
   const { t } = useLang();
   const { environment, setEnvironment } = useNetworkEnvironment();
   const [state, setState] = React.useState<State>(State.Loading);
@@ -109,6 +116,9 @@ export default function NetworkEnvironmentSetting() {
               <MenuItem value="Devnet">Devnet</MenuItem>
               <MenuItem value="Testnet">Testnet</MenuItem>
               <MenuItem value="Mainnet">Mainnet</MenuItem>
+              {(showLocal || environment === "Local") && (
+                <MenuItem value="Local">Local</MenuItem>
+              )}
             </Select>
           </FormControl>
         )}
@@ -0,0 +1,168 @@
+"use client";
+
+import Box from "@mui/material/Box";
+import Card from "@mui/material/Card";
+import Typography from "@mui/material/Typography";
+import List from "@mui/material/List";
+import { useLang } from "../../../../src/LanguageContext";
+import {
+  CustomNetwork,
+  RPC_ENDPOINTS_UPDATED_EVENT,
+  RpcEndpointStatus,
+} from "@app/lib/crate/generated";
+import { Button, CircularProgress, ListItem } from "@mui/material";
+import React from "react";
+import { useNetworkEnvironment } from "@app/lib/context/network-environment-context";
+import { error } from "@tauri-apps/plugin-log";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+
+interface RpcEndpointsSettingProps {
+  /** Custom networks, whose endpoints are listed with the built-in ones. */
+  customNetworks: CustomNetwork[];
+}
+
+export default function RpcEndpointsSetting({
+  customNetworks,
+}: RpcEndpointsSettingProps) {
+  const { t } = useLang();
+  const { environment } = useNetworkEnvironment();
+  const [endpoints, setEndpoints] = React.useState<RpcEndpointStatus[]>([]);
+  const [checking, setChecking] = React.useState(false);
+
+  const fetchEndpoints = async () => {
+    try {
+      const statuses = await invoke<RpcEndpointStatus[]>("get_rpc_endpoints", {
+        network: environment,
+      });
+      setEndpoints(statuses);
+    } catch (e) {
+      error(`Failed to fetch RPC endpoints: ${e}`);
+    }
+  };
+
+  const checkEndpoints = async () => {
+    try {
+      setChecking(true);
+      const statuses = await invoke<RpcEndpointStatus[]>(
+        "check_rpc_endpoints",
+        { network: environment },
+      );
+      setEndpoints(statuses);
+    } catch (e) {
+      error(`Failed to check RPC endpoints: ${e}`);
+    } finally {
+      setChecking(false);
+    }
+  };
+
+  React.useEffect(() => {
+    fetchEndpoints();
+    const unlisten = listen<RpcEndpointStatus[]>(
+      RPC_ENDPOINTS_UPDATED_EVENT,
+      (event) => setEndpoints(event.payload),
+    );
+    return () => {
+      unlisten.then((f) => f());
+    };
+  }, [environment, customNetworks]);
+
+  const describe = (endpoint: RpcEndpointStatus) => {
+    if (!endpoint.healthy) {
+      return endpoint.error ?? t.endpointDown;
+    }
+    const details = [];
+    if (endpoint.latency_ms !== undefined && endpoint.latency_ms !== null) {
+      details.push(`${endpoint.latency_ms} ms`);
+    }
+    if (endpoint.slot_lag) {
+      details.push(t.slotsBehind(endpoint.slot_lag));
+    }
+    return details.join(" · ");
+  };
+
+  return (
+    <Card
+      sx={{
+        width: "100%",
+        borderRadius: "20px",
+        boxShadow: "0 4px 20px rgba(139, 92, 246, 0.08)",
+        border: "1px solid rgba(139, 92, 246, 0.06)",
+        mb: 3,
+        overflow: "hidden",
+        bgcolor: "#FFFFFF",
+      }}
+    >
+      <Box
+        sx={{
+          p: 3,
+          pb: 1,
+          display: "flex",
+          alignItems: "center",
+          justifyContent: "space-between",
+        }}
+      >
+        <Typography
+          variant="h6"
+          sx={{
+            fontSize: "18px",
+            fontWeight: 600,
+            color: "#1F2937",
+            mb: 1,
+            letterSpacing: "-0.02em",
+          }}
+        >
+          {t.rpcEndpoints}
+        </Typography>
+        <Button
+          size="small"
+          disabled={checking}
+          onClick={checkEndpoints}
+          sx={{ textTransform: "none", color: "#8B5CF6" }}
+        >
+          {checking ? <CircularProgress size={16} /> : t.checkNow}
+        </Button>
+      </Box>
+      <List sx={{ p: 0, pb: 1 }}>
+        {endpoints.map((endpoint) => (
+          <ListItem
+            key={endpoint.rank}
+            sx={{ px: 3, display: "flex", justifyContent: "space-between" }}
+          >
+            <Box sx={{ minWidth: 0 }}>
+              <Typography
+                sx={{
+                  fontSize: "15px",
+                  fontWeight: 500,
+                  color: "#1F2937",
+                  overflow: "hidden",
+                  textOverflow: "ellipsis",
+                }}
+              >
+                {endpoint.host}
+              </Typography>
+              <Typography sx={{ fontSize: "13px", color: "#6B7280" }}>
+                {describe(endpoint)}
+              </Typography>
+            </Box>
+            <Typography
+              sx={{
+                fontSize: "13px",
+                fontWeight: 500,
+                color: endpoint.healthy ? "#10B981" : "#EF4444",
+                ml: 2,
+                flexShrink: 0,
+              }}
+            >
+              {endpoint.active
+                ? t.endpointActive
+                : endpoint.healthy
+                  ? ""
+                  : t.endpointDown}
+            </Typography>
+          </ListItem>
+        ))}
+      </List>
+    </Card>
+  );
+}
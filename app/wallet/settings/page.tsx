@@ -17,9 +17,16 @@ import SettingListItem from "./_components/setting-list-item";
 import DestroyWalletsCard from "./_components/destroy-wallets-card";
 import { useLang } from "../../../src/LanguageContext";
 import { useNavigate } from "react-router-dom";
-import { SolanaWallet, STORE_ACTIVE_KEYPAIR } from "@app/lib/crate/generated";
+import {
+  CustomNetwork,
+  SolanaWallet,
+  STORE_ACTIVE_KEYPAIR,
+} from "@app/lib/crate/generated";
 import { store } from "@app/lib/store/store";
 import NetworkEnvironmentSetting from "./_components/setting-network-environment";
+import RpcEndpointsSetting from "./_components/setting-rpc-endpoints";
+import CustomNetworksSetting from "./_components/setting-custom-networks";
+import { invoke } from "@tauri-apps/api/core";
 
 export default function WalletSettingsPage() {
   const router = useNavigate();
@@ -29,6 +36,9 @@ export default function WalletSettingsPage() {
   const [currentWallet, setCurrentWallet] = React.useState<SolanaWallet | null>(
     null,
   );
+  const [customNetworks, setCustomNetworks] = React.useState<CustomNetwork[]>(
+    [],
+  );
 
   React.useEffect(() => {
     const fetchCurrentWallet = async () => {
@@ -39,7 +49,15 @@ export default function WalletSettingsPage() {
         console.error("Error fetching current wallet:", err);
       }
     };
+    const fetchCustomNetworks = async () => {
+      try {
+        setCustomNetworks(await invoke<CustomNetwork[]>("get_custom_networks"));
+      } catch (err) {
+        console.error("Error fetching custom networks:", err);
+      }
+    };
     fetchCurrentWallet();
+    fetchCustomNetworks();
   }, []);
 
   const handleClick = async (
@@ -163,7 +181,16 @@ export default function WalletSettingsPage() {
         </Box>
 
         {/* Network environment */}
-        <NetworkEnvironmentSetting />
+        <NetworkEnvironmentSetting
+          showLocal={customNetworks.some(
+            (network) => network.environment === "Local",
+          )}
+        />
+        <RpcEndpointsSetting customNetworks={customNetworks} />
+        <CustomNetworksSetting
+          networks={customNetworks}
+          onChange={setCustomNetworks}
+        />
 
         {/* Wallet Management Section */}
         <Card
@@ -0,0 +1,19 @@
+use {
+    crate::address_book::update_contacts,
+    chrono::Utc,
+    log::info,
+    smbcloud_wallet_kit::{
+        address_book::add_contact as kit_add_contact,
+        models::address_book::{Contact, ContactInput},
+    },
+    tauri::{command, AppHandle},
+};
+
+#[command]
+pub fn add_contact(app: AppHandle, contact: ContactInput) -> Result<Contact, String> {
+    info!("Adding contact {} on {}", contact.label, contact.network);
+    update_contacts(&app, |contacts| {
+        kit_add_contact(contacts, contact, Utc::now().timestamp())
+            .map_err(|e| format!("Failed to add contact: {}", e))
+    })
+}
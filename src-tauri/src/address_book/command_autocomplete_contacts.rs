@@ -0,0 +1,30 @@
+use {
+    crate::address_book::load_contacts,
+    log::info,
+    smbcloud_wallet_core_model::models::environment::Environment,
+    smbcloud_wallet_kit::{
+        address_book::autocomplete_contacts as kit_autocomplete_contacts,
+        models::address_book::Contact,
+    },
+    tauri::{command, AppHandle},
+};
+
+/// Number of suggestions returned when the frontend does not ask for a limit.
+const DEFAULT_SUGGESTIONS: usize = 5;
+
+#[command]
+pub fn autocomplete_contacts(
+    app: AppHandle,
+    network: Environment,
+    query: String,
+    limit: Option<usize>,
+) -> Result<Vec<Contact>, String> {
+    info!("Autocompleting contacts for {} on {}", query, network);
+    let contacts = load_contacts(&app)?;
+    Ok(kit_autocomplete_contacts(
+        &contacts,
+        network,
+        &query,
+        limit.unwrap_or(DEFAULT_SUGGESTIONS),
+    ))
+}
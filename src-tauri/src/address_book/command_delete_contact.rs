@@ -0,0 +1,14 @@
+use {
+    crate::address_book::update_contacts,
+    log::info,
+    smbcloud_wallet_kit::{address_book::remove_contact, models::address_book::Contact},
+    tauri::{command, AppHandle},
+};
+
+#[command]
+pub fn delete_contact(app: AppHandle, id: String) -> Result<Contact, String> {
+    info!("Deleting contact {}", id);
+    update_contacts(&app, |contacts| {
+        remove_contact(contacts, &id).map_err(|e| format!("Failed to delete contact: {}", e))
+    })
+}
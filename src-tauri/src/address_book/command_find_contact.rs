@@ -0,0 +1,21 @@
+use {
+    crate::address_book::load_contacts,
+    log::info,
+    smbcloud_wallet_core_model::models::environment::Environment,
+    smbcloud_wallet_kit::{
+        address_book::find_contact as kit_find_contact, models::address_book::Contact,
+    },
+    tauri::{command, AppHandle},
+};
+
+/// Look up a contact by address, `@username` or domain.
+#[command]
+pub fn find_contact(
+    app: AppHandle,
+    network: Environment,
+    query: String,
+) -> Result<Option<Contact>, String> {
+    info!("Finding contact {} on {}", query, network);
+    let contacts = load_contacts(&app)?;
+    Ok(kit_find_contact(&contacts, network, &query).cloned())
+}
@@ -0,0 +1,21 @@
+use {
+    crate::address_book::load_contacts,
+    log::info,
+    smbcloud_wallet_core_model::models::environment::Environment,
+    smbcloud_wallet_kit::models::address_book::Contact,
+    tauri::{command, AppHandle},
+};
+
+/// Get all contacts, or only those saved for `network`.
+#[command]
+pub fn get_contacts(app: AppHandle, network: Option<Environment>) -> Result<Vec<Contact>, String> {
+    info!("Getting contacts");
+    let contacts = load_contacts(&app)?;
+    Ok(match network {
+        Some(network) => contacts
+            .into_iter()
+            .filter(|c| c.network == network)
+            .collect(),
+        None => contacts,
+    })
+}
@@ -0,0 +1,22 @@
+use {
+    crate::address_book::update_contacts,
+    log::info,
+    smbcloud_wallet_kit::{
+        address_book::update_contact as kit_update_contact,
+        models::address_book::{Contact, ContactInput},
+    },
+    tauri::{command, AppHandle},
+};
+
+#[command]
+pub fn update_contact(
+    app: AppHandle,
+    id: String,
+    contact: ContactInput,
+) -> Result<Contact, String> {
+    info!("Updating contact {}", id);
+    update_contacts(&app, |contacts| {
+        kit_update_contact(contacts, &id, contact)
+            .map_err(|e| format!("Failed to update contact: {}", e))
+    })
+}
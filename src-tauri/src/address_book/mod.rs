@@ -0,0 +1,61 @@
+use {
+    crate::{constants::store::STORE_CONTACTS, repository},
+    chrono::Utc,
+    smbcloud_wallet_kit::{address_book::record_transfer, models::address_book::Contact},
+    std::sync::Mutex,
+    tauri::{App, AppHandle, Manager},
+};
+
+pub(crate) mod command_add_contact;
+pub(crate) mod command_autocomplete_contacts;
+pub(crate) mod command_delete_contact;
+pub(crate) mod command_find_contact;
+pub(crate) mod command_get_contacts;
+pub(crate) mod command_update_contact;
+
+/// Serializes the updates of the saved contacts, which the commands and the
+/// transfer confirmations load, change and save back.
+#[derive(Default)]
+pub(crate) struct ContactsLock(Mutex<()>);
+
+pub(crate) fn setup_address_book(app: &App) -> Result<(), Box<dyn std::error::Error>> {
+    app.manage(ContactsLock::default());
+    Ok(())
+}
+
+pub(crate) fn load_contacts(app: &AppHandle) -> Result<Vec<Contact>, String> {
+    repository::load_all(app, STORE_CONTACTS)
+}
+
+/// Apply `change` to the saved contacts, saving them if it succeeds.
+pub(crate) fn update_contacts<T>(
+    app: &AppHandle,
+    change: impl FnOnce(&mut Vec<Contact>) -> Result<T, String>,
+) -> Result<T, String> {
+    let lock = app.state::<ContactsLock>();
+    let _guard = lock
+        .0
+        .lock()
+        .map_err(|e| format!("Failed to lock contacts: {}", e))?;
+    let mut contacts = load_contacts(app)?;
+    let result = change(&mut contacts)?;
+    repository::save_all(app, STORE_CONTACTS, &contacts)?;
+    Ok(result)
+}
+
+/// Update the last used time and verified status after a successful transfer.
+pub(crate) fn record_contact_transfer(
+    app: &AppHandle,
+    contact_id: &str,
+    token_address: &str,
+    amount: f64,
+) -> Result<(), String> {
+    update_contacts(app, |contacts| {
+        let contact = contacts
+            .iter_mut()
+            .find(|c| c.id == contact_id)
+            .ok_or_else(|| "Contact not found".to_string())?;
+        record_transfer(contact, token_address, amount, Utc::now().timestamp());
+        Ok(())
+    })
+}
@@ -0,0 +1,207 @@
+use {
+    crate::{
+        constants::store::cache_store,
+        model::cache::{CacheEntry, Cached},
+    },
+    chrono::Utc,
+    log::{debug, error, warn},
+    serde::{de::DeserializeOwned, Serialize},
+    std::{
+        collections::HashSet,
+        fmt::Display,
+        future::Future,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Mutex,
+        },
+        time::Duration,
+    },
+    tauri::{App, AppHandle, Emitter, Manager},
+    tsync::tsync,
+};
+
+#[tsync]
+pub const CACHE_UPDATED_EVENT: &str = "cache_updated";
+
+/// Keys being refreshed in the background, so each is fetched once at a time.
+#[derive(Default)]
+pub(crate) struct CacheRefreshes {
+    keys: Mutex<HashSet<String>>,
+    /// Bumped by every `expire`, so a fetch that started before it does not
+    /// overwrite the expiry with what it read.
+    generation: AtomicU64,
+}
+
+impl CacheRefreshes {
+    fn generation(&self) -> u64 {
+        self.generation.load(Ordering::SeqCst)
+    }
+
+    /// Whether a refresh of `key` may start, i.e. none is running.
+    fn start(&self, key: &str) -> bool {
+        match self.keys.lock() {
+            Ok(mut keys) => keys.insert(key.to_string()),
+            Err(_) => false,
+        }
+    }
+
+    fn finish(&self, key: &str) {
+        if let Ok(mut keys) = self.keys.lock() {
+            keys.remove(key);
+        }
+    }
+}
+
+/// Releases the key of a refresh however its task ends, so a panicking fetch
+/// does not block the key for good.
+struct RefreshGuard {
+    app: AppHandle,
+    key: String,
+}
+
+impl Drop for RefreshGuard {
+    fn drop(&mut self) {
+        self.app.state::<CacheRefreshes>().finish(&self.key);
+    }
+}
+
+pub(crate) fn setup_cache(app: &App) -> Result<(), Box<dyn std::error::Error>> {
+    app.manage(CacheRefreshes::default());
+    Ok(())
+}
+
+/// Serve `key` from the cache, fetching it in the background when it is older
+/// than `max_age`. Only what was never cached is fetched right away, so the
+/// last-known data stays readable offline.
+pub(crate) async fn cached<T, E, F, Fut>(
+    app: &AppHandle,
+    key: String,
+    max_age: Duration,
+    fetch: F,
+) -> Result<Cached<T>, E>
+where
+    T: Serialize + DeserializeOwned + Send + 'static,
+    E: Display + Send + 'static,
+    F: FnOnce(AppHandle) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<T, E>> + Send + 'static,
+{
+    if let Some(entry) = load(app, &key) {
+        match serde_json::from_value(entry.value) {
+            Ok(data) => {
+                let age = Utc::now().signed_duration_since(entry.fetched_at);
+                let stale = entry.expired || age.to_std().is_ok_and(|age| age > max_age);
+                if stale {
+                    spawn_refresh(app.clone(), key, fetch);
+                }
+                return Ok(Cached {
+                    data,
+                    fetched_at: entry.fetched_at,
+                    stale,
+                });
+            }
+            Err(e) => warn!("Dropping unreadable cache entry {}: {}", key, e),
+        }
+    }
+
+    let generation = app.state::<CacheRefreshes>().generation();
+    let data = fetch(app.clone()).await?;
+    // Nothing else has the data yet, so it is kept even if expired meanwhile.
+    let expired = app.state::<CacheRefreshes>().generation() != generation;
+    let entry = save(app, &key, &data, expired);
+    Ok(Cached {
+        data,
+        fetched_at: entry.fetched_at,
+        stale: false,
+    })
+}
+
+/// Mark the entries whose key contains `part` out of date. They are still
+/// served, and refreshed on their next read.
+pub(crate) fn expire(app: &AppHandle, part: &str) {
+    app.state::<CacheRefreshes>()
+        .generation
+        .fetch_add(1, Ordering::SeqCst);
+    let store = match cache_store(app) {
+        Ok(store) => store,
+        Err(e) => {
+            error!("Failed to load cache: {}", e);
+            return;
+        }
+    };
+    for key in store.keys().into_iter().filter(|key| key.contains(part)) {
+        if let Some(mut entry) = load(app, &key) {
+            entry.expired = true;
+            store.set(key, serde_json::json!(entry));
+        }
+    }
+    if let Err(e) = store.save() {
+        error!("Failed to save cache: {}", e);
+    }
+}
+
+/// Forget every cached response.
+pub(crate) fn clear(app: &AppHandle) -> Result<(), String> {
+    let store = cache_store(app).map_err(|_| "Failed to load cache".to_string())?;
+    store.clear();
+    store.save().map_err(|_| "Failed to save cache".to_string())
+}
+
+fn spawn_refresh<T, E, F, Fut>(app: AppHandle, key: String, fetch: F)
+where
+    T: Serialize + Send + 'static,
+    E: Display + Send + 'static,
+    F: FnOnce(AppHandle) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<T, E>> + Send + 'static,
+{
+    if !app.state::<CacheRefreshes>().start(&key) {
+        return;
+    }
+    let guard = RefreshGuard {
+        app: app.clone(),
+        key: key.clone(),
+    };
+    let generation = app.state::<CacheRefreshes>().generation();
+    tauri::async_runtime::spawn(async move {
+        let _guard = guard;
+        match fetch(app.clone()).await {
+            Ok(_) if app.state::<CacheRefreshes>().generation() != generation => {
+                debug!("Dropping refresh of {}, expired while fetching", key);
+            }
+            Ok(data) => {
+                save(&app, &key, &data, false);
+                debug!("Refreshed {}", key);
+                if let Err(e) = app.emit(CACHE_UPDATED_EVENT, &key) {
+                    error!("Failed to emit event: {}", e);
+                }
+            }
+            Err(e) => warn!("Failed to refresh {}, serving the cached copy: {}", key, e),
+        }
+    });
+}
+
+fn load(app: &AppHandle, key: &str) -> Option<CacheEntry> {
+    let store = cache_store(app)
+        .map_err(|e| error!("Failed to load cache: {}", e))
+        .ok()?;
+    serde_json::from_value(store.get(key)?).ok()
+}
+
+/// Cache `data` under `key`. The data was fetched already, so failing to keep
+/// it is only logged.
+fn save<T: Serialize>(app: &AppHandle, key: &str, data: &T, expired: bool) -> CacheEntry {
+    let entry = CacheEntry {
+        value: serde_json::json!(data),
+        fetched_at: Utc::now(),
+        expired,
+    };
+    match cache_store(app) {
+        Ok(store) => {
+            store.set(key, serde_json::json!(entry));
+            if let Err(e) = store.save() {
+                error!("Failed to save cache: {}", e);
+            }
+        }
+        Err(e) => error!("Failed to load cache: {}", e),
+    }
+    entry
+}
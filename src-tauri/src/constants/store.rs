@@ -9,6 +9,9 @@ use tsync::tsync;
 
 #[tsync]
 pub const STORE: &str = ".notwallet.dat";
+/// Last-known network responses, kept apart from the wallet data.
+#[tsync]
+pub const STORE_CACHE: &str = ".cache.dat";
 #[tsync]
 #[allow(dead_code)]
 pub const STORE_ACCOUNT: &str = ".account.dat";
@@ -22,6 +25,20 @@ pub const STORE_SEEDS: &str = "seeds";
 #[tsync]
 pub const STORE_ACTIVE_KEYPAIR: &str = "activeKeypair";
 #[tsync]
+pub const STORE_INVOICES: &str = "invoices";
+#[tsync]
+pub const STORE_CONTACTS: &str = "contacts";
+#[tsync]
+pub const STORE_JOURNAL: &str = "journal";
+#[tsync]
+pub const STORE_PORTFOLIO_SNAPSHOTS: &str = "portfolioSnapshots";
+#[tsync]
+pub const STORE_TOKEN_REGISTRY: &str = "tokenRegistry";
+#[tsync]
+pub const STORE_TOKEN_PREFERENCES: &str = "tokenPreferences";
+#[tsync]
+pub const STORE_CUSTOM_NETWORKS: &str = "customNetworks";
+#[tsync]
 #[allow(dead_code)]
 pub const STORE_PASSWORD: &str = "password";
 
@@ -36,3 +53,7 @@ pub fn store(app: &AppHandle) -> Result<Arc<Store<Wry>>, Error> {
     let path = PathBuf::from(STORE);
     app.store(path.clone())
 }
+
+pub fn cache_store(app: &AppHandle) -> Result<Arc<Store<Wry>>, Error> {
+    app.store(PathBuf::from(STORE_CACHE))
+}
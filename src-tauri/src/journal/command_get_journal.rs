@@ -0,0 +1,17 @@
+use {
+    crate::{journal::load_journal, model::journal::JournalEntry},
+    log::info,
+    tauri::{command, AppHandle},
+};
+
+/// Get the sent transfers, newest first, optionally only those sent from `pubkey`.
+#[command]
+pub fn get_journal(app: AppHandle, pubkey: Option<String>) -> Result<Vec<JournalEntry>, String> {
+    info!("Getting journal");
+    let mut journal: Vec<JournalEntry> = load_journal(&app)?
+        .into_iter()
+        .filter(|entry| pubkey.as_ref().is_none_or(|pubkey| &entry.from == pubkey))
+        .collect();
+    journal.reverse();
+    Ok(journal)
+}
@@ -0,0 +1,80 @@
+use {
+    crate::{
+        constants::store::STORE_JOURNAL,
+        model::journal::{JournalEntry, JournalStatus},
+        repository,
+    },
+    std::{collections::HashSet, sync::Mutex},
+    tauri::{App, AppHandle, Manager},
+};
+
+pub(crate) mod command_get_journal;
+
+/// Serializes the updates of the journal, which the send commands and the
+/// transfer confirmations load, change and save back.
+///
+/// It holds the signatures of the journal, so each wallet transaction is told
+/// apart from the sent ones without loading the journal.
+pub(crate) struct JournalLock(Mutex<HashSet<String>>);
+
+pub(crate) fn setup_journal(app: &App) -> Result<(), Box<dyn std::error::Error>> {
+    let signatures = load_journal(app.handle())?
+        .into_iter()
+        .filter_map(|entry| entry.signature)
+        .collect();
+    app.manage(JournalLock(Mutex::new(signatures)));
+    Ok(())
+}
+
+pub(crate) fn load_journal(app: &AppHandle) -> Result<Vec<JournalEntry>, String> {
+    repository::load_all(app, STORE_JOURNAL)
+}
+
+/// Append an entry to the journal.
+pub(crate) fn record(app: &AppHandle, entry: JournalEntry) -> Result<(), String> {
+    let lock = app.state::<JournalLock>();
+    let mut signatures = lock
+        .0
+        .lock()
+        .map_err(|e| format!("Failed to lock journal: {}", e))?;
+    let mut journal = load_journal(app)?;
+    let signature = entry.signature.clone();
+    journal.push(entry);
+    repository::save_all(app, STORE_JOURNAL, &journal)?;
+    signatures.extend(signature);
+    Ok(())
+}
+
+/// Whether a journal entry was sent with `signature`.
+pub(crate) fn contains(app: &AppHandle, signature: &str) -> Result<bool, String> {
+    let lock = app.state::<JournalLock>();
+    let signatures = lock
+        .0
+        .lock()
+        .map_err(|e| format!("Failed to lock journal: {}", e))?;
+    Ok(signatures.contains(signature))
+}
+
+/// Settle the pending entry with `signature`, returning the updated entry.
+/// An entry that already settled is left as it is, and `None` returned.
+pub(crate) fn update_status(
+    app: &AppHandle,
+    signature: &str,
+    status: JournalStatus,
+) -> Result<Option<JournalEntry>, String> {
+    let lock = app.state::<JournalLock>();
+    let _guard = lock
+        .0
+        .lock()
+        .map_err(|e| format!("Failed to lock journal: {}", e))?;
+    let mut journal = load_journal(app)?;
+    let Some(entry) = journal.iter_mut().find(|entry| {
+        entry.status == JournalStatus::Pending && entry.signature.as_deref() == Some(signature)
+    }) else {
+        return Ok(None);
+    };
+    entry.status = status;
+    let entry = entry.clone();
+    repository::save_all(app, STORE_JOURNAL, &journal)?;
+    Ok(Some(entry))
+}
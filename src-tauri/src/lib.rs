@@ -1,11 +1,20 @@
+mod address_book;
+mod cache;
 mod constants;
 mod google;
+mod journal;
 mod model;
 mod network;
+mod nft;
 mod onramp;
+mod portfolio;
+mod repository;
 mod settings;
 mod setup;
+mod solana_pay;
+mod staking;
 mod swap;
+mod tokens;
 mod wallet;
 
 #[macro_use]
@@ -13,38 +22,91 @@ extern crate dotenv_codegen;
 
 use {
     crate::{
+        address_book::{
+            command_add_contact::add_contact, command_autocomplete_contacts::autocomplete_contacts,
+            command_delete_contact::delete_contact, command_find_contact::find_contact,
+            command_get_contacts::get_contacts, command_update_contact::update_contact,
+        },
         google::{
             command_get_consent_url::get_consent_url, command_get_profile::get_profile,
             command_get_token::get_token,
         },
+        journal::command_get_journal::get_journal,
+        nft::{command_get_nfts::get_nfts, command_transfer_nft::transfer_nft},
         onramp::commands::{onramp_session, stripe_publishable_key},
-        settings::commands::{
-            get_airdrop_environment, get_network_environment, get_xlp_environment,
-            set_airdrop_environment, set_network_environment, set_xlp_environment,
+        portfolio::{
+            command_export_tax_ledger::export_tax_ledger,
+            command_get_portfolio_history::get_portfolio_history,
+        },
+        settings::{
+            command_add_custom_network::add_custom_network,
+            command_check_rpc_endpoints::check_rpc_endpoints,
+            command_get_custom_networks::get_custom_networks,
+            command_get_rpc_endpoints::get_rpc_endpoints,
+            command_remove_custom_network::remove_custom_network,
+            commands::{
+                get_airdrop_environment, get_fiat_currencies, get_fiat_currency,
+                get_network_environment, get_xlp_environment, set_airdrop_environment,
+                set_fiat_currency, set_network_environment, set_xlp_environment,
+            },
         },
         setup::{
             command_start_server::start_server,
             commands::{get_installation_id, is_debug, register_client},
             setup,
         },
+        solana_pay::{
+            command_approve_transaction_request::approve_transaction_request,
+            command_create_invoice::create_invoice,
+            command_create_solana_pay_uri::create_solana_pay_uri,
+            command_get_invoices::get_invoices,
+            command_get_transaction_request::get_transaction_request,
+            command_parse_solana_pay_uri::parse_solana_pay_uri,
+            command_pay_solana_pay_uri::pay_solana_pay_uri, command_watch_invoice::watch_invoice,
+        },
+        staking::{
+            command_create_stake_account::create_stake_account,
+            command_deactivate_stake::deactivate_stake, command_delegate_stake::delegate_stake,
+            command_get_liquid_stake_quote::get_liquid_stake_quote,
+            command_get_liquid_unstake_quote::get_liquid_unstake_quote,
+            command_get_stake_accounts::get_stake_accounts,
+            command_get_stake_pools::get_stake_pools, command_get_stake_rewards::get_stake_rewards,
+            command_get_validators::get_validators, command_liquid_stake::liquid_stake,
+            command_liquid_unstake::liquid_unstake, command_merge_stake::merge_stake,
+            command_split_stake::split_stake, command_withdraw_stake::withdraw_stake,
+        },
         swap::{
             command_build_swap_transaction::build_swap_transaction,
             command_get_swap_quote::get_swap_quote,
             command_get_verified_assets::get_verified_assets,
             command_send_swap_transaction::send_swap_transaction,
         },
+        tokens::{
+            command_add_custom_token::add_custom_token,
+            command_burn_tokens::burn_tokens,
+            command_close_empty_token_accounts::close_empty_token_accounts,
+            command_get_empty_token_accounts::get_empty_token_accounts,
+            command_get_token_preferences::get_token_preferences,
+            command_preview_burn::preview_burn,
+            command_set_token_hidden::set_token_hidden,
+            command_unwrap_sol::unwrap_sol,
+            command_wrap_sol::wrap_sol,
+        },
         wallet::{
             command_balance::get_wallet_balance,
             command_check_pubkey::check_pubkey,
+            command_claim_username::claim_username,
             command_derive_next_keypair::derive_next_keypair,
             command_destroy_all_wallets::destroy_all_wallets,
             command_get_all_keypairs::get_all_keypairs,
             command_get_bach_balance::get_bach_balance,
+            command_get_primary_domain::get_primary_domain,
             command_get_sol_balance::get_sol_balance,
             command_get_treasury_bach_balance::get_treasury_bach_balance,
             command_get_treasury_sol_balance::get_treasury_sol_balance,
             command_import_solana_wallet::{derive_new_keypair, import_solana_wallet},
             command_onboarding_create_wallet::onboarding_create_wallet,
+            command_resolve_recipient::resolve_recipient,
             command_send_token::send_token,
             command_set_active_keypair::set_active_keypair,
             command_sign_message::sign_message,
@@ -74,6 +136,7 @@ pub fn run() {
         )
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_haptics::init())
+        .plugin(tauri_plugin_deep_link::init())
         .setup(|app| {
             // Android-only plugin.
             #[cfg(target_os = "android")]
@@ -118,13 +181,66 @@ pub fn run() {
             set_xlp_environment,
             get_xlp_environment,
             get_network_environment,
+            get_rpc_endpoints,
+            check_rpc_endpoints,
+            get_custom_networks,
+            add_custom_network,
+            remove_custom_network,
             set_network_environment,
+            get_fiat_currency,
+            set_fiat_currency,
+            get_fiat_currencies,
             get_wallet_assets_balance,
             get_verified_assets,
             start_server,
             get_token,
             get_profile,
             get_consent_url,
+            parse_solana_pay_uri,
+            create_solana_pay_uri,
+            pay_solana_pay_uri,
+            get_transaction_request,
+            approve_transaction_request,
+            create_invoice,
+            get_invoices,
+            watch_invoice,
+            get_validators,
+            get_stake_accounts,
+            create_stake_account,
+            delegate_stake,
+            deactivate_stake,
+            withdraw_stake,
+            split_stake,
+            merge_stake,
+            get_stake_rewards,
+            get_stake_pools,
+            get_liquid_stake_quote,
+            get_liquid_unstake_quote,
+            liquid_stake,
+            liquid_unstake,
+            get_contacts,
+            add_contact,
+            update_contact,
+            delete_contact,
+            find_contact,
+            autocomplete_contacts,
+            get_journal,
+            get_portfolio_history,
+            export_tax_ledger,
+            resolve_recipient,
+            claim_username,
+            get_primary_domain,
+            add_custom_token,
+            set_token_hidden,
+            get_token_preferences,
+            get_nfts,
+            transfer_nft,
+            get_empty_token_accounts,
+            close_empty_token_accounts,
+            preview_burn,
+            burn_tokens,
+            wrap_sol,
+            unwrap_sol,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
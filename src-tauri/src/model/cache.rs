@@ -0,0 +1,27 @@
+use {
+    chrono::{DateTime, Utc},
+    serde::{Deserialize, Serialize},
+    tsync::tsync,
+};
+
+/// Data served from the local cache, readable when the app is offline.
+#[derive(Serialize, Debug, Clone)]
+#[tsync]
+pub(crate) struct Cached<T> {
+    pub data: T,
+    /// When the data was fetched from the network.
+    pub fetched_at: DateTime<Utc>,
+    /// The data is out of date and a newer copy is fetched in the background.
+    /// `CACHE_UPDATED_EVENT` is emitted once it is cached.
+    pub stale: bool,
+}
+
+/// A cached response as it is stored.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct CacheEntry {
+    pub value: serde_json::Value,
+    pub fetched_at: DateTime<Utc>,
+    /// Out of date because what it was computed from changed.
+    #[serde(default)]
+    pub expired: bool,
+}
@@ -0,0 +1,31 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use smbcloud_wallet_core_model::models::environment::Environment;
+use tsync::tsync;
+use uuid::Uuid;
+
+/// A transfer sent from the wallet.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[tsync]
+pub(crate) struct JournalEntry {
+    pub id: Uuid,
+    /// The transaction signature, missing if the transfer could not be sent.
+    pub signature: Option<String>,
+    pub status: JournalStatus,
+    pub network: Environment,
+    pub from: String,
+    pub to: String,
+    pub amount: f64,
+    pub token_address: String,
+    /// The address book contact the transfer was sent to, if any.
+    pub contact_id: Option<String>,
+    pub timestamp: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[tsync]
+pub(crate) enum JournalStatus {
+    Pending,
+    Confirmed,
+    Failed { error: String },
+}
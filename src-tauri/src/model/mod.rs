@@ -1,5 +1,7 @@
 pub(crate) mod airdrop;
+pub(crate) mod cache;
 pub(crate) mod client;
+pub(crate) mod journal;
 pub(crate) mod keypair;
 pub(crate) mod onramp;
 pub(crate) mod seed;
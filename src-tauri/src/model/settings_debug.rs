@@ -5,7 +5,7 @@ use {
     tsync::tsync,
 };
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
 #[tsync]
 #[serde(rename_all = "lowercase")]
 pub enum AirdropEnvironment {
@@ -51,6 +51,14 @@ impl AirdropEnvironment {
             AirdropEnvironment::Production => dotenv!("API_BASE_URL"),
         }
     }
+
+    /// Key the username directory signs its attestations with.
+    pub fn username_directory(&self) -> &'static str {
+        match self {
+            AirdropEnvironment::Development => dotenv!("USERNAME_DIRECTORY_PUBKEY_LOCAL"),
+            AirdropEnvironment::Production => dotenv!("USERNAME_DIRECTORY_PUBKEY"),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -1,3 +1,4 @@
 pub(crate) mod airdrop;
 pub(crate) mod check_pubkey;
 pub(crate) mod client;
+pub(crate) mod username;
@@ -0,0 +1,42 @@
+use {
+    crate::model::settings_debug::AirdropEnvironment,
+    reqwest::Client,
+    smbcloud_wallet_core_network::{model::ErrorResponse, request},
+    smbcloud_wallet_kit::models::username::{UsernameChallenge, UsernameProof},
+};
+
+/// Look up the ownership proof of a username in the directory.
+pub(crate) async fn get_username(
+    environment: AirdropEnvironment,
+    username: &str,
+) -> Result<UsernameProof, ErrorResponse> {
+    let url = format!("{}/api/v1/usernames/{}", environment.base_url(), username);
+    let builder = Client::new().get(&url);
+    request(builder).await
+}
+
+/// Ask the directory for a message to sign to claim `username` for `pubkey`.
+pub(crate) async fn get_username_challenge(
+    environment: AirdropEnvironment,
+    username: &str,
+    pubkey: &str,
+) -> Result<UsernameChallenge, ErrorResponse> {
+    let url = format!(
+        "{}/api/v1/usernames/challenge?username={}&pubkey={}",
+        environment.base_url(),
+        username,
+        pubkey
+    );
+    let builder = Client::new().get(&url);
+    request(builder).await
+}
+
+/// Register the username, or move it to a new key, with the signed challenge.
+pub(crate) async fn claim_username(
+    environment: AirdropEnvironment,
+    proof: &UsernameProof,
+) -> Result<UsernameProof, ErrorResponse> {
+    let url = format!("{}/api/v1/usernames", environment.base_url());
+    let builder = Client::new().post(&url).json(proof);
+    request(builder).await
+}
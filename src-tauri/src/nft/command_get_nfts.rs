@@ -0,0 +1,35 @@
+use {
+    crate::setup::wallet_kit::wallet_kit,
+    log::{info, warn},
+    smbcloud_wallet_core_model::models::environment::Environment,
+    smbcloud_wallet_kit::{
+        models::nft::NftCollection,
+        nft::{
+            das::{get_compressed_nfts, DasClient},
+            group_by_collection, holdings,
+        },
+    },
+    tauri::{command, AppHandle},
+};
+
+#[command]
+pub async fn get_nfts(
+    app: AppHandle,
+    network: Environment,
+    pubkey: String,
+) -> Result<Vec<NftCollection>, String> {
+    info!("Getting NFTs of {}", pubkey);
+    let rpc_client = wallet_kit(&app, network)?.rpc_client();
+    let mut collections = holdings::get_nfts(&rpc_client, pubkey.clone())
+        .await
+        .map_err(|e| format!("Failed to get NFTs: {}", e))?;
+
+    // The RPC providers of the app also serve the DAS API. Local validators do
+    // not, so compressed NFTs are left out rather than failing the listing.
+    let das = DasClient::new(rpc_client.url());
+    match get_compressed_nfts(&das, &pubkey).await {
+        Ok(compressed) => collections.extend(compressed),
+        Err(e) => warn!("Failed to get compressed NFTs of {}: {}", pubkey, e),
+    }
+    Ok(group_by_collection(collections))
+}
@@ -0,0 +1,41 @@
+use {
+    crate::{
+        setup::wallet_kit::wallet_kit,
+        wallet::{keypair::load_keypair, recipient::resolve_recipient},
+    },
+    log::info,
+    smbcloud_wallet_core_model::models::environment::Environment,
+    smbcloud_wallet_kit::{
+        models::nft::NftKind,
+        nft::{
+            das::DasClient,
+            transfer::{transfer_compressed_nft, transfer_nft as kit_transfer_nft},
+        },
+    },
+    tauri::{command, AppHandle},
+};
+
+#[command]
+pub async fn transfer_nft(
+    app: AppHandle,
+    network: Environment,
+    from: String,
+    id: String,
+    kind: NftKind,
+    to: String,
+) -> Result<String, String> {
+    info!("Transferring NFT {} from {} to {}", id, from, to);
+    let to = resolve_recipient(&app, network, &to).await?.address;
+    let keypair = load_keypair(&app, &from)?;
+    let rpc_client = wallet_kit(&app, network)?.rpc_client();
+    let result = match kind {
+        NftKind::Standard | NftKind::Programmable => {
+            kit_transfer_nft(&rpc_client, keypair, id, to).await
+        }
+        NftKind::Compressed => {
+            let das = DasClient::new(rpc_client.url());
+            transfer_compressed_nft(&rpc_client, &das, keypair, id, to).await
+        }
+    };
+    result.map_err(|e| format!("Failed to transfer NFT: {}", e))
+}
@@ -0,0 +1,2 @@
+pub(crate) mod command_get_nfts;
+pub(crate) mod command_transfer_nft;
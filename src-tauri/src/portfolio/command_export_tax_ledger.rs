@@ -0,0 +1,56 @@
+use {
+    crate::{
+        constants::network::USER_AGENT, portfolio::current_valuation, setup::wallet_kit::wallet_kit,
+    },
+    log::info,
+    smbcloud_wallet_core_http::{
+        fx_data::fx_history::FrankfurterProvider,
+        price_data::price_history::BirdeyePriceHistoryProvider,
+    },
+    smbcloud_wallet_core_model::models::environment::Environment,
+    smbcloud_wallet_kit::{
+        models::{portfolio_history::CostBasisMethod, tax_export::TaxExportFormat},
+        staking::get_stake_accounts,
+        tax_export::{export_ledger, tax_ledger},
+    },
+    tauri::{command, AppHandle},
+};
+
+/// Export the taxable events of a wallet between `from` and `to`, in the currency
+/// from the settings. Returns the file contents in `format`.
+#[command]
+#[allow(clippy::too_many_arguments)]
+pub async fn export_tax_ledger(
+    app: AppHandle,
+    network: Environment,
+    pubkey: String,
+    from: i64,
+    to: i64,
+    method: CostBasisMethod,
+    format: TaxExportFormat,
+) -> Result<String, String> {
+    info!("Exporting {:?} tax ledger for {}", format, pubkey);
+    let (valuation, _, hidden_mints) = current_valuation(&app, network, &pubkey).await?;
+    let rpc_client = wallet_kit(&app, network)?.rpc_client();
+    let stake_accounts = get_stake_accounts(&rpc_client, pubkey.clone())
+        .await
+        .map_err(|e| e.to_string())?;
+    let price_history = BirdeyePriceHistoryProvider::new(dotenv!("BIRDEYE_API_KEY"), USER_AGENT);
+    let fx_history = FrankfurterProvider::new(USER_AGENT);
+
+    let ledger = tax_ledger(
+        rpc_client.url(),
+        &pubkey,
+        &stake_accounts,
+        &valuation,
+        &price_history,
+        &fx_history,
+        from,
+        to,
+        method,
+        &hidden_mints,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+    export_ledger(&ledger, format).map_err(|e| e.to_string())
+}
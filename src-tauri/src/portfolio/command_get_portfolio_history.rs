@@ -0,0 +1,64 @@
+use {
+    crate::{
+        cache::cached,
+        constants::network::USER_AGENT,
+        model::cache::Cached,
+        portfolio::{current_valuation, load_snapshots},
+        settings::commands::get_fiat_currency,
+        setup::wallet_kit::wallet_kit,
+    },
+    log::info,
+    smbcloud_wallet_core_http::price_data::price_history::BirdeyePriceHistoryProvider,
+    smbcloud_wallet_core_model::models::environment::Environment,
+    smbcloud_wallet_kit::{
+        models::portfolio_history::{CostBasisMethod, HistoryRange, PortfolioHistory},
+        portfolio_history::portfolio_history,
+    },
+    std::time::Duration,
+    tauri::{command, AppHandle},
+};
+
+/// How long a cached history is served before it is computed again.
+const HISTORY_MAX_AGE: Duration = Duration::from_secs(5 * 60);
+
+/// Chart and profit and loss of a wallet in the currency from the settings.
+///
+/// The last computed history is served from the cache and refreshed in the
+/// background.
+#[command]
+pub async fn get_portfolio_history(
+    app: AppHandle,
+    network: Environment,
+    pubkey: String,
+    range: HistoryRange,
+    method: CostBasisMethod,
+) -> Result<Cached<PortfolioHistory>, String> {
+    info!("Getting {:?} portfolio history for {}", range, pubkey);
+    let currency = get_fiat_currency(app.clone()).await;
+    let key = format!(
+        "history:{}:{}:{}:{:?}:{:?}",
+        network, pubkey, currency, range, method
+    );
+    cached(&app, key, HISTORY_MAX_AGE, move |app| async move {
+        let (valuation, usd_rate, hidden_mints) = current_valuation(&app, network, &pubkey).await?;
+        let snapshots = load_snapshots(&app)?;
+        let price_history =
+            BirdeyePriceHistoryProvider::new(dotenv!("BIRDEYE_API_KEY"), USER_AGENT);
+
+        portfolio_history(
+            wallet_kit(&app, network)?.rpc_client().url(),
+            &pubkey,
+            network,
+            &valuation,
+            usd_rate,
+            &snapshots,
+            &price_history,
+            range,
+            method,
+            &hidden_mints,
+        )
+        .await
+        .map_err(|e| e.to_string())
+    })
+    .await
+}
@@ -0,0 +1,60 @@
+use {
+    crate::{
+        constants::store::STORE_PORTFOLIO_SNAPSHOTS, repository,
+        settings::commands::get_fiat_currency, setup::wallet_kit::wallet_kit,
+        tokens::filter_valuation,
+    },
+    log::error,
+    smbcloud_wallet_core_model::models::{environment::Environment, portfolio::PortfolioValuation},
+    smbcloud_wallet_kit::{
+        models::portfolio_history::PortfolioSnapshot,
+        portfolio_history::{record_snapshot, snapshot_from_valuation},
+    },
+    tauri::AppHandle,
+};
+
+pub(crate) mod command_export_tax_ledger;
+pub(crate) mod command_get_portfolio_history;
+
+pub(crate) fn load_snapshots(app: &AppHandle) -> Result<Vec<PortfolioSnapshot>, String> {
+    repository::load_all(app, STORE_PORTFOLIO_SNAPSHOTS)
+}
+
+/// Store a snapshot of `valuation`, at most one per wallet every few minutes.
+pub(crate) fn record(
+    app: &AppHandle,
+    pubkey: &str,
+    network: Environment,
+    valuation: &PortfolioValuation,
+    usd_rate: f64,
+) -> Result<(), String> {
+    let mut snapshots = load_snapshots(app)?;
+    let snapshot = snapshot_from_valuation(pubkey, network, valuation, usd_rate);
+    if record_snapshot(&mut snapshots, snapshot) {
+        repository::save_all(app, STORE_PORTFOLIO_SNAPSHOTS, &snapshots)?;
+    }
+    Ok(())
+}
+
+/// The current wallet value in the currency from the settings, with the units of
+/// that currency one US dollar buys and the hidden tokens left out of it. The
+/// valuation is recorded as a snapshot.
+pub(crate) async fn current_valuation(
+    app: &AppHandle,
+    network: Environment,
+    pubkey: &str,
+) -> Result<(PortfolioValuation, f64, Vec<String>), String> {
+    let currency = get_fiat_currency(app.clone()).await;
+    let kit = wallet_kit(app, network)?;
+    let mut valuation = kit
+        .wallet_balance(pubkey, currency)
+        .await
+        .map_err(|e| e.to_string())?;
+    let hidden_mints = filter_valuation(app, network, pubkey, &mut valuation).await?;
+    let usd_rate = kit.usd_rate(currency).await.map_err(|e| e.to_string())?;
+
+    if let Err(e) = record(app, pubkey, network, &valuation, usd_rate) {
+        error!("Failed to record portfolio snapshot: {}", e);
+    }
+    Ok((valuation, usd_rate, hidden_mints))
+}
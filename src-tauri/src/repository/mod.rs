@@ -0,0 +1,44 @@
+use {
+    crate::constants::store::store,
+    log::warn,
+    serde::{de::DeserializeOwned, Serialize},
+    serde_json::Value,
+    tauri::AppHandle,
+};
+
+/// Load the records stored as a JSON list under `key`.
+///
+/// A missing list is treated as empty and a value that is not a list is an
+/// error. Records that no longer parse are logged and skipped, so one bad
+/// record does not hide the others.
+pub(crate) fn load_all<T: DeserializeOwned>(app: &AppHandle, key: &str) -> Result<Vec<T>, String> {
+    let store = store(app).map_err(|_| "Failed to load store".to_string())?;
+    let values: Vec<Value> = match store.get(key) {
+        Some(value) => {
+            serde_json::from_value(value).map_err(|e| format!("Failed to read {}: {}", key, e))?
+        }
+        None => return Ok(Vec::new()),
+    };
+    let records = values
+        .into_iter()
+        .filter_map(|value| match serde_json::from_value(value) {
+            Ok(record) => Some(record),
+            Err(e) => {
+                warn!("Skipping unreadable record in {}: {}", key, e);
+                None
+            }
+        })
+        .collect();
+    Ok(records)
+}
+
+/// Replace the list stored under `key` and write the store to disk.
+pub(crate) fn save_all<T: Serialize>(
+    app: &AppHandle,
+    key: &str,
+    records: &[T],
+) -> Result<(), String> {
+    let store = store(app).map_err(|_| "Failed to load store".to_string())?;
+    store.set(key, serde_json::json!(records));
+    store.save().map_err(|_| "Failed to save store".to_string())
+}
@@ -0,0 +1,40 @@
+use {
+    crate::settings::{load_custom_networks, update_custom_networks},
+    log::info,
+    smbcloud_wallet_core_model::models::custom_network::CustomNetwork,
+    smbcloud_wallet_kit::rpc_endpoints::verify_network,
+    tauri::{command, AppHandle},
+};
+
+/// Add a network after checking with `getGenesisHash` that its endpoint
+/// serves the expected cluster.
+#[command]
+pub async fn add_custom_network(
+    app: AppHandle,
+    network: CustomNetwork,
+) -> Result<Vec<CustomNetwork>, String> {
+    let duplicate = |networks: &[CustomNetwork]| {
+        networks
+            .iter()
+            .any(|known| known.name == network.name)
+            .then(|| format!("A network named {} already exists", network.name))
+    };
+    if let Some(e) = duplicate(&load_custom_networks(&app)?) {
+        return Err(e);
+    }
+    verify_network(&network).await.map_err(|e| e.to_string())?;
+
+    info!(
+        "Adding custom {} network {}",
+        network.environment, network.name
+    );
+    // Another network of that name may have been added while this one was verified.
+    update_custom_networks(&app, |networks| {
+        if let Some(e) = duplicate(networks) {
+            return Err(e);
+        }
+        networks.push(network.clone());
+        Ok(())
+    })
+    .await
+}
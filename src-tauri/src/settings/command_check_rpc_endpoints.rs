@@ -0,0 +1,14 @@
+use {
+    smbcloud_wallet_core_model::models::{
+        environment::Environment, rpc_endpoint::RpcEndpointStatus,
+    },
+    smbcloud_wallet_kit::rpc_endpoints::RpcEndpoints,
+    tauri::command,
+};
+
+/// Check the RPC endpoints of `network` now, without waiting for the
+/// background check.
+#[command]
+pub async fn check_rpc_endpoints(network: Environment) -> Vec<RpcEndpointStatus> {
+    RpcEndpoints::shared(network).check_health().await
+}
@@ -0,0 +1,11 @@
+use {
+    crate::settings::load_custom_networks,
+    smbcloud_wallet_core_model::models::custom_network::CustomNetwork,
+    tauri::{command, AppHandle},
+};
+
+/// Networks added by the user.
+#[command]
+pub fn get_custom_networks(app: AppHandle) -> Result<Vec<CustomNetwork>, String> {
+    load_custom_networks(&app)
+}
@@ -0,0 +1,13 @@
+use {
+    smbcloud_wallet_core_model::models::{
+        environment::Environment, rpc_endpoint::RpcEndpointStatus,
+    },
+    smbcloud_wallet_kit::rpc_endpoints::RpcEndpoints,
+    tauri::command,
+};
+
+/// Status of the RPC endpoints of `network` from their last health check.
+#[command]
+pub fn get_rpc_endpoints(network: Environment) -> Vec<RpcEndpointStatus> {
+    RpcEndpoints::shared(network).statuses()
+}
@@ -0,0 +1,21 @@
+use {
+    crate::settings::update_custom_networks,
+    log::info,
+    smbcloud_wallet_core_model::models::custom_network::CustomNetwork,
+    tauri::{command, AppHandle},
+};
+
+/// Remove the network named `name`. Calls go back to the built-in endpoints
+/// of its cluster.
+#[command]
+pub async fn remove_custom_network(
+    app: AppHandle,
+    name: String,
+) -> Result<Vec<CustomNetwork>, String> {
+    info!("Removing custom network {}", name);
+    update_custom_networks(&app, |networks| {
+        networks.retain(|network| network.name != name);
+        Ok(())
+    })
+    .await
+}
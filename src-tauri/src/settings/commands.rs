@@ -2,9 +2,10 @@ use {
     crate::{
         constants::store::store,
         model::settings_debug::{AirdropEnvironment, XlpEnvironment},
+        wallet::subscriptions::watch_active_wallet,
     },
     log::{error, info},
-    smbcloud_wallet_core_model::models::environment::Environment,
+    smbcloud_wallet_core_model::models::{currency::FiatCurrency, environment::Environment},
     tauri::{command, AppHandle},
     tsync::tsync,
 };
@@ -15,6 +16,8 @@ const KEY_NETWORK_ENVIRONMENT: &str = "network_environment";
 const KEY_AIRDROP_ENVIRONMENT: &str = "airdrop_environment";
 #[tsync]
 const KEY_XLP_ENVIRONMENT: &str = "xlp_environment";
+#[tsync]
+const KEY_FIAT_CURRENCY: &str = "fiat_currency";
 
 #[command]
 pub async fn get_network_environment(app: AppHandle) -> Environment {
@@ -48,6 +51,9 @@ pub async fn set_network_environment(
     match store_result {
         Ok(store) => {
             store.set(KEY_NETWORK_ENVIRONMENT, environment.to_string());
+            if let Err(e) = watch_active_wallet(&app).await {
+                error!("Failed to follow the active wallet: {}", e);
+            }
             Ok(environment)
         }
         Err(err) => {
@@ -140,3 +146,49 @@ pub async fn set_xlp_environment(
         }
     }
 }
+
+/// Get the currency balances and prices are shown in.
+/// Or set it to USD.
+#[command]
+pub async fn get_fiat_currency(app: AppHandle) -> FiatCurrency {
+    let store_result = store(&app);
+    match store_result {
+        Ok(store) => {
+            if let Some(currency) = store.get(KEY_FIAT_CURRENCY) {
+                info!("Found existing fiat currency: {}", currency);
+                return FiatCurrency::from_value(currency);
+            }
+            info!("No existing fiat currency found. Setting to USD.");
+            store.set(KEY_FIAT_CURRENCY, FiatCurrency::USD.code());
+            FiatCurrency::USD
+        }
+        Err(err) => {
+            error!("Failed to get fiat currency: {}. Setting to USD.", err);
+            FiatCurrency::USD
+        }
+    }
+}
+
+#[command]
+pub async fn set_fiat_currency(
+    app: AppHandle,
+    currency: FiatCurrency,
+) -> Result<FiatCurrency, String> {
+    let store_result = store(&app);
+    match store_result {
+        Ok(store) => {
+            store.set(KEY_FIAT_CURRENCY, currency.code());
+            Ok(currency)
+        }
+        Err(err) => {
+            error!("Failed to set fiat currency: {}", err);
+            Err("Failed to set fiat currency.".to_string())
+        }
+    }
+}
+
+/// Every currency the wallet can show balances in.
+#[command]
+pub fn get_fiat_currencies() -> Vec<FiatCurrency> {
+    FiatCurrency::ALL.to_vec()
+}
@@ -1 +1,74 @@
+use {
+    crate::{constants::store::STORE_CUSTOM_NETWORKS, repository},
+    log::{error, warn},
+    smbcloud_wallet_core_model::models::custom_network::CustomNetwork,
+    smbcloud_wallet_kit::rpc_endpoints::{verify_network, RpcEndpoints},
+    tauri::{App, AppHandle, Manager},
+    tokio::sync::Mutex,
+};
+
+pub(crate) mod command_add_custom_network;
+pub(crate) mod command_check_rpc_endpoints;
+pub(crate) mod command_get_custom_networks;
+pub(crate) mod command_get_rpc_endpoints;
+pub(crate) mod command_remove_custom_network;
 pub(crate) mod commands;
+pub(crate) mod rpc_health;
+
+/// Serializes changes to the custom networks and routing calls to them, so a
+/// slow verification never applies an older list over a newer one.
+#[derive(Default)]
+pub(crate) struct CustomNetworksLock(Mutex<()>);
+
+pub(crate) fn load_custom_networks(app: &AppHandle) -> Result<Vec<CustomNetwork>, String> {
+    repository::load_all(app, STORE_CUSTOM_NETWORKS)
+}
+
+/// Apply `change` to the saved custom networks, save them and route calls to
+/// the endpoints that still serve their cluster.
+pub(crate) async fn update_custom_networks(
+    app: &AppHandle,
+    change: impl FnOnce(&mut Vec<CustomNetwork>) -> Result<(), String>,
+) -> Result<Vec<CustomNetwork>, String> {
+    let lock = app.state::<CustomNetworksLock>();
+    let _guard = lock.0.lock().await;
+    let mut networks = load_custom_networks(app)?;
+    change(&mut networks)?;
+    repository::save_all(app, STORE_CUSTOM_NETWORKS, &networks)?;
+    RpcEndpoints::apply_custom_networks(&verified_networks(&networks).await);
+    Ok(networks)
+}
+
+/// Route calls to the endpoints of the saved custom networks once they are
+/// verified again. Until then calls go to the built-in endpoints.
+pub(crate) fn setup_custom_networks(app: &App) -> Result<(), Box<dyn std::error::Error>> {
+    app.manage(CustomNetworksLock::default());
+    let app = app.handle().clone();
+    tauri::async_runtime::spawn(async move {
+        let lock = app.state::<CustomNetworksLock>();
+        let _guard = lock.0.lock().await;
+        match load_custom_networks(&app) {
+            Ok(networks) => {
+                RpcEndpoints::apply_custom_networks(&verified_networks(&networks).await)
+            }
+            Err(e) => error!("Failed to load custom networks: {}", e),
+        }
+    });
+    Ok(())
+}
+
+/// The networks whose endpoint still serves the cluster it was added for.
+///
+/// An endpoint can be pointed at another cluster after it was added, and a
+/// reset local validator has a new genesis hash. The others stay saved and are
+/// checked again at the next start.
+async fn verified_networks(networks: &[CustomNetwork]) -> Vec<CustomNetwork> {
+    let mut verified = Vec::with_capacity(networks.len());
+    for network in networks {
+        match verify_network(network).await {
+            Ok(()) => verified.push(network.clone()),
+            Err(e) => warn!("Not using custom network {}: {}", network.name, e),
+        }
+    }
+    verified
+}
@@ -0,0 +1,26 @@
+use {
+    crate::settings::commands::get_network_environment,
+    log::{debug, error},
+    smbcloud_wallet_kit::rpc_endpoints::{RpcEndpoints, HEALTH_CHECK_INTERVAL},
+    tauri::{AppHandle, Emitter},
+    tsync::tsync,
+};
+
+#[tsync]
+pub const RPC_ENDPOINTS_UPDATED_EVENT: &str = "rpc_endpoints_updated";
+
+/// Check the RPC endpoints of the selected network in the background for as
+/// long as the app runs, emitting their status after every check.
+pub(crate) fn spawn_rpc_health_checks(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let network = get_network_environment(app.clone()).await;
+            let statuses = RpcEndpoints::shared(network).check_health().await;
+            debug!("Checked {} RPC endpoints of {}", statuses.len(), network);
+            if let Err(e) = app.emit(RPC_ENDPOINTS_UPDATED_EVENT, &statuses) {
+                error!("Failed to emit event: {}", e);
+            }
+            tokio::time::sleep(HEALTH_CHECK_INTERVAL).await;
+        }
+    });
+}
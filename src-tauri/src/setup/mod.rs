@@ -1,13 +1,23 @@
 use {
-    crate::setup::{client::setup_client, store::setup_store},
+    crate::{
+        address_book::setup_address_book,
+        cache::setup_cache,
+        journal::setup_journal,
+        settings::{rpc_health::spawn_rpc_health_checks, setup_custom_networks},
+        setup::{client::setup_client, store::setup_store, wallet_kit::setup_wallet_kits},
+        solana_pay::{deep_link::setup_deep_link, invoice_watcher::setup_invoice_watchers},
+        tokens::setup_tokens,
+        wallet::subscriptions::setup_wallet_subscriptions,
+    },
     log::info,
     tauri::{App, Manager},
 };
 
 mod client;
+pub(crate) mod command_start_server;
 pub(crate) mod commands;
 mod store;
-pub(crate) mod command_start_server;
+pub(crate) mod wallet_kit;
 
 pub(crate) fn setup(app: &App) -> Result<(), Box<dyn std::error::Error>> {
     let app_data_dir = app
@@ -18,5 +28,15 @@ pub(crate) fn setup(app: &App) -> Result<(), Box<dyn std::error::Error>> {
     info!("App local data dir: {:?}", app_data_dir);
     setup_store(app)?;
     setup_client(app)?;
+    setup_wallet_kits(app)?;
+    setup_cache(app)?;
+    setup_tokens(app)?;
+    setup_address_book(app)?;
+    setup_journal(app)?;
+    setup_deep_link(app)?;
+    setup_custom_networks(app)?;
+    spawn_rpc_health_checks(app.handle().clone());
+    setup_wallet_subscriptions(app)?;
+    setup_invoice_watchers(app)?;
     Ok(())
 }
@@ -0,0 +1,45 @@
+use {
+    crate::constants::network::USER_AGENT,
+    log::info,
+    smbcloud_wallet_core_model::models::{environment::Environment, wallet_kit::WalletKitConfig},
+    smbcloud_wallet_kit::kit::WalletKit,
+    std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    },
+    tauri::{App, AppHandle, Manager},
+};
+
+/// One `WalletKit` per network, built on first use and shared by every command.
+#[derive(Default)]
+pub(crate) struct WalletKits {
+    kits: Mutex<HashMap<Environment, Arc<WalletKit>>>,
+}
+
+pub fn setup_wallet_kits(app: &App) -> Result<(), Box<dyn std::error::Error>> {
+    app.manage(WalletKits::default());
+    Ok(())
+}
+
+/// The shared `WalletKit` of `network`.
+pub(crate) fn wallet_kit(app: &AppHandle, network: Environment) -> Result<Arc<WalletKit>, String> {
+    let kits = app.state::<WalletKits>();
+    let mut kits = kits
+        .kits
+        .lock()
+        .map_err(|e| format!("Failed to lock wallet kits: {}", e))?;
+    if let Some(kit) = kits.get(&network) {
+        return Ok(kit.clone());
+    }
+
+    info!("Creating wallet kit for {}", network);
+    let mut config = WalletKitConfig::new(network, USER_AGENT);
+    config.birdeye_api_key = Some(dotenv!("BIRDEYE_API_KEY").to_string());
+    // Transfers are confirmed by the wallet subscriptions instead.
+    config.confirm_transfers = false;
+    let kit = WalletKit::new(config)
+        .map(Arc::new)
+        .map_err(|e| format!("Failed to create wallet kit: {}", e))?;
+    kits.insert(network, kit.clone());
+    Ok(kit)
+}
@@ -0,0 +1,34 @@
+use {
+    crate::wallet::keypair::load_keypair,
+    log::info,
+    smbcloud_wallet_core_model::models::environment::Environment,
+    smbcloud_wallet_kit::{
+        rpc_endpoints::RpcEndpoints,
+        solana_pay::transaction_request::approve_transaction_request as kit_approve_transaction_request,
+    },
+    tauri::{command, AppHandle},
+};
+
+/// Sign and send a merchant transaction the user has reviewed.
+#[command]
+pub async fn approve_transaction_request(
+    app: AppHandle,
+    network: Environment,
+    from: String,
+    transaction: String,
+) -> Result<String, String> {
+    info!("Approving Solana Pay transaction request from {}", from);
+
+    let keypair = load_keypair(&app, &from)?;
+
+    let tx_signature = kit_approve_transaction_request(
+        RpcEndpoints::shared(network).active_url(),
+        keypair,
+        transaction,
+    )
+    .await
+    .map_err(|e| format!("Failed to approve transaction request: {}", e))?;
+
+    info!("Solana Pay transaction sent successfully: {}", tx_signature);
+    Ok(tx_signature)
+}
@@ -0,0 +1,31 @@
+use {
+    crate::solana_pay::invoice_watcher::{save_invoice, spawn_invoice_watcher},
+    log::info,
+    smbcloud_wallet_core_model::models::environment::Environment,
+    smbcloud_wallet_kit::{
+        models::solana_pay::{Invoice, TransferRequest},
+        solana_pay::invoice::create_invoice as kit_create_invoice,
+    },
+    tauri::{command, AppHandle},
+};
+
+/// Invoices expire after 15 minutes unless the frontend asks otherwise.
+const DEFAULT_INVOICE_EXPIRES_IN: i64 = 15 * 60;
+
+/// Create an invoice, store it and start watching the chain for its payment.
+#[command]
+pub fn create_invoice(
+    app: AppHandle,
+    network: Environment,
+    request: TransferRequest,
+    expires_in: Option<i64>,
+) -> Result<Invoice, String> {
+    let expires_in = expires_in.unwrap_or(DEFAULT_INVOICE_EXPIRES_IN);
+    let invoice = kit_create_invoice(request, network, expires_in)
+        .map_err(|e| format!("Failed to create invoice: {}", e))?;
+    info!("Created invoice {}", invoice.reference);
+
+    save_invoice(&app, &invoice)?;
+    spawn_invoice_watcher(app, invoice.clone());
+    Ok(invoice)
+}
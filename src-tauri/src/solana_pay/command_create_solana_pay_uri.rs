@@ -0,0 +1,11 @@
+use {
+    smbcloud_wallet_kit::{
+        models::solana_pay::TransferRequest, solana_pay::transfer_request::encode_transfer_request,
+    },
+    tauri::command,
+};
+
+#[command]
+pub fn create_solana_pay_uri(request: TransferRequest) -> String {
+    encode_transfer_request(&request)
+}
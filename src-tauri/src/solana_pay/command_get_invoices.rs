@@ -0,0 +1,12 @@
+use {
+    crate::solana_pay::invoice_watcher::load_invoices,
+    log::info,
+    smbcloud_wallet_kit::models::solana_pay::Invoice,
+    tauri::{command, AppHandle},
+};
+
+#[command]
+pub fn get_invoices(app: AppHandle) -> Result<Vec<Invoice>, String> {
+    info!("Getting all invoices");
+    load_invoices(&app)
+}
@@ -0,0 +1,20 @@
+use {
+    log::info,
+    smbcloud_wallet_kit::{
+        models::solana_pay::{TransactionRequest, TransactionRequestPreview},
+        solana_pay::transaction_request::{fetch_transaction_request, HttpMerchantClient},
+    },
+    tauri::command,
+};
+
+/// Ask the merchant for the transaction to sign and return a preview for review.
+#[command]
+pub async fn get_transaction_request(
+    request: TransactionRequest,
+    account: String,
+) -> Result<TransactionRequestPreview, String> {
+    info!("Fetching Solana Pay transaction request {}", request.link);
+    fetch_transaction_request(&HttpMerchantClient::default(), request, account)
+        .await
+        .map_err(|e| format!("Failed to fetch transaction request: {}", e))
+}
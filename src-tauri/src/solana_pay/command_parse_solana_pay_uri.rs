@@ -0,0 +1,15 @@
+use {
+    log::debug,
+    smbcloud_wallet_kit::{
+        models::solana_pay::SolanaPayRequest,
+        solana_pay::parse_solana_pay_uri as kit_parse_solana_pay_uri,
+    },
+    tauri::command,
+};
+
+/// Parse a scanned QR code or pasted link into a Solana Pay transfer or transaction request.
+#[command]
+pub fn parse_solana_pay_uri(uri: String) -> Result<SolanaPayRequest, String> {
+    debug!("Parsing Solana Pay URI {}", uri);
+    kit_parse_solana_pay_uri(&uri).map_err(|e| e.to_string())
+}
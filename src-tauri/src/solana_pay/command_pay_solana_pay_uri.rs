@@ -0,0 +1,35 @@
+use {
+    crate::wallet::keypair::load_keypair,
+    log::info,
+    smbcloud_wallet_core_model::models::environment::Environment,
+    smbcloud_wallet_kit::{
+        rpc_endpoints::RpcEndpoints, solana_pay::pay::pay_solana_pay_uri as kit_pay_solana_pay_uri,
+    },
+    tauri::{command, AppHandle},
+};
+
+#[command]
+pub async fn pay_solana_pay_uri(
+    app: AppHandle,
+    network: Environment,
+    from: String,
+    uri: String,
+    amount: Option<f64>,
+) -> Result<String, String> {
+    info!("Paying Solana Pay request {} from {}", uri, from);
+
+    let keypair = load_keypair(&app, &from)?;
+
+    let tx_signature = kit_pay_solana_pay_uri(
+        RpcEndpoints::shared(network).active_url(),
+        keypair,
+        from,
+        uri,
+        amount,
+    )
+    .await
+    .map_err(|e| format!("Failed to pay Solana Pay request: {}", e))?;
+
+    info!("Solana Pay request paid successfully: {}", tx_signature);
+    Ok(tx_signature)
+}
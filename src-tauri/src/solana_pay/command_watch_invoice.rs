@@ -0,0 +1,15 @@
+use {
+    crate::solana_pay::invoice_watcher::{load_invoices, spawn_invoice_watcher},
+    tauri::{command, AppHandle},
+};
+
+/// Watch a stored invoice, unless it is watched already.
+#[command]
+pub fn watch_invoice(app: AppHandle, reference: String) -> Result<(), String> {
+    let invoice = load_invoices(&app)?
+        .into_iter()
+        .find(|invoice| invoice.reference == reference)
+        .ok_or_else(|| "Invoice not found".to_string())?;
+    spawn_invoice_watcher(app, invoice);
+    Ok(())
+}
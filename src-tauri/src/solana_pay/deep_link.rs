@@ -0,0 +1,30 @@
+use {
+    log::{debug, error},
+    smbcloud_wallet_kit::solana_pay::parse_solana_pay_uri,
+    tauri::{App, Emitter},
+    tauri_plugin_deep_link::DeepLinkExt,
+    tsync::tsync,
+};
+
+#[tsync]
+pub const SOLANA_PAY_REQUEST_EVENT: &str = "solana_pay_request";
+
+/// Route `notwallet://` deep links carrying a Solana Pay transfer or transaction
+/// request to the frontend, which shows the payment confirmation screen.
+pub fn setup_deep_link(app: &App) -> Result<(), Box<dyn std::error::Error>> {
+    let handle = app.handle().clone();
+    app.deep_link().on_open_url(move |event| {
+        for url in event.urls() {
+            debug!("Received deep link: {}", url);
+            match parse_solana_pay_uri(url.as_str()) {
+                Ok(request) => {
+                    if let Err(e) = handle.emit(SOLANA_PAY_REQUEST_EVENT, request) {
+                        error!("Failed to emit event: {}", e);
+                    }
+                }
+                Err(e) => debug!("Deep link is not a Solana Pay request: {}", e),
+            }
+        }
+    });
+    Ok(())
+}
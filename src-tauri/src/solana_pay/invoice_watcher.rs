@@ -0,0 +1,127 @@
+use {
+    crate::{
+        constants::store::STORE_INVOICES,
+        repository::{load_all, save_all},
+    },
+    chrono::Utc,
+    log::{error, info},
+    smbcloud_wallet_kit::{
+        models::solana_pay::{Invoice, InvoiceStatus},
+        rpc_endpoints::RpcEndpoints,
+        solana_pay::invoice::watch_invoice,
+    },
+    std::{collections::HashSet, sync::Mutex, time::Duration},
+    tauri::{App, AppHandle, Emitter, Manager},
+    tsync::tsync,
+};
+
+#[tsync]
+pub const INVOICE_UPDATED_EVENT: &str = "invoice_updated";
+
+/// How often the chain is polled for a payment.
+const INVOICE_POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// References of the invoices being watched, so each has one watcher.
+#[derive(Default)]
+pub(crate) struct InvoiceWatchers {
+    references: Mutex<HashSet<String>>,
+}
+
+impl InvoiceWatchers {
+    /// Whether a watcher of `reference` may start, i.e. none is running.
+    fn start(&self, reference: &str) -> bool {
+        match self.references.lock() {
+            Ok(mut references) => references.insert(reference.to_string()),
+            Err(_) => false,
+        }
+    }
+
+    fn finish(&self, reference: &str) {
+        if let Ok(mut references) = self.references.lock() {
+            references.remove(reference);
+        }
+    }
+}
+
+/// Serializes the updates of the stored invoices, which every watcher and
+/// `create_invoice` load, change and save back.
+#[derive(Default)]
+pub(crate) struct InvoicesLock(Mutex<()>);
+
+/// Releases the reference of a watcher however its task ends.
+struct WatcherGuard {
+    app: AppHandle,
+    reference: String,
+}
+
+impl Drop for WatcherGuard {
+    fn drop(&mut self) {
+        self.app.state::<InvoiceWatchers>().finish(&self.reference);
+    }
+}
+
+/// Resume watching the stored invoices that can still be paid. Invoices that
+/// expired while the app was closed get one last check.
+pub(crate) fn setup_invoice_watchers(app: &App) -> Result<(), Box<dyn std::error::Error>> {
+    app.manage(InvoiceWatchers::default());
+    app.manage(InvoicesLock::default());
+    let now = Utc::now().timestamp();
+    for invoice in load_invoices(app.handle())? {
+        if invoice.status == InvoiceStatus::Pending || !invoice.is_settled(now) {
+            spawn_invoice_watcher(app.handle().clone(), invoice);
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn load_invoices(app: &AppHandle) -> Result<Vec<Invoice>, String> {
+    load_all(app, STORE_INVOICES)
+}
+
+/// Insert or replace the invoice with the same reference.
+pub(crate) fn save_invoice(app: &AppHandle, invoice: &Invoice) -> Result<(), String> {
+    let lock = app.state::<InvoicesLock>();
+    let _guard = lock
+        .0
+        .lock()
+        .map_err(|e| format!("Failed to lock invoices: {}", e))?;
+    let mut invoices = load_invoices(app)?;
+    match invoices
+        .iter_mut()
+        .find(|existing| existing.reference == invoice.reference)
+    {
+        Some(existing) => *existing = invoice.clone(),
+        None => invoices.push(invoice.clone()),
+    }
+    save_all(app, STORE_INVOICES, &invoices)
+}
+
+/// Watch the invoice in the background, persisting and emitting every update.
+/// Does nothing if the invoice is watched already.
+pub(crate) fn spawn_invoice_watcher(app: AppHandle, invoice: Invoice) {
+    let reference = invoice.reference.clone();
+    if !app.state::<InvoiceWatchers>().start(&reference) {
+        return;
+    }
+    tauri::async_runtime::spawn(async move {
+        let _guard = WatcherGuard {
+            app: app.clone(),
+            reference: reference.clone(),
+        };
+        info!("Watching invoice {}", reference);
+        let rpc_url = RpcEndpoints::shared(invoice.network).active_url();
+        let result = watch_invoice(rpc_url, invoice, INVOICE_POLL_INTERVAL, |updated| {
+            if let Err(e) = save_invoice(&app, updated) {
+                error!("Failed to save invoice: {}", e);
+            }
+            if let Err(e) = app.emit(INVOICE_UPDATED_EVENT, updated) {
+                error!("Failed to emit event: {}", e);
+            }
+        })
+        .await;
+        match result {
+            Ok(invoice) => info!("Invoice {} settled as {:?}", reference, invoice.status),
+            Err(e) => error!("Stopped watching invoice {}: {}", reference, e),
+        }
+    });
+}
@@ -0,0 +1,10 @@
+pub(crate) mod command_approve_transaction_request;
+pub(crate) mod command_create_invoice;
+pub(crate) mod command_create_solana_pay_uri;
+pub(crate) mod command_get_invoices;
+pub(crate) mod command_get_transaction_request;
+pub(crate) mod command_parse_solana_pay_uri;
+pub(crate) mod command_pay_solana_pay_uri;
+pub(crate) mod command_watch_invoice;
+pub(crate) mod deep_link;
+pub(crate) mod invoice_watcher;
@@ -0,0 +1,23 @@
+use {
+    crate::{setup::wallet_kit::wallet_kit, wallet::keypair::load_keypair},
+    log::info,
+    smbcloud_wallet_core_model::models::{environment::Environment, stake::StakeOperation},
+    smbcloud_wallet_kit::staking::create_stake_account as kit_create_stake_account,
+    tauri::{command, AppHandle},
+};
+
+#[command]
+pub async fn create_stake_account(
+    app: AppHandle,
+    network: Environment,
+    from: String,
+    amount: f64,
+    vote_account: String,
+) -> Result<StakeOperation, String> {
+    info!("Staking {} SOL from {} with {}", amount, from, vote_account);
+    let keypair = load_keypair(&app, &from)?;
+    let kit = wallet_kit(&app, network)?;
+    kit_create_stake_account(&kit.rpc_client(), keypair, amount, vote_account)
+        .await
+        .map_err(|e| format!("Failed to create stake account: {}", e))
+}
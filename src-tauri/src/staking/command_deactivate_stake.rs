@@ -0,0 +1,22 @@
+use {
+    crate::{setup::wallet_kit::wallet_kit, wallet::keypair::load_keypair},
+    log::info,
+    smbcloud_wallet_core_model::models::environment::Environment,
+    smbcloud_wallet_kit::staking::deactivate_stake as kit_deactivate_stake,
+    tauri::{command, AppHandle},
+};
+
+#[command]
+pub async fn deactivate_stake(
+    app: AppHandle,
+    network: Environment,
+    from: String,
+    stake_account: String,
+) -> Result<String, String> {
+    info!("Deactivating {}", stake_account);
+    let keypair = load_keypair(&app, &from)?;
+    let kit = wallet_kit(&app, network)?;
+    kit_deactivate_stake(&kit.rpc_client(), keypair, stake_account)
+        .await
+        .map_err(|e| format!("Failed to deactivate stake: {}", e))
+}
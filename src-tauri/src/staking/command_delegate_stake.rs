@@ -0,0 +1,23 @@
+use {
+    crate::{setup::wallet_kit::wallet_kit, wallet::keypair::load_keypair},
+    log::info,
+    smbcloud_wallet_core_model::models::environment::Environment,
+    smbcloud_wallet_kit::staking::delegate_stake as kit_delegate_stake,
+    tauri::{command, AppHandle},
+};
+
+#[command]
+pub async fn delegate_stake(
+    app: AppHandle,
+    network: Environment,
+    from: String,
+    stake_account: String,
+    vote_account: String,
+) -> Result<String, String> {
+    info!("Delegating {} to {}", stake_account, vote_account);
+    let keypair = load_keypair(&app, &from)?;
+    let kit = wallet_kit(&app, network)?;
+    kit_delegate_stake(&kit.rpc_client(), keypair, stake_account, vote_account)
+        .await
+        .map_err(|e| format!("Failed to delegate stake: {}", e))
+}
@@ -0,0 +1,27 @@
+use {
+    crate::setup::wallet_kit::wallet_kit,
+    log::info,
+    smbcloud_wallet_core_model::models::environment::Environment,
+    smbcloud_wallet_kit::{
+        liquid_staking::quote_liquid_stake, models::liquid_staking::LiquidStakeQuote,
+    },
+    tauri::{command, AppHandle},
+};
+
+/// Quote a deposit into the stake pool, compared with the Jupiter swap route.
+#[command]
+pub async fn get_liquid_stake_quote(
+    app: AppHandle,
+    network: Environment,
+    pool: String,
+    amount: f64,
+) -> Result<LiquidStakeQuote, String> {
+    info!(
+        "Getting liquid stake quote for {} SOL into {}",
+        amount, pool
+    );
+    let kit = wallet_kit(&app, network)?;
+    quote_liquid_stake(&kit.rpc_client(), pool, amount)
+        .await
+        .map_err(|e| format!("Failed to get liquid stake quote: {}", e))
+}
@@ -0,0 +1,26 @@
+use {
+    crate::setup::wallet_kit::wallet_kit,
+    log::info,
+    smbcloud_wallet_core_model::models::environment::Environment,
+    smbcloud_wallet_kit::{
+        liquid_staking::quote_liquid_unstake, models::liquid_staking::LiquidUnstakeQuote,
+    },
+    tauri::{command, AppHandle},
+};
+
+#[command]
+pub async fn get_liquid_unstake_quote(
+    app: AppHandle,
+    network: Environment,
+    pool: String,
+    pool_tokens: f64,
+) -> Result<LiquidUnstakeQuote, String> {
+    info!(
+        "Getting liquid unstake quote for {} pool tokens of {}",
+        pool_tokens, pool
+    );
+    let kit = wallet_kit(&app, network)?;
+    quote_liquid_unstake(&kit.rpc_client(), pool, pool_tokens)
+        .await
+        .map_err(|e| format!("Failed to get liquid unstake quote: {}", e))
+}
@@ -0,0 +1,20 @@
+use {
+    crate::setup::wallet_kit::wallet_kit,
+    log::info,
+    smbcloud_wallet_core_model::models::{environment::Environment, stake::StakeAccount},
+    smbcloud_wallet_kit::staking::get_stake_accounts as kit_get_stake_accounts,
+    tauri::{command, AppHandle},
+};
+
+#[command]
+pub async fn get_stake_accounts(
+    app: AppHandle,
+    network: Environment,
+    pubkey: String,
+) -> Result<Vec<StakeAccount>, String> {
+    info!("Getting stake accounts of {}", pubkey);
+    let kit = wallet_kit(&app, network)?;
+    kit_get_stake_accounts(&kit.rpc_client(), pubkey)
+        .await
+        .map_err(|e| format!("Failed to get stake accounts: {}", e))
+}
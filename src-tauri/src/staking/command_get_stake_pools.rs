@@ -0,0 +1,22 @@
+use {
+    crate::setup::wallet_kit::wallet_kit,
+    log::info,
+    smbcloud_wallet_core_model::models::environment::Environment,
+    smbcloud_wallet_kit::{
+        liquid_staking::get_stake_pools as kit_get_stake_pools,
+        models::liquid_staking::StakePoolInfo,
+    },
+    tauri::{command, AppHandle},
+};
+
+#[command]
+pub async fn get_stake_pools(
+    app: AppHandle,
+    network: Environment,
+) -> Result<Vec<StakePoolInfo>, String> {
+    info!("Getting stake pools on {}", network);
+    let kit = wallet_kit(&app, network)?;
+    kit_get_stake_pools(&kit.rpc_client())
+        .await
+        .map_err(|e| format!("Failed to get stake pools: {}", e))
+}
@@ -0,0 +1,21 @@
+use {
+    crate::setup::wallet_kit::wallet_kit,
+    log::info,
+    smbcloud_wallet_core_model::models::{environment::Environment, stake::StakeReward},
+    smbcloud_wallet_kit::staking::get_stake_rewards as kit_get_stake_rewards,
+    tauri::{command, AppHandle},
+};
+
+#[command]
+pub async fn get_stake_rewards(
+    app: AppHandle,
+    network: Environment,
+    stake_accounts: Vec<String>,
+    epoch: Option<u64>,
+) -> Result<Vec<StakeReward>, String> {
+    info!("Getting rewards of {} stake accounts", stake_accounts.len());
+    let kit = wallet_kit(&app, network)?;
+    kit_get_stake_rewards(&kit.rpc_client(), stake_accounts, epoch)
+        .await
+        .map_err(|e| format!("Failed to get stake rewards: {}", e))
+}
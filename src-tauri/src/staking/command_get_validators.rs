@@ -0,0 +1,19 @@
+use {
+    crate::setup::wallet_kit::wallet_kit,
+    log::info,
+    smbcloud_wallet_core_model::models::{environment::Environment, stake::Validator},
+    smbcloud_wallet_kit::staking::get_validators as kit_get_validators,
+    tauri::{command, AppHandle},
+};
+
+#[command]
+pub async fn get_validators(
+    app: AppHandle,
+    network: Environment,
+) -> Result<Vec<Validator>, String> {
+    info!("Getting validators on {}", network);
+    let kit = wallet_kit(&app, network)?;
+    kit_get_validators(&kit.rpc_client())
+        .await
+        .map_err(|e| format!("Failed to get validators: {}", e))
+}
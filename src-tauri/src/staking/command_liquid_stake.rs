@@ -0,0 +1,24 @@
+use {
+    crate::{setup::wallet_kit::wallet_kit, wallet::keypair::load_keypair},
+    log::info,
+    smbcloud_wallet_core_model::models::environment::Environment,
+    smbcloud_wallet_kit::liquid_staking::deposit_sol,
+    tauri::{command, AppHandle},
+};
+
+/// Deposit SOL directly into the stake pool.
+#[command]
+pub async fn liquid_stake(
+    app: AppHandle,
+    network: Environment,
+    from: String,
+    pool: String,
+    amount: f64,
+) -> Result<String, String> {
+    info!("Depositing {} SOL from {} into {}", amount, from, pool);
+    let keypair = load_keypair(&app, &from)?;
+    let kit = wallet_kit(&app, network)?;
+    deposit_sol(&kit.rpc_client(), keypair, pool, amount)
+        .await
+        .map_err(|e| format!("Failed to deposit into stake pool: {}", e))
+}
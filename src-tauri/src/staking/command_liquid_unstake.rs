@@ -0,0 +1,27 @@
+use {
+    crate::{setup::wallet_kit::wallet_kit, wallet::keypair::load_keypair},
+    log::info,
+    smbcloud_wallet_core_model::models::environment::Environment,
+    smbcloud_wallet_kit::liquid_staking::withdraw_sol,
+    tauri::{command, AppHandle},
+};
+
+/// Withdraw SOL directly from the stake pool reserve.
+#[command]
+pub async fn liquid_unstake(
+    app: AppHandle,
+    network: Environment,
+    from: String,
+    pool: String,
+    pool_tokens: f64,
+) -> Result<String, String> {
+    info!(
+        "Withdrawing {} pool tokens of {} to {}",
+        pool_tokens, pool, from
+    );
+    let keypair = load_keypair(&app, &from)?;
+    let kit = wallet_kit(&app, network)?;
+    withdraw_sol(&kit.rpc_client(), keypair, pool, pool_tokens)
+        .await
+        .map_err(|e| format!("Failed to withdraw from stake pool: {}", e))
+}
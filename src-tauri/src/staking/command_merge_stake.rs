@@ -0,0 +1,23 @@
+use {
+    crate::{setup::wallet_kit::wallet_kit, wallet::keypair::load_keypair},
+    log::info,
+    smbcloud_wallet_core_model::models::environment::Environment,
+    smbcloud_wallet_kit::staking::merge_stake as kit_merge_stake,
+    tauri::{command, AppHandle},
+};
+
+#[command]
+pub async fn merge_stake(
+    app: AppHandle,
+    network: Environment,
+    from: String,
+    destination: String,
+    source: String,
+) -> Result<String, String> {
+    info!("Merging {} into {}", source, destination);
+    let keypair = load_keypair(&app, &from)?;
+    let kit = wallet_kit(&app, network)?;
+    kit_merge_stake(&kit.rpc_client(), keypair, destination, source)
+        .await
+        .map_err(|e| format!("Failed to merge stake: {}", e))
+}
@@ -0,0 +1,23 @@
+use {
+    crate::{setup::wallet_kit::wallet_kit, wallet::keypair::load_keypair},
+    log::info,
+    smbcloud_wallet_core_model::models::{environment::Environment, stake::StakeOperation},
+    smbcloud_wallet_kit::staking::split_stake as kit_split_stake,
+    tauri::{command, AppHandle},
+};
+
+#[command]
+pub async fn split_stake(
+    app: AppHandle,
+    network: Environment,
+    from: String,
+    stake_account: String,
+    amount: f64,
+) -> Result<StakeOperation, String> {
+    info!("Splitting {} SOL from {}", amount, stake_account);
+    let keypair = load_keypair(&app, &from)?;
+    let kit = wallet_kit(&app, network)?;
+    kit_split_stake(&kit.rpc_client(), keypair, stake_account, amount)
+        .await
+        .map_err(|e| format!("Failed to split stake: {}", e))
+}
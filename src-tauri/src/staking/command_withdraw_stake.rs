@@ -0,0 +1,24 @@
+use {
+    crate::{setup::wallet_kit::wallet_kit, wallet::keypair::load_keypair},
+    log::info,
+    smbcloud_wallet_core_model::models::environment::Environment,
+    smbcloud_wallet_kit::staking::withdraw_stake as kit_withdraw_stake,
+    tauri::{command, AppHandle},
+};
+
+/// Withdraw from an inactive stake account. Without an amount the account is closed.
+#[command]
+pub async fn withdraw_stake(
+    app: AppHandle,
+    network: Environment,
+    from: String,
+    stake_account: String,
+    amount: Option<f64>,
+) -> Result<String, String> {
+    info!("Withdrawing {:?} SOL from {}", amount, stake_account);
+    let keypair = load_keypair(&app, &from)?;
+    let kit = wallet_kit(&app, network)?;
+    kit_withdraw_stake(&kit.rpc_client(), keypair, stake_account, amount)
+        .await
+        .map_err(|e| format!("Failed to withdraw stake: {}", e))
+}
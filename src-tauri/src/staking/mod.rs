@@ -0,0 +1,14 @@
+pub(crate) mod command_create_stake_account;
+pub(crate) mod command_deactivate_stake;
+pub(crate) mod command_delegate_stake;
+pub(crate) mod command_get_liquid_stake_quote;
+pub(crate) mod command_get_liquid_unstake_quote;
+pub(crate) mod command_get_stake_accounts;
+pub(crate) mod command_get_stake_pools;
+pub(crate) mod command_get_stake_rewards;
+pub(crate) mod command_get_validators;
+pub(crate) mod command_liquid_stake;
+pub(crate) mod command_liquid_unstake;
+pub(crate) mod command_merge_stake;
+pub(crate) mod command_split_stake;
+pub(crate) mod command_withdraw_stake;
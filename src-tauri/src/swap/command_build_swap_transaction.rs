@@ -1,15 +1,17 @@
 use {
-    smbcloud_wallet_core_network::model::ErrorResponse,
-    smbcloud_wallet_kit::{
-        models::swap::{SwapTransactionPayload, SwapTransactionResponse},
-        swap::build_swap_transaction as build_swap_tx,
-    },
-    tauri::command,
+    crate::setup::wallet_kit::wallet_kit,
+    smbcloud_wallet_core_model::models::environment::Environment,
+    smbcloud_wallet_kit::models::swap::{SwapTransactionPayload, SwapTransactionResponse},
+    tauri::{command, AppHandle},
 };
 
 #[command]
 pub async fn build_swap_transaction(
+    app: AppHandle,
     payload: SwapTransactionPayload,
-) -> Result<SwapTransactionResponse, ErrorResponse> {
-    build_swap_tx(payload).await
+) -> Result<SwapTransactionResponse, String> {
+    wallet_kit(&app, Environment::Mainnet)?
+        .build_swap_transaction(payload)
+        .await
+        .map_err(|e| format!("Failed to build swap transaction: {}", e))
 }
@@ -1,15 +1,21 @@
 use {
-    smbcloud_wallet_core_network::model::ErrorResponse,
-    smbcloud_wallet_kit::{models::swap::SwapQuoteResponse, swap::get_jupiter_swap_quote},
-    tauri::command,
+    crate::setup::wallet_kit::wallet_kit,
+    smbcloud_wallet_core_model::models::environment::Environment,
+    smbcloud_wallet_kit::models::swap::SwapQuoteResponse,
+    tauri::{command, AppHandle},
 };
 
+/// Jupiter only routes on mainnet.
 #[command]
 pub async fn get_swap_quote(
+    app: AppHandle,
     from_token: &str,
     to_token: &str,
     amount: f64,
     slippage_bps: u64,
-) -> Result<SwapQuoteResponse, ErrorResponse> {
-    get_jupiter_swap_quote(from_token, to_token, amount, slippage_bps).await
+) -> Result<SwapQuoteResponse, String> {
+    wallet_kit(&app, Environment::Mainnet)?
+        .swap_quote(from_token, to_token, amount, slippage_bps)
+        .await
+        .map_err(|e| format!("Failed to get swap quote: {}", e))
 }
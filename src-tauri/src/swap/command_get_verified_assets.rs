@@ -1,9 +1,11 @@
 use {
-    smbcloud_wallet_core_model::models::asset_solana::SolanaAsset,
-    smbcloud_wallet_core_network::model::ErrorResponse, tauri::command,
+    crate::tokens::token_registry::registry,
+    smbcloud_wallet_core_model::models::asset_metadata::Metadata,
+    smbcloud_wallet_core_network::model::ErrorResponse,
+    tauri::{command, AppHandle},
 };
 
 #[command]
-pub async fn get_verified_assets() -> Result<Vec<SolanaAsset>, ErrorResponse> {
-    Ok(SolanaAsset::verified_assets())
+pub async fn get_verified_assets(app: AppHandle) -> Result<Vec<Metadata>, ErrorResponse> {
+    Ok(registry(&app).verified())
 }
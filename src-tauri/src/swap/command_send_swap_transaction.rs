@@ -1,10 +1,10 @@
 use {
     crate::constants::store::{store, STORE_ACTIVE_KEYPAIR},
     crate::model::keypair::SolanaWallet,
+    crate::setup::wallet_kit::wallet_kit,
     bs58,
     smbcloud_wallet_core_model::models::environment::Environment,
     smbcloud_wallet_core_network::model::{ErrorCode, ErrorResponse},
-    smbcloud_wallet_kit::swap::send_jupiter_swap_transaction,
     solana_sdk::signature::{Keypair, Signature},
     tauri::{command, AppHandle},
 };
@@ -46,5 +46,14 @@ pub async fn send_swap_transaction(
         code: ErrorCode::ParseError,
         message: "Failed to create keypair from private key".to_string(),
     })?;
-    send_jupiter_swap_transaction(network.rpc_url(), swap_transaction, keypair).await
+    let kit = wallet_kit(&app, network).map_err(|message| ErrorResponse::Error {
+        code: ErrorCode::Unknown,
+        message,
+    })?;
+    kit.send_swap_transaction(&keypair, swap_transaction)
+        .await
+        .map_err(|e| ErrorResponse::Error {
+            code: ErrorCode::NetworkError,
+            message: e.to_string(),
+        })
 }
@@ -0,0 +1,44 @@
+use {
+    crate::{
+        cache,
+        setup::wallet_kit::wallet_kit,
+        tokens::{
+            token_registry::{registry, remember},
+            update_preferences,
+        },
+    },
+    log::info,
+    smbcloud_wallet_core::token_registry::resolve_tokens,
+    smbcloud_wallet_core_model::models::{
+        asset_metadata::Metadata, environment::Environment,
+        token_preferences::add_custom_token as add_custom,
+    },
+    tauri::{command, AppHandle},
+};
+
+/// Add a token by mint, reading its decimals and metadata from chain.
+///
+/// Custom tokens are listed even when the wallet holds none.
+#[command]
+pub async fn add_custom_token(
+    app: AppHandle,
+    network: Environment,
+    mint: String,
+) -> Result<Metadata, String> {
+    info!("Adding custom token {}", mint);
+    let metadata = resolve_tokens(
+        registry(&app),
+        wallet_kit(&app, network)?.rpc_client().url(),
+        std::slice::from_ref(&mint),
+    )
+    .await
+    .map_err(|e| e.to_string())?
+    .pop()
+    .ok_or_else(|| format!("{} is not a token mint", mint))?;
+    remember(&app, vec![metadata.clone()])?;
+
+    update_preferences(&app, |preferences| add_custom(preferences, &mint))?;
+    // Custom tokens are listed once the cached balances are fetched again.
+    cache::expire(&app, "assets:");
+    Ok(metadata)
+}
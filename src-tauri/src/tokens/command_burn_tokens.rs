@@ -0,0 +1,23 @@
+use {
+    crate::{setup::wallet_kit::wallet_kit, wallet::keypair::load_keypair},
+    log::info,
+    smbcloud_wallet_core_model::models::environment::Environment,
+    smbcloud_wallet_kit::{burn::burn_tokens as kit_burn_tokens, models::burn::BurnResult},
+    tauri::{command, AppHandle},
+};
+
+#[command]
+pub async fn burn_tokens(
+    app: AppHandle,
+    network: Environment,
+    from: String,
+    mints: Vec<String>,
+    force: Option<bool>,
+) -> Result<BurnResult, String> {
+    info!("Burning {} mints of {}", mints.len(), from);
+    let keypair = load_keypair(&app, &from)?;
+    let kit = wallet_kit(&app, network)?;
+    kit_burn_tokens(&kit.rpc_client(), keypair, mints, force.unwrap_or(false))
+        .await
+        .map_err(|e| format!("Failed to burn tokens: {}", e))
+}
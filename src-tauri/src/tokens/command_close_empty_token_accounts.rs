@@ -0,0 +1,29 @@
+use {
+    crate::{setup::wallet_kit::wallet_kit, wallet::keypair::load_keypair},
+    log::info,
+    smbcloud_wallet_core_model::models::environment::Environment,
+    smbcloud_wallet_kit::{
+        models::token_accounts::ClosedTokenAccounts,
+        token_accounts::close_empty_token_accounts as kit_close_empty_token_accounts,
+    },
+    tauri::{command, AppHandle},
+};
+
+#[command]
+pub async fn close_empty_token_accounts(
+    app: AppHandle,
+    network: Environment,
+    from: String,
+    accounts: Vec<String>,
+) -> Result<ClosedTokenAccounts, String> {
+    info!(
+        "Closing {} empty token accounts of {}",
+        accounts.len(),
+        from
+    );
+    let keypair = load_keypair(&app, &from)?;
+    let kit = wallet_kit(&app, network)?;
+    kit_close_empty_token_accounts(&kit.rpc_client(), keypair, accounts)
+        .await
+        .map_err(|e| format!("Failed to close token accounts: {}", e))
+}
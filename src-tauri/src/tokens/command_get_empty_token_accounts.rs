@@ -0,0 +1,23 @@
+use {
+    crate::setup::wallet_kit::wallet_kit,
+    log::info,
+    smbcloud_wallet_core_model::models::environment::Environment,
+    smbcloud_wallet_kit::{
+        models::token_accounts::RentReclaim,
+        token_accounts::get_empty_token_accounts as kit_get_empty_token_accounts,
+    },
+    tauri::{command, AppHandle},
+};
+
+#[command]
+pub async fn get_empty_token_accounts(
+    app: AppHandle,
+    network: Environment,
+    pubkey: String,
+) -> Result<RentReclaim, String> {
+    info!("Getting empty token accounts of {}", pubkey);
+    let kit = wallet_kit(&app, network)?;
+    kit_get_empty_token_accounts(&kit.rpc_client(), pubkey)
+        .await
+        .map_err(|e| format!("Failed to get empty token accounts: {}", e))
+}
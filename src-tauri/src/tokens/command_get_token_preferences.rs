@@ -0,0 +1,11 @@
+use {
+    crate::tokens::load_preferences,
+    smbcloud_wallet_core_model::models::token_preferences::TokenPreference,
+    tauri::{command, AppHandle},
+};
+
+/// Tokens the user added, hid or chose to show.
+#[command]
+pub fn get_token_preferences(app: AppHandle) -> Result<Vec<TokenPreference>, String> {
+    load_preferences(&app)
+}
@@ -0,0 +1,21 @@
+use {
+    crate::setup::wallet_kit::wallet_kit,
+    log::info,
+    smbcloud_wallet_core_model::models::environment::Environment,
+    smbcloud_wallet_kit::{burn::preview_burn as kit_preview_burn, models::burn::BurnSummary},
+    tauri::{command, AppHandle},
+};
+
+#[command]
+pub async fn preview_burn(
+    app: AppHandle,
+    network: Environment,
+    pubkey: String,
+    mints: Vec<String>,
+) -> Result<BurnSummary, String> {
+    info!("Previewing the burn of {} mints of {}", mints.len(), pubkey);
+    let kit = wallet_kit(&app, network)?;
+    kit_preview_burn(&kit.rpc_client(), pubkey, mints)
+        .await
+        .map_err(|e| format!("Failed to preview burn: {}", e))
+}
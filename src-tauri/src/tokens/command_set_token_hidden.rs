@@ -0,0 +1,26 @@
+use {
+    crate::{cache, tokens::update_preferences},
+    log::info,
+    smbcloud_wallet_core_model::models::token_preferences::{
+        set_token_hidden as set_hidden, TokenPreference,
+    },
+    tauri::{command, AppHandle},
+};
+
+/// Hide or show a token in every balance and history, or with `None` leave it
+/// to the spam classification.
+#[command]
+pub fn set_token_hidden(
+    app: AppHandle,
+    mint: String,
+    hidden: Option<bool>,
+) -> Result<Vec<TokenPreference>, String> {
+    info!("Setting {} hidden: {:?}", mint, hidden);
+    let preferences =
+        update_preferences(&app, |preferences| set_hidden(preferences, &mint, hidden))?;
+    // Cached balances go by the preferences as they are read, cached
+    // histories and spam choices need to be computed again.
+    cache::expire(&app, "assets:");
+    cache::expire(&app, "history:");
+    Ok(preferences)
+}
@@ -0,0 +1,21 @@
+use {
+    crate::{setup::wallet_kit::wallet_kit, wallet::keypair::load_keypair},
+    log::info,
+    smbcloud_wallet_core_model::models::environment::Environment,
+    smbcloud_wallet_kit::wsol::unwrap_sol as kit_unwrap_sol,
+    tauri::{command, AppHandle},
+};
+
+#[command]
+pub async fn unwrap_sol(
+    app: AppHandle,
+    network: Environment,
+    from: String,
+) -> Result<String, String> {
+    info!("Unwrapping the wrapped SOL of {}", from);
+    let keypair = load_keypair(&app, &from)?;
+    let kit = wallet_kit(&app, network)?;
+    kit_unwrap_sol(&kit.rpc_client(), keypair)
+        .await
+        .map_err(|e| format!("Failed to unwrap SOL: {}", e))
+}
@@ -0,0 +1,22 @@
+use {
+    crate::{setup::wallet_kit::wallet_kit, wallet::keypair::load_keypair},
+    log::info,
+    smbcloud_wallet_core_model::models::environment::Environment,
+    smbcloud_wallet_kit::wsol::wrap_sol as kit_wrap_sol,
+    tauri::{command, AppHandle},
+};
+
+#[command]
+pub async fn wrap_sol(
+    app: AppHandle,
+    network: Environment,
+    from: String,
+    amount: f64,
+) -> Result<String, String> {
+    info!("Wrapping {} SOL of {}", amount, from);
+    let keypair = load_keypair(&app, &from)?;
+    let kit = wallet_kit(&app, network)?;
+    kit_wrap_sol(&kit.rpc_client(), keypair, amount)
+        .await
+        .map_err(|e| format!("Failed to wrap SOL: {}", e))
+}
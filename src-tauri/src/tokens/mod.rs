@@ -0,0 +1,275 @@
+use {
+    crate::{
+        constants::{network::USER_AGENT, store::STORE_TOKEN_PREFERENCES},
+        repository,
+        setup::wallet_kit::wallet_kit,
+        tokens::token_registry::registry,
+    },
+    log::error,
+    smbcloud_wallet_core_http::price_data::price_service::PriceService,
+    smbcloud_wallet_core_model::{
+        models::{
+            asset_metadata::Metadata,
+            balance_v1::BalanceV1,
+            environment::Environment,
+            portfolio::PortfolioValuation,
+            token_preferences::{TokenPreference, TokenVisibility},
+        },
+        token_registry::spam::{is_suspected_spam, is_unpriced_dust},
+    },
+    smbcloud_wallet_kit::token_accounts::owner_initiated_mints,
+    std::{
+        collections::{HashMap, HashSet},
+        sync::Mutex,
+    },
+    tauri::{App, AppHandle, Manager},
+};
+
+pub(crate) mod command_add_custom_token;
+pub(crate) mod command_burn_tokens;
+pub(crate) mod command_close_empty_token_accounts;
+pub(crate) mod command_get_empty_token_accounts;
+pub(crate) mod command_get_token_preferences;
+pub(crate) mod command_preview_burn;
+pub(crate) mod command_set_token_hidden;
+pub(crate) mod command_unwrap_sol;
+pub(crate) mod command_wrap_sol;
+pub(crate) mod token_registry;
+
+/// Serializes changes to the token preferences, so two commands reading and
+/// saving them at once do not lose one of the changes.
+#[derive(Default)]
+pub(crate) struct PreferencesLock(Mutex<()>);
+
+/// Whether a wallet moved a token itself, by network, wallet and mint.
+///
+/// Reading it takes a few requests per token, so answers are kept for the
+/// session. A token the wallet swaps for later is no longer dust anyway.
+#[derive(Default)]
+pub(crate) struct InitiatedMints {
+    checked: Mutex<HashMap<(Environment, String, String), bool>>,
+}
+
+pub(crate) fn setup_tokens(app: &App) -> Result<(), Box<dyn std::error::Error>> {
+    app.manage(PreferencesLock::default());
+    app.manage(InitiatedMints::default());
+    Ok(())
+}
+
+pub(crate) fn load_preferences(app: &AppHandle) -> Result<Vec<TokenPreference>, String> {
+    repository::load_all(app, STORE_TOKEN_PREFERENCES)
+}
+
+/// Apply `change` to the saved preferences and return them.
+pub(crate) fn update_preferences(
+    app: &AppHandle,
+    change: impl FnOnce(&mut Vec<TokenPreference>),
+) -> Result<Vec<TokenPreference>, String> {
+    let lock = app.state::<PreferencesLock>();
+    let _guard = lock
+        .0
+        .lock()
+        .map_err(|e| format!("Failed to lock token preferences: {}", e))?;
+    let mut preferences = load_preferences(app)?;
+    change(&mut preferences);
+    repository::save_all(app, STORE_TOKEN_PREFERENCES, &preferences)?;
+    Ok(preferences)
+}
+
+/// Of `mints`, the unpriced dust tokens `owner` never sent or swapped itself.
+///
+/// Tokens whose history could not be read are taken as asked for, so a failing
+/// RPC does not hide them.
+async fn unsolicited_mints(
+    app: &AppHandle,
+    network: Environment,
+    owner: &str,
+    mints: Vec<String>,
+) -> HashSet<String> {
+    let key = |mint: &str| (network, owner.to_string(), mint.to_string());
+    let mut unsolicited = HashSet::new();
+    let mut unchecked = Vec::new();
+    {
+        let state = app.state::<InitiatedMints>();
+        let Ok(checked) = state.checked.lock() else {
+            return unsolicited;
+        };
+        for mint in mints {
+            match checked.get(&key(&mint)) {
+                Some(true) => {}
+                Some(false) => {
+                    unsolicited.insert(mint);
+                }
+                None => unchecked.push(mint),
+            }
+        }
+    }
+    if unchecked.is_empty() {
+        return unsolicited;
+    }
+
+    let initiated = match wallet_kit(app, network) {
+        Ok(kit) => owner_initiated_mints(&kit.rpc_client(), owner.to_string(), &unchecked)
+            .await
+            .map_err(|e| e.to_string()),
+        Err(e) => Err(e),
+    };
+    let initiated = match initiated {
+        Ok(initiated) => initiated,
+        Err(e) => {
+            error!("Failed to read the activity of unpriced tokens: {}", e);
+            return unsolicited;
+        }
+    };
+    if let Ok(mut checked) = app.state::<InitiatedMints>().checked.lock() {
+        for mint in &unchecked {
+            checked.insert(key(mint), initiated.contains(mint));
+        }
+    }
+    unsolicited.extend(
+        unchecked
+            .into_iter()
+            .filter(|mint| !initiated.contains(mint)),
+    );
+    unsolicited
+}
+
+/// Set the visibility of `balances` of `owner` and add the custom tokens the
+/// wallet holds none of.
+pub(crate) async fn classify_balances(
+    app: &AppHandle,
+    network: Environment,
+    owner: &str,
+    mut balances: Vec<BalanceV1>,
+) -> Vec<BalanceV1> {
+    let preferences = load_preferences(app).unwrap_or_else(|e| {
+        error!("Failed to load token preferences: {}", e);
+        Vec::new()
+    });
+    let registry = registry(app);
+    for preference in preferences.iter().filter(|preference| preference.custom) {
+        let held = balances
+            .iter()
+            .any(|balance| balance.meta.address == preference.mint);
+        if let (false, Some(meta)) = (held, registry.get(&preference.mint)) {
+            balances.push(BalanceV1 {
+                meta,
+                balance: 0,
+                ui_amount: 0.0,
+                visibility: TokenVisibility::Visible,
+            });
+        }
+    }
+
+    // Only unverified tokens can be spam, so only they are priced.
+    let unverified = balances
+        .iter()
+        .filter(|balance| !balance.meta.verified)
+        .map(|balance| balance.meta.address.clone())
+        .collect::<Vec<_>>();
+    let prices = if unverified.is_empty() {
+        Default::default()
+    } else {
+        PriceService::public(USER_AGENT)
+            .get_prices(&unverified)
+            .await
+            .unwrap_or_else(|e| {
+                error!("Failed to price unverified tokens: {}", e);
+                Default::default()
+            })
+    };
+
+    let usd_price = |balance: &BalanceV1| {
+        prices
+            .get(&balance.meta.address)
+            .map(|price| price.usd_price)
+    };
+    let dust = balances
+        .iter()
+        .filter(|balance| {
+            !balance.meta.verified && is_unpriced_dust(usd_price(balance), balance.ui_amount)
+        })
+        .map(|balance| balance.meta.address.clone())
+        .collect();
+    let unsolicited = unsolicited_mints(app, network, owner, dust).await;
+
+    for balance in &mut balances {
+        let spam = is_suspected_spam(
+            &balance.meta,
+            usd_price(balance),
+            balance.ui_amount,
+            unsolicited.contains(&balance.meta.address),
+        );
+        balance.visibility = TokenVisibility::of(&balance.meta.address, &preferences, spam);
+    }
+    balances
+}
+
+/// Update the visibility of `balances` classified earlier to the current
+/// preferences, keeping their spam classification.
+pub(crate) fn apply_preferences(app: &AppHandle, balances: &mut [BalanceV1]) {
+    let preferences = load_preferences(app).unwrap_or_else(|e| {
+        error!("Failed to load token preferences: {}", e);
+        Vec::new()
+    });
+    for balance in balances {
+        let spam = balance.visibility == TokenVisibility::Spam;
+        balance.visibility = TokenVisibility::of(&balance.meta.address, &preferences, spam);
+    }
+}
+
+/// Leave hidden and suspected spam tokens out of the `valuation` of `owner` and
+/// return their mints.
+pub(crate) async fn filter_valuation(
+    app: &AppHandle,
+    network: Environment,
+    owner: &str,
+    valuation: &mut PortfolioValuation,
+) -> Result<Vec<String>, String> {
+    let preferences = load_preferences(app)?;
+    let registry = registry(app);
+    let mut hidden: Vec<String> = preferences
+        .iter()
+        .filter(|preference| preference.hidden == Some(true))
+        .map(|preference| preference.mint.clone())
+        .collect();
+    let assets = valuation
+        .assets
+        .iter()
+        .map(|asset| {
+            let metadata = registry.get(&asset.mint).unwrap_or_else(|| {
+                let mut metadata = Metadata::unknown(asset.mint.clone(), 0);
+                if let Some(symbol) = &asset.symbol {
+                    metadata.symbol = symbol.clone();
+                }
+                metadata
+            });
+            (asset, metadata)
+        })
+        .collect::<Vec<_>>();
+    let dust = assets
+        .iter()
+        .filter(|(asset, metadata)| {
+            !metadata.verified && is_unpriced_dust(asset.price, asset.amount)
+        })
+        .map(|(asset, _)| asset.mint.clone())
+        .collect();
+    let unsolicited = unsolicited_mints(app, network, owner, dust).await;
+
+    for (asset, metadata) in &assets {
+        let spam = is_suspected_spam(
+            metadata,
+            asset.price,
+            asset.amount,
+            unsolicited.contains(&asset.mint),
+        );
+        if !TokenVisibility::of(&asset.mint, &preferences, spam).is_visible() {
+            hidden.push(asset.mint.clone());
+        }
+    }
+    hidden.sort();
+    hidden.dedup();
+
+    valuation.retain_assets(|asset| !hidden.contains(&asset.mint));
+    Ok(hidden)
+}
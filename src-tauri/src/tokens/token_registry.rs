@@ -0,0 +1,33 @@
+use {
+    crate::{constants::store::STORE_TOKEN_REGISTRY, repository},
+    log::error,
+    smbcloud_wallet_core_model::{models::asset_metadata::Metadata, token_registry::TokenRegistry},
+    std::sync::Once,
+    tauri::AppHandle,
+};
+
+static LOAD_CACHE: Once = Once::new();
+
+/// The shared token registry, with the metadata cached by previous runs.
+pub(crate) fn registry(app: &AppHandle) -> &'static TokenRegistry {
+    let registry = TokenRegistry::shared();
+    LOAD_CACHE.call_once(
+        || match repository::load_all::<Metadata>(app, STORE_TOKEN_REGISTRY) {
+            Ok(cached) => registry.load_cache(cached),
+            Err(e) => error!("Failed to load the token registry cache: {}", e),
+        },
+    );
+    registry
+}
+
+/// Remember `tokens` and persist the metadata that is not bundled.
+pub(crate) fn remember(app: &AppHandle, tokens: Vec<Metadata>) -> Result<(), String> {
+    let registry = registry(app);
+    registry.load_cache(tokens);
+    let cache = registry.cache();
+    let saved = repository::load_all::<Metadata>(app, STORE_TOKEN_REGISTRY)?;
+    if cache.len() == saved.len() {
+        return Ok(());
+    }
+    repository::save_all(app, STORE_TOKEN_REGISTRY, &cache)
+}
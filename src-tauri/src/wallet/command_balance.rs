@@ -1,29 +1,67 @@
 use {
-    crate::{constants::network::USER_AGENT, model::settings_debug::XlpEnvironment},
-    log::info,
-    smbcloud_wallet_core_http::xlp::get_wallet_balance::wallet_balance,
-    smbcloud_wallet_core_model::models::environment::Environment,
-    smbcloud_wallet_core_network::model::ErrorResponse,
-    tauri::command,
+    crate::{
+        cache::cached, model::cache::Cached, portfolio, settings::commands::get_fiat_currency,
+        setup::wallet_kit::wallet_kit, tokens::filter_valuation,
+    },
+    log::{error, info},
+    smbcloud_wallet_core_model::models::{environment::Environment, portfolio::PortfolioValuation},
+    smbcloud_wallet_core_network::model::{ErrorCode, ErrorResponse},
+    std::time::Duration,
+    tauri::{command, AppHandle},
 };
 
+/// How long a cached wallet value is served before it is fetched again.
+const BALANCE_MAX_AGE: Duration = Duration::from_secs(30);
+
+/// The wallet value in the currency from the settings.
+///
+/// The last-known value is served from the cache and refreshed in the
+/// background. Hidden and suspected spam tokens are left out. Every valuation
+/// is also kept as a portfolio snapshot for the history chart.
 #[command]
 pub async fn get_wallet_balance(
-    environment: XlpEnvironment,
+    app: AppHandle,
     network: Environment,
     pubkey: String,
-) -> Result<String, ErrorResponse> {
+) -> Result<Cached<PortfolioValuation>, ErrorResponse> {
     info!("Getting wallet balance for {}", pubkey);
-    match wallet_balance(
-        environment.base_url(),
-        network,
-        &pubkey,
-        dotenv!("XLP_API_KEY"),
-        USER_AGENT,
-    )
-    .await
-    {
-        Ok(balance) => Ok(balance.value),
-        Err(err) => return Err(err),
+    let currency = get_fiat_currency(app.clone()).await;
+    let key = format!("balance:{}:{}:{}", network, pubkey, currency);
+
+    // Hidden tokens are left out on every read, so hiding one applies to the
+    // cached value too.
+    let owner = pubkey.clone();
+    let mut valuation = cached(&app, key, BALANCE_MAX_AGE, move |app| async move {
+        let kit = wallet_kit(&app, network).map_err(|message| ErrorResponse::Error {
+            code: ErrorCode::Unknown,
+            message,
+        })?;
+        let valuation =
+            kit.wallet_balance(&pubkey, currency)
+                .await
+                .map_err(|e| ErrorResponse::Error {
+                    code: ErrorCode::NetworkError,
+                    message: e.to_string(),
+                })?;
+
+        // The valuation just fetched the rate, so this is served from the cache.
+        match kit.usd_rate(currency).await {
+            Ok(usd_rate) => {
+                let mut visible = valuation.clone();
+                if let Err(e) = filter_valuation(&app, network, &pubkey, &mut visible).await {
+                    error!("Failed to leave hidden tokens out: {}", e);
+                }
+                if let Err(e) = portfolio::record(&app, &pubkey, network, &visible, usd_rate) {
+                    error!("Failed to record portfolio snapshot: {}", e);
+                }
+            }
+            Err(e) => error!("Failed to record portfolio snapshot: {}", e),
+        }
+        Ok::<_, ErrorResponse>(valuation)
+    })
+    .await?;
+    if let Err(e) = filter_valuation(&app, network, &owner, &mut valuation.data).await {
+        error!("Failed to leave hidden tokens out: {}", e);
     }
+    Ok(valuation)
 }
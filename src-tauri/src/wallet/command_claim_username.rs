@@ -0,0 +1,76 @@
+use {
+    crate::{
+        constants::store::{store, STORE_ACTIVE_KEYPAIR, STORE_KEYPAIRS},
+        model::{keypair::SolanaWallet, settings_debug::AirdropEnvironment},
+        network::username::{claim_username as network_claim_username, get_username_challenge},
+        wallet::keypair::load_keypair,
+    },
+    log::info,
+    smbcloud_wallet_core_network::model::{ErrorCode, ErrorResponse},
+    smbcloud_wallet_kit::{
+        models::username::UsernameProof,
+        username::{normalize_username, sign_username_claim},
+    },
+    tauri::{command, AppHandle},
+};
+
+/// Register `username` in the directory, or claim it for another wallet, by
+/// signing the directory challenge with the wallet key.
+#[command]
+pub async fn claim_username(
+    app: AppHandle,
+    environment: AirdropEnvironment,
+    pubkey: String,
+    username: String,
+) -> Result<UsernameProof, ErrorResponse> {
+    let username = normalize_username(&username).map_err(|e| ErrorResponse::Error {
+        code: ErrorCode::ParseError,
+        message: e.to_string(),
+    })?;
+    info!("Claiming @{} for {}", username, pubkey);
+
+    let keypair = load_keypair(&app, &pubkey).map_err(|message| ErrorResponse::Error {
+        code: ErrorCode::Unknown,
+        message,
+    })?;
+    let challenge = get_username_challenge(environment, &username, &pubkey).await?;
+    let proof =
+        sign_username_claim(&keypair, &username, &challenge).map_err(|e| ErrorResponse::Error {
+            code: ErrorCode::ParseError,
+            message: e.to_string(),
+        })?;
+    let proof = network_claim_username(environment, &proof).await?;
+
+    // Keep the local username in sync with the directory
+    let store = store(&app).map_err(|_| ErrorResponse::Error {
+        code: ErrorCode::Unknown,
+        message: "Failed to load store".to_string(),
+    })?;
+    // A list that fails to parse is never written back, it holds the keys
+    let mut keypairs: Vec<SolanaWallet> = match store.get(STORE_KEYPAIRS) {
+        Some(value) => serde_json::from_value(value).map_err(|e| ErrorResponse::Error {
+            code: ErrorCode::ParseError,
+            message: format!("Failed to parse wallets: {}", e),
+        })?,
+        None => Vec::new(),
+    };
+    for wallet in keypairs.iter_mut().filter(|wallet| wallet.pubkey == pubkey) {
+        wallet.username = Some(username.clone());
+    }
+    store.set(STORE_KEYPAIRS, serde_json::json!(keypairs));
+    if let Some(value) = store.get(STORE_ACTIVE_KEYPAIR) {
+        if let Ok(mut active_wallet) = serde_json::from_value::<SolanaWallet>(value) {
+            if active_wallet.pubkey == pubkey {
+                active_wallet.username = Some(username.clone());
+                store.set(STORE_ACTIVE_KEYPAIR, serde_json::json!(&active_wallet));
+            }
+        }
+    }
+    store.save().map_err(|_| ErrorResponse::Error {
+        code: ErrorCode::Unknown,
+        message: "Failed to update keypair".to_string(),
+    })?;
+
+    info!("Claimed @{} for {}", username, pubkey);
+    Ok(proof)
+}
@@ -1,6 +1,11 @@
 use {
-    crate::constants::store::{
-        store, STORE_ACTIVE_KEYPAIR, STORE_KEYPAIRS, STORE_PASSWORD, STORE_SEEDS, STORE_WALLET,
+    crate::{
+        cache,
+        constants::store::{
+            store, STORE_ACTIVE_KEYPAIR, STORE_CONTACTS, STORE_INVOICES, STORE_JOURNAL,
+            STORE_KEYPAIRS, STORE_PASSWORD, STORE_PORTFOLIO_SNAPSHOTS, STORE_SEEDS,
+            STORE_TOKEN_PREFERENCES, STORE_WALLET,
+        },
     },
     log::info,
     tauri::{command, AppHandle},
@@ -18,11 +23,17 @@ pub fn destroy_all_wallets(app: AppHandle) -> Result<String, String> {
     store.delete(STORE_ACTIVE_KEYPAIR);
     store.delete(STORE_PASSWORD);
     store.delete(STORE_WALLET);
+    store.delete(STORE_INVOICES);
+    store.delete(STORE_CONTACTS);
+    store.delete(STORE_JOURNAL);
+    store.delete(STORE_PORTFOLIO_SNAPSHOTS);
+    store.delete(STORE_TOKEN_PREFERENCES);
 
     // Save the cleared store
     store
         .save()
         .map_err(|_| "Failed to save cleared store".to_string())?;
+    cache::clear(&app)?;
 
     info!("All wallet data has been destroyed");
     Ok("All wallet data has been successfully destroyed".to_string())
@@ -9,11 +9,12 @@ use {
     },
     smbcloud_wallet_core_model::models::environment::Environment,
     smbcloud_wallet_core_rpc::balance::spl_balance::spl_balance,
+    smbcloud_wallet_kit::rpc_endpoints::RpcEndpoints,
     tauri::command,
 };
 
 #[command]
-pub fn get_bach_balance(network: Environment, pubkey: String) -> String {
+pub async fn get_bach_balance(network: Environment, pubkey: String) -> String {
     info!("Getting Bach balance for {}", pubkey);
     let bach_token_address = if network == Environment::Local {
         BACH_TOKEN_ADDRESS_LOCAL.to_string()
@@ -25,12 +26,15 @@ pub fn get_bach_balance(network: Environment, pubkey: String) -> String {
         ADDRESS_BACH_TOKEN.to_string()
     };
 
-    match spl_balance(
-        network.rpc_url(),
-        pubkey,
-        SPL_TOKEN_PROGRAM_ID.to_string(),
-        bach_token_address,
-    ) {
+    let balance = RpcEndpoints::shared(network).read_with_failover(|rpc_url| {
+        spl_balance(
+            rpc_url,
+            pubkey.clone(),
+            SPL_TOKEN_PROGRAM_ID.to_string(),
+            bach_token_address.clone(),
+        )
+    });
+    match balance.await {
         Ok(balance) => balance,
         Err(_) => "0.0".to_string(),
     }
@@ -0,0 +1,17 @@
+use {
+    log::info, smbcloud_wallet_core_model::models::environment::Environment,
+    smbcloud_wallet_core_rpc::sns::primary_domain::primary_domain,
+    smbcloud_wallet_kit::rpc_endpoints::RpcEndpoints, tauri::command,
+};
+
+/// Get the primary `.sol` domain of an address, for display in history and contacts.
+#[command]
+pub async fn get_primary_domain(
+    network: Environment,
+    pubkey: String,
+) -> Result<Option<String>, String> {
+    info!("Getting primary domain of {}", pubkey);
+    primary_domain(RpcEndpoints::shared(network).active_url(), pubkey)
+        .await
+        .map_err(|e| format!("Failed to get primary domain: {}", e))
+}
@@ -1,10 +1,22 @@
 use {
-    log::info, smbcloud_wallet_core_model::models::environment::Environment,
-    smbcloud_wallet_kit::balance::sol_balance, tauri::command,
+    crate::setup::wallet_kit::wallet_kit,
+    log::{error, info},
+    smbcloud_wallet_core_model::models::environment::Environment,
+    tauri::{command, AppHandle},
 };
 
 #[command]
-pub fn get_sol_balance(network: Environment, pubkey: String) -> String {
+pub async fn get_sol_balance(app: AppHandle, network: Environment, pubkey: String) -> String {
     info!("Getting balance for {}", pubkey);
-    sol_balance(network.rpc_url(), pubkey)
+    let sol_amount = match wallet_kit(&app, network) {
+        Ok(kit) => kit.sol_balance(&pubkey).await.unwrap_or_else(|e| {
+            error!("Failed to get SOL balance: {}", e);
+            0.0
+        }),
+        Err(e) => {
+            error!("Failed to get SOL balance: {}", e);
+            0.0
+        }
+    };
+    format!("{:.9} SOL", sol_amount)
 }
@@ -9,11 +9,12 @@ use {
     },
     smbcloud_wallet_core_model::models::environment::Environment,
     smbcloud_wallet_core_rpc::balance::spl_balance::spl_balance,
+    smbcloud_wallet_kit::rpc_endpoints::RpcEndpoints,
     tauri::command,
 };
 
 #[command]
-pub fn get_treasury_bach_balance(network: Environment) -> String {
+pub async fn get_treasury_bach_balance(network: Environment) -> String {
     info!("Getting treasury BACH balance");
     let treasury_address = "3YAyrP4mjiLRuHZQjfskmmVBbF7urtfDLfnLtW2jzgx3";
     let bach_token_address = if network == Environment::Local {
@@ -25,12 +26,15 @@ pub fn get_treasury_bach_balance(network: Environment) -> String {
     } else {
         ADDRESS_BACH_TOKEN.to_string()
     };
-    match spl_balance(
-        network.rpc_url(),
-        treasury_address.to_string(),
-        SPL_TOKEN_PROGRAM_ID.to_string(),
-        bach_token_address,
-    ) {
+    let balance = RpcEndpoints::shared(network).read_with_failover(|rpc_url| {
+        spl_balance(
+            rpc_url,
+            treasury_address.to_string(),
+            SPL_TOKEN_PROGRAM_ID.to_string(),
+            bach_token_address.clone(),
+        )
+    });
+    match balance.await {
         Ok(balance) => balance,
         Err(_) => "0.0".to_string(),
     }
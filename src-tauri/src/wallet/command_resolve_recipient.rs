@@ -0,0 +1,18 @@
+use {
+    crate::wallet::recipient::resolve_recipient as wallet_resolve_recipient,
+    log::info,
+    smbcloud_wallet_core_model::models::environment::Environment,
+    smbcloud_wallet_kit::models::username::ResolvedRecipient,
+    tauri::{command, AppHandle},
+};
+
+/// Resolve an address, `@username` or `.sol` domain so the address can be shown before signing.
+#[command]
+pub async fn resolve_recipient(
+    app: AppHandle,
+    network: Environment,
+    to: String,
+) -> Result<ResolvedRecipient, String> {
+    info!("Resolving recipient {} on {}", to, network);
+    wallet_resolve_recipient(&app, network, &to).await
+}
@@ -1,13 +1,18 @@
 use {
     crate::{
-        constants::store::{store, STORE_KEYPAIRS},
-        model::keypair::SolanaWallet,
+        address_book::load_contacts,
+        journal,
+        model::journal::{JournalEntry, JournalStatus},
+        setup::wallet_kit::wallet_kit,
+        wallet::{keypair::load_keypair, recipient::resolve_recipient},
     },
-    log::info,
-    smbcloud_wallet_constants::{assets_solana::ADDRESS_SOL, constants::SPL_TOKEN_PROGRAM_ID},
+    chrono::Utc,
+    log::{error, info},
+    smbcloud_wallet_constants::assets_solana::ADDRESS_SOL,
     smbcloud_wallet_core_model::models::environment::Environment,
-    smbcloud_wallet_kit::transactions::{create_token_transfer_ix, create_transfer_ix},
-    tauri::{command, AppHandle},
+    smbcloud_wallet_kit::{models::username::RecipientSource, subscriptions::WalletSubscriptions},
+    tauri::{command, AppHandle, Manager},
+    uuid::Uuid,
 };
 
 #[command]
@@ -18,53 +23,84 @@ pub async fn send_token(
     to: String,
     amount: f64,
     token_address: String,
+    contact_id: Option<String>,
+    expected_address: Option<String>,
 ) -> Result<String, String> {
     info!(
         "Sending {} {} from {} to {}",
         amount, token_address, from, to
     );
 
-    // Get the sender's keypair
-    let store = store(&app).map_err(|_| "Failed to load store".to_string())?;
-    let keypairs: Vec<SolanaWallet> = match store.get(STORE_KEYPAIRS) {
-        Some(value) => serde_json::from_value(value).unwrap_or_default(),
-        None => return Err("No keypairs found".to_string()),
-    };
-
-    let sender = keypairs
-        .iter()
-        .find(|k| k.pubkey == from)
-        .ok_or_else(|| "Sender keypair not found".to_string())?;
+    // Resolve `@username` and `.sol` recipients to an address. A name may
+    // point elsewhere by now, so it must still resolve to the address the
+    // user confirmed.
+    let resolved = resolve_recipient(&app, network, &to).await?;
+    if resolved.source != RecipientSource::Address
+        && expected_address.as_ref() != Some(&resolved.address)
+    {
+        return Err(format!(
+            "{} no longer resolves to the confirmed address",
+            resolved.input
+        ));
+    }
+    let to = resolved.address;
 
-    // Convert the private key from bs58 to keypair
-    let privkey_bytes = bs58::decode(&sender.privkey)
-        .into_vec()
-        .map_err(|_| "Failed to decode private key".to_string())?;
+    // Make sure the contact matches the recipient before anything is sent
+    if let Some(contact_id) = &contact_id {
+        let contacts = load_contacts(&app)?;
+        let contact = contacts
+            .iter()
+            .find(|c| &c.id == contact_id)
+            .ok_or_else(|| "Contact not found".to_string())?;
+        if contact.address != to || contact.network != network {
+            return Err("Recipient does not match the contact".to_string());
+        }
+    }
 
-    let keypair = solana_sdk::signer::keypair::Keypair::from_bytes(&privkey_bytes)
-        .map_err(|_| "Failed to create keypair from private key".to_string())?;
+    let keypair = load_keypair(&app, &from)?;
 
     // Create and send the transaction based on token type
-    let tx_signature = if token_address == ADDRESS_SOL {
-        // Create SOL transfer instruction
-        create_transfer_ix(network.rpc_url(), keypair, from, to, amount)
+    let kit = wallet_kit(&app, network)?;
+    let result = if token_address == ADDRESS_SOL {
+        kit.transfer_sol(&keypair, &to, amount)
             .await
-            .map_err(|e| format!("Failed to send SOL: {:?}", e))?
+            .map_err(|e| format!("Failed to send SOL: {:?}", e))
     } else {
-        // Create token transfer instruction
-        create_token_transfer_ix(
-            network.rpc_url(),
-            keypair,
-            from,
-            to,
-            token_address,
-            SPL_TOKEN_PROGRAM_ID.to_string(),
-            amount,
-        )
-        .await
-        .map_err(|e| format!("Failed to send tokens: {:?}", e))?
+        // The token program is read from the mint, so Token-2022 tokens work too
+        kit.transfer_token(&keypair, &to, &token_address, amount)
+            .await
+            .map_err(|e| format!("Failed to send tokens: {:?}", e))
+    };
+
+    // The transfer already happened, so bookkeeping errors are only logged.
+    // The contact is credited once the journal entry is confirmed.
+    let entry = JournalEntry {
+        id: Uuid::new_v4(),
+        signature: result.as_ref().ok().cloned(),
+        status: match &result {
+            Ok(_) => JournalStatus::Pending,
+            Err(e) => JournalStatus::Failed { error: e.clone() },
+        },
+        network,
+        from,
+        to,
+        amount,
+        token_address,
+        contact_id,
+        timestamp: Utc::now(),
     };
+    if let Err(e) = journal::record(&app, entry) {
+        error!("Failed to record transfer in journal: {}", e);
+    }
+    // The subscriptions settle the journal entry once the network confirms it
+    if let Ok(signature) = &result {
+        let subscriptions = app.state::<WalletSubscriptions>();
+        if let Err(e) = subscriptions.watch_signature(network, signature) {
+            error!("Failed to watch transfer: {}", e);
+        }
+    }
 
+    let tx_signature = result?;
     info!("Transaction sent successfully: {}", tx_signature);
     Ok(tx_signature)
 }
@@ -2,7 +2,9 @@ use {
     crate::{
         constants::store::{store, STORE_ACTIVE_KEYPAIR},
         model::keypair::SolanaWallet,
+        wallet::subscriptions::watch_active_wallet,
     },
+    log::error,
     serde_json::json,
     tauri::{command, AppHandle},
 };
@@ -14,5 +16,10 @@ pub fn set_active_keypair(app: AppHandle, keypair: SolanaWallet) -> Result<(), S
     store
         .save()
         .map_err(|_| "Failed to save active keypair".to_string())?;
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = watch_active_wallet(&app).await {
+            error!("Failed to follow the active wallet: {}", e);
+        }
+    });
     Ok(())
 }
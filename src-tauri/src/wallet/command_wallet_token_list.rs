@@ -1,29 +1,88 @@
 use {
-    crate::{constants::network::USER_AGENT, model::settings_debug::XlpEnvironment},
-    log::info,
+    crate::{
+        cache::cached,
+        constants::network::USER_AGENT,
+        model::{cache::Cached, settings_debug::XlpEnvironment},
+        tokens::{
+            apply_preferences, classify_balances,
+            token_registry::{registry, remember},
+        },
+    },
+    log::{error, info},
     smbcloud_wallet_core_http::xlp::get_wallet_assets_balance::wallet_token_list,
     smbcloud_wallet_core_model::models::{balance_v1::BalanceV1, environment::Environment},
     smbcloud_wallet_core_network::model::ErrorResponse,
-    tauri::command,
+    std::time::Duration,
+    tauri::{command, AppHandle},
 };
 
+/// How long cached token balances are served before they are fetched again.
+const ASSETS_MAX_AGE: Duration = Duration::from_secs(30);
+
+/// The tokens of a wallet with their balances.
+///
+/// The last-known balances are served from the cache and refreshed in the
+/// background. Hidden and suspected spam tokens are left out unless
+/// `include_hidden` is set.
 #[command]
 pub async fn get_wallet_assets_balance(
+    app: AppHandle,
     environment: XlpEnvironment,
     network: Environment,
     pubkey: String,
-) -> Result<Vec<BalanceV1>, ErrorResponse> {
+    include_hidden: Option<bool>,
+) -> Result<Cached<Vec<BalanceV1>>, ErrorResponse> {
     info!("Getting wallet assets balance for {}", pubkey);
-    match wallet_token_list(
+    let key = format!("assets:{}:{}", network, pubkey);
+    let mut balances = cached(&app, key, ASSETS_MAX_AGE, move |app| async move {
+        fetch_balances(&app, environment, network, &pubkey).await
+    })
+    .await?;
+
+    // Choices made since the balances were cached apply right away.
+    apply_preferences(&app, &mut balances.data);
+    if !include_hidden.unwrap_or(false) {
+        balances
+            .data
+            .retain(|balance| balance.visibility.is_visible());
+    }
+    Ok(balances)
+}
+
+/// Every token of the wallet, hidden ones included, with its visibility.
+async fn fetch_balances(
+    app: &AppHandle,
+    environment: XlpEnvironment,
+    network: Environment,
+    pubkey: &str,
+) -> Result<Vec<BalanceV1>, ErrorResponse> {
+    let balances = wallet_token_list(
         environment.base_url(),
         network,
-        &pubkey,
+        pubkey,
         dotenv!("XLP_API_KEY"),
         USER_AGENT,
     )
-    .await
-    {
-        Ok(balance) => Ok(balance.data),
-        Err(err) => return Err(err),
+    .await?
+    .data;
+
+    // Remember tokens the registry does not know yet, then prefer the registry
+    // metadata, which carries the verified flag.
+    let tokens = balances
+        .iter()
+        .map(|balance| balance.meta.clone())
+        .collect();
+    if let Err(e) = remember(app, tokens) {
+        error!("Failed to save the token registry cache: {}", e);
     }
+    let registry = registry(app);
+    let balances = balances
+        .into_iter()
+        .map(|balance| BalanceV1 {
+            meta: registry.get(&balance.meta.address).unwrap_or(balance.meta),
+            ..balance
+        })
+        .collect();
+
+    Ok(classify_balances(app, network, pubkey, balances).await)
 }
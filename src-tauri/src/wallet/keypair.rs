@@ -0,0 +1,30 @@
+use {
+    crate::{
+        constants::store::{store, STORE_KEYPAIRS},
+        model::keypair::SolanaWallet,
+    },
+    solana_sdk::signer::keypair::Keypair,
+    tauri::AppHandle,
+};
+
+/// Load the signing keypair of a stored wallet.
+pub(crate) fn load_keypair(app: &AppHandle, pubkey: &str) -> Result<Keypair, String> {
+    let store = store(app).map_err(|_| "Failed to load store".to_string())?;
+    let keypairs: Vec<SolanaWallet> = match store.get(STORE_KEYPAIRS) {
+        Some(value) => serde_json::from_value(value).unwrap_or_default(),
+        None => return Err("No keypairs found".to_string()),
+    };
+
+    let wallet = keypairs
+        .iter()
+        .find(|k| k.pubkey == pubkey)
+        .ok_or_else(|| "Keypair not found".to_string())?;
+
+    // Convert the private key from bs58 to keypair
+    let privkey_bytes = bs58::decode(&wallet.privkey)
+        .into_vec()
+        .map_err(|_| "Failed to decode private key".to_string())?;
+
+    Keypair::from_bytes(&privkey_bytes)
+        .map_err(|_| "Failed to create keypair from private key".to_string())
+}
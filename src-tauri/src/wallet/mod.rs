@@ -1,16 +1,22 @@
 pub(crate) mod command_balance;
 pub(crate) mod command_check_pubkey;
+pub(crate) mod command_claim_username;
 pub(crate) mod command_derive_next_keypair;
 pub(crate) mod command_destroy_all_wallets;
 pub(crate) mod command_get_all_keypairs;
 pub(crate) mod command_get_bach_balance;
+pub(crate) mod command_get_primary_domain;
 pub(crate) mod command_get_sol_balance;
 pub(crate) mod command_get_treasury_bach_balance;
 pub(crate) mod command_get_treasury_sol_balance;
 pub(crate) mod command_import_solana_wallet;
 pub(crate) mod command_onboarding_create_wallet;
+pub(crate) mod command_resolve_recipient;
 pub(crate) mod command_send_token;
 pub(crate) mod command_set_active_keypair;
 pub(crate) mod command_sign_message;
 pub(crate) mod command_update_username;
 pub(crate) mod command_wallet_token_list;
+pub(crate) mod keypair;
+pub(crate) mod recipient;
+pub(crate) mod subscriptions;
@@ -0,0 +1,63 @@
+use {
+    crate::{network::username::get_username, settings::commands::get_airdrop_environment},
+    chrono::Utc,
+    log::info,
+    smbcloud_wallet_core_model::models::environment::Environment,
+    smbcloud_wallet_core_rpc::sns::{normalize_domain, resolve_domain::resolve_domain},
+    smbcloud_wallet_kit::{
+        models::username::{RecipientSource, ResolvedRecipient},
+        rpc_endpoints::RpcEndpoints,
+        username::{is_username, normalize_username, verify_username_proof},
+    },
+    solana_sdk::pubkey::Pubkey,
+    std::str::FromStr,
+    tauri::AppHandle,
+};
+
+/// Resolve what the user typed in the send field to an address.
+///
+/// `@username` recipients are looked up in the username directory and only
+/// accepted with a valid ownership proof and a current directory attestation. `.sol` domains are resolved on `network`.
+pub(crate) async fn resolve_recipient(
+    app: &AppHandle,
+    network: Environment,
+    to: &str,
+) -> Result<ResolvedRecipient, String> {
+    let input = to.trim().to_string();
+    if is_username(&input) {
+        let username = normalize_username(&input).map_err(|e| e.to_string())?;
+        let environment = get_airdrop_environment(app.clone()).await;
+        let proof = get_username(environment, &username)
+            .await
+            .map_err(|e| format!("Failed to resolve @{}: {}", username, e))?;
+        let directory = Pubkey::from_str(environment.username_directory())
+            .map_err(|_| "Invalid username directory key".to_string())?;
+        let pubkey = verify_username_proof(&proof, &username, &directory, Utc::now().timestamp())
+            .map_err(|e| format!("Failed to verify @{}: {}", username, e))?;
+        info!("Resolved @{} to {}", username, pubkey);
+        return Ok(ResolvedRecipient {
+            input,
+            address: pubkey.to_string(),
+            source: RecipientSource::Username,
+        });
+    }
+
+    if let Some(domain) = normalize_domain(&input) {
+        let address = resolve_domain(RpcEndpoints::shared(network).active_url(), domain.clone())
+            .await
+            .map_err(|e| format!("Failed to resolve {}: {}", domain, e))?;
+        info!("Resolved {} to {}", domain, address);
+        return Ok(ResolvedRecipient {
+            input,
+            address,
+            source: RecipientSource::Domain,
+        });
+    }
+
+    Pubkey::from_str(&input).map_err(|_| format!("Invalid recipient: {}", input))?;
+    Ok(ResolvedRecipient {
+        address: input.clone(),
+        input,
+        source: RecipientSource::Address,
+    })
+}
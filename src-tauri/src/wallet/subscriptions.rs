@@ -0,0 +1,197 @@
+use {
+    crate::{
+        address_book::record_contact_transfer,
+        cache,
+        constants::store::{store, STORE_ACTIVE_KEYPAIR},
+        journal::{self, load_journal},
+        model::{journal::JournalStatus, keypair::SolanaWallet},
+        settings::commands::get_network_environment,
+    },
+    chrono::Utc,
+    log::{debug, error, info},
+    smbcloud_wallet_core_model::models::environment::Environment,
+    smbcloud_wallet_kit::{
+        models::subscriptions::{SignatureUpdate, TransactionUpdate, WalletNotification},
+        rpc_endpoints::RpcEndpoints,
+        subscriptions::{check_signature_statuses, WalletSubscriptions, SIGNATURE_CHECK_INTERVAL},
+    },
+    std::{collections::HashMap, time::Duration},
+    tauri::{App, AppHandle, Emitter, Manager},
+    tokio::sync::mpsc::unbounded_channel,
+    tsync::tsync,
+};
+
+#[tsync]
+pub const BALANCE_CHANGED_EVENT: &str = "balance_changed";
+
+#[tsync]
+pub const INCOMING_TRANSFER_EVENT: &str = "incoming_transfer";
+
+#[tsync]
+pub const JOURNAL_UPDATED_EVENT: &str = "journal_updated";
+
+/// Start following the active wallet, and the journal transfers still
+/// waiting for confirmation.
+pub(crate) fn setup_wallet_subscriptions(app: &App) -> Result<(), Box<dyn std::error::Error>> {
+    let (notifications, mut receiver) = unbounded_channel();
+    let (subscriptions, worker) = WalletSubscriptions::new(notifications);
+    tauri::async_runtime::spawn(worker.run());
+
+    let handle = app.handle().clone();
+    tauri::async_runtime::spawn(async move {
+        while let Some(notification) = receiver.recv().await {
+            forward(&handle, notification);
+        }
+    });
+
+    for entry in load_journal(app.handle())? {
+        if let (JournalStatus::Pending, Some(signature)) = (&entry.status, &entry.signature) {
+            subscriptions.watch_signature(entry.network, signature)?;
+        }
+    }
+    app.manage(subscriptions);
+
+    let handle = app.handle().clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = watch_active_wallet(&handle).await {
+            error!("Failed to follow the active wallet: {}", e);
+        }
+    });
+    spawn_journal_checks(app.handle().clone());
+    Ok(())
+}
+
+/// Look up the pending journal transfers sent on other networks than the one
+/// of the active wallet with `getSignatureStatuses`, for as long as the app
+/// runs. The subscriptions settle those of the followed network.
+fn spawn_journal_checks(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(SIGNATURE_CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = check_pending_transfers(&app).await {
+                error!("Failed to check pending transfers: {}", e);
+            }
+        }
+    });
+}
+
+async fn check_pending_transfers(app: &AppHandle) -> Result<(), String> {
+    let followed = followed_wallet(app).await?.map(|(network, _)| network);
+    let now = Utc::now();
+    let mut pending: HashMap<Environment, Vec<(String, Duration)>> = HashMap::new();
+    for entry in load_journal(app)? {
+        if Some(entry.network) == followed {
+            continue;
+        }
+        if let (JournalStatus::Pending, Some(signature)) = (entry.status, entry.signature) {
+            let age = (now - entry.timestamp).to_std().unwrap_or_default();
+            pending
+                .entry(entry.network)
+                .or_default()
+                .push((signature, age));
+        }
+    }
+    for (network, signatures) in pending {
+        debug!(
+            "Checking {} pending transfers on {}",
+            signatures.len(),
+            network
+        );
+        match check_signature_statuses(RpcEndpoints::shared(network).active_url(), &signatures)
+            .await
+        {
+            Ok(updates) => updates
+                .into_iter()
+                .for_each(|update| update_journal(app, update)),
+            Err(e) => error!("Failed to check transfers on {}: {}", network, e),
+        }
+    }
+    Ok(())
+}
+
+/// Follow the active wallet on the selected network, if there is one.
+pub(crate) async fn watch_active_wallet(app: &AppHandle) -> Result<(), String> {
+    let Some((network, wallet)) = followed_wallet(app).await? else {
+        return Ok(());
+    };
+    app.state::<WalletSubscriptions>()
+        .watch(network, &wallet.pubkey)
+        .map_err(|e| e.to_string())
+}
+
+/// The selected network and the active wallet followed on it, if there is one.
+async fn followed_wallet(app: &AppHandle) -> Result<Option<(Environment, SolanaWallet)>, String> {
+    let store = store(app).map_err(|_| "Failed to load store".to_string())?;
+    let Some(value) = store.get(STORE_ACTIVE_KEYPAIR) else {
+        return Ok(None);
+    };
+    let wallet: SolanaWallet =
+        serde_json::from_value(value).map_err(|_| "Failed to parse wallet".to_string())?;
+    let network = get_network_environment(app.clone()).await;
+    Ok(Some((network, wallet)))
+}
+
+fn forward(app: &AppHandle, notification: WalletNotification) {
+    let result = match notification {
+        WalletNotification::Account(update) => {
+            cache::expire(app, &update.wallet);
+            app.emit(BALANCE_CHANGED_EVENT, update)
+        }
+        WalletNotification::Transaction(update) if is_incoming(app, &update) => {
+            cache::expire(app, &update.wallet);
+            app.emit(INCOMING_TRANSFER_EVENT, update)
+        }
+        WalletNotification::Transaction(_) => Ok(()),
+        WalletNotification::Signature(update) => {
+            update_journal(app, update);
+            Ok(())
+        }
+    };
+    if let Err(e) = result {
+        error!("Failed to emit event: {}", e);
+    }
+}
+
+/// Whether the transaction was not sent from this wallet app.
+fn is_incoming(app: &AppHandle, update: &TransactionUpdate) -> bool {
+    if update.error.is_some() {
+        return false;
+    }
+    match journal::contains(app, &update.signature) {
+        Ok(sent) => !sent,
+        Err(e) => {
+            error!("Failed to read journal: {}", e);
+            false
+        }
+    }
+}
+
+fn update_journal(app: &AppHandle, update: SignatureUpdate) {
+    let status = match update.error {
+        None => JournalStatus::Confirmed,
+        Some(error) => JournalStatus::Failed { error },
+    };
+    match journal::update_status(app, &update.signature, status) {
+        Ok(Some(entry)) => {
+            info!(
+                "Transfer {} settled as {:?}",
+                update.signature, entry.status
+            );
+            // Only transfers that reached the contact count for it
+            if let (JournalStatus::Confirmed, Some(contact_id)) = (&entry.status, &entry.contact_id)
+            {
+                if let Err(e) =
+                    record_contact_transfer(app, contact_id, &entry.token_address, entry.amount)
+                {
+                    error!("Failed to update contact: {}", e);
+                }
+            }
+            if let Err(e) = app.emit(JOURNAL_UPDATED_EVENT, entry) {
+                error!("Failed to emit event: {}", e);
+            }
+        }
+        Ok(None) => {}
+        Err(e) => error!("Failed to update journal: {}", e),
+    }
+}
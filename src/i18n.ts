@@ -166,6 +166,7 @@ export interface Translation {
   enterCustomAddress: string;
   recipientAddress: string;
   enterRecipientPublicKey: string;
+  confirmResolvedRecipient: (input: string, address: string) => string;
   customAddress: string;
   sending: string;
   failedToGetQuote: string;
@@ -211,6 +212,33 @@ export interface Translation {
   viewRecoveryPhrase: string;
   importExisting: string;
   network: string;
+  rpcEndpoints: string;
+  checkNow: string;
+  endpointActive: string;
+  endpointDown: string;
+  slotsBehind: (slots: number) => string;
+  customNetworks: string;
+  noCustomNetworks: string;
+  addNetwork: string;
+  addNetworkFailed: string;
+  removeNetwork: string;
+  networkName: string;
+  cluster: string;
+  rpcUrl: string;
+  wsUrl: string;
+  wsUrlHelper: string;
+  genesisHash: string;
+  genesisHashHelper: string;
+  verifying: string;
+  add: string;
+  lastUpdated: (time: string) => string;
+  paymentRequest: string;
+  pay: string;
+  approve: string;
+  loadingPaymentRequest: string;
+  paymentRequestInstructions: string;
+  paymentSent: string;
+  paymentFailed: string;
   management: string;
   importRecovery: string;
   destroyWallets: string;
@@ -474,6 +502,8 @@ export const translations: Record<SupportedLanguages, Translation> = {
     enterCustomAddress: "Enter custom address",
     recipientAddress: "Recipient Address",
     enterRecipientPublicKey: "Enter recipient public key",
+    confirmResolvedRecipient: (input: string, address: string) =>
+      `${input} is ${address}. Press send again to confirm.`,
     customAddress: "Custom Address",
     sending: "Sending...",
     failedToGetQuote: "Failed to get swap quote",
@@ -519,6 +549,34 @@ export const translations: Record<SupportedLanguages, Translation> = {
     viewRecoveryPhrase: "View your recovery phrase",
     importExisting: "Import an existing wallet",
     network: "Network",
+    rpcEndpoints: "RPC Endpoints",
+    checkNow: "Check now",
+    endpointActive: "Active",
+    endpointDown: "Down",
+    slotsBehind: (slots: number) => `${slots} slots behind`,
+    customNetworks: "Custom Networks",
+    noCustomNetworks: "Add your own RPC provider or cluster.",
+    addNetwork: "Add network",
+    addNetworkFailed: "Failed to add network",
+    removeNetwork: "Remove network",
+    networkName: "Name",
+    cluster: "Cluster",
+    rpcUrl: "RPC URL",
+    wsUrl: "WebSocket URL",
+    wsUrlHelper: "Optional, derived from the RPC URL when empty.",
+    genesisHash: "Genesis hash",
+    genesisHashHelper:
+      "The endpoint must return this hash, so the wallet never uses another cluster.",
+    verifying: "Verifying...",
+    add: "Add",
+    lastUpdated: (time: string) => `Last updated ${time}`,
+    paymentRequest: "Payment Request",
+    pay: "Pay",
+    approve: "Approve",
+    loadingPaymentRequest: "Loading payment request...",
+    paymentRequestInstructions: "This transaction will",
+    paymentSent: "Payment sent",
+    paymentFailed: "Payment failed",
     management: "Wallet Management",
     importRecovery: "Import & Recovery",
     destroyWallets: "Destroy Wallets",
@@ -784,6 +842,8 @@ export const translations: Record<SupportedLanguages, Translation> = {
     enterCustomAddress: "Ange anpassad adress",
     recipientAddress: "Mottagaradress",
     enterRecipientPublicKey: "Ange mottagarens publika nyckel",
+    confirmResolvedRecipient: (input: string, address: string) =>
+      `${input} är ${address}. Tryck på skicka igen för att bekräfta.`,
     customAddress: "Anpassad adress",
     sending: "Skickar...",
     failedToGetQuote: "Misslyckades att få swap-offert",
@@ -829,6 +889,35 @@ export const translations: Record<SupportedLanguages, Translation> = {
     viewRecoveryPhrase: "Visa din återställningsfras",
     importExisting: "Importera en befintlig plånbok",
     network: "Natverk",
+    rpcEndpoints: "RPC-noder",
+    checkNow: "Kontrollera nu",
+    endpointActive: "Aktiv",
+    endpointDown: "Nere",
+    slotsBehind: (slots: number) => `${slots} slottar efter`,
+    customNetworks: "Egna nätverk",
+    noCustomNetworks:
+      "Lägg till din egen RPC-leverantör eller ditt eget kluster.",
+    addNetwork: "Lägg till nätverk",
+    addNetworkFailed: "Kunde inte lägga till nätverket",
+    removeNetwork: "Ta bort nätverk",
+    networkName: "Namn",
+    cluster: "Kluster",
+    rpcUrl: "RPC-URL",
+    wsUrl: "WebSocket-URL",
+    wsUrlHelper: "Valfri, härleds från RPC-URL:en om den är tom.",
+    genesisHash: "Genesis-hash",
+    genesisHashHelper:
+      "Noden måste returnera denna hash, så att plånboken aldrig använder ett annat kluster.",
+    verifying: "Verifierar...",
+    add: "Lägg till",
+    lastUpdated: (time: string) => `Senast uppdaterad ${time}`,
+    paymentRequest: "Betalningsbegäran",
+    pay: "Betala",
+    approve: "Godkänn",
+    loadingPaymentRequest: "Laddar betalningsbegäran...",
+    paymentRequestInstructions: "Transaktionen kommer att",
+    paymentSent: "Betalningen har skickats",
+    paymentFailed: "Betalningen misslyckades",
     management: "Plånbokshantering",
     importRecovery: "Import & återställning",
     destroyWallets: "Förstör plånböcker",
@@ -1094,6 +1183,8 @@ export const translations: Record<SupportedLanguages, Translation> = {
     enterCustomAddress: "Masukkan alamat khusus",
     recipientAddress: "Alamat Penerima",
     enterRecipientPublicKey: "Masukkan kunci publik penerima",
+    confirmResolvedRecipient: (input: string, address: string) =>
+      `${input} adalah ${address}. Tekan kirim lagi untuk konfirmasi.`,
     customAddress: "Alamat Khusus",
     sending: "Mengirim...",
     failedToGetQuote: "Gagal mendapatkan kutipan swap",
@@ -1139,6 +1230,34 @@ export const translations: Record<SupportedLanguages, Translation> = {
     viewRecoveryPhrase: "Lihat frasa pemulihan Anda",
     importExisting: "Impor dompet yang ada",
     network: "Jaringan",
+    rpcEndpoints: "Endpoint RPC",
+    checkNow: "Periksa sekarang",
+    endpointActive: "Aktif",
+    endpointDown: "Tidak aktif",
+    slotsBehind: (slots: number) => `${slots} slot tertinggal`,
+    customNetworks: "Jaringan Kustom",
+    noCustomNetworks: "Tambahkan penyedia RPC atau klaster Anda sendiri.",
+    addNetwork: "Tambah jaringan",
+    addNetworkFailed: "Gagal menambahkan jaringan",
+    removeNetwork: "Hapus jaringan",
+    networkName: "Nama",
+    cluster: "Klaster",
+    rpcUrl: "URL RPC",
+    wsUrl: "URL WebSocket",
+    wsUrlHelper: "Opsional, diturunkan dari URL RPC jika kosong.",
+    genesisHash: "Hash genesis",
+    genesisHashHelper:
+      "Endpoint harus mengembalikan hash ini, agar dompet tidak pernah memakai klaster lain.",
+    verifying: "Memverifikasi...",
+    add: "Tambah",
+    lastUpdated: (time: string) => `Terakhir diperbarui ${time}`,
+    paymentRequest: "Permintaan Pembayaran",
+    pay: "Bayar",
+    approve: "Setujui",
+    loadingPaymentRequest: "Memuat permintaan pembayaran...",
+    paymentRequestInstructions: "Transaksi ini akan",
+    paymentSent: "Pembayaran terkirim",
+    paymentFailed: "Pembayaran gagal",
     management: "Manajemen Dompet",
     importRecovery: "Impor & Pemulihan",
     destroyWallets: "Hancurkan Dompet",